@@ -85,6 +85,18 @@ impl Hashchain {
         Ok(())
     }
 
+    /// Gets the chain id of the structure.
+    #[must_use]
+    pub const fn get_chain_id(&self) -> &[u8; 32] {
+        &self.chain_id
+    }
+
+    /// Gets the account id of the contract of the structure.
+    #[must_use]
+    pub const fn get_contract_account_id(&self) -> &AccountId {
+        &self.contract_account_id
+    }
+
     /// Gets the current block height of the structure.
     #[must_use]
     pub const fn get_current_block_height(&self) -> u64 {
@@ -236,17 +248,7 @@ impl BlockHashchainComputer {
 
     /// Adds a transaction.
     pub fn add_tx(&mut self, method_name: &str, input: &[u8], output: &[u8], log_bloom: &Bloom) {
-        let data = [
-            &saturating_cast(method_name.len()).to_be_bytes(),
-            method_name.as_bytes(),
-            &saturating_cast(input.len()).to_be_bytes(),
-            input,
-            &saturating_cast(output.len()).to_be_bytes(),
-            output,
-        ]
-        .concat();
-
-        let tx_hash = keccak(&data).0;
+        let tx_hash = compute_tx_hash(method_name, input, output);
 
         self.txs_logs_bloom.accrue_bloom(log_bloom);
         self.txs_merkle_tree.add(tx_hash);
@@ -262,17 +264,14 @@ impl BlockHashchainComputer {
     ) -> RawH256 {
         let txs_hash = self.txs_merkle_tree.compute_hash();
 
-        let data = [
+        compute_block_hashchain(
             chain_id,
             contract_account_id,
-            &current_block_height.to_be_bytes(),
+            current_block_height,
             &previous_block_hashchain,
             &txs_hash,
-            self.txs_logs_bloom.as_bytes(),
-        ]
-        .concat();
-
-        keccak(&data).0
+            &self.txs_logs_bloom,
+        )
     }
 
     /// Clears the transactions added.
@@ -287,6 +286,46 @@ impl BlockHashchainComputer {
     }
 }
 
+/// Computes the hash of a transaction, i.e. the leaf of the transactions Merkle Tree of a block.
+#[must_use]
+pub fn compute_tx_hash(method_name: &str, input: &[u8], output: &[u8]) -> RawH256 {
+    let data = [
+        &saturating_cast(method_name.len()).to_be_bytes(),
+        method_name.as_bytes(),
+        &saturating_cast(input.len()).to_be_bytes(),
+        input,
+        &saturating_cast(output.len()).to_be_bytes(),
+        output,
+    ]
+    .concat();
+
+    keccak(&data).0
+}
+
+/// Computes the block hashchain from the hash of the transactions Merkle Tree and the logs
+/// bloom of the block.
+#[must_use]
+pub fn compute_block_hashchain(
+    chain_id: &[u8; 32],
+    contract_account_id: &[u8],
+    block_height: u64,
+    previous_block_hashchain: &RawH256,
+    txs_hash: &RawH256,
+    txs_logs_bloom: &Bloom,
+) -> RawH256 {
+    let data = [
+        chain_id,
+        contract_account_id,
+        &block_height.to_be_bytes(),
+        previous_block_hashchain,
+        txs_hash,
+        txs_logs_bloom.as_bytes(),
+    ]
+    .concat();
+
+    keccak(&data).0
+}
+
 fn saturating_cast(x: usize) -> u32 {
     x.try_into().unwrap_or(u32::MAX)
 }
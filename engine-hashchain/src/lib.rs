@@ -4,6 +4,7 @@ pub mod bloom;
 pub mod error;
 pub mod hashchain;
 pub mod merkle;
+pub mod proof;
 #[cfg(test)]
 mod tests;
 pub mod wrapped_io;
@@ -117,6 +117,67 @@ impl Default for StreamCompactMerkleTree {
     }
 }
 
+/// Proof of the inclusion of a leaf hash in a Merkle Tree built like `StreamCompactMerkleTree`,
+/// i.e. the last node of a level is duplicated when the level has an odd number of nodes.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MerkleProof {
+    /// Position of the leaf in the tree, starting from 0.
+    pub leaf_index: u64,
+    /// Hashes of the siblings of the nodes on the path from the leaf to the root.
+    pub siblings: Vec<RawH256>,
+}
+
+impl MerkleProof {
+    /// Builds the proof of the inclusion of the leaf at `leaf_index` in the tree of `leaves`.
+    /// Returns `None` if there is no leaf at that index.
+    /// For n leaves, this function is O(n).
+    pub fn new(leaves: &[RawH256], leaf_index: usize) -> Option<Self> {
+        if leaf_index >= leaves.len() {
+            return None;
+        }
+        let proof_leaf_index = u64::try_from(leaf_index).ok()?;
+
+        let mut siblings = Vec::new();
+        let mut level = leaves.to_vec();
+        let mut index = leaf_index;
+
+        while level.len() > 1 {
+            let sibling_index = index ^ 1;
+            // a missing right sibling means the node is duplicated
+            siblings.push(*level.get(sibling_index).unwrap_or(&level[index]));
+
+            level = level
+                .chunks(2)
+                .map(|pair| {
+                    let right = pair.get(1).unwrap_or(&pair[0]);
+                    keccak(&[pair[0], *right].concat()).0
+                })
+                .collect();
+            index /= 2;
+        }
+
+        Some(Self {
+            leaf_index: proof_leaf_index,
+            siblings,
+        })
+    }
+
+    /// Computes the hash of the Merkle Tree from the leaf hash and its proof.
+    pub fn compute_root(&self, leaf_hash: RawH256) -> RawH256 {
+        let mut index = self.leaf_index;
+
+        self.siblings.iter().fold(leaf_hash, |node, sibling| {
+            let data = if index % 2 == 0 {
+                [node, *sibling]
+            } else {
+                [*sibling, node]
+            };
+            index /= 2;
+            keccak(&data.concat()).0
+        })
+    }
+}
+
 /// Compact Merkle Subtree.
 /// For leaves, this represents only the leaf node with height 1 and the hash of the leaf.
 /// For bigger subtrees, this represents the entire balanced subtree with its height and merkle hash.
@@ -357,6 +418,43 @@ mod tests {
         assert_eq!(merkle_tree.subtrees.len(), 0);
     }
 
+    #[test]
+    fn merkle_proofs() {
+        for leaves_count in 1..=17 {
+            let leaves: Vec<_> = (0..leaves_count).map(hash).collect();
+
+            let mut merkle_tree = StreamCompactMerkleTree::new();
+            for leaf in &leaves {
+                merkle_tree.add(*leaf);
+            }
+            let merkle_tree_hash = merkle_tree.compute_hash();
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = MerkleProof::new(&leaves, index).unwrap();
+                assert_eq!(proof.compute_root(*leaf), merkle_tree_hash);
+                // the proof doesn't hold for another leaf
+                assert_ne!(proof.compute_root(hash(100)), merkle_tree_hash);
+            }
+
+            assert!(MerkleProof::new(&leaves, leaves.len()).is_none());
+        }
+    }
+
+    #[test]
+    fn merkle_proof_of_wrong_position() {
+        let leaves: Vec<_> = (1..=4).map(hash).collect();
+        let merkle_tree_hash = hash_concatenation(
+            hash_concatenation(leaves[0], leaves[1]),
+            hash_concatenation(leaves[2], leaves[3]),
+        );
+
+        let mut proof = MerkleProof::new(&leaves, 1).unwrap();
+        assert_eq!(proof.compute_root(leaves[1]), merkle_tree_hash);
+
+        proof.leaf_index = 0;
+        assert_ne!(proof.compute_root(leaves[1]), merkle_tree_hash);
+    }
+
     fn hash(number: u16) -> RawH256 {
         keccak(&number.to_be_bytes()).0
     }
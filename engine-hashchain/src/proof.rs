@@ -0,0 +1,60 @@
+//! Proofs of the inclusion of a transaction in a block of the hashchain.
+//! The proofs are built by the standalone engine, which knows all the transactions of a block,
+//! and can be verified by anyone knowing the published block hashchain, without trusting the
+//! party which built the proof.
+
+use crate::{
+    bloom::Bloom,
+    hashchain::{compute_block_hashchain, compute_tx_hash},
+    merkle::MerkleProof,
+};
+use aurora_engine_types::{
+    account_id::AccountId,
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    types::RawH256,
+    String, Vec,
+};
+
+/// Proof of the inclusion of a transaction in a block of the hashchain.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq, Eq)]
+pub struct TxInclusionProof {
+    pub chain_id: [u8; 32],
+    pub contract_account_id: AccountId,
+    pub block_height: u64,
+    pub previous_block_hashchain: RawH256,
+    /// Logs bloom of all the transactions of the block.
+    pub logs_bloom: Bloom,
+    /// Name of the engine method called by the transaction.
+    pub method_name: String,
+    /// Input of the engine method.
+    pub input: Vec<u8>,
+    /// Output of the engine method.
+    pub output: Vec<u8>,
+    /// Proof of the inclusion of the transaction in the transactions Merkle Tree of the block.
+    pub merkle_proof: MerkleProof,
+}
+
+impl TxInclusionProof {
+    /// Computes the hashchain of the block the transaction is included in according to the proof.
+    #[must_use]
+    pub fn compute_block_hashchain(&self) -> RawH256 {
+        let tx_hash = compute_tx_hash(&self.method_name, &self.input, &self.output);
+        let txs_hash = self.merkle_proof.compute_root(tx_hash);
+
+        compute_block_hashchain(
+            &self.chain_id,
+            self.contract_account_id.as_bytes(),
+            self.block_height,
+            &self.previous_block_hashchain,
+            &txs_hash,
+            &self.logs_bloom,
+        )
+    }
+}
+
+/// Checks the proof against the published hashchain of the block the transaction is claimed to
+/// be included in.
+#[must_use]
+pub fn verify_tx_inclusion(proof: &TxInclusionProof, block_hashchain: &RawH256) -> bool {
+    proof.compute_block_hashchain() == *block_hashchain
+}
@@ -1,6 +1,8 @@
 use crate::{
     bloom::Bloom,
-    hashchain::{Hashchain, HashchainBuilder},
+    hashchain::{compute_tx_hash, Hashchain, HashchainBuilder},
+    merkle::MerkleProof,
+    proof::{verify_tx_inclusion, TxInclusionProof},
 };
 use aurora_engine_types::account_id::AccountId;
 
@@ -175,6 +177,60 @@ fn test_serialization_round_trip() {
     assert_eq!(round_trip, hashchain);
 }
 
+#[test]
+fn test_tx_inclusion_proof() {
+    let chain_id = [1; 32];
+    let contract_account_id: AccountId = "aurora".parse().unwrap();
+    let previous_block_hashchain = [7; 32];
+    let block_height = 5;
+    let txs: Vec<_> = (0..5u8)
+        .map(|i| ("submit", vec![i; 10], vec![i; 3]))
+        .collect();
+    let logs_bloom = {
+        let mut bloom = Bloom::default();
+        bloom.accrue(b"log");
+        bloom
+    };
+
+    let mut hashchain = HashchainBuilder::default()
+        .with_account_id(contract_account_id.clone())
+        .with_chain_id(chain_id)
+        .with_current_block_height(block_height)
+        .with_previous_hashchain(previous_block_hashchain)
+        .build();
+    for (method_name, input, output) in &txs {
+        hashchain
+            .add_block_tx(block_height, method_name, input, output, &logs_bloom)
+            .unwrap();
+    }
+    hashchain.move_to_block(block_height + 1).unwrap();
+    let block_hashchain = hashchain.get_previous_block_hashchain();
+
+    let leaves: Vec<_> = txs
+        .iter()
+        .map(|(method_name, input, output)| compute_tx_hash(method_name, input, output))
+        .collect();
+
+    for (index, (method_name, input, output)) in txs.iter().enumerate() {
+        let mut proof = TxInclusionProof {
+            chain_id,
+            contract_account_id: contract_account_id.clone(),
+            block_height,
+            previous_block_hashchain,
+            logs_bloom,
+            method_name: (*method_name).to_string(),
+            input: input.clone(),
+            output: output.clone(),
+            merkle_proof: MerkleProof::new(&leaves, index).unwrap(),
+        };
+        assert!(verify_tx_inclusion(&proof, &block_hashchain));
+
+        // A modified transaction is not included in the block
+        proof.output.push(0);
+        assert!(!verify_tx_inclusion(&proof, &block_hashchain));
+    }
+}
+
 fn len_be_bytes(arr: &[u8]) -> [u8; 4] {
     let len = arr.len();
     u32::try_from(len).unwrap().to_be_bytes()
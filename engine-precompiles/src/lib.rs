@@ -143,7 +143,7 @@ impl<'a, I: IO + Copy, E: Env, H: ReadOnlyPromiseHandler> executor::stack::Preco
         }
 
         let result = match self.all_precompiles.get(&address)? {
-            AllPrecompiles::ExitToNear(p) => process_precompile(p, handle),
+            AllPrecompiles::ExitToNear(p) => process_handle_based_precompile(p, handle),
             AllPrecompiles::ExitToEthereum(p) => process_precompile(p, handle),
             AllPrecompiles::PredecessorAccount(p) => process_precompile(p, handle),
             AllPrecompiles::PrepaidGas(p) => process_precompile(p, handle),
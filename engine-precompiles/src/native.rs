@@ -15,7 +15,7 @@ use crate::{
 };
 
 use crate::prelude::types::EthGas;
-use crate::PrecompileOutput;
+use crate::{HandleBasedPrecompile, PrecompileOutput};
 use aurora_engine_types::{
     account_id::AccountId,
     parameters::{
@@ -24,7 +24,8 @@ use aurora_engine_types::{
     types::NEP141Wei,
 };
 use evm::backend::Log;
-use evm::{Context, ExitError};
+use evm::executor::stack::{PrecompileFailure, PrecompileHandle};
+use evm::{Context, ExitError, ExitFatal};
 
 const ERR_TARGET_TOKEN_NOT_FOUND: &str = "Target token not found";
 const UNWRAP_WNEAR_MSG: &str = "unwrap";
@@ -225,6 +226,107 @@ impl<I> ExitToNear<I> {
     }
 }
 
+pub mod native_erc20 {
+    //! Functions for reading state related to ERC-20 tokens native to Aurora.
+    //!
+    //! Such tokens are bridged to a NEP-141 contract deployed by the engine. They are locked
+    //! in the exit to NEAR precompile address when moved to NEAR and unlocked when they return.
+    use crate::prelude::{
+        sdk::io::IO,
+        storage::{bytes_to_key, KeyPrefix},
+        types::Address,
+        Vec,
+    };
+
+    /// Storage key marking the given ERC-20 address as native to Aurora.
+    #[must_use]
+    pub fn native_erc20_key(address: &Address) -> Vec<u8> {
+        bytes_to_key(KeyPrefix::NativeErc20, address.as_bytes())
+    }
+
+    /// Returns true if the ERC-20 token is native to Aurora (rather than bridged from NEAR).
+    pub fn is_native_erc20<I: IO>(io: &I, address: &Address) -> bool {
+        io.storage_has_key(&native_erc20_key(address))
+    }
+}
+
+pub mod denylist {
+    //! Functions for reading the denylist of EVM addresses. The addresses in the list can't
+    //! send transactions, receive assets bridged from NEAR or exit assets to NEAR.
+    use crate::prelude::{
+        sdk::io::IO,
+        storage::{bytes_to_key, KeyPrefix},
+        types::Address,
+        Vec,
+    };
+
+    pub const ERR_DENIED_SENDER: &str = "ERR_DENIED_SENDER";
+
+    /// Storage key marking the given address as denied.
+    #[must_use]
+    pub fn denylist_key(address: &Address) -> Vec<u8> {
+        bytes_to_key(KeyPrefix::Denylist, address.as_bytes())
+    }
+
+    /// Returns true if the address is in the denylist.
+    pub fn is_denied<I: IO>(io: &I, address: &Address) -> bool {
+        io.storage_has_key(&denylist_key(address))
+    }
+}
+
+mod flags {
+    pub(super) const ETH: u8 = 0x0;
+    pub(super) const ERC20: u8 = 0x1;
+    pub(super) const NATIVE_ERC20: u8 = 0x2;
+}
+
+// ETH transfer input format: (85 bytes)
+//  - flag (1 byte)
+//  - refund_address (20 bytes)
+//  - recipient_account_id (max 64 bytes)
+// ERC20 transfer input format: (117 bytes)
+//  - flag (1 byte)
+//  - refund_address (20 bytes)
+//  - amount (32 bytes)
+//  - recipient_account_id (max 64 bytes)
+// Native ERC20 transfer input format: (137 bytes)
+//  - flag (1 byte)
+//  - refund_address (20 bytes)
+//  - erc20_address (20 bytes)
+//  - amount (32 bytes)
+//  - recipient_account_id (max 64 bytes)
+#[cfg(feature = "error_refund")]
+fn parse_input(input: &[u8]) -> Result<(Address, &[u8]), ExitError> {
+    let max = if input.first() == Some(&flags::NATIVE_ERC20) {
+        137
+    } else {
+        117
+    };
+    validate_input_size(input, 21, max)?;
+    let mut buffer = [0; 20];
+    buffer.copy_from_slice(&input[1..21]);
+    let refund_address = Address::from_array(buffer);
+    Ok((refund_address, &input[21..]))
+}
+
+#[cfg(not(feature = "error_refund"))]
+fn parse_input(input: &[u8]) -> Result<&[u8], ExitError> {
+    validate_input_size(input, 3, 117)?;
+    Ok(&input[1..])
+}
+
+/// Splits the native ERC-20 transfer input (without the flag and the refund address)
+/// into the ERC-20 address, the amount and the recipient.
+fn parse_native_erc20_input(input: &[u8]) -> Result<(Address, U256, &[u8]), ExitError> {
+    if input.len() < 52 {
+        return Err(ExitError::Other(Cow::from("ERR_INVALID_INPUT")));
+    }
+    let erc20_address = Address::try_from_slice(&input[..20])
+        .map_err(|_| ExitError::Other(Cow::from("ERR_WRONG_ADDRESS")))?;
+    let amount = U256::from_big_endian(&input[20..52]);
+    Ok((erc20_address, amount, &input[52..]))
+}
+
 fn validate_input_size(input: &[u8], min: usize, max: usize) -> Result<(), ExitError> {
     if input.len() < min || input.len() > max {
         return Err(ExitError::Other(Cow::from("ERR_INVALID_INPUT")));
@@ -283,29 +385,6 @@ impl<I: IO> Precompile for ExitToNear<I> {
         context: &Context,
         is_static: bool,
     ) -> EvmPrecompileResult {
-        // ETH transfer input format: (85 bytes)
-        //  - flag (1 byte)
-        //  - refund_address (20 bytes)
-        //  - recipient_account_id (max 64 bytes)
-        // ERC20 transfer input format: (117 bytes)
-        //  - flag (1 byte)
-        //  - refund_address (20 bytes)
-        //  - amount (32 bytes)
-        //  - recipient_account_id (max 64 bytes)
-        #[cfg(feature = "error_refund")]
-        fn parse_input(input: &[u8]) -> Result<(Address, &[u8]), ExitError> {
-            validate_input_size(input, 21, 117)?;
-            let mut buffer = [0; 20];
-            buffer.copy_from_slice(&input[1..21]);
-            let refund_address = Address::from_array(buffer);
-            Ok((refund_address, &input[21..]))
-        }
-        #[cfg(not(feature = "error_refund"))]
-        fn parse_input(input: &[u8]) -> Result<&[u8], ExitError> {
-            validate_input_size(input, 3, 117)?;
-            Ok(&input[1..])
-        }
-
         if let Some(target_gas) = target_gas {
             if Self::required_gas(input)? > target_gas {
                 return Err(ExitError::OutOfGas);
@@ -322,6 +401,7 @@ impl<I: IO> Precompile for ExitToNear<I> {
         // First byte of the input is a flag, selecting the behavior to be triggered:
         //      0x0 -> Eth transfer
         //      0x1 -> Erc20 transfer
+        //      0x2 -> Transfer of an Erc20 native to Aurora
         let flag = input[0];
         #[cfg(feature = "error_refund")]
         let (refund_address, mut input) = parse_input(input)?;
@@ -330,7 +410,7 @@ impl<I: IO> Precompile for ExitToNear<I> {
         let current_account_id = self.current_account_id.clone();
 
         let (nep141_address, args, exit_event, method, transfer_near_args) = match flag {
-            0x0 => {
+            flags::ETH => {
                 // ETH transfer
                 //
                 // Input slice format:
@@ -361,7 +441,7 @@ impl<I: IO> Precompile for ExitToNear<I> {
                     )));
                 }
             }
-            0x1 => {
+            flags::ERC20 => {
                 // ERC20 transfer
                 //
                 // This precompile branch is expected to be called from the ERC20 burn function.
@@ -377,6 +457,11 @@ impl<I: IO> Precompile for ExitToNear<I> {
                 }
 
                 let erc20_address = context.caller;
+                // The tokens native to Aurora are not burned, they must be locked by the
+                // `NATIVE_ERC20` transfer instead.
+                if native_erc20::is_native_erc20(&self.io, &Address::new(erc20_address)) {
+                    return Err(ExitError::Other(Cow::from("ERR_NATIVE_ERC20_EXIT")));
+                }
                 let nep141_address = get_nep141_from_erc20(erc20_address.as_bytes(), &self.io)?;
 
                 let amount = U256::from_big_endian(&input[..32]);
@@ -424,11 +509,62 @@ impl<I: IO> Precompile for ExitToNear<I> {
                     transfer_near_args,
                 )
             }
+            flags::NATIVE_ERC20 => {
+                // Transfer of an ERC20 native to Aurora
+                //
+                // This precompile branch is called directly by the token holder. The tokens are
+                // locked in this precompile's address (see `run_with_handle`) and the same amount
+                // is minted by the NEP-141 contract deployed for the token.
+                //
+                // Input slice format:
+                //      erc20_address (20 bytes) - the address of the ERC20 token
+                //      amount (U256 big-endian bytes) - the amount to lock
+                //      recipient_account_id (bytes) - the NEAR recipient account which will receive NEP-141 tokens
+
+                if context.apparent_value != U256::from(0) {
+                    return Err(ExitError::Other(Cow::from(
+                        "ERR_ETH_ATTACHED_FOR_ERC20_EXIT",
+                    )));
+                }
+
+                let (erc20_address, amount, rest) = parse_native_erc20_input(input)?;
+                input = rest;
+                if !native_erc20::is_native_erc20(&self.io, &erc20_address) {
+                    return Err(ExitError::Other(Cow::from("ERR_NOT_NATIVE_ERC20")));
+                }
+                let nep141_address = get_nep141_from_erc20(erc20_address.as_bytes(), &self.io)?;
+
+                validate_amount(amount)?;
+                let recipient = parse_recipient(input)?;
+
+                (
+                    nep141_address,
+                    // There is no way to inject json, given the encoding of both arguments
+                    // as decimal and valid account id respectively.
+                    format!(
+                        r#"{{"account_id": "{}", "amount": "{}"}}"#,
+                        recipient.receiver_account_id,
+                        amount.as_u128()
+                    ),
+                    events::ExitToNear {
+                        sender: Address::new(context.caller),
+                        erc20_address,
+                        dest: recipient.receiver_account_id.to_string(),
+                        amount,
+                    },
+                    "mint",
+                    None,
+                )
+            }
             _ => return Err(ExitError::Other(Cow::from("ERR_INVALID_FLAG"))),
         };
 
+        if denylist::is_denied(&self.io, &exit_event.sender) {
+            return Err(ExitError::Other(Cow::from(denylist::ERR_DENIED_SENDER)));
+        }
+
         #[cfg(feature = "error_refund")]
-        let erc20_address = if flag == 0 {
+        let erc20_address = if flag == flags::ETH {
             None
         } else {
             Some(exit_event.erc20_address)
@@ -490,6 +626,75 @@ impl<I: IO> Precompile for ExitToNear<I> {
     }
 }
 
+impl<I: IO> HandleBasedPrecompile for ExitToNear<I> {
+    fn run_with_handle(
+        &self,
+        handle: &mut impl PrecompileHandle,
+    ) -> Result<PrecompileOutput, PrecompileFailure> {
+        let output = self
+            .run(
+                handle.input(),
+                handle.gas_limit().map(EthGas::new),
+                handle.context(),
+                handle.is_static(),
+            )
+            .map_err(|exit_status| match exit_status {
+                // The ERC-20 tokens are burnt by the token contract before it calls the
+                // precompile, and the legacy contracts ignore the result of the call. So the
+                // rejection must abort the whole transaction instead of the precompile call only.
+                ExitError::Other(message) if message == denylist::ERR_DENIED_SENDER => {
+                    PrecompileFailure::Fatal {
+                        exit_status: ExitFatal::Other(message),
+                    }
+                }
+                exit_status => PrecompileFailure::Error { exit_status },
+            })?;
+
+        if handle.input().first() == Some(&flags::NATIVE_ERC20) {
+            // The input was validated by `run` above.
+            #[cfg(feature = "error_refund")]
+            let (_, input) = parse_input(handle.input())
+                .map_err(|exit_status| PrecompileFailure::Error { exit_status })?;
+            #[cfg(not(feature = "error_refund"))]
+            let input = parse_input(handle.input())
+                .map_err(|exit_status| PrecompileFailure::Error { exit_status })?;
+            let (erc20_address, amount, _) = parse_native_erc20_input(input)
+                .map_err(|exit_status| PrecompileFailure::Error { exit_status })?;
+            let sender = handle.context().caller;
+            lock_native_erc20(handle, erc20_address, sender, amount)?;
+        }
+
+        Ok(output)
+    }
+}
+
+/// Moves `amount` of the native ERC-20 token from `sender` to the exit to NEAR precompile address.
+/// The sender must have approved the precompile address to spend the tokens beforehand.
+fn lock_native_erc20(
+    handle: &mut impl PrecompileHandle,
+    erc20_address: Address,
+    sender: crate::prelude::H160,
+    amount: U256,
+) -> Result<(), PrecompileFailure> {
+    let tx_data = crate::xcc::transfer_from_args(sender, exit_to_near::ADDRESS.raw(), amount);
+    let context = Context {
+        address: erc20_address.raw(),
+        caller: exit_to_near::ADDRESS.raw(),
+        apparent_value: U256::zero(),
+    };
+    let (exit_reason, return_value) =
+        handle.call(erc20_address.raw(), None, tx_data, None, false, &context);
+    match exit_reason {
+        evm::ExitReason::Succeed(_) => Ok(()),
+        evm::ExitReason::Revert(r) => Err(PrecompileFailure::Revert {
+            exit_status: r,
+            output: return_value,
+        }),
+        evm::ExitReason::Error(e) => Err(PrecompileFailure::Error { exit_status: e }),
+        evm::ExitReason::Fatal(f) => Err(PrecompileFailure::Fatal { exit_status: f }),
+    }
+}
+
 pub struct ExitToEthereum<I> {
     current_account_id: AccountId,
     io: I,
@@ -598,6 +803,11 @@ impl<I: IO> Precompile for ExitToEthereum<I> {
                 }
 
                 let erc20_address = context.caller;
+                // The tokens native to Aurora are not burned, they must be locked by the
+                // `NATIVE_ERC20` transfer instead.
+                if native_erc20::is_native_erc20(&self.io, &Address::new(erc20_address)) {
+                    return Err(ExitError::Other(Cow::from("ERR_NATIVE_ERC20_EXIT")));
+                }
                 let nep141_address = get_nep141_from_erc20(erc20_address.as_bytes(), &self.io)?;
 
                 let amount = U256::from_big_endian(&input[..32]);
@@ -673,7 +883,8 @@ impl<I: IO> Precompile for ExitToEthereum<I> {
 #[cfg(test)]
 mod tests {
     use super::{
-        exit_to_ethereum, exit_to_near, parse_recipient, validate_amount, validate_input_size,
+        exit_to_ethereum, exit_to_near, parse_native_erc20_input, parse_recipient, validate_amount,
+        validate_input_size,
     };
     use crate::{native::Recipient, prelude::sdk::types::near_account_to_evm_address};
     use aurora_engine_types::U256;
@@ -742,6 +953,22 @@ mod tests {
         validate_amount(U256::from(u128::MAX)).unwrap();
     }
 
+    #[test]
+    fn test_parse_native_erc20_input() {
+        let erc20_address = [0x11; 20];
+        let amount = U256::from(1_000u64);
+        let mut amount_bytes = [0u8; 32];
+        amount.to_big_endian(&mut amount_bytes);
+        let input = [&erc20_address[..], &amount_bytes, b"test.near"].concat();
+
+        let (address, parsed_amount, recipient) = parse_native_erc20_input(&input).unwrap();
+        assert_eq!(address.as_bytes(), &erc20_address);
+        assert_eq!(parsed_amount, amount);
+        assert_eq!(recipient, b"test.near");
+
+        assert!(parse_native_erc20_input(&input[..51]).is_err());
+    }
+
     #[test]
     fn test_parse_recipient() {
         assert_eq!(
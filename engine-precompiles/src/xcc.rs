@@ -9,7 +9,10 @@ use aurora_engine_types::{
     account_id::AccountId,
     borsh::{BorshDeserialize, BorshSerialize},
     format,
-    parameters::{CrossContractCallArgs, PromiseCreateArgs},
+    parameters::{
+        xcc::SubAccountKey, CrossContractCallArgs, NearPromise, PromiseAction, PromiseArgs,
+        PromiseBatchAction, PromiseCreateArgs, SimpleNearPromise,
+    },
     types::{balance::ZERO_YOCTO, Address, EthGas, NearGas},
     vec, Cow, Vec, H160, H256, U256,
 };
@@ -45,6 +48,20 @@ pub mod costs {
     pub const ROUTER_EXEC_BASE: NearGas = NearGas::new(7_000_000_000_000);
     pub const ROUTER_EXEC_PER_CALLBACK: NearGas = NearGas::new(12_000_000_000_000);
     pub const ROUTER_SCHEDULE: NearGas = NearGas::new(5_000_000_000_000);
+    /// NEAR gas attached to the router call (in addition to `ROUTER_EXEC_BASE`) to cover
+    /// creating a sub-account and adding its access keys.
+    pub const ROUTER_CREATE_SUB_ACCOUNT: NearGas = NearGas::new(20_000_000_000_000);
+    /// NEAR gas attached to the router call (in addition to `ROUTER_EXEC_BASE` and
+    /// `ROUTER_INITIALIZE`) made by the engine to deploy the router code to a sub-account.
+    pub const ROUTER_DEPLOY_SUB_ACCOUNT: NearGas = NearGas::new(20_000_000_000_000);
+    /// NEAR gas attached to the `initialize` call of a newly deployed router contract.
+    pub const ROUTER_INITIALIZE: NearGas = NearGas::new(15_000_000_000_000);
+
+    /// Total NEAR gas of the router call deploying the router code to a sub-account.
+    #[must_use]
+    pub fn router_deploy_sub_account() -> NearGas {
+        ROUTER_EXEC_BASE + ROUTER_DEPLOY_SUB_ACCOUNT + ROUTER_INITIALIZE
+    }
 }
 
 mod consts {
@@ -52,6 +69,8 @@ mod consts {
     pub(super) const ERR_SERIALIZE: &str = "ERR_XCC_CALL_SERIALIZE";
     pub(super) const ERR_STATIC: &str = "ERR_INVALID_IN_STATIC";
     pub(super) const ERR_DELEGATE: &str = "ERR_INVALID_IN_DELEGATE";
+    pub(super) const ERR_INVALID_SUB_ACCOUNT: &str = "ERR_INVALID_XCC_SUB_ACCOUNT";
+    pub(super) const ERR_SUB_ACCOUNT_BALANCE: &str = "ERR_INSUFFICIENT_XCC_SUB_ACCOUNT_BALANCE";
     pub(super) const ROUTER_EXEC_NAME: &str = "execute";
     pub(super) const ROUTER_SCHEDULE_NAME: &str = "schedule";
     /// Solidity selector for the ERC-20 transferFrom function
@@ -91,6 +110,13 @@ pub mod cross_contract_call {
         0x0072657175697265645f6e656172,
         0x0072657175697265645f6e656172,
     );
+
+    /// Topic of the log emitted when a sub-account of a router is created or its keys are
+    /// updated. The second topic is the address of the caller and the log data is the
+    /// UTF-8 encoded id of the sub-account. The log precedes the one of the promise acting on
+    /// the sub-account, so that the engine can deploy the router code to it if needed.
+    pub const SUB_ACCOUNT_TOPIC: H256 =
+        crate::make_h256(0x007375625f6163636f756e74, 0x007375625f6163636f756e74);
 }
 
 impl<I: IO> HandleBasedPrecompile for CrossContractCall<I> {
@@ -133,6 +159,7 @@ impl<I: IO> HandleBasedPrecompile for CrossContractCall<I> {
         let target_account_id = create_target_account_id(sender, self.engine_account_id.as_ref());
         let args = CrossContractCallArgs::try_from_slice(input)
             .map_err(|_| ExitError::Other(Cow::from(consts::ERR_INVALID_INPUT)))?;
+        let mut sub_account_id = None;
         let (promise, attached_near) = match args {
             CrossContractCallArgs::Eager(call) => {
                 let call_gas = call.total_gas();
@@ -166,8 +193,85 @@ impl<I: IO> HandleBasedPrecompile for CrossContractCall<I> {
                 };
                 (promise, attached_near)
             }
+            CrossContractCallArgs::CreateSubAccount(args) => {
+                let account_id = create_sub_account_id(&args.name, &target_account_id)
+                    .ok_or_else(|| revert_with_message(consts::ERR_INVALID_SUB_ACCOUNT))?;
+                // The initial balance covers the storage of the router code the engine deploys
+                // to the sub-account once it is created.
+                if args.initial_balance < state::STORAGE_AMOUNT {
+                    return Err(revert_with_message(consts::ERR_SUB_ACCOUNT_BALANCE));
+                }
+                let mut actions = vec![
+                    PromiseAction::CreateAccount,
+                    PromiseAction::Transfer {
+                        amount: args.initial_balance,
+                    },
+                ];
+                actions.extend(args.keys.iter().map(SubAccountKey::to_action));
+                let call = PromiseArgs::Recursive(NearPromise::Simple(SimpleNearPromise::Batch(
+                    PromiseBatchAction {
+                        target_account_id: account_id.clone(),
+                        actions,
+                    },
+                )));
+                let attached_near = call.total_near();
+                let promise = PromiseCreateArgs {
+                    target_account_id,
+                    method: consts::ROUTER_EXEC_NAME.into(),
+                    args: call
+                        .try_to_vec()
+                        .map_err(|_| ExitError::Other(Cow::from(consts::ERR_SERIALIZE)))?,
+                    attached_balance: ZERO_YOCTO,
+                    attached_gas: costs::ROUTER_EXEC_BASE + costs::ROUTER_CREATE_SUB_ACCOUNT,
+                };
+                sub_account_id = Some(account_id);
+                (promise, attached_near)
+            }
+            CrossContractCallArgs::UpdateSubAccountKeys(args) => {
+                let account_id = create_sub_account_id(&args.name, &target_account_id)
+                    .ok_or_else(|| revert_with_message(consts::ERR_INVALID_SUB_ACCOUNT))?;
+                // The router of the sub-account changes its own keys.
+                let batch = PromiseArgs::Recursive(NearPromise::Simple(SimpleNearPromise::Batch(
+                    PromiseBatchAction {
+                        target_account_id: account_id.clone(),
+                        actions: args.to_actions(),
+                    },
+                )));
+                let call = PromiseArgs::Create(PromiseCreateArgs {
+                    target_account_id: account_id.clone(),
+                    method: consts::ROUTER_EXEC_NAME.into(),
+                    args: batch
+                        .try_to_vec()
+                        .map_err(|_| ExitError::Other(Cow::from(consts::ERR_SERIALIZE)))?,
+                    attached_balance: ZERO_YOCTO,
+                    attached_gas: costs::ROUTER_EXEC_BASE,
+                });
+                let promise = PromiseCreateArgs {
+                    target_account_id,
+                    method: consts::ROUTER_EXEC_NAME.into(),
+                    args: call
+                        .try_to_vec()
+                        .map_err(|_| ExitError::Other(Cow::from(consts::ERR_SERIALIZE)))?,
+                    attached_balance: ZERO_YOCTO,
+                    attached_gas: costs::ROUTER_EXEC_BASE + call.total_gas(),
+                };
+                sub_account_id = Some(account_id);
+                (promise, ZERO_YOCTO)
+            }
         };
         cost += EthGas::new(promise.attached_gas.as_u64() / costs::CROSS_CONTRACT_CALL_NEAR_GAS);
+        if let Some(account_id) = &sub_account_id {
+            // The engine deploys the router code to sub-accounts without an up-to-date version.
+            let latest_version = state::get_latest_code_version(&self.io);
+            if state::get_code_version_of_sub_account(&self.io, account_id)
+                .map_or(true, |version| version < latest_version)
+            {
+                cost += EthGas::new(
+                    costs::router_deploy_sub_account().as_u64()
+                        / costs::CROSS_CONTRACT_CALL_NEAR_GAS,
+                );
+            }
+        }
         check_cost(cost)?;
 
         let required_near =
@@ -227,8 +331,22 @@ impl<I: IO> HandleBasedPrecompile for CrossContractCall<I> {
                 .map_err(|_| ExitError::Other(Cow::from(consts::ERR_SERIALIZE)))?,
         };
 
+        // The sub-account log must come first, see `SUB_ACCOUNT_TOPIC`.
+        let mut logs = Vec::with_capacity(2);
+        if let Some(account_id) = sub_account_id {
+            logs.push(Log {
+                address: cross_contract_call::ADDRESS.raw(),
+                topics: vec![
+                    cross_contract_call::SUB_ACCOUNT_TOPIC,
+                    encode_address(sender),
+                ],
+                data: account_id.as_bytes().to_vec(),
+            });
+        }
+        logs.push(promise_log);
+
         Ok(PrecompileOutput {
-            logs: vec![promise_log],
+            logs,
             cost,
             ..Default::default()
         })
@@ -240,6 +358,7 @@ pub mod state {
 
     use aurora_engine_sdk::error::ReadU32Error;
     use aurora_engine_sdk::io::{StorageIntermediate, IO};
+    use aurora_engine_types::account_id::AccountId;
     use aurora_engine_types::parameters::xcc::CodeVersion;
     use aurora_engine_types::storage::{self, KeyPrefix};
     use aurora_engine_types::types::{Address, Yocto};
@@ -276,6 +395,21 @@ pub mod state {
         read_version(io, &key)
     }
 
+    /// Get the version of the router deployed to the given sub-account of a router (if it exists).
+    pub fn get_code_version_of_sub_account<I: IO>(
+        io: &I,
+        account_id: &AccountId,
+    ) -> Option<CodeVersion> {
+        get_code_version_of_address(io, &sub_account_address(account_id))
+    }
+
+    /// The versions of the routers deployed to sub-accounts are stored like the ones of the
+    /// routers of addresses, under an address derived from the id of the sub-account.
+    #[must_use]
+    pub fn sub_account_address(account_id: &AccountId) -> Address {
+        aurora_engine_sdk::types::near_account_to_evm_address(account_id.as_bytes())
+    }
+
     /// Private utility method for reading code version from storage.
     fn read_version<I: IO>(io: &I, key: &[u8]) -> Option<CodeVersion> {
         match io.read_u32(key) {
@@ -286,7 +420,7 @@ pub mod state {
     }
 }
 
-fn transfer_from_args(from: H160, to: H160, amount: U256) -> Vec<u8> {
+pub(crate) fn transfer_from_args(from: H160, to: H160, amount: U256) -> Vec<u8> {
     let args = ethabi::encode(&[
         ethabi::Token::Address(from),
         ethabi::Token::Address(to),
@@ -301,6 +435,20 @@ fn create_target_account_id(sender: H160, engine_account_id: &str) -> AccountId
         .unwrap_or_default()
 }
 
+fn encode_address(a: H160) -> H256 {
+    let mut result = [0u8; 32];
+    result[12..].copy_from_slice(a.as_bytes());
+    H256(result)
+}
+
+/// Returns the id `{name}.{router}` if `name` is a single valid NEAR account id part.
+fn create_sub_account_id(name: &str, router_account_id: &AccountId) -> Option<AccountId> {
+    if name.is_empty() || name.contains('.') {
+        return None;
+    }
+    format!("{name}.{router_account_id}").parse().ok()
+}
+
 fn revert_with_message(message: &str) -> PrecompileFailure {
     PrecompileFailure::Revert {
         exit_status: evm::ExitRevert::Reverted,
@@ -312,7 +460,7 @@ fn revert_with_message(message: &str) -> PrecompileFailure {
 mod tests {
     use crate::prelude::sdk::types::near_account_to_evm_address;
     use crate::xcc::cross_contract_call;
-    use aurora_engine_types::{vec, H160, U256};
+    use aurora_engine_types::{account_id::AccountId, vec, H160, U256};
     use rand::Rng;
 
     #[test]
@@ -323,6 +471,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_create_sub_account_id() {
+        let router: AccountId = "000000000000000000000000000000000000000a.aurora"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            super::create_sub_account_id("dao", &router)
+                .unwrap()
+                .as_ref(),
+            "dao.000000000000000000000000000000000000000a.aurora"
+        );
+        assert!(super::create_sub_account_id("", &router).is_none());
+        assert!(super::create_sub_account_id("a.dao", &router).is_none());
+        assert!(super::create_sub_account_id("DAO", &router).is_none());
+    }
+
     #[test]
     fn test_transfer_from_encoding() {
         let mut rng = rand::thread_rng();
@@ -1,6 +1,7 @@
 use crate::error::{OneYoctoAttachError, PrivateCallError};
 use crate::prelude::{NearGas, H256};
 use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::public_key::PublicKey;
 
 pub const DEFAULT_PREPAID_GAS: NearGas = NearGas::new(300_000_000_000_000);
 
@@ -37,6 +38,10 @@ impl Timestamp {
 pub trait Env {
     /// Account ID that signed the transaction.
     fn signer_account_id(&self) -> AccountId;
+    /// Public key of the access key that signed the transaction, if it is known.
+    fn signer_account_pk(&self) -> Option<PublicKey> {
+        None
+    }
     /// Account ID of the currently executing contract.
     fn current_account_id(&self) -> AccountId;
     /// Account ID which called the current contract.
@@ -74,6 +79,7 @@ pub trait Env {
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct Fixed {
     pub signer_account_id: AccountId,
+    pub signer_account_pk: Option<PublicKey>,
     pub current_account_id: AccountId,
     pub predecessor_account_id: AccountId,
     pub block_height: u64,
@@ -88,6 +94,10 @@ impl Env for Fixed {
         self.signer_account_id.clone()
     }
 
+    fn signer_account_pk(&self) -> Option<PublicKey> {
+        self.signer_account_pk
+    }
+
     fn current_account_id(&self) -> AccountId {
         self.current_account_id.clone()
     }
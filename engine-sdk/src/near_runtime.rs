@@ -2,6 +2,7 @@ use crate::io::StorageIntermediate;
 use crate::prelude::NearGas;
 use crate::promise::PromiseId;
 use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::borsh::BorshDeserialize;
 use aurora_engine_types::parameters::{PromiseAction, PromiseBatchAction, PromiseCreateArgs};
 use aurora_engine_types::public_key::PublicKey;
 use aurora_engine_types::types::PromiseResult;
@@ -224,6 +225,14 @@ impl crate::env::Env for Runtime {
         Self::read_account_id()
     }
 
+    fn signer_account_pk(&self) -> Option<PublicKey> {
+        unsafe {
+            exports::signer_account_pk(Self::ENV_REGISTER_ID.0);
+        }
+        // The public key is written to the register in its borsh representation.
+        PublicKey::try_from_slice(&Self::ENV_REGISTER_ID.to_vec()).ok()
+    }
+
     fn current_account_id(&self) -> AccountId {
         unsafe {
             exports::current_account_id(Self::ENV_REGISTER_ID.0);
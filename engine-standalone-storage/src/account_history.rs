@@ -0,0 +1,258 @@
+//! Queries over the history of the EVM accounts. The values are decoded from the engine keys
+//! changed by the transactions, so the callers do not need to know the layout of the engine
+//! storage. The results are paginated by block height: each query returns the changes made in
+//! the blocks of the `from_height..=to_height` range, ordered by block height and transaction
+//! position.
+
+use crate::{
+    construct_storage_key, diff::DiffValue, error, height_range_bounds, Storage, StoragePrefix,
+    TransactionIncluded,
+};
+use aurora_engine_types::{
+    storage::{address_to_key, storage_to_key, KeyPrefix},
+    types::{Address, Wei},
+    H256, U256,
+};
+
+/// Size of the block height and transaction position suffix of the engine keys in the DB.
+const KEY_SUFFIX_LEN: usize = 10;
+
+/// A value of an account, as set by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry<T> {
+    pub block_height: u64,
+    pub transaction_position: u16,
+    /// Hash of the transaction which made the change. It is zero if the transaction is not
+    /// known, e.g. if the change was imported from a snapshot.
+    pub tx_hash: H256,
+    pub value: T,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodeChange {
+    Deployed(Vec<u8>),
+    /// The code was removed, e.g. by a `SELFDESTRUCT`.
+    Removed,
+    /// The storage generation of the account was bumped, which discards all its storage slots.
+    GenerationBumped(u32),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageChange {
+    /// The storage generation of the account the slot belongs to.
+    pub generation: u32,
+    pub value: H256,
+}
+
+impl Storage {
+    /// Returns the balance of the account after each transaction which changed it.
+    pub fn balance_history(
+        &self,
+        address: &Address,
+        from_height: u64,
+        to_height: u64,
+    ) -> Result<Vec<HistoryEntry<Wei>>, error::Error> {
+        let key = address_to_key(KeyPrefix::Balance, address);
+        self.engine_key_history(&key, from_height, to_height, |value| {
+            decode_u256(&key, &value).map(Wei::new)
+        })
+    }
+
+    /// Returns the nonce of the account after each transaction which changed it.
+    pub fn nonce_history(
+        &self,
+        address: &Address,
+        from_height: u64,
+        to_height: u64,
+    ) -> Result<Vec<HistoryEntry<U256>>, error::Error> {
+        let key = address_to_key(KeyPrefix::Nonce, address);
+        self.engine_key_history(&key, from_height, to_height, |value| {
+            decode_u256(&key, &value)
+        })
+    }
+
+    /// Returns the deployments and removals of the code of the account, and the bumps of its
+    /// storage generation. A `SELFDESTRUCT` results in a `Removed` change followed by a
+    /// `GenerationBumped` one, with the same transaction hash.
+    pub fn code_history(
+        &self,
+        address: &Address,
+        from_height: u64,
+        to_height: u64,
+    ) -> Result<Vec<HistoryEntry<CodeChange>>, error::Error> {
+        let key = address_to_key(KeyPrefix::Code, address);
+        let mut result = self.engine_key_history(&key, from_height, to_height, |value| {
+            Ok(value
+                .take_value()
+                .map_or(CodeChange::Removed, CodeChange::Deployed))
+        })?;
+        result.extend(
+            self.generation_history(address, from_height, to_height)?
+                .into_iter()
+                .map(|entry| HistoryEntry {
+                    block_height: entry.block_height,
+                    transaction_position: entry.transaction_position,
+                    tx_hash: entry.tx_hash,
+                    value: CodeChange::GenerationBumped(entry.value),
+                }),
+        );
+        // The sort is stable, so the code changes stay before the generation bumps of the
+        // same transaction.
+        result.sort_by_key(|entry| (entry.block_height, entry.transaction_position));
+        Ok(result)
+    }
+
+    /// Returns the value of the storage slot of the account after each transaction which
+    /// changed it. A bump of the storage generation of the account resets the slot to zero, so
+    /// it is also part of the history.
+    pub fn storage_history(
+        &self,
+        address: &Address,
+        slot: &H256,
+        from_height: u64,
+        to_height: u64,
+    ) -> Result<Vec<HistoryEntry<StorageChange>>, error::Error> {
+        // The slot may have been written in any of the generations the account had before the
+        // end of the range.
+        let bumps = self.generation_history(address, 0, to_height)?;
+        let mut generations: Vec<u32> = bumps.iter().map(|entry| entry.value).collect();
+        generations.push(0);
+        generations.sort_unstable();
+        generations.dedup();
+
+        let mut result = Vec::new();
+        for generation in generations {
+            let key = storage_to_key(address, slot, generation);
+            result.extend(self.engine_key_history(
+                key.as_ref(),
+                from_height,
+                to_height,
+                |value| {
+                    let value = match value {
+                        DiffValue::Modified(bytes) if bytes.len() == 32 => H256::from_slice(&bytes),
+                        DiffValue::Modified(_) => {
+                            return Err(error::Error::EngineValueCorrupted(key.as_ref().to_vec()))
+                        }
+                        DiffValue::Deleted => H256::zero(),
+                    };
+                    Ok(StorageChange { generation, value })
+                },
+            )?);
+        }
+        result.extend(
+            bumps
+                .into_iter()
+                .filter(|entry| entry.block_height >= from_height)
+                .map(|entry| HistoryEntry {
+                    block_height: entry.block_height,
+                    transaction_position: entry.transaction_position,
+                    tx_hash: entry.tx_hash,
+                    value: StorageChange {
+                        generation: entry.value,
+                        value: H256::zero(),
+                    },
+                }),
+        );
+        result.sort_by_key(|entry| (entry.block_height, entry.transaction_position));
+        Ok(result)
+    }
+
+    fn generation_history(
+        &self,
+        address: &Address,
+        from_height: u64,
+        to_height: u64,
+    ) -> Result<Vec<HistoryEntry<u32>>, error::Error> {
+        let key = address_to_key(KeyPrefix::Generation, address);
+        self.engine_key_history(&key, from_height, to_height, |value| match value {
+            DiffValue::Modified(bytes) => <[u8; 4]>::try_from(bytes.as_slice())
+                .map(u32::from_be_bytes)
+                .map_err(|_| error::Error::EngineValueCorrupted(key.to_vec())),
+            DiffValue::Deleted => Ok(0),
+        })
+    }
+
+    fn engine_key_history<T, F>(
+        &self,
+        engine_key: &[u8],
+        from_height: u64,
+        to_height: u64,
+        decode: F,
+    ) -> Result<Vec<HistoryEntry<T>>, error::Error>
+    where
+        F: Fn(DiffValue) -> Result<T, error::Error>,
+    {
+        let db_key_prefix = construct_storage_key(StoragePrefix::Engine, engine_key);
+        let n = db_key_prefix.len();
+        let (lower_bound, upper_bound) =
+            height_range_bounds(&db_key_prefix, from_height, to_height);
+        let mut opt = rocksdb::ReadOptions::default();
+        opt.set_iterate_upper_bound(upper_bound);
+        opt.set_iterate_lower_bound(lower_bound);
+
+        let mut result = Vec::new();
+        // The keys are ordered by block height, so each block hash is only read once.
+        let mut cached_block_hash: Option<(u64, Option<H256>)> = None;
+        for maybe_elem in self.db.iterator_opt(rocksdb::IteratorMode::Start, opt) {
+            let (k, v) = maybe_elem?;
+            // Longer engine keys starting with the same bytes are in the range too.
+            if k.len() != n + KEY_SUFFIX_LEN {
+                continue;
+            }
+            let block_height = {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(&k[n..(n + 8)]);
+                u64::from_be_bytes(buf)
+            };
+            let transaction_position = {
+                let mut buf = [0u8; 2];
+                buf.copy_from_slice(&k[(n + 8)..(n + 10)]);
+                u16::from_be_bytes(buf)
+            };
+            if cached_block_hash.map(|(height, _)| height) != Some(block_height) {
+                let block_hash = match self.get_block_hash_by_height(block_height) {
+                    Ok(block_hash) => Some(block_hash),
+                    Err(error::Error::NoBlockAtHeight(_)) => None,
+                    Err(e) => return Err(e),
+                };
+                cached_block_hash = Some((block_height, block_hash));
+            }
+            let block_hash = cached_block_hash.and_then(|(_, block_hash)| block_hash);
+            let tx_hash = self.tx_hash_or_zero(block_hash, transaction_position)?;
+            result.push(HistoryEntry {
+                block_height,
+                transaction_position,
+                tx_hash,
+                value: decode(DiffValue::try_from_bytes(&v)?)?,
+            });
+        }
+        Ok(result)
+    }
+
+    /// Returns the hash of the transaction at the position of the block, or zero if the
+    /// transaction is not known.
+    fn tx_hash_or_zero(
+        &self,
+        block_hash: Option<H256>,
+        position: u16,
+    ) -> Result<H256, error::Error> {
+        let Some(block_hash) = block_hash else {
+            return Ok(H256::zero());
+        };
+        match self.get_transaction_by_position(TransactionIncluded {
+            block_hash,
+            position,
+        }) {
+            Err(error::Error::TransactionNotFound(_)) => Ok(H256::zero()),
+            other => other,
+        }
+    }
+}
+
+fn decode_u256(key: &[u8], value: &DiffValue) -> Result<U256, error::Error> {
+    match value {
+        DiffValue::Modified(bytes) if bytes.len() == 32 => Ok(U256::from_big_endian(bytes)),
+        DiffValue::Modified(_) => Err(error::Error::EngineValueCorrupted(key.to_vec())),
+        DiffValue::Deleted => Ok(U256::zero()),
+    }
+}
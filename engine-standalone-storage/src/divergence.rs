@@ -0,0 +1,120 @@
+//! Detection of the divergences between the standalone execution of the transactions and their
+//! execution on NEAR. Without it, a transaction producing a different outcome in the standalone
+//! engine silently corrupts the state of the storage, and every later transaction touching the
+//! same accounts builds on top of the corrupted state.
+
+use crate::sync::{error, types::TransactionMessage, TransactionExecutionResult};
+use aurora_engine::parameters::{ResultLog, SubmitResult, TransactionStatus};
+use aurora_engine_types::{
+    borsh::{self, BorshDeserialize, BorshSerialize},
+    types::RawH256,
+    H256,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum Divergence {
+    /// The receipt succeeded on NEAR while the engine failed to execute the transaction
+    /// locally, or the other way around.
+    Status { on_chain: bool, local: bool },
+    /// The EVM execution status of the transaction differs.
+    SubmitStatus {
+        on_chain: TransactionStatus,
+        local: TransactionStatus,
+    },
+    /// The EVM gas used by the transaction differs.
+    Gas { on_chain: u64, local: u64 },
+    /// The EVM logs emitted by the transaction differ.
+    Logs {
+        on_chain: Vec<ResultLog>,
+        local: Vec<ResultLog>,
+    },
+    /// The bytes returned by the engine differ.
+    Output { on_chain: Vec<u8>, local: Vec<u8> },
+}
+
+/// The divergences of a transaction, as stored by `Storage::set_divergence_record`.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct DivergenceRecord {
+    pub block_height: u64,
+    pub transaction_position: u16,
+    pub near_receipt_id: RawH256,
+    pub tx_hash: RawH256,
+    pub divergences: Vec<Divergence>,
+    /// The logs emitted by the receipt on NEAR, which usually explain why it failed.
+    pub on_chain_logs: Vec<String>,
+}
+
+/// Compares the outcome of the local execution of the transaction with its on-chain outcome.
+/// Returns `None` if they match, or if the on-chain outcome is not part of the message.
+#[must_use]
+pub fn detect_divergence(
+    block_height: u64,
+    message: &TransactionMessage,
+    tx_hash: H256,
+    result: &Result<Option<TransactionExecutionResult>, error::Error>,
+    engine_output: &[u8],
+) -> Option<DivergenceRecord> {
+    let on_chain = message.on_chain_outcome.as_ref()?;
+    let local_succeeded = !matches!(
+        result,
+        Err(_) | Ok(Some(TransactionExecutionResult::Submit(Err(_))))
+    );
+    let local_submit_result: Option<&SubmitResult> = match result {
+        Ok(Some(TransactionExecutionResult::Submit(Ok(submit_result)))) => Some(submit_result),
+        _ => None,
+    };
+
+    let mut divergences = Vec::new();
+    if message.succeeded != local_succeeded {
+        divergences.push(Divergence::Status {
+            on_chain: message.succeeded,
+            local: local_succeeded,
+        });
+    }
+    // Only the EVM transactions return a `SubmitResult`.
+    let on_chain_submit_result = local_submit_result
+        .and(on_chain.output.as_deref())
+        .and_then(|output| SubmitResult::try_from_slice(output).ok());
+    if let (Some(on_chain), Some(local)) = (&on_chain_submit_result, local_submit_result) {
+        if on_chain.status != local.status {
+            divergences.push(Divergence::SubmitStatus {
+                on_chain: on_chain.status.clone(),
+                local: local.status.clone(),
+            });
+        }
+        if on_chain.gas_used != local.gas_used {
+            divergences.push(Divergence::Gas {
+                on_chain: on_chain.gas_used,
+                local: local.gas_used,
+            });
+        }
+        if on_chain.logs != local.logs {
+            divergences.push(Divergence::Logs {
+                on_chain: on_chain.logs.clone(),
+                local: local.logs.clone(),
+            });
+        }
+    }
+    // A failed receipt does not return anything.
+    if let Some(output) = on_chain.output.as_ref().filter(|_| message.succeeded) {
+        if local_succeeded && output != engine_output {
+            divergences.push(Divergence::Output {
+                on_chain: output.clone(),
+                local: engine_output.to_vec(),
+            });
+        }
+    }
+
+    if divergences.is_empty() {
+        return None;
+    }
+
+    Some(DivergenceRecord {
+        block_height,
+        transaction_position: message.position,
+        near_receipt_id: message.near_receipt_id.0,
+        tx_hash: tx_hash.0,
+        divergences,
+        on_chain_logs: on_chain.logs.clone(),
+    })
+}
@@ -40,6 +40,8 @@ pub struct EngineStateAccess<'db, 'input, 'output> {
     bound_block_height: u64,
     bound_tx_position: u16,
     transaction_diff: &'output RefCell<Diff>,
+    /// Changes not committed to the DB yet, read through before the DB.
+    base_diff: &'output Diff,
     output: &'output Cell<Vec<u8>>,
     db: &'db DB,
 }
@@ -50,6 +52,7 @@ impl<'db, 'input, 'output> EngineStateAccess<'db, 'input, 'output> {
         bound_block_height: u64,
         bound_tx_position: u16,
         transaction_diff: &'output RefCell<Diff>,
+        base_diff: &'output Diff,
         output: &'output Cell<Vec<u8>>,
         db: &'db DB,
     ) -> Self {
@@ -58,6 +61,7 @@ impl<'db, 'input, 'output> EngineStateAccess<'db, 'input, 'output> {
             bound_block_height,
             bound_tx_position,
             transaction_diff,
+            base_diff,
             output,
             db,
         }
@@ -96,6 +100,11 @@ impl<'db, 'input: 'db, 'output: 'db> IO for EngineStateAccess<'db, 'input, 'outp
                 .value()
                 .map(|bytes| EngineStorageValue::Vec(bytes.to_vec()));
         }
+        if let Some(diff) = self.base_diff.get(key) {
+            return diff
+                .value()
+                .map(|bytes| EngineStorageValue::Vec(bytes.to_vec()));
+        }
 
         let opt = self.construct_engine_read(key);
         let mut iter = self.db.iterator_opt(rocksdb::IteratorMode::End, opt);
@@ -12,6 +12,15 @@ pub enum Error {
     Rocksdb(rocksdb::Error),
     EngineAccountIdNotSet,
     EngineAccountIdCorrupted,
+    /// The block does not have a hashchain transaction at the given index.
+    HashchainTxNotFound(u64, usize),
+    /// The proof does not lead to the block hashchain computed by the engine.
+    HashchainProofMismatch(u64),
+    BlockHashchainNotFound(u64),
+    /// The hashchain state cannot be moved to the block at the given height.
+    HashchainBlockHeightIncorrect(u64),
+    /// The value of the engine key cannot be decoded.
+    EngineValueCorrupted(Vec<u8>),
 }
 
 impl From<rocksdb::Error> for Error {
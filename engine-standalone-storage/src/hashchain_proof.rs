@@ -0,0 +1,99 @@
+//! Construction of the proofs of inclusion of transactions in the block hashchain.
+//! The engine only keeps the compact form of the transactions Merkle tree, so the leaves of
+//! a block are rebuilt by re-executing its hashchain transactions against the stored state.
+
+use crate::{
+    error,
+    sync::{self, types::TransactionKindTag},
+    Diff, DiffValue, Storage, TransactionIncluded,
+};
+use aurora_engine::hashchain::HASHCHAIN_STATE;
+use aurora_engine_hashchain::{
+    hashchain::{compute_tx_hash, Hashchain},
+    merkle::MerkleProof,
+    proof::TxInclusionProof,
+    wrapped_io::{CachedIO, IOCache},
+};
+use aurora_engine_modexp::ModExpAlgorithm;
+use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
+use std::cell::RefCell;
+
+/// Builds the proof of inclusion of the `tx_index`-th hashchain transaction of the block at
+/// `block_height`. Only the transactions recorded in the hashchain are counted, i.e. the ones
+/// which updated the hashchain state.
+pub fn compute_tx_inclusion_proof<M: ModExpAlgorithm + 'static>(
+    storage: &Storage,
+    block_height: u64,
+    tx_index: usize,
+) -> Result<TxInclusionProof, error::Error> {
+    let block_hash = storage.get_block_hash_by_height(block_height)?;
+    let block_metadata = storage.get_block_metadata(block_hash)?;
+    let engine_account_id = storage.get_engine_account_id()?;
+    let hashchain_key = bytes_to_key(KeyPrefix::Hashchain, HASHCHAIN_STATE);
+
+    let mut leaves = Vec::new();
+    let mut target = None;
+    let mut block_state = None;
+
+    for tx_hash in storage.get_block_transactions(block_hash)? {
+        let message = storage.get_transaction_data(tx_hash)?;
+        let diff = storage.get_transaction_diff(TransactionIncluded {
+            block_hash,
+            position: message.position,
+        })?;
+        let Some(state_bytes) = diff.get(&hashchain_key).and_then(DiffValue::value) else {
+            continue;
+        };
+
+        let cache = RefCell::new(IOCache::default());
+        storage.with_engine_access(block_height, message.position, &message.raw_input, |io| {
+            sync::execute_transaction::<_, M, _>(
+                &message,
+                block_height,
+                &block_metadata,
+                engine_account_id.clone(),
+                CachedIO::new(io, &cache),
+                |_| Diff::default(),
+            )
+        });
+        let IOCache { input, output } = cache.into_inner();
+        let method_name: &'static str = TransactionKindTag::from(&message.transaction).into();
+
+        leaves.push(compute_tx_hash(method_name, &input, &output));
+        if leaves.len() == tx_index + 1 {
+            target = Some((method_name, input, output));
+        }
+        block_state = Some(state_bytes.to_vec());
+    }
+
+    let not_found = || error::Error::HashchainTxNotFound(block_height, tx_index);
+    let (method_name, input, output) = target.ok_or_else(not_found)?;
+    let merkle_proof = MerkleProof::new(&leaves, tx_index).ok_or_else(not_found)?;
+    let mut hashchain = block_state
+        .as_deref()
+        .map(Hashchain::try_deserialize)
+        .ok_or_else(not_found)??;
+
+    let proof = TxInclusionProof {
+        chain_id: *hashchain.get_chain_id(),
+        contract_account_id: hashchain.get_contract_account_id().clone(),
+        block_height,
+        previous_block_hashchain: hashchain.get_previous_block_hashchain(),
+        logs_bloom: *hashchain.get_logs_bloom(),
+        method_name: method_name.into(),
+        input,
+        output,
+        merkle_proof,
+    };
+
+    // Moving the hashchain state to the next block computes the hashchain of this block,
+    // which the proof must lead to.
+    hashchain
+        .move_to_block(block_height + 1)
+        .map_err(|_| error::Error::HashchainProofMismatch(block_height))?;
+    if proof.compute_block_hashchain() != hashchain.get_previous_block_hashchain() {
+        return Err(error::Error::HashchainProofMismatch(block_height));
+    }
+
+    Ok(proof)
+}
@@ -0,0 +1,131 @@
+//! Verification of the block hashchains computed by the standalone engine against the ones
+//! computed on-chain. The blocks are replayed (re-executed over an in-memory overlay of the
+//! storage, which is never written), so that a divergence introduced by the standalone execution
+//! can be located at the first block it impacts, instead of showing up as a mismatch of the
+//! latest hashchain only.
+
+use crate::{engine_state::EngineStateAccess, error, sync, Diff, Storage, TransactionIncluded};
+use aurora_engine_modexp::ModExpAlgorithm;
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_types::types::RawH256;
+use std::collections::BTreeMap;
+
+/// The first block for which the standalone hashchain does not match the on-chain one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HashchainDivergence {
+    pub block_height: u64,
+    /// The hashchain computed on-chain.
+    pub expected: RawH256,
+    /// The hashchain computed by the standalone engine, `None` if the block is not finalized in
+    /// the standalone storage.
+    pub computed: Option<RawH256>,
+}
+
+/// Replays the blocks in the `from_height..=to_height` range, then compares the block
+/// hashchains computed by the replay with the `expected` ones, indexed by block height.
+/// The hashchains of the blocks which are not finalized by the replayed transactions are read
+/// from the storage. The expected values are typically collected with the `get_latest_hashchain`
+/// view method of the engine, which returns the hashchain of the previous block.
+/// Returns the divergence with the lowest block height, if any.
+pub fn verify_block_hashchains<M: ModExpAlgorithm + 'static>(
+    storage: &Storage,
+    from_height: u64,
+    to_height: u64,
+    expected: &BTreeMap<u64, RawH256>,
+) -> Result<Option<HashchainDivergence>, error::Error> {
+    let replayed = replay_block_hashchains::<M>(storage, from_height, to_height)?;
+
+    for (&block_height, &expected) in expected.range(from_height..=to_height) {
+        let computed = match replayed.get(&block_height) {
+            Some(&hashchain) => Some(hashchain),
+            None => match storage.get_block_hashchain(block_height) {
+                Ok(hashchain) => Some(hashchain),
+                Err(error::Error::BlockHashchainNotFound(_)) => None,
+                Err(e) => return Err(e),
+            },
+        };
+        if computed != Some(expected) {
+            return Ok(Some(HashchainDivergence {
+                block_height,
+                expected,
+                computed,
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Executes the transactions of the blocks again, each one over the changes of the previous
+/// ones, and returns the hashchains of the blocks they finalize.
+fn replay_block_hashchains<M: ModExpAlgorithm + 'static>(
+    storage: &Storage,
+    from_height: u64,
+    to_height: u64,
+) -> Result<BTreeMap<u64, RawH256>, error::Error> {
+    let engine_account_id = storage.get_engine_account_id()?;
+    let mut overlay = Diff::default();
+    let mut result = BTreeMap::new();
+
+    for block_height in from_height..=to_height {
+        let block_hash = match storage.get_block_hash_by_height(block_height) {
+            Ok(block_hash) => block_hash,
+            Err(error::Error::NoBlockAtHeight(_)) => continue,
+            Err(e) => return Err(e),
+        };
+        let block_metadata = storage.get_block_metadata(block_hash)?;
+
+        for tx_hash in storage.get_block_transactions(block_hash)? {
+            let message = storage.get_transaction_data(tx_hash)?;
+            let position = message.position;
+            let previous_diff = storage.get_transaction_diff(TransactionIncluded {
+                block_hash,
+                position,
+            })?;
+            let access = storage.with_engine_access_over_diff(
+                block_height,
+                position,
+                &message.raw_input,
+                &overlay,
+                |io| {
+                    sync::execute_transaction::<_, M, _>(
+                        &message,
+                        block_height,
+                        &block_metadata,
+                        engine_account_id.clone(),
+                        io,
+                        EngineStateAccess::get_transaction_diff,
+                    )
+                },
+            );
+            let (_, diff, _) = access.result;
+
+            result.extend(storage.finalized_block_hashchains(
+                block_height,
+                position,
+                &diff,
+                &overlay,
+            )?);
+
+            // The storage still contains the changes of the previous execution of the
+            // transaction, so the ones the replay didn't make must be undone in the overlay.
+            for (key, _) in previous_diff.iter() {
+                if diff.get(key).is_some() {
+                    continue;
+                }
+                let value = storage
+                    .with_engine_access_over_diff(block_height, position, &[], &overlay, |io| {
+                        io.read_storage(key).map(|value| value.to_vec())
+                    })
+                    .result;
+                match value {
+                    Some(value) => overlay.modify(key.clone(), value),
+                    None => overlay.delete(key.clone()),
+                }
+            }
+            overlay.append(diff);
+        }
+    }
+
+    Ok(result)
+}
@@ -5,8 +5,18 @@
     clippy::missing_panics_doc,
     clippy::missing_errors_doc
 )]
+use aurora_engine::hashchain::HASHCHAIN_STATE;
+use aurora_engine_hashchain::hashchain::Hashchain;
 use aurora_engine_sdk::env::Timestamp;
-use aurora_engine_types::{account_id::AccountId, H256};
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_types::{
+    account_id::AccountId,
+    borsh::{BorshDeserialize, BorshSerialize},
+    storage::{bytes_to_key, KeyPrefix},
+    types::RawH256,
+    H256,
+};
+use divergence::DivergenceRecord;
 use rocksdb::DB;
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
@@ -15,12 +25,19 @@ use sync::types::TransactionMessage;
 
 const VERSION: u8 = 0;
 
+pub mod account_history;
 pub mod diff;
+pub mod divergence;
 pub mod engine_state;
 pub mod error;
+pub mod hashchain_proof;
+pub mod hashchain_verification;
 pub mod json_snapshot;
+pub mod near_lake;
 pub mod promise;
+pub mod promise_emulator;
 pub mod relayer_db;
+pub mod state_change;
 /// Functions for receiving new blocks and transactions to keep the storage up to date.
 pub mod sync;
 
@@ -42,6 +59,9 @@ pub enum StoragePrefix {
     Engine = 0x05,
     BlockMetadata = 0x06,
     EngineAccountId = 0x07,
+    BlockHashchain = 0x08,
+    ImportCheckpoint = 0x09,
+    Divergence = 0x0a,
 }
 
 impl From<StoragePrefix> for u8 {
@@ -55,11 +75,15 @@ impl From<StoragePrefix> for u8 {
             StoragePrefix::Engine => 0x05,
             StoragePrefix::BlockMetadata => 0x06,
             StoragePrefix::EngineAccountId => 0x07,
+            StoragePrefix::BlockHashchain => 0x08,
+            StoragePrefix::ImportCheckpoint => 0x09,
+            StoragePrefix::Divergence => 0x0a,
         }
     }
 }
 
 const ACCOUNT_ID_KEY: &[u8] = b"engine_account_id";
+const RELAYER_IMPORT_KEY: &[u8] = b"relayer_import";
 
 pub struct Storage {
     db: DB,
@@ -87,6 +111,26 @@ impl Storage {
         Ok(account_id)
     }
 
+    /// Records the height of the last block fully imported from the relayer database.
+    pub fn set_relayer_import_checkpoint(
+        &mut self,
+        block_height: u64,
+    ) -> Result<(), rocksdb::Error> {
+        let key = construct_storage_key(StoragePrefix::ImportCheckpoint, RELAYER_IMPORT_KEY);
+        self.db.put(key, block_height.to_be_bytes())
+    }
+
+    /// Returns the height of the last block fully imported from the relayer database, if any.
+    pub fn get_relayer_import_checkpoint(&self) -> Result<Option<u64>, error::Error> {
+        let key = construct_storage_key(StoragePrefix::ImportCheckpoint, RELAYER_IMPORT_KEY);
+        let checkpoint = self.db.get_pinned(key)?.map(|slice| {
+            let mut buf = [0u8; 8];
+            buf.copy_from_slice(slice.as_ref());
+            u64::from_be_bytes(buf)
+        });
+        Ok(checkpoint)
+    }
+
     pub fn get_latest_block(&self) -> Result<(H256, u64), error::Error> {
         self.block_read(rocksdb::IteratorMode::End)
     }
@@ -153,9 +197,83 @@ impl Storage {
         block_height: u64,
         block_metadata: &BlockMetadata,
     ) -> Result<(), rocksdb::Error> {
-        let block_height_bytes = block_height.to_be_bytes();
+        let mut batch = rocksdb::WriteBatch::default();
+        Self::put_block_data(&mut batch, block_hash, block_height, block_metadata);
+        self.db.write(batch)
+    }
 
+    /// Writes the block data and all the transactions of the block (in the order given) in a
+    /// single batch, so that the block is either fully applied or not applied at all. The
+    /// divergence records of all the transactions are written, whether they are included or not.
+    pub fn set_block_included<'a, T>(
+        &mut self,
+        block_hash: H256,
+        block_height: u64,
+        block_metadata: &BlockMetadata,
+        transactions: T,
+    ) -> Result<(), error::Error>
+    where
+        T: IntoIterator<Item = &'a sync::TransactionIncludedOutcome>,
+    {
         let mut batch = rocksdb::WriteBatch::default();
+        Self::put_block_data(&mut batch, block_hash, block_height, block_metadata);
+
+        // The transactions of the block are not in the DB yet, so their changes are read from
+        // `block_diff` by the following ones.
+        let mut block_diff = Diff::default();
+        for outcome in transactions {
+            if let Some(record) = &outcome.divergence {
+                Self::put_divergence_record(&mut batch, record)?;
+            }
+            if !outcome.is_included() {
+                continue;
+            }
+            self.transaction_to_batch(
+                &mut batch,
+                block_height,
+                outcome.hash,
+                &outcome.info,
+                &outcome.diff,
+                &block_diff,
+                |batch, key, value| batch.put(key, value),
+            )?;
+            block_diff.append(outcome.diff.clone());
+        }
+
+        self.db.write(batch).map_err(Into::into)
+    }
+
+    /// Writes the transaction, if it is included, and its divergence record in a single batch.
+    pub fn set_transaction_outcome(
+        &mut self,
+        outcome: &sync::TransactionIncludedOutcome,
+    ) -> Result<(), error::Error> {
+        let mut batch = rocksdb::WriteBatch::default();
+        if let Some(record) = &outcome.divergence {
+            Self::put_divergence_record(&mut batch, record)?;
+        }
+        if outcome.is_included() {
+            let block_height = self.get_block_height_by_hash(outcome.info.block_hash)?;
+            self.transaction_to_batch(
+                &mut batch,
+                block_height,
+                outcome.hash,
+                &outcome.info,
+                &outcome.diff,
+                &Diff::default(),
+                |batch, key, value| batch.put(key, value),
+            )?;
+        }
+        self.db.write(batch).map_err(Into::into)
+    }
+
+    fn put_block_data(
+        batch: &mut rocksdb::WriteBatch,
+        block_hash: H256,
+        block_height: u64,
+        block_metadata: &BlockMetadata,
+    ) {
+        let block_height_bytes = block_height.to_be_bytes();
 
         let storage_key = construct_storage_key(StoragePrefix::BlockHash, &block_height_bytes);
         batch.put(storage_key, block_hash);
@@ -165,8 +283,67 @@ impl Storage {
 
         let storage_key = construct_storage_key(StoragePrefix::BlockMetadata, block_hash.as_ref());
         batch.put(storage_key, block_metadata.to_bytes());
+    }
 
-        self.db.write(batch)
+    /// Returns the hashchain of the block at the given height. The hashchain of a block is only
+    /// known once it is finalized, i.e. once a hashchain transaction of a later block is included.
+    pub fn get_block_hashchain(&self, block_height: u64) -> Result<RawH256, error::Error> {
+        let storage_key =
+            construct_storage_key(StoragePrefix::BlockHashchain, &block_height.to_be_bytes());
+        self.db
+            .get_pinned(storage_key)?
+            .map(|slice| {
+                let mut buf = [0u8; 32];
+                buf.copy_from_slice(slice.as_ref());
+                buf
+            })
+            .ok_or(error::Error::BlockHashchainNotFound(block_height))
+    }
+
+    /// Records the divergences between the standalone and the on-chain execution of a
+    /// transaction, replacing the previous record of the transaction if any.
+    pub fn set_divergence_record(&mut self, record: &DivergenceRecord) -> Result<(), error::Error> {
+        let mut batch = rocksdb::WriteBatch::default();
+        Self::put_divergence_record(&mut batch, record)?;
+        self.db.write(batch).map_err(Into::into)
+    }
+
+    /// Returns the divergence records of the blocks in the `from_height..=to_height` range,
+    /// ordered by block height and transaction position.
+    pub fn get_divergence_records(
+        &self,
+        from_height: u64,
+        to_height: u64,
+    ) -> Result<Vec<DivergenceRecord>, error::Error> {
+        let (lower_bound, upper_bound) = height_range_bounds(
+            &construct_storage_key(StoragePrefix::Divergence, &[]),
+            from_height,
+            to_height,
+        );
+        let mut opt = rocksdb::ReadOptions::default();
+        opt.set_iterate_upper_bound(upper_bound);
+        opt.set_iterate_lower_bound(lower_bound);
+
+        let mut result = Vec::new();
+        for maybe_elem in self.db.iterator_opt(rocksdb::IteratorMode::Start, opt) {
+            let (_, value) = maybe_elem?;
+            result.push(DivergenceRecord::try_from_slice(&value)?);
+        }
+        Ok(result)
+    }
+
+    fn put_divergence_record(
+        batch: &mut rocksdb::WriteBatch,
+        record: &DivergenceRecord,
+    ) -> Result<(), error::Error> {
+        let key = [
+            record.block_height.to_be_bytes().as_slice(),
+            &record.transaction_position.to_be_bytes(),
+        ]
+        .concat();
+        let storage_key = construct_storage_key(StoragePrefix::Divergence, &key);
+        batch.put(storage_key, record.try_to_vec()?);
+        Ok(())
     }
 
     pub fn get_transaction_data(
@@ -194,6 +371,23 @@ impl Storage {
             .ok_or(error::Error::TransactionNotFound(tx_included))
     }
 
+    /// Returns the hashes of the transactions included in the block, ordered by their position.
+    pub fn get_block_transactions(&self, block_hash: H256) -> Result<Vec<H256>, error::Error> {
+        let db_key_prefix =
+            construct_storage_key(StoragePrefix::TransactionHash, block_hash.as_ref());
+        let n = db_key_prefix.len();
+        let iter = self.db.prefix_iterator(&db_key_prefix);
+        let mut result = Vec::new();
+        for maybe_elem in iter {
+            let (k, v) = maybe_elem?;
+            if k.len() < n || k[0..n] != db_key_prefix {
+                break;
+            }
+            result.push(H256::from_slice(v.as_ref()));
+        }
+        Ok(result)
+    }
+
     pub fn get_transaction_diff(
         &self,
         tx_included: TransactionIncluded,
@@ -236,32 +430,116 @@ impl Storage {
         diff: &Diff,
         mut batch: rocksdb::WriteBatch,
         action: F,
+    ) -> Result<(), error::Error> {
+        let block_height = self.get_block_height_by_hash(tx_msg.block_hash)?;
+        self.transaction_to_batch(
+            &mut batch,
+            block_height,
+            tx_hash,
+            tx_msg,
+            diff,
+            &Diff::default(),
+            action,
+        )?;
+        self.db.write(batch).map_err(Into::into)
+    }
+
+    /// Applies `action` to all the DB keys of the transaction. `base_diff` contains the changes
+    /// of the previous transactions which are not in the DB yet.
+    #[allow(clippy::too_many_arguments)]
+    fn transaction_to_batch<F: Fn(&mut rocksdb::WriteBatch, &[u8], &[u8])>(
+        &self,
+        batch: &mut rocksdb::WriteBatch,
+        block_height: u64,
+        tx_hash: H256,
+        tx_msg: &TransactionMessage,
+        diff: &Diff,
+        base_diff: &Diff,
+        action: F,
     ) -> Result<(), error::Error> {
         let tx_included = TransactionIncluded {
             block_hash: tx_msg.block_hash,
             position: tx_msg.position,
         };
         let tx_included_bytes = tx_included.to_bytes();
-        let block_height = self.get_block_height_by_hash(tx_included.block_hash)?;
 
         let storage_key = construct_storage_key(StoragePrefix::TransactionHash, &tx_included_bytes);
-        action(&mut batch, &storage_key, tx_hash.as_ref());
+        action(batch, &storage_key, tx_hash.as_ref());
 
         let storage_key = construct_storage_key(StoragePrefix::TransactionData, tx_hash.as_ref());
         let msg_bytes = tx_msg.to_bytes();
-        action(&mut batch, &storage_key, &msg_bytes);
+        action(batch, &storage_key, &msg_bytes);
 
         let storage_key = construct_storage_key(StoragePrefix::Diff, &tx_included_bytes);
         let diff_bytes = diff.try_to_bytes().unwrap();
-        action(&mut batch, &storage_key, &diff_bytes);
+        action(batch, &storage_key, &diff_bytes);
 
         for (key, value) in diff.iter() {
             let storage_key = construct_engine_key(key, block_height, tx_included.position);
             let value_bytes = value.try_to_bytes().unwrap();
-            action(&mut batch, &storage_key, &value_bytes);
+            action(batch, &storage_key, &value_bytes);
         }
 
-        self.db.write(batch).map_err(Into::into)
+        let finalized_hashchains =
+            self.finalized_block_hashchains(block_height, tx_included.position, diff, base_diff)?;
+        for (height, hashchain) in finalized_hashchains {
+            let storage_key =
+                construct_storage_key(StoragePrefix::BlockHashchain, &height.to_be_bytes());
+            action(batch, &storage_key, &hashchain);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the hashchains of the blocks finalized by the transaction at the given position.
+    /// A transaction finalizes blocks when it moves the hashchain state to a new block: all the
+    /// blocks from the one of the previous state up to the one of the transaction (excluded).
+    pub(crate) fn finalized_block_hashchains(
+        &self,
+        block_height: u64,
+        transaction_position: u16,
+        diff: &Diff,
+        base_diff: &Diff,
+    ) -> Result<Vec<(u64, RawH256)>, error::Error> {
+        let key = bytes_to_key(KeyPrefix::Hashchain, HASHCHAIN_STATE);
+        let Some(bytes) = diff.get(&key).and_then(DiffValue::value) else {
+            return Ok(Vec::new());
+        };
+        let hashchain = Hashchain::try_deserialize(bytes)?;
+        let current_height = hashchain.get_current_block_height();
+        let previous_bytes = self
+            .with_engine_access_over_diff(
+                block_height,
+                transaction_position,
+                &[],
+                base_diff,
+                |io| io.read_storage(&key).map(|value| value.to_vec()),
+            )
+            .result;
+        let mut previous = previous_bytes
+            .as_deref()
+            .map(Hashchain::try_deserialize)
+            .transpose()?;
+
+        let mut result = Vec::new();
+        if let Some(previous) = previous.as_mut() {
+            if previous.get_current_block_height() == current_height {
+                return Ok(result);
+            }
+            // The intermediate blocks have no transactions, so their hashchains can be computed
+            // from the previous state.
+            while previous.get_current_block_height() + 1 < current_height {
+                let height = previous.get_current_block_height();
+                previous
+                    .move_to_block(height + 1)
+                    .map_err(|_| error::Error::HashchainBlockHeightIncorrect(height + 1))?;
+                result.push((height, previous.get_previous_block_hashchain()));
+            }
+        }
+        if let Some(height) = current_height.checked_sub(1) {
+            result.push((height, hashchain.get_previous_block_hashchain()));
+        }
+        Ok(result)
     }
 
     /// Returns a list of transactions that modified the key, and the values _after_ each transaction.
@@ -385,6 +663,30 @@ impl Storage {
         input: &'input [u8],
         f: F,
     ) -> EngineAccessResult<R>
+    where
+        F: for<'output> FnOnce(engine_state::EngineStateAccess<'db, 'input, 'output>) -> R,
+    {
+        self.with_engine_access_over_diff(
+            block_height,
+            transaction_position,
+            input,
+            &Diff::default(),
+            f,
+        )
+    }
+
+    /// Same as `with_engine_access`, but the Engine state seen by the closure is the one of the
+    /// storage with the changes of `base_diff` applied on top. This allows executing several
+    /// transactions one after the other without committing any of them to the storage.
+    /// The returned diff only contains the changes made by the closure.
+    pub fn with_engine_access_over_diff<'db, 'input, R, F>(
+        &'db self,
+        block_height: u64,
+        transaction_position: u16,
+        input: &'input [u8],
+        base_diff: &Diff,
+        f: F,
+    ) -> EngineAccessResult<R>
     where
         F: for<'output> FnOnce(engine_state::EngineStateAccess<'db, 'input, 'output>) -> R,
     {
@@ -396,6 +698,7 @@ impl Storage {
             block_height,
             transaction_position,
             &diff,
+            base_diff,
             &engine_output,
             &self.db,
         );
@@ -491,6 +794,16 @@ fn construct_storage_key(prefix: StoragePrefix, key: &[u8]) -> Vec<u8> {
     [&[VERSION], &[u8::from(prefix)], key].concat()
 }
 
+/// Returns the iteration bounds of the DB keys made of the prefix followed by a block height in
+/// the `from_height..=to_height` range and a transaction position.
+fn height_range_bounds(prefix: &[u8], from_height: u64, to_height: u64) -> (Vec<u8>, Vec<u8>) {
+    let lower_bound = [prefix, &from_height.to_be_bytes()].concat();
+    // The keys are suffixed with the transaction position, so this bound is greater than
+    // all the keys of the last block.
+    let upper_bound = [prefix, &to_height.to_be_bytes(), &[0xff; 3]].concat();
+    (lower_bound, upper_bound)
+}
+
 fn construct_engine_key(key: &[u8], block_height: u64, transaction_position: u16) -> Vec<u8> {
     construct_storage_key(
         StoragePrefix::Engine,
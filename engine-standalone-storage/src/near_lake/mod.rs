@@ -0,0 +1,383 @@
+//! Ingestion of NEAR blocks stored in the NEAR Lake format in a local directory. Each block is
+//! stored in a directory named after its height (e.g. `000012345678`, the padding is optional),
+//! which contains the `block.json` file and one `shard_<id>.json` file per shard. The receipts
+//! executed by the engine are turned into `sync` messages and consumed in order. The messages
+//! carry the outcome of the receipts, so the divergences of the standalone execution are
+//! recorded.
+
+use crate::sync::{
+    self,
+    types::{BlockMessage, Message, OnChainOutcome, TransactionMessage},
+};
+use crate::{error::ParseTransactionKindError, BlockMetadata, Storage};
+use aurora_engine_modexp::ModExpAlgorithm;
+use aurora_engine_sdk::env::Timestamp;
+use aurora_engine_types::{account_id::AccountId, public_key::PublicKey, H256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub mod types;
+
+const BLOCK_FILE_NAME: &str = "block.json";
+
+/// A receipt addressed to the engine which could not be turned into a transaction message.
+/// Only the receipts which succeeded on NEAR are reported, since the failed ones do not impact
+/// the state of the engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IngestionIssue {
+    /// The file of a shard listed in the block is not present.
+    MissingShard { block_height: u64, shard_id: u64 },
+    /// The data receipt of a promise the receipt depends on was not found in this block or the
+    /// previous ones.
+    MissingPromiseData {
+        block_height: u64,
+        receipt_id: H256,
+        data_id: H256,
+    },
+    /// The method name or the input of the receipt is not understood by `parse_transaction_kind`.
+    Unparseable {
+        block_height: u64,
+        receipt_id: H256,
+        error: ParseTransactionKindError,
+    },
+}
+
+/// Messages extracted from a NEAR Lake block.
+#[derive(Debug)]
+pub struct LakeBlockMessages {
+    pub block: BlockMessage,
+    /// The transactions of the block, in execution order.
+    pub transactions: Vec<TransactionMessage>,
+    pub issues: Vec<IngestionIssue>,
+}
+
+#[derive(Debug, Default)]
+pub struct IngestionReport {
+    pub blocks: usize,
+    pub transactions: usize,
+    pub issues: Vec<IngestionIssue>,
+}
+
+pub struct LakeReader {
+    root: PathBuf,
+    engine_account_id: AccountId,
+    /// Data receipts seen so far and not yet consumed by a callback, indexed by data ID.
+    promise_data: HashMap<H256, Option<Vec<u8>>>,
+}
+
+impl LakeReader {
+    pub fn new<P: AsRef<Path>>(root: P, engine_account_id: AccountId) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+            engine_account_id,
+            promise_data: HashMap::new(),
+        }
+    }
+
+    /// Heights and directories of the blocks present in the directory, in increasing height
+    /// order.
+    pub fn block_dirs(&self) -> Result<Vec<(u64, PathBuf)>, error::Error> {
+        let mut blocks = Vec::new();
+        for entry in std::fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            if let Some(height) = entry.file_name().to_str().and_then(|s| s.parse().ok()) {
+                blocks.push((height, entry.path()));
+            }
+        }
+        blocks.sort_unstable_by_key(|(height, _)| *height);
+        Ok(blocks)
+    }
+
+    /// Reads the block at the given height from its directory. The blocks must be read in
+    /// increasing height order, because the callbacks may depend on data receipts included in
+    /// previous blocks.
+    pub fn read_block(
+        &mut self,
+        block_height: u64,
+        block_dir: &Path,
+    ) -> Result<LakeBlockMessages, error::Error> {
+        let block = types::LakeBlock::load_from_file(block_dir.join(BLOCK_FILE_NAME))
+            .map_err(|e| error::Error::File(block_dir.join(BLOCK_FILE_NAME), e.to_string()))?;
+        let block_hash = decode_hash(&block.header.hash)?;
+        let block_message = BlockMessage {
+            height: block.header.height,
+            hash: block_hash,
+            metadata: BlockMetadata {
+                timestamp: Timestamp::new(block.header.timestamp),
+                random_seed: decode_hash(&block.header.random_value)?,
+            },
+        };
+
+        let mut issues = Vec::new();
+        let mut shards = Vec::with_capacity(block.chunks.len());
+        for chunk in &block.chunks {
+            let shard_path = block_dir.join(format!("shard_{}.json", chunk.shard_id));
+            if !shard_path.exists() {
+                issues.push(IngestionIssue::MissingShard {
+                    block_height,
+                    shard_id: chunk.shard_id,
+                });
+                continue;
+            }
+            let shard = types::LakeShard::load_from_file(&shard_path)
+                .map_err(|e| error::Error::File(shard_path, e.to_string()))?;
+            shards.push(shard);
+        }
+
+        // The data receipts of the block must be known before executing the callbacks, which
+        // may be executed in the same block.
+        for receipt in shards
+            .iter()
+            .filter_map(|shard| shard.chunk.as_ref())
+            .flat_map(|chunk| &chunk.receipts)
+        {
+            if let types::LakeReceiptEnum::Data { data_id, data } = &receipt.receipt {
+                let data = data
+                    .as_ref()
+                    .map(aurora_engine_sdk::base64::decode)
+                    .transpose()?;
+                self.promise_data.insert(decode_hash(data_id)?, data);
+            }
+        }
+
+        let mut transactions = Vec::new();
+        for outcome in shards
+            .iter()
+            .flat_map(|shard| &shard.receipt_execution_outcomes)
+        {
+            self.read_receipt(
+                block_height,
+                block_hash,
+                outcome,
+                &mut transactions,
+                &mut issues,
+            )?;
+        }
+
+        Ok(LakeBlockMessages {
+            block: block_message,
+            transactions,
+            issues,
+        })
+    }
+
+    fn read_receipt(
+        &mut self,
+        block_height: u64,
+        block_hash: H256,
+        outcome: &types::LakeReceiptExecutionOutcome,
+        transactions: &mut Vec<TransactionMessage>,
+        issues: &mut Vec<IngestionIssue>,
+    ) -> Result<(), error::Error> {
+        let receipt = &outcome.receipt;
+        if receipt.receiver_id.as_bytes() != self.engine_account_id.as_bytes() {
+            return Ok(());
+        }
+        let types::LakeReceiptEnum::Action {
+            signer_id,
+            signer_public_key,
+            input_data_ids,
+            actions,
+        } = &receipt.receipt
+        else {
+            return Ok(());
+        };
+        let receipt_id = decode_hash(&receipt.receipt_id)?;
+        let succeeded = outcome.execution_outcome.outcome.status.is_success();
+
+        let mut promise_data = Vec::with_capacity(input_data_ids.len());
+        for data_id in input_data_ids {
+            let data_id = decode_hash(data_id)?;
+            match self.promise_data.remove(&data_id) {
+                Some(data) => promise_data.push(data),
+                None => {
+                    if succeeded {
+                        issues.push(IngestionIssue::MissingPromiseData {
+                            block_height,
+                            receipt_id,
+                            data_id,
+                        });
+                    }
+                    return Ok(());
+                }
+            }
+        }
+
+        let signer: AccountId = parse_account_id(signer_id)?;
+        let signer_public_key = Some(parse_public_key(signer_public_key)?);
+        let caller: AccountId = parse_account_id(&receipt.predecessor_id)?;
+        for (index, action) in actions.iter().enumerate() {
+            let types::LakeAction::FunctionCall { function_call } = action else {
+                continue;
+            };
+            let raw_input = aurora_engine_sdk::base64::decode(&function_call.args)?;
+            let attached_near = function_call
+                .deposit
+                .parse()
+                .map_err(|_| error::Error::InvalidDeposit(function_call.deposit.clone()))?;
+            let transaction = match sync::parse_transaction_kind(
+                &function_call.method_name,
+                raw_input.clone(),
+                &promise_data,
+            ) {
+                Ok(transaction) => transaction,
+                Err(error) => {
+                    if succeeded {
+                        issues.push(IngestionIssue::Unparseable {
+                            block_height,
+                            receipt_id,
+                            error,
+                        });
+                    }
+                    continue;
+                }
+            };
+            // The status and the logs of a receipt are the ones of all its actions, while the
+            // output is the one of its last action.
+            let is_last_action = index + 1 == actions.len();
+            let on_chain_outcome =
+                on_chain_outcome(&outcome.execution_outcome.outcome, is_last_action)?;
+            let position = u16::try_from(transactions.len())
+                .map_err(|_| error::Error::TooManyTransactions(block_height))?;
+            transactions.push(TransactionMessage {
+                block_hash,
+                near_receipt_id: action_receipt_id(receipt_id, index),
+                position,
+                succeeded,
+                signer: signer.clone(),
+                signer_public_key,
+                caller: caller.clone(),
+                attached_near,
+                transaction,
+                promise_data: promise_data.clone(),
+                raw_input,
+                on_chain_outcome: Some(on_chain_outcome),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Consumes all the blocks of the directory in height order, committing the transactions to the
+/// storage. The engine account of the storage must be set.
+pub fn ingest_directory<M: ModExpAlgorithm + 'static, P: AsRef<Path>>(
+    storage: &mut Storage,
+    path: P,
+) -> Result<IngestionReport, error::Error> {
+    let engine_account_id = storage.get_engine_account_id()?;
+    let mut reader = LakeReader::new(path, engine_account_id);
+    let mut report = IngestionReport::default();
+
+    for (block_height, block_dir) in reader.block_dirs()? {
+        let LakeBlockMessages {
+            block,
+            transactions,
+            issues,
+        } = reader.read_block(block_height, &block_dir)?;
+        sync::consume_message::<M>(storage, Message::Block(block))?;
+        report.blocks += 1;
+
+        for transaction in transactions {
+            let outcome =
+                sync::consume_message::<M>(storage, Message::Transaction(Box::new(transaction)))?;
+            outcome.commit(storage)?;
+            report.transactions += 1;
+        }
+        report.issues.extend(issues);
+    }
+
+    Ok(report)
+}
+
+fn on_chain_outcome(
+    outcome: &types::LakeExecutionOutcome,
+    is_last_action: bool,
+) -> Result<OnChainOutcome, error::Error> {
+    let output = match &outcome.status {
+        _ if !is_last_action => None,
+        types::LakeExecutionStatus::SuccessValue(value) => {
+            Some(aurora_engine_sdk::base64::decode(value)?)
+        }
+        _ => Some(Vec::new()),
+    };
+
+    Ok(OnChainOutcome {
+        output,
+        logs: outcome.logs.clone(),
+    })
+}
+
+/// The actions of a batched receipt are executed as separate transactions, which must have
+/// distinct ids. The first action keeps the id of the receipt, the ids of the next ones are
+/// derived from it.
+#[must_use]
+pub fn action_receipt_id(receipt_id: H256, action_index: usize) -> H256 {
+    if action_index == 0 {
+        return receipt_id;
+    }
+    let action_index = u64::try_from(action_index).unwrap_or(u64::MAX);
+    aurora_engine_sdk::keccak(&[receipt_id.as_bytes(), &action_index.to_be_bytes()].concat())
+}
+
+fn decode_hash(value: &str) -> Result<H256, error::Error> {
+    let bytes = bs58::decode(value)
+        .into_vec()
+        .map_err(|_| error::Error::InvalidHash(value.into()))?;
+    if bytes.len() != 32 {
+        return Err(error::Error::InvalidHash(value.into()));
+    }
+    Ok(H256::from_slice(&bytes))
+}
+
+fn parse_account_id(value: &str) -> Result<AccountId, error::Error> {
+    value
+        .parse()
+        .map_err(|_| error::Error::InvalidAccountId(value.into()))
+}
+
+fn parse_public_key(value: &str) -> Result<PublicKey, error::Error> {
+    value
+        .parse()
+        .map_err(|_| error::Error::InvalidPublicKey(value.into()))
+}
+
+pub mod error {
+    use aurora_engine_sdk::base64::DecodeError;
+    use std::path::PathBuf;
+
+    #[derive(Debug)]
+    pub enum Error {
+        Storage(crate::Error),
+        Io(std::io::Error),
+        /// The file could not be read or is not valid JSON.
+        File(PathBuf, String),
+        Base64(DecodeError),
+        InvalidHash(String),
+        InvalidAccountId(String),
+        InvalidPublicKey(String),
+        InvalidDeposit(String),
+        TooManyTransactions(u64),
+    }
+
+    impl From<crate::Error> for Error {
+        fn from(e: crate::Error) -> Self {
+            Self::Storage(e)
+        }
+    }
+
+    impl From<std::io::Error> for Error {
+        fn from(e: std::io::Error) -> Self {
+            Self::Io(e)
+        }
+    }
+
+    impl From<DecodeError> for Error {
+        fn from(e: DecodeError) -> Self {
+            Self::Base64(e)
+        }
+    }
+}
@@ -0,0 +1,144 @@
+//! Subset of the NEAR Lake (`near-indexer-primitives`) JSON format needed to extract the
+//! transactions executed by the engine. Unknown fields are ignored.
+
+use serde::Deserialize;
+use std::path::Path;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LakeBlock {
+    pub author: String,
+    pub header: LakeBlockHeader,
+    pub chunks: Vec<LakeChunkHeader>,
+}
+
+impl LakeBlock {
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        load_from_file(path)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LakeBlockHeader {
+    pub height: u64,
+    /// Base 58 encoding of the block hash
+    pub hash: String,
+    /// Time the block was produced (in ns since the unix epoch)
+    pub timestamp: u64,
+    /// Base 58 encoding of the random value of the block
+    pub random_value: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LakeChunkHeader {
+    pub shard_id: u64,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LakeShard {
+    pub shard_id: u64,
+    pub chunk: Option<LakeChunk>,
+    pub receipt_execution_outcomes: Vec<LakeReceiptExecutionOutcome>,
+}
+
+impl LakeShard {
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        load_from_file(path)
+    }
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LakeChunk {
+    /// Receipts included in the chunk. The data receipts are used to fill the promise data of
+    /// the callbacks, which are executed in the same or a later block.
+    pub receipts: Vec<LakeReceipt>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LakeReceiptExecutionOutcome {
+    pub receipt: LakeReceipt,
+    pub execution_outcome: LakeExecutionOutcomeWithId,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LakeReceipt {
+    pub predecessor_id: String,
+    pub receiver_id: String,
+    /// Base 58 encoding of the receipt ID
+    pub receipt_id: String,
+    pub receipt: LakeReceiptEnum,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum LakeReceiptEnum {
+    Action {
+        signer_id: String,
+        /// Public key of the access key that signed the transaction, e.g. `ed25519:...`
+        signer_public_key: String,
+        /// Base 58 encoding of the IDs of the data this receipt waits for
+        input_data_ids: Vec<String>,
+        actions: Vec<LakeAction>,
+    },
+    Data {
+        /// Base 58 encoding of the data ID
+        data_id: String,
+        /// Base 64 encoding of the data, `None` if the promise failed
+        data: Option<String>,
+    },
+}
+
+/// Only function calls are relevant to the engine, the other actions are not parsed.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum LakeAction {
+    FunctionCall {
+        #[serde(rename = "FunctionCall")]
+        function_call: LakeFunctionCall,
+    },
+    Other(serde_json::Value),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LakeFunctionCall {
+    pub method_name: String,
+    /// Base 64 encoding of the input
+    pub args: String,
+    /// Attached yoctoNEAR, as a decimal string
+    pub deposit: String,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LakeExecutionOutcomeWithId {
+    pub outcome: LakeExecutionOutcome,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct LakeExecutionOutcome {
+    pub logs: Vec<String>,
+    pub status: LakeExecutionStatus,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub enum LakeExecutionStatus {
+    Unknown,
+    Failure(serde_json::Value),
+    SuccessValue(String),
+    SuccessReceiptId(String),
+}
+
+impl LakeExecutionStatus {
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        matches!(self, Self::SuccessValue(_) | Self::SuccessReceiptId(_))
+    }
+}
+
+fn load_from_file<T, P>(path: P) -> Result<T, Box<dyn std::error::Error>>
+where
+    T: for<'de> Deserialize<'de>,
+    P: AsRef<Path>,
+{
+    let file = std::fs::File::open(path)?;
+    let reader = std::io::BufReader::new(file);
+    let value = serde_json::from_reader(reader)?;
+    Ok(value)
+}
@@ -55,3 +55,83 @@ impl<'a> PromiseHandler for NoScheduler<'a> {
         *self
     }
 }
+
+/// A promise created by the engine during the execution of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduledPromise {
+    Call(PromiseCreateArgs),
+    Callback {
+        base: PromiseId,
+        callback: PromiseCreateArgs,
+    },
+    Batch(PromiseBatchAction),
+}
+
+/// Implements `PromiseHandler` by recording the promises created by the engine instead of
+/// ignoring them, so that they can be executed afterwards (see `promise_emulator`).
+/// The id of a promise is its index in `promises`.
+#[derive(Debug, Clone)]
+pub struct Scheduler<'a> {
+    pub promise_data: &'a [Option<Vec<u8>>],
+    pub promises: Vec<ScheduledPromise>,
+    /// The promise the result of the transaction is taken from, if any.
+    pub returned_promise: Option<PromiseId>,
+}
+
+impl<'a> Scheduler<'a> {
+    #[must_use]
+    pub const fn new(promise_data: &'a [Option<Vec<u8>>]) -> Self {
+        Self {
+            promise_data,
+            promises: Vec::new(),
+            returned_promise: None,
+        }
+    }
+
+    fn push(&mut self, promise: ScheduledPromise) -> PromiseId {
+        let id = u64::try_from(self.promises.len()).unwrap_or(u64::MAX);
+        self.promises.push(promise);
+        PromiseId::new(id)
+    }
+}
+
+impl<'a> PromiseHandler for Scheduler<'a> {
+    type ReadOnly = NoScheduler<'a>;
+
+    fn promise_results_count(&self) -> u64 {
+        self.read_only().promise_results_count()
+    }
+
+    fn promise_result(&self, index: u64) -> Option<PromiseResult> {
+        self.read_only().promise_result(index)
+    }
+
+    unsafe fn promise_create_call(&mut self, args: &PromiseCreateArgs) -> PromiseId {
+        self.push(ScheduledPromise::Call(args.clone()))
+    }
+
+    unsafe fn promise_attach_callback(
+        &mut self,
+        base: PromiseId,
+        callback: &PromiseCreateArgs,
+    ) -> PromiseId {
+        self.push(ScheduledPromise::Callback {
+            base,
+            callback: callback.clone(),
+        })
+    }
+
+    unsafe fn promise_create_batch(&mut self, args: &PromiseBatchAction) -> PromiseId {
+        self.push(ScheduledPromise::Batch(args.clone()))
+    }
+
+    fn promise_return(&mut self, promise: PromiseId) {
+        self.returned_promise = Some(promise);
+    }
+
+    fn read_only(&self) -> Self::ReadOnly {
+        NoScheduler {
+            promise_data: self.promise_data,
+        }
+    }
+}
@@ -0,0 +1,321 @@
+//! Local execution of the promises created by the engine. The standalone engine normally ignores
+//! them (see `promise::NoScheduler`) because it is fed the receipts executed on NEAR. The emulator
+//! instead executes the promises itself, which allows dry-running flows spanning several receipts
+//! (e.g. `exit_to_near` followed by `exit_to_near_precompile_callback`) before sending them to
+//! the network:
+//! - calls targeting the engine account are executed against the storage, with the changes made
+//!   by the previous receipts of the flow applied on top. Nothing is committed to the storage.
+//! - calls targeting other accounts are answered by mock responders registered by the user.
+
+use crate::{
+    engine_state::EngineStateAccess,
+    error::ParseTransactionKindError,
+    promise::{ScheduledPromise, Scheduler},
+    sync::{
+        self,
+        types::{TransactionKindTag, TransactionMessage},
+        TransactionExecutionResult,
+    },
+    BlockMetadata, Diff, Storage,
+};
+use aurora_engine_modexp::ModExpAlgorithm;
+use aurora_engine_types::{
+    account_id::AccountId,
+    parameters::{PromiseAction, PromiseBatchAction, PromiseCreateArgs},
+    types::PromiseResult,
+    H256,
+};
+use std::collections::HashMap;
+
+/// Maximum depth of nested promises, to prevent flows calling each other forever.
+pub const MAX_PROMISE_DEPTH: usize = 64;
+
+/// Mock of a method of an account other than the engine.
+pub type Responder = Box<dyn FnMut(&PromiseCreateArgs) -> PromiseResult>;
+
+#[derive(Debug)]
+pub enum Error {
+    Storage(crate::Error),
+    ParseTransactionKind(ParseTransactionKindError),
+    MissingResponder {
+        target_account_id: AccountId,
+        method: String,
+    },
+    MaxDepthExceeded,
+}
+
+impl From<crate::Error> for Error {
+    fn from(e: crate::Error) -> Self {
+        Self::Storage(e)
+    }
+}
+
+impl From<ParseTransactionKindError> for Error {
+    fn from(e: ParseTransactionKindError) -> Self {
+        Self::ParseTransactionKind(e)
+    }
+}
+
+/// A receipt executed by the emulator.
+#[derive(Debug)]
+pub struct EmulatedReceipt {
+    pub receiver_id: AccountId,
+    pub method: String,
+    pub result: PromiseResult,
+    /// Result of the execution by the engine, `None` for the receipts answered by a responder.
+    pub engine_result: Option<Result<Option<TransactionExecutionResult>, sync::error::Error>>,
+}
+
+pub struct PromiseEmulator<'db> {
+    storage: &'db Storage,
+    engine_account_id: AccountId,
+    block_height: u64,
+    block_metadata: BlockMetadata,
+    responders: HashMap<(AccountId, String), Responder>,
+    diff: Diff,
+    receipts: Vec<EmulatedReceipt>,
+}
+
+impl<'db> PromiseEmulator<'db> {
+    /// Creates an emulator executing the receipts at the given block height, i.e. on top of the
+    /// state of the storage before that block.
+    pub fn new(
+        storage: &'db Storage,
+        block_height: u64,
+        block_metadata: BlockMetadata,
+    ) -> Result<Self, crate::Error> {
+        let engine_account_id = storage.get_engine_account_id()?;
+        Ok(Self {
+            storage,
+            engine_account_id,
+            block_height,
+            block_metadata,
+            responders: HashMap::new(),
+            diff: Diff::default(),
+            receipts: Vec::new(),
+        })
+    }
+
+    /// Registers the mock of the `method` of `account_id`, replacing the previous one if any.
+    pub fn register_responder<F>(&mut self, account_id: AccountId, method: &str, responder: F)
+    where
+        F: FnMut(&PromiseCreateArgs) -> PromiseResult + 'static,
+    {
+        self.responders
+            .insert((account_id, method.into()), Box::new(responder));
+    }
+
+    /// Executes the transaction and all the promises it creates, recursively. Returns the final
+    /// result of the transaction, i.e. the result of the promise it returns if any.
+    pub fn execute<M: ModExpAlgorithm + 'static>(
+        &mut self,
+        transaction_message: &TransactionMessage,
+    ) -> Result<PromiseResult, Error> {
+        self.execute_message::<M>(transaction_message, 0)
+    }
+
+    /// The receipts executed so far. A receipt is recorded once the promises it created are
+    /// executed, so callbacks appear before the receipts which scheduled them.
+    #[must_use]
+    pub fn receipts(&self) -> &[EmulatedReceipt] {
+        &self.receipts
+    }
+
+    /// The changes made to the engine state by the receipts executed so far.
+    #[must_use]
+    pub const fn diff(&self) -> &Diff {
+        &self.diff
+    }
+
+    #[must_use]
+    pub fn into_diff(self) -> Diff {
+        self.diff
+    }
+
+    fn execute_message<M: ModExpAlgorithm + 'static>(
+        &mut self,
+        message: &TransactionMessage,
+        depth: usize,
+    ) -> Result<PromiseResult, Error> {
+        if depth > MAX_PROMISE_DEPTH {
+            return Err(Error::MaxDepthExceeded);
+        }
+
+        let mut scheduler = Scheduler::new(&message.promise_data);
+        let access = self.storage.with_engine_access_over_diff(
+            self.block_height,
+            message.position,
+            &message.raw_input,
+            &self.diff,
+            |io| {
+                sync::execute_transaction_with_handler::<_, M, _, _>(
+                    message,
+                    self.block_height,
+                    &self.block_metadata,
+                    self.engine_account_id.clone(),
+                    io,
+                    EngineStateAccess::get_transaction_diff,
+                    &mut scheduler,
+                )
+            },
+        );
+        let (_, diff, engine_result) = access.result;
+        let method_name: &'static str = TransactionKindTag::from(&message.transaction).into();
+        let succeeded = !matches!(
+            engine_result,
+            Err(_) | Ok(Some(TransactionExecutionResult::Submit(Err(_))))
+        );
+
+        // The changes of a failed receipt are discarded, and its promises are not executed.
+        let result = if succeeded {
+            self.diff.append(diff);
+            let Scheduler {
+                promises,
+                returned_promise,
+                ..
+            } = scheduler;
+            let results = self.execute_promises::<M>(message, promises, depth)?;
+            returned_promise
+                .and_then(|id| usize::try_from(id.raw()).ok())
+                .and_then(|index| results.get(index).cloned())
+                .unwrap_or(PromiseResult::Successful(access.engine_output))
+        } else {
+            PromiseResult::Failed
+        };
+
+        self.receipts.push(EmulatedReceipt {
+            receiver_id: self.engine_account_id.clone(),
+            method: method_name.into(),
+            result: result.clone(),
+            engine_result: Some(engine_result),
+        });
+
+        Ok(result)
+    }
+
+    fn execute_promises<M: ModExpAlgorithm + 'static>(
+        &mut self,
+        parent: &TransactionMessage,
+        promises: Vec<ScheduledPromise>,
+        depth: usize,
+    ) -> Result<Vec<PromiseResult>, Error> {
+        let mut results: Vec<PromiseResult> = Vec::with_capacity(promises.len());
+
+        for (index, promise) in promises.into_iter().enumerate() {
+            let receipt_id = derive_receipt_id(parent.near_receipt_id, index);
+            let result = match promise {
+                ScheduledPromise::Call(args) => {
+                    self.execute_call::<M>(parent, receipt_id, &args, Vec::new(), depth)?
+                }
+                ScheduledPromise::Callback { base, callback } => {
+                    let base_result = usize::try_from(base.raw())
+                        .ok()
+                        .and_then(|index| results.get(index).cloned())
+                        .unwrap_or(PromiseResult::Failed);
+                    self.execute_call::<M>(parent, receipt_id, &callback, vec![base_result], depth)?
+                }
+                ScheduledPromise::Batch(batch) => {
+                    self.execute_batch::<M>(parent, receipt_id, &batch, depth)?
+                }
+            };
+            results.push(result);
+        }
+
+        Ok(results)
+    }
+
+    /// Only the function calls of a batch are emulated, the other actions are assumed to succeed.
+    fn execute_batch<M: ModExpAlgorithm + 'static>(
+        &mut self,
+        parent: &TransactionMessage,
+        receipt_id: H256,
+        batch: &PromiseBatchAction,
+        depth: usize,
+    ) -> Result<PromiseResult, Error> {
+        let mut result = PromiseResult::Successful(Vec::new());
+
+        for action in &batch.actions {
+            if let PromiseAction::FunctionCall {
+                name,
+                args,
+                attached_yocto,
+                gas,
+            } = action
+            {
+                let call = PromiseCreateArgs {
+                    target_account_id: batch.target_account_id.clone(),
+                    method: name.clone(),
+                    args: args.clone(),
+                    attached_balance: *attached_yocto,
+                    attached_gas: *gas,
+                };
+                result = self.execute_call::<M>(parent, receipt_id, &call, Vec::new(), depth)?;
+                if !matches!(result, PromiseResult::Successful(_)) {
+                    break;
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    fn execute_call<M: ModExpAlgorithm + 'static>(
+        &mut self,
+        parent: &TransactionMessage,
+        receipt_id: H256,
+        call: &PromiseCreateArgs,
+        promise_results: Vec<PromiseResult>,
+        depth: usize,
+    ) -> Result<PromiseResult, Error> {
+        if call.target_account_id == self.engine_account_id {
+            let promise_data: Vec<Option<Vec<u8>>> = promise_results
+                .into_iter()
+                .map(|result| match result {
+                    PromiseResult::Successful(bytes) => Some(bytes),
+                    PromiseResult::Failed | PromiseResult::NotReady => None,
+                })
+                .collect();
+            let transaction =
+                sync::parse_transaction_kind(&call.method, call.args.clone(), &promise_data)?;
+            let message = TransactionMessage {
+                block_hash: parent.block_hash,
+                near_receipt_id: receipt_id,
+                position: parent.position,
+                succeeded: true,
+                signer: parent.signer.clone(),
+                signer_public_key: parent.signer_public_key,
+                caller: self.engine_account_id.clone(),
+                attached_near: call.attached_balance.as_u128(),
+                transaction,
+                promise_data,
+                raw_input: call.args.clone(),
+                on_chain_outcome: None,
+            };
+            return self.execute_message::<M>(&message, depth + 1);
+        }
+
+        let key = (call.target_account_id.clone(), call.method.clone());
+        let responder = self
+            .responders
+            .get_mut(&key)
+            .ok_or_else(|| Error::MissingResponder {
+                target_account_id: key.0.clone(),
+                method: key.1.clone(),
+            })?;
+        let result = responder(call);
+
+        self.receipts.push(EmulatedReceipt {
+            receiver_id: key.0,
+            method: key.1,
+            result: result.clone(),
+            engine_result: None,
+        });
+
+        Ok(result)
+    }
+}
+
+fn derive_receipt_id(parent_id: H256, index: usize) -> H256 {
+    let index = u64::try_from(index).unwrap_or(u64::MAX);
+    aurora_engine_sdk::keccak(&[parent_id.as_bytes(), &index.to_be_bytes()].concat())
+}
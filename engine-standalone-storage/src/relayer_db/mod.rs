@@ -1,27 +1,16 @@
-use aurora_engine::parameters::SubmitArgs;
 use aurora_engine::{engine, state};
 use aurora_engine_sdk::env::{self, Env, DEFAULT_PREPAID_GAS};
 use aurora_engine_transactions::EthTransactionKind;
 use aurora_engine_types::account_id::AccountId;
 use aurora_engine_types::H256;
-use postgres::fallible_iterator::FallibleIterator;
+use std::num::NonZeroUsize;
 
 use crate::{BlockMetadata, Storage};
 
+pub mod source;
 pub mod types;
 
-const TRANSACTION_QUERY: &str = "
-SELECT
-  transaction.block, transaction.index, transaction.id,
-  transaction.hash, transaction.near_hash, transaction.near_receipt_hash,
-  transaction.from, transaction.to, transaction.nonce, transaction.gas_price,
-  transaction.gas_limit, transaction.gas_used, transaction.value, transaction.input,
-  transaction.v, transaction.r, transaction.s, transaction.status, transaction.output,
-  block.hash as block_hash
-FROM transaction INNER JOIN block
-ON transaction.block = block.id
-ORDER BY transaction.block, transaction.index
-";
+pub use source::{MemoryRowSource, RowSource};
 
 /// Opens a Postgres connection to a running server hosting the relayer database.
 pub fn connect_without_tls(
@@ -31,65 +20,137 @@ pub fn connect_without_tls(
     postgres::Client::connect(&connection_string, postgres::NoTls)
 }
 
-pub fn read_block_data(
-    connection: &mut postgres::Client,
-) -> Result<postgres::RowIter<'_>, postgres::Error> {
-    connection.query_raw::<_, u32, _>("SELECT * FROM block", std::iter::empty())
+/// A transaction for which the outcome of the execution by the standalone engine differs from
+/// the one recorded by the relayer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionMismatch {
+    pub block_height: u64,
+    pub near_hash: H256,
+    /// Status recorded by the relayer.
+    pub expected_status: bool,
+    /// Gas used recorded by the relayer.
+    pub expected_gas_used: u64,
+    pub status: bool,
+    /// `None` if the Engine failed to execute the transaction.
+    pub gas_used: Option<u64>,
 }
 
-pub fn read_transaction_data(
-    connection: &mut postgres::Client,
-) -> Result<postgres::RowIter<'_>, postgres::Error> {
-    connection.query_raw::<_, u32, _>(TRANSACTION_QUERY, std::iter::empty())
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub blocks: usize,
+    pub transactions: usize,
+    /// Height of the last block imported, `None` if there was no new block in the source.
+    pub last_block_height: Option<u64>,
+    pub mismatches: Vec<TransactionMismatch>,
 }
 
-pub fn initialize_blocks<I>(storage: &mut Storage, mut rows: I) -> Result<(), error::Error>
-where
-    I: FallibleIterator<Item = types::BlockRow, Error = postgres::Error>,
-{
-    while let Some(row) = rows.next()? {
-        let metadata = BlockMetadata {
-            timestamp: env::Timestamp::new(row.timestamp.unwrap_or(0)),
-            // TODO: need relayer to index this, tracking issue: https://github.com/aurora-is-near/aurora-relayer/issues/135
-            random_seed: H256([0; 32]),
+/// Imports the blocks and transactions of the relayer database into the storage, reading the
+/// source in pages of at most `page_size` blocks. The import resumes after the last block
+/// imported by a previous call (see `Storage::get_relayer_import_checkpoint`), so it can be
+/// interrupted and run again as new rows are added to the source.
+pub fn import<S: RowSource>(
+    storage: &mut Storage,
+    source: &mut S,
+    engine_state: &state::EngineState,
+    page_size: NonZeroUsize,
+) -> Result<ImportReport, error::Error> {
+    let mut report = ImportReport::default();
+    let mut importer = TransactionImporter::new(engine_state);
+    let mut from_height = storage
+        .get_relayer_import_checkpoint()?
+        .map_or(0, |height| height + 1);
+
+    loop {
+        let blocks = source
+            .read_blocks(from_height, page_size.get())
+            .map_err(|e| error::Error::Source(e.into()))?;
+        let Some(to_height) = blocks.last().map(|block| block.id) else {
+            break;
         };
+        let mut transactions = source
+            .read_transactions(from_height, to_height)
+            .map_err(|e| error::Error::Source(e.into()))?
+            .into_iter()
+            .peekable();
+
+        for block in blocks {
+            import_block(storage, &block)?;
+            while let Some(row) = transactions.next_if(|row| row.block <= block.id) {
+                if let Some(mismatch) = importer.import(storage, row)? {
+                    report.mismatches.push(mismatch);
+                }
+                report.transactions += 1;
+            }
+            storage
+                .set_relayer_import_checkpoint(block.id)
+                .map_err(crate::Error::Rocksdb)?;
+            report.blocks += 1;
+            report.last_block_height = Some(block.id);
+        }
 
-        storage
-            .set_block_data(row.hash, row.id, &metadata)
-            .map_err(crate::Error::Rocksdb)?;
+        let Some(next_height) = to_height.checked_add(1) else {
+            break;
+        };
+        from_height = next_height;
     }
-    Ok(())
+
+    Ok(report)
 }
 
-pub fn initialize_transactions<I>(
-    storage: &mut Storage,
-    mut rows: I,
-    engine_state: &state::EngineState,
-) -> Result<(), error::Error>
-where
-    I: FallibleIterator<Item = types::TransactionRow, Error = postgres::Error>,
-{
-    let signer_account_id = "relayer.aurora".parse().unwrap();
-    let predecessor_account_id: AccountId = "relayer.aurora".parse().unwrap();
-    let current_account_id = "aurora".parse().unwrap();
-    let relayer_address =
-        aurora_engine_sdk::types::near_account_to_evm_address(predecessor_account_id.as_bytes());
-    let mut env = env::Fixed {
-        signer_account_id,
-        current_account_id,
-        predecessor_account_id,
-        block_height: 0,
-        block_timestamp: env::Timestamp::new(0),
-        attached_deposit: 0,
-        random_seed: H256::zero(),
-        prepaid_gas: DEFAULT_PREPAID_GAS,
+fn import_block(storage: &mut Storage, row: &types::BlockRow) -> Result<(), error::Error> {
+    let metadata = BlockMetadata {
+        timestamp: env::Timestamp::new(row.timestamp.unwrap_or(0)),
+        random_seed: row.random_seed.unwrap_or_default(),
     };
-    // We use the Noop handler here since the relayer DB does not contain any promise information.
-    let mut handler = aurora_engine_sdk::promise::Noop;
 
-    while let Some(row) = rows.next()? {
+    storage
+        .set_block_data(row.hash, row.id, &metadata)
+        .map_err(crate::Error::Rocksdb)?;
+    Ok(())
+}
+
+struct TransactionImporter<'a> {
+    engine_state: &'a state::EngineState,
+    env: env::Fixed,
+    relayer_address: aurora_engine_types::types::Address,
+}
+
+impl<'a> TransactionImporter<'a> {
+    fn new(engine_state: &'a state::EngineState) -> Self {
+        let signer_account_id = "relayer.aurora".parse().unwrap();
+        let predecessor_account_id: AccountId = "relayer.aurora".parse().unwrap();
+        let current_account_id = "aurora".parse().unwrap();
+        let relayer_address = aurora_engine_sdk::types::near_account_to_evm_address(
+            predecessor_account_id.as_bytes(),
+        );
+        let env = env::Fixed {
+            signer_account_id,
+            signer_account_pk: None,
+            current_account_id,
+            predecessor_account_id,
+            block_height: 0,
+            block_timestamp: env::Timestamp::new(0),
+            attached_deposit: 0,
+            random_seed: H256::zero(),
+            prepaid_gas: DEFAULT_PREPAID_GAS,
+        };
+        Self {
+            engine_state,
+            env,
+            relayer_address,
+        }
+    }
+
+    /// Executes the transaction and persists its diff if the outcome is consistent with the
+    /// relayer's record. Returns the mismatch between the outcome and the record, if any.
+    fn import(
+        &mut self,
+        storage: &mut Storage,
+        row: types::TransactionRow,
+    ) -> Result<Option<TransactionMismatch>, error::Error> {
         let near_tx_hash = row.near_hash;
         let tx_succeeded = row.status;
+        let expected_gas_used = row.gas_used;
         let transaction_position = row.index;
         let block_height = row.block;
         let block_hash = row.block_hash;
@@ -98,49 +159,52 @@ where
         let transaction_bytes: Vec<u8> = (&tx).into();
         let tx_hash = aurora_engine_sdk::keccak(&transaction_bytes);
 
-        env.block_height = block_height;
-        env.block_timestamp = block_metadata.timestamp;
-        env.random_seed = block_metadata.random_seed;
+        self.env.block_height = block_height;
+        self.env.block_timestamp = block_metadata.timestamp;
+        self.env.random_seed = block_metadata.random_seed;
 
-        let args = SubmitArgs {
-            tx_data: transaction_bytes.clone(),
-            ..Default::default()
-        };
+        // We use the Noop handler here since the relayer DB does not contain any promise information.
+        let mut handler = aurora_engine_sdk::promise::Noop;
+        let env = &self.env;
         let result = storage.with_engine_access(block_height, transaction_position, &[], |io| {
+            let transaction = engine::parse_transaction(env, &transaction_bytes)?;
             engine::submit(
                 io,
-                &env,
-                &args,
-                engine_state.clone(),
+                env,
+                transaction,
+                None,
+                self.engine_state.clone(),
                 env.current_account_id(),
-                relayer_address,
+                self.relayer_address,
                 &mut handler,
             )
         });
-        match result.result {
-            // Engine errors would always turn into panics on the NEAR side, so we do not need to persist
-            // any diff. Therefore, even if the error was expected, we still continue to the next transaction.
-            Err(e) => {
-                if tx_succeeded {
-                    println!(
-                        "WARN: Transaction with NEAR hash {near_tx_hash:?} expected to succeed, but failed with error message {e:?}",
-                    );
-                }
-                continue;
-            }
-            Ok(result) => {
-                if result.status.is_fail() && tx_succeeded {
-                    println!(
-                        "WARN: Transaction with NEAR hash {near_tx_hash:?} expected to succeed, but failed with error message {:?}",
-                        result.status
-                    );
-                    continue;
-                }
-                // if result.status.is_fail() && !tx_succeeded then this is consistent; we
-                // should still persist the diff because failed transactions can impact the state.
-                // For example, a transaction that runs of out of gas still has its balance deducted
-                // for the gas spent. Therefore, we do not have a `continue` statement here.
-            }
+        let (status, gas_used) = match &result.result {
+            Ok(result) => (result.status.is_ok(), Some(result.gas_used)),
+            Err(_) => (false, None),
+        };
+        let mismatch = if status == tx_succeeded && gas_used == Some(expected_gas_used) {
+            None
+        } else {
+            Some(TransactionMismatch {
+                block_height,
+                near_hash: near_tx_hash,
+                expected_status: tx_succeeded,
+                expected_gas_used,
+                status,
+                gas_used,
+            })
+        };
+
+        // Engine errors would always turn into panics on the NEAR side, so we do not need to persist
+        // any diff. Therefore, even if the error was expected, we still continue to the next transaction.
+        // If the transaction failed while it was expected to succeed, the diff is not persisted either.
+        // However, if `status` and `tx_succeeded` are both false then this is consistent; we
+        // should still persist the diff because failed transactions can impact the state.
+        // For example, a transaction that runs of out of gas still has its balance deducted
+        // for the gas spent.
+        if gas_used.is_none() || (tx_succeeded && !status) {
+            return Ok(mismatch);
         }
 
         let diff = result.diff;
@@ -149,16 +213,18 @@ where
             near_receipt_id: near_tx_hash,
             position: transaction_position,
             succeeded: true,
-            signer: env.signer_account_id(),
-            caller: env.predecessor_account_id(),
+            signer: self.env.signer_account_id(),
+            signer_public_key: self.env.signer_account_pk(),
+            caller: self.env.predecessor_account_id(),
             attached_near: 0,
             transaction: crate::sync::types::TransactionKind::Submit(tx),
             promise_data: Vec::new(),
             raw_input: transaction_bytes,
+            on_chain_outcome: None,
         };
         storage.set_transaction_included(tx_hash, &tx_msg, &diff)?;
+        Ok(mismatch)
     }
-    Ok(())
 }
 
 pub mod error {
@@ -167,7 +233,7 @@ pub mod error {
     #[derive(Debug)]
     pub enum Error {
         Storage(crate::Error),
-        Postgres(postgres::Error),
+        Source(super::source::SourceError),
         EngineState(state::EngineStateError),
         Engine(engine::EngineError),
     }
@@ -178,12 +244,6 @@ pub mod error {
         }
     }
 
-    impl From<postgres::Error> for Error {
-        fn from(e: postgres::Error) -> Self {
-            Self::Postgres(e)
-        }
-    }
-
     impl From<state::EngineStateError> for Error {
         fn from(e: state::EngineStateError) -> Self {
             Self::EngineState(e)
@@ -199,7 +259,6 @@ pub mod error {
 
 #[cfg(test)]
 mod test {
-    use super::FallibleIterator;
     use crate::relayer_db::types::ConnectionParams;
     use crate::sync::types::{TransactionKind, TransactionMessage};
     use aurora_engine::fungible_token::FungibleTokenMetadata;
@@ -222,6 +281,9 @@ mod test {
             upgrade_delay_blocks: 0,
             is_paused: false,
             key_manager: None,
+            roles: Default::default(),
+            is_deploy_allowlist_enabled: false,
+            silo_params: Default::default(),
         };
 
         // Initialize engine and connector states in storage.
@@ -262,29 +324,27 @@ mod test {
                         near_receipt_id: H256::zero(),
                         succeeded: true,
                         signer: "aurora".parse().unwrap(),
+                        signer_public_key: None,
                         caller: "aurora".parse().unwrap(),
                         attached_near: 0,
                         transaction: TransactionKind::Unknown,
                         promise_data: Vec::new(),
                         raw_input: Vec::new(),
+                        on_chain_outcome: None,
                     },
                     &diff,
                 )
                 .unwrap();
         }
-        let block_rows = super::read_block_data(&mut connection).unwrap();
-        super::initialize_blocks(
-            &mut storage,
-            block_rows.map(|row| Ok(row.try_into().unwrap())),
-        )
-        .unwrap();
-        let tx_rows = super::read_transaction_data(&mut connection).unwrap();
-        super::initialize_transactions(
+        let report = super::import(
             &mut storage,
-            tx_rows.map(|row| Ok(row.try_into().unwrap())),
+            &mut connection,
             &engine_state,
+            std::num::NonZeroUsize::new(1000).unwrap(),
         )
         .unwrap();
+        assert!(report.blocks > 0);
+        assert_eq!(report.mismatches, Vec::new());
 
         connection.close().unwrap();
     }
@@ -0,0 +1,131 @@
+use super::types::{BlockRow, TransactionRow};
+
+/// Error type of the row sources which do not have a specific one.
+pub type SourceError = Box<dyn std::error::Error + Send + Sync>;
+
+const BLOCK_QUERY: &str = "
+SELECT * FROM block
+WHERE id >= $1
+ORDER BY id
+LIMIT $2
+";
+
+const TRANSACTION_QUERY: &str = "
+SELECT
+  transaction.block, transaction.index, transaction.id,
+  transaction.hash, transaction.near_hash, transaction.near_receipt_hash,
+  transaction.from, transaction.to, transaction.nonce, transaction.gas_price,
+  transaction.gas_limit, transaction.gas_used, transaction.value, transaction.input,
+  transaction.v, transaction.r, transaction.s, transaction.status, transaction.output,
+  block.hash as block_hash
+FROM transaction INNER JOIN block
+ON transaction.block = block.id
+WHERE transaction.block BETWEEN $1 AND $2
+ORDER BY transaction.block, transaction.index
+";
+
+/// Source of the rows of the relayer database. It is implemented for a connection to the
+/// Postgres server hosting the database, and for rows held in memory, which allows importing
+/// exports of the database (e.g. CSV or Parquet files) without a running server.
+pub trait RowSource {
+    type Error: Into<SourceError>;
+
+    /// Returns at most `limit` blocks with a height greater than or equal to `from_height`,
+    /// in increasing height order.
+    fn read_blocks(&mut self, from_height: u64, limit: usize)
+        -> Result<Vec<BlockRow>, Self::Error>;
+
+    /// Returns the transactions included in the blocks with a height in the
+    /// `from_height..=to_height` range, ordered by block height and position in the block.
+    fn read_transactions(
+        &mut self,
+        from_height: u64,
+        to_height: u64,
+    ) -> Result<Vec<TransactionRow>, Self::Error>;
+}
+
+impl RowSource for postgres::Client {
+    type Error = SourceError;
+
+    fn read_blocks(
+        &mut self,
+        from_height: u64,
+        limit: usize,
+    ) -> Result<Vec<BlockRow>, Self::Error> {
+        let from_height = i64::try_from(from_height)?;
+        let limit = i64::try_from(limit)?;
+        self.query(BLOCK_QUERY, &[&from_height, &limit])?
+            .into_iter()
+            .map(|row| BlockRow::try_from(row).map_err(Into::into))
+            .collect()
+    }
+
+    fn read_transactions(
+        &mut self,
+        from_height: u64,
+        to_height: u64,
+    ) -> Result<Vec<TransactionRow>, Self::Error> {
+        let from_height = i64::try_from(from_height)?;
+        let to_height = i64::try_from(to_height)?;
+        self.query(TRANSACTION_QUERY, &[&from_height, &to_height])?
+            .into_iter()
+            .map(|row| TransactionRow::try_from(row).map_err(Into::into))
+            .collect()
+    }
+}
+
+/// Rows held in memory, e.g. parsed from an export of the relayer database.
+#[derive(Debug, Default, Clone)]
+pub struct MemoryRowSource {
+    blocks: Vec<BlockRow>,
+    transactions: Vec<TransactionRow>,
+}
+
+impl MemoryRowSource {
+    #[must_use]
+    pub fn new(mut blocks: Vec<BlockRow>, mut transactions: Vec<TransactionRow>) -> Self {
+        blocks.sort_unstable_by_key(|block| block.id);
+        transactions.sort_unstable_by_key(|tx| (tx.block, tx.index));
+        Self {
+            blocks,
+            transactions,
+        }
+    }
+}
+
+impl RowSource for MemoryRowSource {
+    type Error = std::convert::Infallible;
+
+    fn read_blocks(
+        &mut self,
+        from_height: u64,
+        limit: usize,
+    ) -> Result<Vec<BlockRow>, Self::Error> {
+        let start = self.blocks.partition_point(|block| block.id < from_height);
+        Ok(self
+            .blocks
+            .iter()
+            .skip(start)
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+
+    fn read_transactions(
+        &mut self,
+        from_height: u64,
+        to_height: u64,
+    ) -> Result<Vec<TransactionRow>, Self::Error> {
+        let start = self
+            .transactions
+            .partition_point(|tx| tx.block < from_height);
+        let end = self
+            .transactions
+            .partition_point(|tx| tx.block <= to_height);
+        Ok(self
+            .transactions
+            .get(start..end)
+            .unwrap_or_default()
+            .to_vec())
+    }
+}
@@ -5,7 +5,9 @@ use aurora_engine_transactions::{
 use aurora_engine_types::types::{Address, Wei};
 use aurora_engine_types::{H256, U256};
 use std::convert::TryFrom;
+use std::fmt;
 use std::io::{Cursor, Read};
+use std::num::TryFromIntError;
 use std::time::SystemTime;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -41,7 +43,7 @@ impl Default for ConnectionParams {
 }
 
 /// Row from the `block` table in the relayer's DB.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct BlockRow {
     /// Chain ID the block is from
     pub chain: u64,
@@ -67,10 +69,38 @@ pub struct BlockRow {
     pub state_root: H256,
     /// Root hash for receipts trie
     pub receipts_root: H256,
+    /// Random seed of the NEAR block. Not indexed by older versions of the relayer,
+    /// see https://github.com/aurora-is-near/aurora-relayer/issues/135
+    pub random_seed: Option<H256>,
 }
 
+/// Error returned when a row of the relayer's DB has an invalid value.
+#[derive(Debug)]
+pub enum ParseError {
+    Int(TryFromIntError),
+    /// The value of the field is not a 32 bytes hash.
+    InvalidHash(&'static str),
+}
+
+impl From<TryFromIntError> for ParseError {
+    fn from(e: TryFromIntError) -> Self {
+        Self::Int(e)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(e) => write!(f, "Invalid integer: {e}"),
+            Self::InvalidHash(field) => write!(f, "Invalid hash in field {field}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
 impl TryFrom<postgres::Row> for BlockRow {
-    type Error = std::num::TryFromIntError;
+    type Error = ParseError;
 
     fn try_from(row: postgres::Row) -> Result<Self, Self::Error> {
         let chain: i32 = row.get("chain");
@@ -85,12 +115,13 @@ impl TryFrom<postgres::Row> for BlockRow {
         let transactions_root = get_hash(&row, "transactions_root");
         let state_root = get_hash(&row, "state_root");
         let receipts_root = get_hash(&row, "receipts_root");
+        let random_seed: Option<&[u8]> = row.try_get("random_seed").ok().flatten();
 
         Ok(Self {
             chain: chain.try_into()?,
             id: id.try_into()?,
             hash,
-            near_hash: near_hash.map(H256::from_slice),
+            near_hash: parse_optional_hash(near_hash, "near_hash")?,
             timestamp,
             size: size.try_into()?,
             gas_limit,
@@ -99,12 +130,13 @@ impl TryFrom<postgres::Row> for BlockRow {
             transactions_root,
             state_root,
             receipts_root,
+            random_seed: parse_optional_hash(random_seed, "random_seed")?,
         })
     }
 }
 
 /// Row from the `transaction` table in the relayer's DB.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TransactionRow {
     /// Block height where the transaction was included in the chain
     pub block: u64,
@@ -229,6 +261,19 @@ fn get_hash(row: &postgres::Row, field: &str) -> H256 {
     H256::from_slice(value)
 }
 
+fn parse_optional_hash(
+    value: Option<&[u8]>,
+    field: &'static str,
+) -> Result<Option<H256>, ParseError> {
+    value
+        .map(|bytes| {
+            <[u8; 32]>::try_from(bytes)
+                .map(H256)
+                .map_err(|_| ParseError::InvalidHash(field))
+        })
+        .transpose()
+}
+
 fn get_address(row: &postgres::Row, field: &str) -> Address {
     let value: &[u8] = row.get(field);
     Address::try_from_slice(value).unwrap()
@@ -0,0 +1,432 @@
+//! Decoding of the raw engine keys and values of a `Diff` into typed changes, e.g. to show what a
+//! transaction changed in a human-readable form. The keys which are not understood (or with a
+//! value which cannot be decoded) are kept as `EngineChange::Unknown`, so no change is lost.
+
+use crate::{
+    construct_engine_key, construct_storage_key, error, Diff, DiffValue, Storage, StoragePrefix,
+    TransactionIncluded,
+};
+use aurora_engine::state::{BorshableEngineState, BorshableEngineStateV1, EngineState};
+use aurora_engine_hashchain::hashchain::Hashchain;
+use aurora_engine_types::{
+    account_id::AccountId,
+    borsh::BorshDeserialize,
+    parameters::{
+        engine::{RelayerKeyMetadata, WhitelistKind},
+        xcc::CodeVersion,
+    },
+    public_key::PublicKey,
+    storage::{EthConnectorStorageId, KeyPrefix, VersionPrefix},
+    types::{Address, NEP141Wei, Wei},
+    H256, U256,
+};
+
+/// Key of the lists of entries the engine keeps for the views, e.g. the list of the denied
+/// addresses.
+const LIST_KEY: &[u8] = b"LIST";
+/// Key of the status of a whitelist, present only if the whitelist is enabled.
+const WHITELIST_STATUS_KEY: &[u8] = b"STATUS";
+
+/// A change of an engine key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateChange {
+    pub key: Vec<u8>,
+    /// Value of the key before the change. It is `None` if it is not known, and
+    /// `DiffValue::Deleted` if the key was not set.
+    pub old_value: Option<DiffValue>,
+    pub new_value: DiffValue,
+    pub change: EngineChange,
+}
+
+/// The decoded change, with the values after the change. A deleted key is decoded as its
+/// default value (e.g. a zero balance) when the engine reads it this way, and as `None`
+/// otherwise.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineChange {
+    EngineState(Option<EngineState>),
+    /// A configuration key other than the engine state, e.g. the pause flags.
+    Config {
+        key: Vec<u8>,
+        value: Option<Vec<u8>>,
+    },
+    Nonce {
+        address: Address,
+        nonce: U256,
+    },
+    Balance {
+        address: Address,
+        balance: Wei,
+    },
+    Code {
+        address: Address,
+        code: Option<Vec<u8>>,
+    },
+    Storage {
+        address: Address,
+        generation: u32,
+        slot: H256,
+        value: H256,
+    },
+    Generation {
+        address: Address,
+        generation: u32,
+    },
+    RelayerAddress {
+        relayer: AccountId,
+        address: Option<Address>,
+    },
+    Nep141ToErc20 {
+        nep141: AccountId,
+        erc20: Option<Address>,
+    },
+    Erc20ToNep141 {
+        erc20: Address,
+        nep141: Option<AccountId>,
+    },
+    /// Balance of the account in the fungible token of the eth-connector.
+    EthConnectorBalance {
+        account_id: AccountId,
+        balance: Option<NEP141Wei>,
+    },
+    /// Latest version of the router contract of the cross-contract calls.
+    XccLatestVersion(Option<CodeVersion>),
+    /// Version of the router contract deployed for the address.
+    XccAddressVersion {
+        address: Address,
+        version: Option<CodeVersion>,
+    },
+    Hashchain(Option<Hashchain>),
+    /// A relayer function call key was added by the engine (with its metadata), or removed.
+    RelayerFunctionCallKey {
+        public_key: PublicKey,
+        metadata: Option<RelayerKeyMetadata>,
+    },
+    RelayerFunctionCallKeys(Vec<PublicKey>),
+    /// Code of the NEP-141 contracts deployed for the ERC-20 tokens native to Aurora.
+    Nep141Code(Option<Vec<u8>>),
+    NativeErc20 {
+        address: Address,
+        native: bool,
+    },
+    /// Latest code of the registry of the ERC-20 code of the bridged tokens.
+    Erc20Code(Option<Vec<u8>>),
+    Erc20LatestVersion(Option<CodeVersion>),
+    /// Version of the ERC-20 code of the bridged token.
+    Erc20TokenVersion {
+        address: Address,
+        version: Option<CodeVersion>,
+    },
+    /// The address was added to, or removed from, one of the address lists of the engine.
+    AddressListEntry {
+        list: AddressListKind,
+        address: Address,
+        added: bool,
+    },
+    /// All the addresses of one of the address lists, in the order they were added.
+    AddressListEntries {
+        list: AddressListKind,
+        addresses: Vec<Address>,
+    },
+    WhitelistStatus {
+        kind: WhitelistKind,
+        enabled: bool,
+    },
+    WhitelistAccount {
+        account_id: AccountId,
+        added: bool,
+    },
+    WhitelistAccounts(Vec<AccountId>),
+    Unknown,
+}
+
+/// The lists of EVM addresses kept by the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressListKind {
+    Denylist,
+    DeployAllowlist,
+    FeeWaivedSenders,
+    /// The whitelist of the addresses which can sign the transactions of a silo.
+    Whitelist,
+}
+
+/// Decodes the changes of the diff, without their old values.
+#[must_use]
+pub fn decode_diff(diff: &Diff) -> Vec<StateChange> {
+    diff.iter()
+        .map(|(key, value)| StateChange {
+            key: key.clone(),
+            old_value: None,
+            new_value: value.clone(),
+            change: decode_change(key, value.value()).unwrap_or(EngineChange::Unknown),
+        })
+        .collect()
+}
+
+impl Storage {
+    /// Decodes the changes made by the transaction, with the values of the keys before the
+    /// transaction.
+    pub fn get_transaction_changes(
+        &self,
+        tx_included: TransactionIncluded,
+    ) -> Result<Vec<StateChange>, error::Error> {
+        let block_height = self.get_block_height_by_hash(tx_included.block_hash)?;
+        let diff = self.get_transaction_diff(tx_included)?;
+        let mut result = decode_diff(&diff);
+        for change in &mut result {
+            change.old_value = Some(self.read_engine_value_before(
+                &change.key,
+                block_height,
+                tx_included.position,
+            )?);
+        }
+        Ok(result)
+    }
+
+    /// Returns the value of the engine key before the transaction at the given position.
+    fn read_engine_value_before(
+        &self,
+        engine_key: &[u8],
+        block_height: u64,
+        transaction_position: u16,
+    ) -> Result<DiffValue, error::Error> {
+        let mut opt = rocksdb::ReadOptions::default();
+        opt.set_iterate_upper_bound(construct_engine_key(
+            engine_key,
+            block_height,
+            transaction_position,
+        ));
+        opt.set_iterate_lower_bound(construct_storage_key(StoragePrefix::Engine, engine_key));
+        let mut iter = self.db.iterator_opt(rocksdb::IteratorMode::End, opt);
+        iter.next().map_or(Ok(DiffValue::Deleted), |maybe_elem| {
+            let (_, value) = maybe_elem?;
+            Ok(DiffValue::try_from_bytes(&value)?)
+        })
+    }
+}
+
+/// Returns `None` if the key or the value cannot be decoded.
+fn decode_change(key: &[u8], value: Option<&[u8]>) -> Option<EngineChange> {
+    let (&version, rest) = key.split_first()?;
+    let (&prefix, body) = rest.split_first()?;
+    if version != u8::from(VersionPrefix::V1) {
+        return None;
+    }
+
+    let change = match KeyPrefix::try_from(prefix).ok()? {
+        KeyPrefix::Config if body == aurora_engine::state::STATE_KEY => {
+            EngineChange::EngineState(value.map(decode_engine_state).transpose()?)
+        }
+        KeyPrefix::Config => EngineChange::Config {
+            key: body.to_vec(),
+            value: value.map(<[u8]>::to_vec),
+        },
+        KeyPrefix::Nonce => EngineChange::Nonce {
+            address: Address::try_from_slice(body).ok()?,
+            nonce: decode_u256(value)?,
+        },
+        KeyPrefix::Balance => EngineChange::Balance {
+            address: Address::try_from_slice(body).ok()?,
+            balance: Wei::new(decode_u256(value)?),
+        },
+        KeyPrefix::Code => EngineChange::Code {
+            address: Address::try_from_slice(body).ok()?,
+            code: value.map(<[u8]>::to_vec),
+        },
+        KeyPrefix::Storage => decode_storage_change(body, value)?,
+        KeyPrefix::Generation => EngineChange::Generation {
+            address: Address::try_from_slice(body).ok()?,
+            generation: value.map_or(Some(0), |bytes| {
+                <[u8; 4]>::try_from(bytes).ok().map(u32::from_be_bytes)
+            })?,
+        },
+        KeyPrefix::RelayerEvmAddressMap => EngineChange::RelayerAddress {
+            relayer: AccountId::try_from(body).ok()?,
+            address: value.map(Address::try_from_slice).transpose().ok()?,
+        },
+        KeyPrefix::Nep141Erc20Map => EngineChange::Nep141ToErc20 {
+            nep141: AccountId::try_from(body).ok()?,
+            erc20: value.map(Address::try_from_slice).transpose().ok()?,
+        },
+        KeyPrefix::Erc20Nep141Map => EngineChange::Erc20ToNep141 {
+            erc20: Address::try_from_slice(body).ok()?,
+            nep141: value.map(AccountId::try_from).transpose().ok()?,
+        },
+        KeyPrefix::EthConnector => {
+            let (&storage_id, account_id) = body.split_first()?;
+            if storage_id != u8::from(EthConnectorStorageId::FungibleToken) {
+                return None;
+            }
+            EngineChange::EthConnectorBalance {
+                account_id: AccountId::try_from(account_id).ok()?,
+                balance: value.map(NEP141Wei::try_from_slice).transpose().ok()?,
+            }
+        }
+        KeyPrefix::CrossContractCall
+            if body == aurora_engine_precompiles::xcc::state::VERSION_KEY =>
+        {
+            EngineChange::XccLatestVersion(value.map(decode_code_version).transpose()?)
+        }
+        KeyPrefix::CrossContractCall if body.len() == 20 => EngineChange::XccAddressVersion {
+            address: Address::try_from_slice(body).ok()?,
+            version: value.map(decode_code_version).transpose()?,
+        },
+        KeyPrefix::Hashchain if body == aurora_engine::hashchain::HASHCHAIN_STATE => {
+            EngineChange::Hashchain(
+                value
+                    .map(|bytes| Hashchain::try_deserialize(bytes).ok())
+                    .transpose()?,
+            )
+        }
+        KeyPrefix::RelayerFunctionCallKey if body == LIST_KEY => {
+            EngineChange::RelayerFunctionCallKeys(decode_list(value)?)
+        }
+        KeyPrefix::RelayerFunctionCallKey => EngineChange::RelayerFunctionCallKey {
+            public_key: decode_public_key(body)?,
+            metadata: value.map(decode_relayer_key_metadata).transpose()?,
+        },
+        KeyPrefix::NativeErc20 if body == aurora_engine::native_erc20::CODE_KEY => {
+            EngineChange::Nep141Code(value.map(<[u8]>::to_vec))
+        }
+        KeyPrefix::NativeErc20 => EngineChange::NativeErc20 {
+            address: Address::try_from_slice(body).ok()?,
+            native: value.is_some(),
+        },
+        KeyPrefix::Erc20Code if body == aurora_engine::erc20_code::CODE_KEY => {
+            EngineChange::Erc20Code(value.map(<[u8]>::to_vec))
+        }
+        KeyPrefix::Erc20Code if body == aurora_engine::erc20_code::VERSION_KEY => {
+            EngineChange::Erc20LatestVersion(value.map(decode_code_version).transpose()?)
+        }
+        KeyPrefix::Erc20Code => EngineChange::Erc20TokenVersion {
+            address: Address::try_from_slice(body).ok()?,
+            version: value.map(decode_code_version).transpose()?,
+        },
+        KeyPrefix::Denylist => decode_address_list_change(AddressListKind::Denylist, body, value)?,
+        KeyPrefix::DeployAllowlist => {
+            decode_address_list_change(AddressListKind::DeployAllowlist, body, value)?
+        }
+        KeyPrefix::FeeWaivedSenders => {
+            decode_address_list_change(AddressListKind::FeeWaivedSenders, body, value)?
+        }
+        KeyPrefix::Whitelist => decode_whitelist_change(body, value)?,
+        KeyPrefix::CrossContractCall | KeyPrefix::Hashchain => return None,
+    };
+
+    Some(change)
+}
+
+fn decode_storage_change(body: &[u8], value: Option<&[u8]>) -> Option<EngineChange> {
+    // The key is the address and the slot, with the generation in between if it is not zero.
+    let (address, generation, slot) = match body.len() {
+        52 => (&body[..20], 0, &body[20..]),
+        56 => (
+            &body[..20],
+            u32::from_le_bytes(body[20..24].try_into().ok()?),
+            &body[24..],
+        ),
+        _ => return None,
+    };
+    let value = match value {
+        Some(bytes) if bytes.len() == 32 => H256::from_slice(bytes),
+        Some(_) => return None,
+        None => H256::zero(),
+    };
+
+    Some(EngineChange::Storage {
+        address: Address::try_from_slice(address).ok()?,
+        generation,
+        slot: H256::from_slice(slot),
+        value,
+    })
+}
+
+/// The entries of the address lists are stored under their own keys, so their values only mark
+/// the presence of the address.
+fn decode_address_list_change(
+    list: AddressListKind,
+    body: &[u8],
+    value: Option<&[u8]>,
+) -> Option<EngineChange> {
+    if body == LIST_KEY {
+        return Some(EngineChange::AddressListEntries {
+            list,
+            addresses: decode_list(value)?,
+        });
+    }
+    Some(EngineChange::AddressListEntry {
+        list,
+        address: Address::try_from_slice(body).ok()?,
+        added: value.is_some(),
+    })
+}
+
+fn decode_whitelist_change(body: &[u8], value: Option<&[u8]>) -> Option<EngineChange> {
+    // The keys of each whitelist are prefixed with its kind.
+    let (&kind, body) = body.split_first()?;
+    let kind = match kind {
+        0 => WhitelistKind::Address,
+        1 => WhitelistKind::Account,
+        _ => return None,
+    };
+    if body == WHITELIST_STATUS_KEY {
+        return Some(EngineChange::WhitelistStatus {
+            kind,
+            enabled: value.is_some(),
+        });
+    }
+    match kind {
+        WhitelistKind::Address => {
+            decode_address_list_change(AddressListKind::Whitelist, body, value)
+        }
+        WhitelistKind::Account if body == LIST_KEY => {
+            Some(EngineChange::WhitelistAccounts(decode_list(value)?))
+        }
+        WhitelistKind::Account => Some(EngineChange::WhitelistAccount {
+            account_id: AccountId::try_from(body).ok()?,
+            added: value.is_some(),
+        }),
+    }
+}
+
+/// The keys are stored without their type, which is given by their length.
+fn decode_public_key(bytes: &[u8]) -> Option<PublicKey> {
+    match bytes.len() {
+        32 => Some(PublicKey::Ed25519(bytes.try_into().ok()?)),
+        64 => Some(PublicKey::Secp256k1(bytes.try_into().ok()?)),
+        _ => None,
+    }
+}
+
+fn decode_relayer_key_metadata(bytes: &[u8]) -> Option<RelayerKeyMetadata> {
+    // Keys added before the metadata was introduced are marked with a single byte.
+    if bytes == [1] {
+        return Some(RelayerKeyMetadata::default());
+    }
+    RelayerKeyMetadata::try_from_slice(bytes).ok()
+}
+
+/// A deleted list is empty.
+fn decode_list<T: BorshDeserialize>(value: Option<&[u8]>) -> Option<Vec<T>> {
+    value.map_or(Some(Vec::new()), |bytes| Vec::try_from_slice(bytes).ok())
+}
+
+fn decode_engine_state(bytes: &[u8]) -> Option<EngineState> {
+    BorshableEngineState::try_from_slice(bytes)
+        .map(Into::into)
+        .or_else(|_| BorshableEngineStateV1::try_from_slice(bytes).map(Into::into))
+        .ok()
+}
+
+fn decode_code_version(bytes: &[u8]) -> Option<CodeVersion> {
+    <[u8; 4]>::try_from(bytes)
+        .ok()
+        .map(|bytes| CodeVersion(u32::from_le_bytes(bytes)))
+}
+
+fn decode_u256(value: Option<&[u8]>) -> Option<U256> {
+    match value {
+        Some(bytes) if bytes.len() == 32 => Some(U256::from_big_endian(bytes)),
+        Some(_) => None,
+        None => Some(U256::zero()),
+    }
+}
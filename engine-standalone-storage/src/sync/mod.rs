@@ -7,6 +7,7 @@ use aurora_engine_modexp::ModExpAlgorithm;
 use aurora_engine_sdk::{
     env::{self, DEFAULT_PREPAID_GAS},
     io::IO,
+    promise::PromiseHandler,
 };
 use aurora_engine_transactions::EthTransactionKind;
 use aurora_engine_types::{
@@ -15,10 +16,15 @@ use aurora_engine_types::{
 };
 use std::{io, str::FromStr};
 
+pub mod parallel;
 pub mod types;
 
-use crate::{error::ParseTransactionKindError, BlockMetadata, Diff, Storage};
-use types::{Message, TransactionKind, TransactionKindTag, TransactionMessage};
+use crate::{
+    divergence::{detect_divergence, DivergenceRecord},
+    error::ParseTransactionKindError,
+    BlockMetadata, Diff, Storage,
+};
+use types::{BlockMessage, Message, TransactionKind, TransactionKindTag, TransactionMessage};
 
 /// Try to parse an Aurora transaction from raw information available in a Near action
 /// (method name, input bytes, data returned from promises).
@@ -66,8 +72,18 @@ pub fn parse_transaction_kind(
         }
         TransactionKindTag::Deploy => TransactionKind::Deploy(bytes),
         TransactionKindTag::DeployErc20 => {
-            let deploy_args =
-                parameters::DeployErc20TokenArgs::try_from_slice(&bytes).map_err(f)?;
+            // The metadata synchronization flag only affects the scheduled promises,
+            // so it is not part of the transaction kind.
+            let deploy_args = parameters::DeployErc20TokenArgsV2::deserialize(&bytes)
+                .map(|args| parameters::DeployErc20TokenArgs {
+                    nep141: args.nep141,
+                })
+                .ok_or_else(|| {
+                    ParseTransactionKindError::failed_deserialization::<io::Error>(
+                        tx_kind_tag,
+                        None,
+                    )
+                })?;
             TransactionKind::DeployErc20(deploy_args)
         }
         TransactionKindTag::FtOnTransfer => {
@@ -205,11 +221,17 @@ pub fn parse_transaction_kind(
             TransactionKind::SetKeyManager(args)
         }
         TransactionKindTag::AddRelayerKey => {
-            let args = parameters::RelayerKeyArgs::try_from_slice(&bytes).map_err(f)?;
+            let args: parameters::RelayerKeyArgsV2 = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
             TransactionKind::AddRelayerKey(args)
         }
         TransactionKindTag::RemoveRelayerKey => {
-            let args = parameters::RelayerKeyArgs::try_from_slice(&bytes).map_err(f)?;
+            let args: parameters::RelayerKeyArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
             TransactionKind::RemoveRelayerKey(args)
         }
         TransactionKindTag::StartHashchain => {
@@ -223,6 +245,158 @@ pub fn parse_transaction_kind(
                 })?;
             TransactionKind::SetErc20Metadata(args)
         }
+        TransactionKindTag::SetNep141Code => TransactionKind::SetNep141Code(bytes),
+        TransactionKindTag::DeployNep141 => {
+            let args = parameters::DeployNep141TokenArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::DeployNep141(args)
+        }
+        TransactionKindTag::RegisterNep141 => {
+            let args = parameters::RegisterNep141TokenArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::RegisterNep141(args)
+        }
+        TransactionKindTag::SyncErc20Metadata => {
+            let args = parameters::SyncErc20MetadataArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::SyncErc20Metadata(args)
+        }
+        TransactionKindTag::SyncErc20MetadataCallback => {
+            let args = parameters::SyncErc20MetadataArgs::try_from_slice(&bytes).map_err(f)?;
+            TransactionKind::SyncErc20MetadataCallback(args)
+        }
+        TransactionKindTag::SetErc20Code => TransactionKind::SetErc20Code(bytes),
+        TransactionKindTag::MigrateErc20 => {
+            let address = Address::try_from_slice(&bytes).map_err(|e| {
+                ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+            })?;
+            TransactionKind::MigrateErc20(address)
+        }
+        TransactionKindTag::GrantRole => {
+            let args: parameters::RoleArgs =
+                serde_json::from_slice(bytes.as_slice()).map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::GrantRole(args)
+        }
+        TransactionKindTag::RevokeRole => {
+            let args: parameters::RoleArgs =
+                serde_json::from_slice(bytes.as_slice()).map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::RevokeRole(args)
+        }
+        TransactionKindTag::StateMigration => TransactionKind::StateMigration(bytes),
+        TransactionKindTag::RemoveExpiredRelayerKeys => TransactionKind::RemoveExpiredRelayerKeys,
+        TransactionKindTag::PauseMethods => {
+            let args: parameters::PauseMethodsArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::PauseMethods(args)
+        }
+        TransactionKindTag::ResumeMethods => {
+            let args: parameters::PauseMethodsArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::ResumeMethods(args)
+        }
+        TransactionKindTag::DenyAddress => {
+            let args: parameters::DenylistArgs =
+                serde_json::from_slice(bytes.as_slice()).map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::DenyAddress(args)
+        }
+        TransactionKindTag::AllowAddress => {
+            let args: parameters::DenylistArgs =
+                serde_json::from_slice(bytes.as_slice()).map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::AllowAddress(args)
+        }
+        TransactionKindTag::SetDeployAllowlistEnabled => {
+            let args: parameters::DeployAllowlistModeArgs =
+                serde_json::from_slice(bytes.as_slice()).map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::SetDeployAllowlistEnabled(args)
+        }
+        TransactionKindTag::AddDeployer => {
+            let args: parameters::DeployerArgs =
+                serde_json::from_slice(bytes.as_slice()).map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::AddDeployer(args)
+        }
+        TransactionKindTag::RemoveDeployer => {
+            let args: parameters::DeployerArgs =
+                serde_json::from_slice(bytes.as_slice()).map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::RemoveDeployer(args)
+        }
+        TransactionKindTag::SetSiloParams => {
+            let args: parameters::SiloParams =
+                serde_json::from_slice(bytes.as_slice()).map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::SetSiloParams(args)
+        }
+        TransactionKindTag::AddFeeWaivedSenders => {
+            let args: parameters::AddressesArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::AddFeeWaivedSenders(args)
+        }
+        TransactionKindTag::RemoveFeeWaivedSenders => {
+            let args: parameters::AddressesArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::RemoveFeeWaivedSenders(args)
+        }
+        TransactionKindTag::SetWhitelistStatus => {
+            let args: parameters::WhitelistStatusArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::SetWhitelistStatus(args)
+        }
+        TransactionKindTag::AddWhitelistedAddresses => {
+            let args: parameters::AddressesArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::AddWhitelistedAddresses(args)
+        }
+        TransactionKindTag::RemoveWhitelistedAddresses => {
+            let args: parameters::AddressesArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::RemoveWhitelistedAddresses(args)
+        }
+        TransactionKindTag::AddWhitelistedAccounts => {
+            let args: parameters::AccountsArgs =
+                serde_json::from_slice(bytes.as_slice()).map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::AddWhitelistedAccounts(args)
+        }
+        TransactionKindTag::RemoveWhitelistedAccounts => {
+            let args: parameters::AccountsArgs =
+                serde_json::from_slice(bytes.as_slice()).map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::RemoveWhitelistedAccounts(args)
+        }
+        TransactionKindTag::ListLegacyRelayerKeys => {
+            let args: parameters::RelayerKeysArgs = serde_json::from_slice(bytes.as_slice())
+                .map_err(|e| {
+                    ParseTransactionKindError::failed_deserialization(tx_kind_tag, Some(e))
+                })?;
+            TransactionKind::ListLegacyRelayerKeys(args)
+        }
         TransactionKindTag::Unknown => {
             return Err(ParseTransactionKindError::UnknownMethodName {
                 name: method_name.into(),
@@ -251,8 +425,9 @@ pub fn consume_message<M: ModExpAlgorithm + 'static>(
         }
 
         Message::Transaction(transaction_message) => {
-            // Failed transactions have no impact on the state of our database.
-            if !transaction_message.succeeded {
+            // Failed transactions have no impact on the state of our database. They are still
+            // executed if their on-chain outcome is known, to check that they fail locally too.
+            if !transaction_message.succeeded && transaction_message.on_chain_outcome.is_none() {
                 return Ok(ConsumeMessageOutcome::FailedTransactionIgnored);
             }
 
@@ -262,28 +437,42 @@ pub fn consume_message<M: ModExpAlgorithm + 'static>(
             let block_metadata = storage.get_block_metadata(block_hash)?;
             let engine_account_id = storage.get_engine_account_id()?;
 
-            let (tx_hash, diff, result) = storage
-                .with_engine_access(
-                    block_height,
-                    transaction_position,
-                    &transaction_message.raw_input,
-                    |io| {
-                        execute_transaction::<_, M, _>(
-                            transaction_message.as_ref(),
-                            block_height,
-                            &block_metadata,
-                            engine_account_id,
-                            io,
-                            EngineStateAccess::get_transaction_diff,
-                        )
-                    },
-                )
-                .result;
+            let access = storage.with_engine_access(
+                block_height,
+                transaction_position,
+                &transaction_message.raw_input,
+                |io| {
+                    execute_transaction::<_, M, _>(
+                        transaction_message.as_ref(),
+                        block_height,
+                        &block_metadata,
+                        engine_account_id,
+                        io,
+                        EngineStateAccess::get_transaction_diff,
+                    )
+                },
+            );
+            let (tx_hash, diff, result) = access.result;
+            let divergence = detect_divergence(
+                block_height,
+                &transaction_message,
+                tx_hash,
+                &result,
+                &access.engine_output,
+            );
+            if !transaction_message.succeeded {
+                return Ok(divergence
+                    .map_or(ConsumeMessageOutcome::FailedTransactionIgnored, |record| {
+                        ConsumeMessageOutcome::FailedTransactionDiverged(Box::new(record))
+                    }));
+            }
+
             let outcome = TransactionIncludedOutcome {
                 hash: tx_hash,
                 info: *transaction_message,
                 diff,
                 maybe_result: result,
+                divergence,
             };
             Ok(ConsumeMessageOutcome::TransactionIncluded(Box::new(
                 outcome,
@@ -292,6 +481,77 @@ pub fn consume_message<M: ModExpAlgorithm + 'static>(
     }
 }
 
+/// Executes all the transactions of a block, in the given order, and commits the block with
+/// them in a single write. Each transaction sees the changes made by the previous ones through
+/// an in-memory overlay. Like in `consume_message`, the transactions which failed on NEAR are
+/// executed if their on-chain outcome is known, to record their divergences, but their changes
+/// are not committed. If the Engine fails to execute any of the (successful on NEAR)
+/// transactions, the whole block is rolled back: only the divergence record of the transaction
+/// is written to the storage.
+pub fn consume_block<M: ModExpAlgorithm + 'static>(
+    storage: &mut Storage,
+    block_message: &BlockMessage,
+    transactions: Vec<TransactionMessage>,
+) -> Result<ConsumeBlockOutcome, crate::Error> {
+    let block_height = block_message.height;
+    let block_metadata = &block_message.metadata;
+    let engine_account_id = storage.get_engine_account_id()?;
+    let mut block_diff = Diff::default();
+    let mut outcomes = Vec::with_capacity(transactions.len());
+
+    // Failed transactions have no impact on the state of our database.
+    let transactions = transactions
+        .into_iter()
+        .filter(|tx| tx.succeeded || tx.on_chain_outcome.is_some());
+    for transaction_message in transactions {
+        let access = storage.with_engine_access_over_diff(
+            block_height,
+            transaction_message.position,
+            &transaction_message.raw_input,
+            &block_diff,
+            |io| {
+                execute_transaction::<_, M, _>(
+                    &transaction_message,
+                    block_height,
+                    block_metadata,
+                    engine_account_id.clone(),
+                    io,
+                    EngineStateAccess::get_transaction_diff,
+                )
+            },
+        );
+        let (tx_hash, diff, result) = access.result;
+        let divergence = detect_divergence(
+            block_height,
+            &transaction_message,
+            tx_hash,
+            &result,
+            &access.engine_output,
+        );
+        let outcome = TransactionIncludedOutcome {
+            hash: tx_hash,
+            info: transaction_message,
+            diff,
+            maybe_result: result,
+            divergence,
+        };
+        if outcome.info.succeeded && outcome.is_error() {
+            if let Some(record) = &outcome.divergence {
+                storage.set_divergence_record(record)?;
+            }
+            return Ok(ConsumeBlockOutcome::RolledBack(Box::new(outcome)));
+        }
+        if outcome.is_included() {
+            block_diff.append(outcome.diff.clone());
+        }
+        outcomes.push(outcome);
+    }
+
+    storage.set_block_included(block_message.hash, block_height, block_metadata, &outcomes)?;
+
+    Ok(ConsumeBlockOutcome::Committed(outcomes))
+}
+
 pub fn execute_transaction_message<M: ModExpAlgorithm + 'static>(
     storage: &Storage,
     transaction_message: TransactionMessage,
@@ -317,11 +577,19 @@ pub fn execute_transaction_message<M: ModExpAlgorithm + 'static>(
         },
     );
     let (tx_hash, diff, maybe_result) = result.result;
+    let divergence = detect_divergence(
+        block_height,
+        &transaction_message,
+        tx_hash,
+        &maybe_result,
+        &result.engine_output,
+    );
     let outcome = TransactionIncludedOutcome {
         hash: tx_hash,
         info: transaction_message,
         diff,
         maybe_result,
+        divergence,
     };
     Ok(outcome)
 }
@@ -342,6 +610,43 @@ where
     I: IO + Copy,
     M: ModExpAlgorithm + 'static,
     F: FnOnce(&I) -> Diff,
+{
+    // We can ignore promises in the standalone engine because it processes each receipt separately
+    // and it is fed a stream of receipts (it does not schedule them)
+    let mut handler = crate::promise::NoScheduler {
+        promise_data: &transaction_message.promise_data,
+    };
+    execute_transaction_with_handler::<_, M, _, _>(
+        transaction_message,
+        block_height,
+        block_metadata,
+        engine_account_id,
+        io,
+        get_diff,
+        &mut handler,
+    )
+}
+
+/// Same as `execute_transaction`, but the promises created by the engine are given to `handler`,
+/// which also provides the results of the promises the transaction is a callback of.
+pub fn execute_transaction_with_handler<I, M, F, P>(
+    transaction_message: &TransactionMessage,
+    block_height: u64,
+    block_metadata: &BlockMetadata,
+    engine_account_id: AccountId,
+    io: I,
+    get_diff: F,
+    handler: &mut P,
+) -> (
+    H256,
+    Diff,
+    Result<Option<TransactionExecutionResult>, error::Error>,
+)
+where
+    I: IO + Copy,
+    M: ModExpAlgorithm + 'static,
+    F: FnOnce(&I) -> Diff,
+    P: PromiseHandler,
 {
     let signer_account_id = transaction_message.signer.clone();
     let predecessor_account_id = transaction_message.caller.clone();
@@ -349,6 +654,7 @@ where
     let current_account_id = engine_account_id;
     let env = env::Fixed {
         signer_account_id,
+        signer_account_pk: transaction_message.signer_public_key,
         current_account_id,
         predecessor_account_id,
         block_height,
@@ -360,35 +666,24 @@ where
 
     let (tx_hash, result) = match &transaction_message.transaction {
         TransactionKind::Submit(tx) => {
-            // We can ignore promises in the standalone engine because it processes each receipt separately
-            // and it is fed a stream of receipts (it does not schedule them)
-            let mut handler = crate::promise::NoScheduler {
-                promise_data: &transaction_message.promise_data,
-            };
             let tx_data: Vec<u8> = tx.into();
             let tx_hash = aurora_engine_sdk::keccak(&tx_data);
-            let result = contract_methods::evm_transactions::submit(io, &env, &mut handler)
+            let result = contract_methods::evm_transactions::submit(io, &env, handler)
                 .map(|submit_result| Some(TransactionExecutionResult::Submit(Ok(submit_result))))
                 .map_err(Into::into);
 
             (tx_hash, result)
         }
         TransactionKind::SubmitWithArgs(args) => {
-            let mut handler = crate::promise::NoScheduler {
-                promise_data: &transaction_message.promise_data,
-            };
             let tx_hash = aurora_engine_sdk::keccak(&args.tx_data);
-            let result =
-                contract_methods::evm_transactions::submit_with_args(io, &env, &mut handler)
-                    .map(|submit_result| {
-                        Some(TransactionExecutionResult::Submit(Ok(submit_result)))
-                    })
-                    .map_err(Into::into);
+            let result = contract_methods::evm_transactions::submit_with_args(io, &env, handler)
+                .map(|submit_result| Some(TransactionExecutionResult::Submit(Ok(submit_result))))
+                .map_err(Into::into);
 
             (tx_hash, result)
         }
         other => {
-            let result = non_submit_execute(other, io, &env, &transaction_message.promise_data);
+            let result = non_submit_execute(other, io, &env, handler);
             (near_receipt_id, result)
         }
     };
@@ -402,56 +697,46 @@ where
 /// The `submit` transaction kind is special because it is the only one where the transaction hash
 /// differs from the NEAR receipt hash.
 #[allow(clippy::too_many_lines)]
-fn non_submit_execute<I: IO + Copy>(
+fn non_submit_execute<I: IO + Copy, P: PromiseHandler>(
     transaction: &TransactionKind,
     io: I,
     env: &env::Fixed,
-    promise_data: &[Option<Vec<u8>>],
+    handler: &mut P,
 ) -> Result<Option<TransactionExecutionResult>, error::Error> {
     let result = match transaction {
         TransactionKind::Call(_) => {
-            // We can ignore promises in the standalone engine (see above)
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let result = contract_methods::evm_transactions::call(io, env, &mut handler)?;
+            let result = contract_methods::evm_transactions::call(io, env, handler)?;
 
             Some(TransactionExecutionResult::Submit(Ok(result)))
         }
 
         TransactionKind::Deploy(_) => {
-            // We can ignore promises in the standalone engine (see above)
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let result = contract_methods::evm_transactions::deploy_code(io, env, &mut handler)?;
+            let result = contract_methods::evm_transactions::deploy_code(io, env, handler)?;
 
             Some(TransactionExecutionResult::Submit(Ok(result)))
         }
 
         TransactionKind::DeployErc20(_) => {
-            // No promises can be created by `deploy_erc20_token`
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let result = contract_methods::connector::deploy_erc20_token(io, env, &mut handler)?;
+            let result = contract_methods::connector::deploy_erc20_token(io, env, handler)?;
 
             Some(TransactionExecutionResult::DeployErc20(result))
         }
 
         TransactionKind::FtOnTransfer(_) => {
             // No promises can be created by `ft_on_transfer`
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::connector::ft_on_transfer(io, env, &mut handler)?;
+            contract_methods::connector::ft_on_transfer(io, env, handler)?;
 
             None
         }
 
         TransactionKind::FtTransferCall(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let promise_args =
-                contract_methods::connector::ft_transfer_call(io, env, &mut handler)?;
+            let promise_args = contract_methods::connector::ft_transfer_call(io, env, handler)?;
 
             Some(TransactionExecutionResult::Promise(promise_args))
         }
 
         TransactionKind::ResolveTransfer(_, _) => {
-            let handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::connector::ft_resolve_transfer(io, env, &handler)?;
+            contract_methods::connector::ft_resolve_transfer(io, env, &*handler)?;
 
             None
         }
@@ -469,30 +754,25 @@ fn non_submit_execute<I: IO + Copy>(
         }
 
         TransactionKind::Deposit(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let promise_args = contract_methods::connector::deposit(io, env, &mut handler)?;
+            let promise_args = contract_methods::connector::deposit(io, env, handler)?;
 
             Some(TransactionExecutionResult::Promise(promise_args))
         }
 
         TransactionKind::FinishDeposit(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let maybe_promise_args =
-                contract_methods::connector::finish_deposit(io, env, &mut handler)?;
+            let maybe_promise_args = contract_methods::connector::finish_deposit(io, env, handler)?;
 
             maybe_promise_args.map(TransactionExecutionResult::Promise)
         }
 
         TransactionKind::StorageDeposit(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::connector::storage_deposit(io, env, &mut handler)?;
+            contract_methods::connector::storage_deposit(io, env, handler)?;
 
             None
         }
 
         TransactionKind::StorageUnregister(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::connector::storage_unregister(io, env, &mut handler)?;
+            contract_methods::connector::storage_unregister(io, env, handler)?;
 
             None
         }
@@ -516,12 +796,8 @@ fn non_submit_execute<I: IO + Copy>(
         }
 
         TransactionKind::ExitToNear(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            let maybe_result = contract_methods::connector::exit_to_near_precompile_callback(
-                io,
-                env,
-                &mut handler,
-            )?;
+            let maybe_result =
+                contract_methods::connector::exit_to_near_precompile_callback(io, env, handler)?;
 
             maybe_result.map(|submit_result| TransactionExecutionResult::Submit(Ok(submit_result)))
         }
@@ -548,8 +824,7 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::FactoryUpdateAddressVersion(_) => {
-            let handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::xcc::factory_update_address_version(io, env, &handler)?;
+            contract_methods::xcc::factory_update_address_version(io, env, &*handler)?;
 
             None
         }
@@ -559,8 +834,7 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::FundXccSubAccound(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::xcc::fund_xcc_sub_account(io, env, &mut handler)?;
+            contract_methods::xcc::fund_xcc_sub_account(io, env, handler)?;
 
             None
         }
@@ -603,14 +877,12 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::AddRelayerKey(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::admin::add_relayer_key(io, env, &mut handler)?;
+            contract_methods::admin::add_relayer_key(io, env, handler)?;
 
             None
         }
         TransactionKind::RemoveRelayerKey(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::admin::remove_relayer_key(io, env, &mut handler)?;
+            contract_methods::admin::remove_relayer_key(io, env, handler)?;
 
             None
         }
@@ -620,8 +892,143 @@ fn non_submit_execute<I: IO + Copy>(
             None
         }
         TransactionKind::SetErc20Metadata(_) => {
-            let mut handler = crate::promise::NoScheduler { promise_data };
-            contract_methods::connector::set_erc20_metadata(io, env, &mut handler)?;
+            contract_methods::connector::set_erc20_metadata(io, env, handler)?;
+
+            None
+        }
+        TransactionKind::SetNep141Code(_) => {
+            contract_methods::connector::set_nep141_code(io, env)?;
+
+            None
+        }
+        TransactionKind::DeployNep141(_) => {
+            contract_methods::connector::deploy_nep141_token(io, env, handler)?;
+
+            None
+        }
+        TransactionKind::RegisterNep141(_) => {
+            contract_methods::connector::register_nep141_token(io, env, &*handler)?;
+
+            None
+        }
+        TransactionKind::SyncErc20Metadata(_) => {
+            let promise_args = contract_methods::connector::sync_erc20_metadata(io, env, handler)?;
+
+            Some(TransactionExecutionResult::Promise(promise_args))
+        }
+        TransactionKind::SyncErc20MetadataCallback(_) => {
+            let result =
+                contract_methods::connector::sync_erc20_metadata_callback(io, env, handler)?;
+
+            Some(TransactionExecutionResult::Submit(Ok(result)))
+        }
+        TransactionKind::SetErc20Code(_) => {
+            contract_methods::connector::set_erc20_code(io, env)?;
+
+            None
+        }
+        TransactionKind::MigrateErc20(_) => {
+            contract_methods::connector::migrate_erc20_token(io, env)?;
+
+            None
+        }
+        TransactionKind::GrantRole(_) => {
+            contract_methods::admin::grant_role(io, env)?;
+
+            None
+        }
+        TransactionKind::RevokeRole(_) => {
+            contract_methods::admin::revoke_role(io, env)?;
+
+            None
+        }
+        TransactionKind::StateMigration(_) => {
+            contract_methods::admin::state_migration(io, env)?;
+
+            None
+        }
+        TransactionKind::RemoveExpiredRelayerKeys => {
+            contract_methods::admin::remove_expired_relayer_keys(io, env, handler)?;
+
+            None
+        }
+        TransactionKind::PauseMethods(_) => {
+            contract_methods::admin::pause_methods(io, env)?;
+
+            None
+        }
+        TransactionKind::ResumeMethods(_) => {
+            contract_methods::admin::resume_methods(io, env)?;
+
+            None
+        }
+        TransactionKind::DenyAddress(_) => {
+            contract_methods::admin::deny_address(io, env)?;
+
+            None
+        }
+        TransactionKind::AllowAddress(_) => {
+            contract_methods::admin::allow_address(io, env)?;
+
+            None
+        }
+        TransactionKind::SetDeployAllowlistEnabled(_) => {
+            contract_methods::admin::set_deploy_allowlist_enabled(io, env)?;
+
+            None
+        }
+        TransactionKind::AddDeployer(_) => {
+            contract_methods::admin::add_deployer(io, env)?;
+
+            None
+        }
+        TransactionKind::RemoveDeployer(_) => {
+            contract_methods::admin::remove_deployer(io, env)?;
+
+            None
+        }
+        TransactionKind::SetSiloParams(_) => {
+            contract_methods::admin::set_silo_params(io, env)?;
+
+            None
+        }
+        TransactionKind::AddFeeWaivedSenders(_) => {
+            contract_methods::admin::add_fee_waived_senders(io, env)?;
+
+            None
+        }
+        TransactionKind::RemoveFeeWaivedSenders(_) => {
+            contract_methods::admin::remove_fee_waived_senders(io, env)?;
+
+            None
+        }
+        TransactionKind::SetWhitelistStatus(_) => {
+            contract_methods::admin::set_whitelist_status(io, env)?;
+
+            None
+        }
+        TransactionKind::AddWhitelistedAddresses(_) => {
+            contract_methods::admin::add_whitelisted_addresses(io, env)?;
+
+            None
+        }
+        TransactionKind::RemoveWhitelistedAddresses(_) => {
+            contract_methods::admin::remove_whitelisted_addresses(io, env)?;
+
+            None
+        }
+        TransactionKind::AddWhitelistedAccounts(_) => {
+            contract_methods::admin::add_whitelisted_accounts(io, env)?;
+
+            None
+        }
+        TransactionKind::RemoveWhitelistedAccounts(_) => {
+            contract_methods::admin::remove_whitelisted_accounts(io, env)?;
+
+            None
+        }
+        TransactionKind::ListLegacyRelayerKeys(_) => {
+            contract_methods::admin::list_legacy_relayer_keys(io, env)?;
 
             None
         }
@@ -634,33 +1041,61 @@ fn non_submit_execute<I: IO + Copy>(
 pub enum ConsumeMessageOutcome {
     BlockAdded,
     FailedTransactionIgnored,
+    /// The transaction failed on NEAR but not locally.
+    FailedTransactionDiverged(Box<DivergenceRecord>),
     TransactionIncluded(Box<TransactionIncludedOutcome>),
 }
 
 impl ConsumeMessageOutcome {
     pub fn commit(&self, storage: &mut Storage) -> Result<(), crate::error::Error> {
-        if let Self::TransactionIncluded(x) = self {
-            x.commit(storage)?;
+        match self {
+            Self::TransactionIncluded(x) => x.commit(storage)?,
+            Self::FailedTransactionDiverged(record) => storage.set_divergence_record(record)?,
+            Self::BlockAdded | Self::FailedTransactionIgnored => (),
         }
         Ok(())
     }
 }
 
+#[derive(Debug)]
+pub enum ConsumeBlockOutcome {
+    /// The block and the outcomes of its transactions were written to the storage. Only the
+    /// divergence records of the transactions which failed on NEAR were written.
+    Committed(Vec<TransactionIncludedOutcome>),
+    /// Nothing but the divergence record of this transaction was written, because the Engine
+    /// failed to execute it.
+    RolledBack(Box<TransactionIncludedOutcome>),
+}
+
 #[derive(Debug)]
 pub struct TransactionIncludedOutcome {
     pub hash: aurora_engine_types::H256,
     pub info: TransactionMessage,
     pub diff: crate::Diff,
     pub maybe_result: Result<Option<TransactionExecutionResult>, error::Error>,
+    /// Differences with the on-chain outcome of the transaction, if it is known.
+    pub divergence: Option<DivergenceRecord>,
 }
 
 impl TransactionIncludedOutcome {
     pub fn commit(&self, storage: &mut Storage) -> Result<(), crate::error::Error> {
-        match self.maybe_result.as_ref() {
-            Err(_) | Ok(Some(TransactionExecutionResult::Submit(Err(_)))) => (), // do not persist if Engine encounters an error
-            _ => storage.set_transaction_included(self.hash, &self.info, &self.diff)?,
-        };
-        Ok(())
+        storage.set_transaction_outcome(self)
+    }
+
+    /// Returns true if the changes of the transaction are persisted, i.e. if it succeeded on
+    /// NEAR and the Engine did not encounter an error while executing it.
+    #[must_use]
+    pub const fn is_included(&self) -> bool {
+        self.info.succeeded && !self.is_error()
+    }
+
+    /// Returns true if the Engine encountered an error while executing the transaction.
+    #[must_use]
+    pub const fn is_error(&self) -> bool {
+        matches!(
+            self.maybe_result,
+            Err(_) | Ok(Some(TransactionExecutionResult::Submit(Err(_))))
+        )
     }
 }
 
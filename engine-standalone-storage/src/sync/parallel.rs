@@ -0,0 +1,353 @@
+//! Optimistic parallel execution of the transactions of a block, in the spirit of Block-STM.
+//!
+//! All the transactions are first executed concurrently against the state before the block,
+//! recording the keys each of them reads. The results are then validated in block order: a
+//! transaction which read a key written by a previous transaction of the block may have seen a
+//! stale value, so it is executed again on top of the changes of the previous transactions.
+//! The Engine being deterministic, the transactions which did not read any such key produce the
+//! same outcome as in the sequential execution. Therefore the outcomes (and diffs) are exactly
+//! the ones of the sequential path.
+//!
+//! The hashchain state is updated by every transaction, so it is excluded from the conflicts:
+//! it is recomputed sequentially during the validation instead, from the input and output of
+//! each transaction. Note that other keys updated by every transaction make all the transactions
+//! of the block conflict with each other, in which case the execution is not faster than the
+//! sequential one.
+
+use super::{
+    execute_transaction,
+    types::{BlockMessage, TransactionKind, TransactionKindTag},
+    TransactionIncludedOutcome,
+};
+use crate::{
+    divergence::detect_divergence, error, sync::types::TransactionMessage, Diff, DiffValue, Storage,
+};
+use aurora_engine::hashchain::HASHCHAIN_STATE;
+use aurora_engine_hashchain::hashchain::Hashchain;
+use aurora_engine_modexp::ModExpAlgorithm;
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_types::{
+    account_id::AccountId,
+    storage::{bytes_to_key, KeyPrefix},
+};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug)]
+pub struct ParallelExecutionOutcome {
+    /// Outcomes of the transactions, in block order.
+    pub outcomes: Vec<TransactionIncludedOutcome>,
+    /// Number of transactions executed again because of a conflict with a previous one.
+    pub re_executed: usize,
+}
+
+impl ParallelExecutionOutcome {
+    /// Writes the block and the transactions executed without error, like the sequential path
+    /// which does not persist the transactions the Engine failed to execute. The divergence
+    /// records of all the transactions are written.
+    pub fn commit(
+        &self,
+        storage: &mut Storage,
+        block_message: &BlockMessage,
+    ) -> Result<(), crate::Error> {
+        storage.set_block_included(
+            block_message.hash,
+            block_message.height,
+            &block_message.metadata,
+            &self.outcomes,
+        )
+    }
+}
+
+/// Executes the transactions of the block using `num_threads` threads. Nothing is written to the
+/// storage, see `ParallelExecutionOutcome::commit`.
+pub fn execute_block<M: ModExpAlgorithm + 'static>(
+    storage: &Storage,
+    block_message: &BlockMessage,
+    transactions: Vec<TransactionMessage>,
+    num_threads: NonZeroUsize,
+) -> Result<ParallelExecutionOutcome, crate::Error> {
+    let engine_account_id = storage.get_engine_account_id()?;
+    // Failed transactions have no impact on the state of our database. They are still executed
+    // if their on-chain outcome is known, to check that they fail locally too.
+    let transactions: Vec<TransactionMessage> = transactions
+        .into_iter()
+        .filter(|tx| tx.succeeded || tx.on_chain_outcome.is_some())
+        .collect();
+
+    let hashchain_key = bytes_to_key(KeyPrefix::Hashchain, HASHCHAIN_STATE);
+
+    let next_index = AtomicUsize::new(0);
+    let base_diff = Diff::default();
+    let mut speculative: Vec<(usize, Execution)> = std::thread::scope(|scope| {
+        let workers: Vec<_> = (0..num_threads.get())
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut executions = Vec::new();
+                    loop {
+                        let index = next_index.fetch_add(1, Ordering::Relaxed);
+                        let Some(transaction) = transactions.get(index) else {
+                            break executions;
+                        };
+                        let execution = execute::<M>(
+                            storage,
+                            block_message,
+                            &engine_account_id,
+                            transaction,
+                            &base_diff,
+                            &hashchain_key,
+                        );
+                        executions.push((index, execution));
+                    }
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("Speculative execution panicked"))
+            .collect()
+    });
+    speculative.sort_unstable_by_key(|(index, _)| *index);
+
+    let mut block_diff = Diff::default();
+    let mut written_keys: HashSet<Vec<u8>> = HashSet::new();
+    let mut hashchain = BlockHashchain::new(storage, block_message, &transactions)?;
+    let mut outcomes = Vec::with_capacity(transactions.len());
+    let mut re_executed = 0;
+
+    for (transaction, (_, execution)) in transactions.iter().zip(speculative) {
+        let execution = if execution.reads.iter().any(|key| written_keys.contains(key)) {
+            re_executed += 1;
+            execute::<M>(
+                storage,
+                block_message,
+                &engine_account_id,
+                transaction,
+                &block_diff,
+                &hashchain_key,
+            )
+        } else {
+            execution
+        };
+        let mut outcome = execution.outcome;
+        // The changes of the transactions which failed on NEAR or which the Engine failed to
+        // execute are not persisted.
+        if outcome.is_included() {
+            hashchain.update(&mut outcome, &execution.output, &hashchain_key)?;
+            written_keys.extend(
+                outcome
+                    .diff
+                    .iter()
+                    .map(|(key, _)| key)
+                    .filter(|key| **key != hashchain_key)
+                    .cloned(),
+            );
+            block_diff.append(outcome.diff.clone());
+        }
+        outcomes.push(outcome);
+    }
+
+    Ok(ParallelExecutionOutcome {
+        outcomes,
+        re_executed,
+    })
+}
+
+/// The hashchain state computed sequentially over the transactions of the block.
+struct BlockHashchain {
+    block_height: u64,
+    /// The state after the previous transactions, `None` if the hashchain is not started.
+    current: Option<Hashchain>,
+}
+
+impl BlockHashchain {
+    /// Reads the hashchain state before the block, moved to the block.
+    fn new(
+        storage: &Storage,
+        block_message: &BlockMessage,
+        transactions: &[TransactionMessage],
+    ) -> Result<Self, crate::Error> {
+        let block_height = block_message.height;
+        let position = transactions.first().map_or(0, |tx| tx.position);
+        let key = bytes_to_key(KeyPrefix::Hashchain, HASHCHAIN_STATE);
+        let bytes = storage
+            .with_engine_access(block_height, position, &[], |io| {
+                io.read_storage(&key).map(|value| value.to_vec())
+            })
+            .result;
+        let mut current = bytes
+            .as_deref()
+            .map(Hashchain::try_deserialize)
+            .transpose()?;
+        if let Some(hashchain) = current.as_mut() {
+            if hashchain.get_current_block_height() < block_height {
+                hashchain
+                    .move_to_block(block_height)
+                    .map_err(|_| error::Error::HashchainBlockHeightIncorrect(block_height))?;
+            }
+        }
+        Ok(Self {
+            block_height,
+            current,
+        })
+    }
+
+    /// Replaces the hashchain state computed by the execution of the transaction, which may
+    /// not have seen the previous transactions of the block, with the sequential one.
+    fn update(
+        &mut self,
+        outcome: &mut TransactionIncludedOutcome,
+        output: &[u8],
+        key: &[u8],
+    ) -> Result<(), crate::Error> {
+        let Some(bytes) = outcome.diff.get(key).and_then(DiffValue::value) else {
+            return Ok(());
+        };
+        let executed = Hashchain::try_deserialize(bytes)?;
+        // These transactions replace the hashchain state instead of updating it.
+        let is_reset = matches!(
+            outcome.info.transaction,
+            TransactionKind::NewEngine(_) | TransactionKind::StartHashchain(_)
+        );
+        match self.current.as_mut() {
+            Some(hashchain) if !is_reset => {
+                let method_name: &'static str =
+                    TransactionKindTag::from(&outcome.info.transaction).into();
+                // The logs bloom of the executed state contains the one of the transaction,
+                // and the other logs it contains are in the sequential state already.
+                hashchain
+                    .add_block_tx(
+                        self.block_height,
+                        method_name,
+                        &outcome.info.raw_input,
+                        output,
+                        executed.get_logs_bloom(),
+                    )
+                    .map_err(|_| error::Error::HashchainBlockHeightIncorrect(self.block_height))?;
+                outcome
+                    .diff
+                    .modify(key.to_vec(), hashchain.try_serialize()?);
+            }
+            _ => self.current = Some(executed),
+        }
+        Ok(())
+    }
+}
+
+struct Execution {
+    outcome: TransactionIncludedOutcome,
+    /// Output of the Engine, recorded in the hashchain.
+    output: Vec<u8>,
+    reads: HashSet<Vec<u8>>,
+}
+
+fn execute<M: ModExpAlgorithm + 'static>(
+    storage: &Storage,
+    block_message: &BlockMessage,
+    engine_account_id: &AccountId,
+    transaction_message: &TransactionMessage,
+    base_diff: &Diff,
+    hashchain_key: &[u8],
+) -> Execution {
+    let reads = RefCell::new(HashSet::new());
+    let result = storage.with_engine_access_over_diff(
+        block_message.height,
+        transaction_message.position,
+        &transaction_message.raw_input,
+        base_diff,
+        |io| {
+            execute_transaction::<_, M, _>(
+                transaction_message,
+                block_message.height,
+                &block_message.metadata,
+                engine_account_id.clone(),
+                ReadRecordingIO {
+                    inner: io,
+                    reads: &reads,
+                },
+                |_| Diff::default(),
+            )
+        },
+    );
+    let (hash, _, maybe_result) = result.result;
+    let divergence = detect_divergence(
+        block_message.height,
+        transaction_message,
+        hash,
+        &maybe_result,
+        &result.engine_output,
+    );
+
+    // The hashchain state is recomputed sequentially, so it can't conflict.
+    let mut reads = reads.into_inner();
+    reads.remove(hashchain_key);
+
+    Execution {
+        outcome: TransactionIncludedOutcome {
+            hash,
+            info: transaction_message.clone(),
+            diff: result.diff,
+            maybe_result,
+            divergence,
+        },
+        output: result.engine_output,
+        reads,
+    }
+}
+
+/// Records the keys accessed through the wrapped IO. The writes are recorded as well because
+/// they return the previous value of the key.
+#[derive(Clone, Copy)]
+struct ReadRecordingIO<'a, I> {
+    inner: I,
+    reads: &'a RefCell<HashSet<Vec<u8>>>,
+}
+
+impl<'a, I> ReadRecordingIO<'a, I> {
+    fn record(&self, key: &[u8]) {
+        self.reads.borrow_mut().insert(key.to_vec());
+    }
+}
+
+impl<'a, I: IO> IO for ReadRecordingIO<'a, I> {
+    type StorageValue = I::StorageValue;
+
+    fn read_input(&self) -> Self::StorageValue {
+        self.inner.read_input()
+    }
+
+    fn return_output(&mut self, value: &[u8]) {
+        self.inner.return_output(value);
+    }
+
+    fn read_storage(&self, key: &[u8]) -> Option<Self::StorageValue> {
+        self.record(key);
+        self.inner.read_storage(key)
+    }
+
+    fn storage_has_key(&self, key: &[u8]) -> bool {
+        self.record(key);
+        self.inner.storage_has_key(key)
+    }
+
+    fn write_storage(&mut self, key: &[u8], value: &[u8]) -> Option<Self::StorageValue> {
+        self.record(key);
+        self.inner.write_storage(key, value)
+    }
+
+    fn write_storage_direct(
+        &mut self,
+        key: &[u8],
+        value: Self::StorageValue,
+    ) -> Option<Self::StorageValue> {
+        self.record(key);
+        self.inner.write_storage_direct(key, value)
+    }
+
+    fn remove_storage(&mut self, key: &[u8]) -> Option<Self::StorageValue> {
+        self.record(key);
+        self.inner.remove_storage(key)
+    }
+}
@@ -3,6 +3,7 @@ use aurora_engine::parameters;
 use aurora_engine::xcc::{AddressVersionUpdateArgs, FundXccArgs};
 use aurora_engine_transactions::{EthTransactionKind, NormalizedEthTransaction};
 use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::public_key::PublicKey;
 use aurora_engine_types::types::Address;
 use aurora_engine_types::{
     borsh::{self, BorshDeserialize, BorshSerialize},
@@ -10,7 +11,7 @@ use aurora_engine_types::{
     H256, U256,
 };
 use std::borrow::Cow;
-use strum::EnumString;
+use strum::{EnumString, IntoStaticStr};
 
 /// Type describing the format of messages sent to the storage layer for keeping
 /// it in sync with the blockchain.
@@ -40,6 +41,8 @@ pub struct TransactionMessage {
     pub succeeded: bool,
     /// NEAR account that signed the transaction
     pub signer: AccountId,
+    /// Public key of the access key that signed the transaction, if it is known
+    pub signer_public_key: Option<PublicKey>,
     /// NEAR account that called the Aurora engine contract
     pub caller: AccountId,
     /// Amount of NEAR token attached to the transaction
@@ -51,6 +54,20 @@ pub struct TransactionMessage {
     pub promise_data: Vec<Option<Vec<u8>>>,
     /// Raw bytes passed as input when executed in the Near Runtime.
     pub raw_input: Vec<u8>,
+    /// Outcome of the execution on NEAR, if known. It is compared with the outcome of the
+    /// standalone execution to detect divergences.
+    pub on_chain_outcome: Option<OnChainOutcome>,
+}
+
+/// Outcome of the execution of a transaction on NEAR.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct OnChainOutcome {
+    /// Bytes returned by the receipt (empty if it failed). The EVM transactions (`submit`,
+    /// `call`, `deploy_code`, ...) return a `SubmitResult`. `None` if they are not known, e.g.
+    /// for the actions of a batched receipt other than the last one.
+    pub output: Option<Vec<u8>>,
+    /// Logs emitted by the receipt on NEAR.
+    pub logs: Vec<String>,
 }
 
 impl TransactionMessage {
@@ -141,12 +158,66 @@ pub enum TransactionKind {
     /// Set the relayer key manager
     SetKeyManager(parameters::RelayerKeyManagerArgs),
     /// Add a new relayer public function call access key
-    AddRelayerKey(parameters::RelayerKeyArgs),
+    AddRelayerKey(parameters::RelayerKeyArgsV2),
     /// Remove the relayer public function call access key
     RemoveRelayerKey(parameters::RelayerKeyArgs),
     StartHashchain(parameters::StartHashchainArgs),
     /// Set metadata of ERC-20 contract.
     SetErc20Metadata(parameters::SetErc20MetadataArgs),
+    /// Update the bytecode of NEP-141 contracts deployed for ERC-20 tokens native to Aurora
+    SetNep141Code(Vec<u8>),
+    /// Deploy a NEP-141 token for an ERC-20 token native to Aurora
+    DeployNep141(parameters::DeployNep141TokenArgs),
+    /// Callback registering the NEP-141 token deployed by `deploy_nep141_token`
+    RegisterNep141(parameters::RegisterNep141TokenArgs),
+    /// Synchronize metadata of ERC-20 contract with the NEP-141 `ft_metadata`.
+    SyncErc20Metadata(parameters::SyncErc20MetadataArgs),
+    /// Callback of `sync_erc20_metadata` setting metadata of ERC-20 contract.
+    SyncErc20MetadataCallback(parameters::SyncErc20MetadataArgs),
+    /// Update the bytecode of ERC-20 contracts deployed for bridged NEP-141 tokens
+    SetErc20Code(Vec<u8>),
+    /// Replace the code of a bridged ERC-20 token with the latest bytecode
+    MigrateErc20(Address),
+    /// Grant a role to an account
+    GrantRole(parameters::RoleArgs),
+    /// Revoke a role from an account
+    RevokeRole(parameters::RoleArgs),
+    /// Run the pending migrations of the engine storage
+    StateMigration(Vec<u8>),
+    /// Remove the expired relayer function call keys
+    RemoveExpiredRelayerKeys,
+    /// Pause the engine methods with the given names
+    PauseMethods(parameters::PauseMethodsArgs),
+    /// Resume the engine methods with the given names
+    ResumeMethods(parameters::PauseMethodsArgs),
+    /// Add an EVM address to the denylist
+    DenyAddress(parameters::DenylistArgs),
+    /// Remove an EVM address from the denylist
+    AllowAddress(parameters::DenylistArgs),
+    /// Enable or disable the deploy allowlist mode
+    SetDeployAllowlistEnabled(parameters::DeployAllowlistModeArgs),
+    /// Add an EVM address to the approved deployers
+    AddDeployer(parameters::DeployerArgs),
+    /// Remove an EVM address from the approved deployers
+    RemoveDeployer(parameters::DeployerArgs),
+    /// Set the economic settings of the silo
+    SetSiloParams(parameters::SiloParams),
+    /// Add EVM addresses to the senders which are not charged for gas
+    AddFeeWaivedSenders(parameters::AddressesArgs),
+    /// Remove EVM addresses from the senders which are not charged for gas
+    RemoveFeeWaivedSenders(parameters::AddressesArgs),
+    /// Enable or disable a whitelist of the silo
+    SetWhitelistStatus(parameters::WhitelistStatusArgs),
+    /// Add EVM addresses to the whitelist of the transaction signers
+    AddWhitelistedAddresses(parameters::AddressesArgs),
+    /// Remove EVM addresses from the whitelist of the transaction signers
+    RemoveWhitelistedAddresses(parameters::AddressesArgs),
+    /// Add NEAR accounts to the whitelist of the accounts executing EVM transactions
+    AddWhitelistedAccounts(parameters::AccountsArgs),
+    /// Remove NEAR accounts from the whitelist of the accounts executing EVM transactions
+    RemoveWhitelistedAccounts(parameters::AccountsArgs),
+    /// Add the relayer keys created before the list of the keys was introduced to the list
+    ListLegacyRelayerKeys(parameters::RelayerKeysArgs),
     /// Sentinel kind for cases where a NEAR receipt caused a
     /// change in Aurora state, but we failed to parse the Action.
     Unknown,
@@ -382,6 +453,41 @@ impl TransactionKind {
             Self::RemoveRelayerKey(_) => Self::no_evm_execution("remove_relayer_key"),
             Self::StartHashchain(_) => Self::no_evm_execution("start_hashchain"),
             Self::SetErc20Metadata(_) => Self::no_evm_execution("set_erc20_metadata"),
+            Self::SetNep141Code(_) => Self::no_evm_execution("set_nep141_code"),
+            Self::DeployNep141(_) => Self::no_evm_execution("deploy_nep141_token"),
+            Self::RegisterNep141(_) => Self::no_evm_execution("register_nep141_token"),
+            Self::SyncErc20Metadata(_) => Self::no_evm_execution("sync_erc20_metadata"),
+            Self::SyncErc20MetadataCallback(_) => {
+                Self::no_evm_execution("sync_erc20_metadata_callback")
+            }
+            Self::SetErc20Code(_) => Self::no_evm_execution("set_erc20_code"),
+            Self::MigrateErc20(_) => Self::no_evm_execution("migrate_erc20_token"),
+            Self::GrantRole(_) => Self::no_evm_execution("grant_role"),
+            Self::RevokeRole(_) => Self::no_evm_execution("revoke_role"),
+            Self::StateMigration(_) => Self::no_evm_execution("state_migration"),
+            Self::RemoveExpiredRelayerKeys => Self::no_evm_execution("remove_expired_relayer_keys"),
+            Self::PauseMethods(_) => Self::no_evm_execution("pause_methods"),
+            Self::ResumeMethods(_) => Self::no_evm_execution("resume_methods"),
+            Self::DenyAddress(_) => Self::no_evm_execution("deny_address"),
+            Self::AllowAddress(_) => Self::no_evm_execution("allow_address"),
+            Self::SetDeployAllowlistEnabled(_) => {
+                Self::no_evm_execution("set_deploy_allowlist_enabled")
+            }
+            Self::AddDeployer(_) => Self::no_evm_execution("add_deployer"),
+            Self::RemoveDeployer(_) => Self::no_evm_execution("remove_deployer"),
+            Self::SetSiloParams(_) => Self::no_evm_execution("set_silo_params"),
+            Self::AddFeeWaivedSenders(_) => Self::no_evm_execution("add_fee_waived_senders"),
+            Self::RemoveFeeWaivedSenders(_) => Self::no_evm_execution("remove_fee_waived_senders"),
+            Self::SetWhitelistStatus(_) => Self::no_evm_execution("set_whitelist_status"),
+            Self::AddWhitelistedAddresses(_) => Self::no_evm_execution("add_whitelisted_addresses"),
+            Self::RemoveWhitelistedAddresses(_) => {
+                Self::no_evm_execution("remove_whitelisted_addresses")
+            }
+            Self::AddWhitelistedAccounts(_) => Self::no_evm_execution("add_whitelisted_accounts"),
+            Self::RemoveWhitelistedAccounts(_) => {
+                Self::no_evm_execution("remove_whitelisted_accounts")
+            }
+            Self::ListLegacyRelayerKeys(_) => Self::no_evm_execution("list_legacy_relayer_keys"),
         }
     }
 
@@ -420,7 +526,9 @@ impl TransactionKind {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString)]
+/// The tag is serialized to (and parsed from) the name of the engine method called by the
+/// transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumString, IntoStaticStr)]
 pub enum TransactionKindTag {
     #[strum(serialize = "submit")]
     Submit,
@@ -494,6 +602,60 @@ pub enum TransactionKindTag {
     StartHashchain,
     #[strum(serialize = "set_erc20_metadata")]
     SetErc20Metadata,
+    #[strum(serialize = "set_nep141_code")]
+    SetNep141Code,
+    #[strum(serialize = "deploy_nep141_token")]
+    DeployNep141,
+    #[strum(serialize = "register_nep141_token")]
+    RegisterNep141,
+    #[strum(serialize = "sync_erc20_metadata")]
+    SyncErc20Metadata,
+    #[strum(serialize = "sync_erc20_metadata_callback")]
+    SyncErc20MetadataCallback,
+    #[strum(serialize = "set_erc20_code")]
+    SetErc20Code,
+    #[strum(serialize = "migrate_erc20_token")]
+    MigrateErc20,
+    #[strum(serialize = "grant_role")]
+    GrantRole,
+    #[strum(serialize = "revoke_role")]
+    RevokeRole,
+    #[strum(serialize = "state_migration")]
+    StateMigration,
+    #[strum(serialize = "remove_expired_relayer_keys")]
+    RemoveExpiredRelayerKeys,
+    #[strum(serialize = "pause_methods")]
+    PauseMethods,
+    #[strum(serialize = "resume_methods")]
+    ResumeMethods,
+    #[strum(serialize = "deny_address")]
+    DenyAddress,
+    #[strum(serialize = "allow_address")]
+    AllowAddress,
+    #[strum(serialize = "set_deploy_allowlist_enabled")]
+    SetDeployAllowlistEnabled,
+    #[strum(serialize = "add_deployer")]
+    AddDeployer,
+    #[strum(serialize = "remove_deployer")]
+    RemoveDeployer,
+    #[strum(serialize = "set_silo_params")]
+    SetSiloParams,
+    #[strum(serialize = "add_fee_waived_senders")]
+    AddFeeWaivedSenders,
+    #[strum(serialize = "remove_fee_waived_senders")]
+    RemoveFeeWaivedSenders,
+    #[strum(serialize = "set_whitelist_status")]
+    SetWhitelistStatus,
+    #[strum(serialize = "add_whitelisted_addresses")]
+    AddWhitelistedAddresses,
+    #[strum(serialize = "remove_whitelisted_addresses")]
+    RemoveWhitelistedAddresses,
+    #[strum(serialize = "add_whitelisted_accounts")]
+    AddWhitelistedAccounts,
+    #[strum(serialize = "remove_whitelisted_accounts")]
+    RemoveWhitelistedAccounts,
+    #[strum(serialize = "list_legacy_relayer_keys")]
+    ListLegacyRelayerKeys,
     Unknown,
 }
 
@@ -538,11 +700,37 @@ impl TransactionKind {
             Self::FundXccSubAccound(args) => args.try_to_vec().unwrap_or_default(),
             Self::PauseContract | Self::ResumeContract | Self::Unknown => Vec::new(),
             Self::SetKeyManager(args) => args.try_to_vec().unwrap_or_default(),
-            Self::AddRelayerKey(args) | Self::RemoveRelayerKey(args) => {
-                args.try_to_vec().unwrap_or_default()
-            }
+            Self::AddRelayerKey(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::RemoveRelayerKey(args) => serde_json::to_vec(args).unwrap_or_default(),
             Self::StartHashchain(args) => args.try_to_vec().unwrap_or_default(),
             Self::SetErc20Metadata(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::SetNep141Code(bytes) => bytes.clone(),
+            Self::DeployNep141(args) => args.try_to_vec().unwrap_or_default(),
+            Self::RegisterNep141(args) => args.try_to_vec().unwrap_or_default(),
+            Self::SyncErc20Metadata(args) => args.try_to_vec().unwrap_or_default(),
+            Self::SyncErc20MetadataCallback(args) => args.try_to_vec().unwrap_or_default(),
+            Self::SetErc20Code(bytes) => bytes.clone(),
+            Self::MigrateErc20(address) => address.as_bytes().to_vec(),
+            Self::GrantRole(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::RevokeRole(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::StateMigration(bytes) => bytes.clone(),
+            Self::RemoveExpiredRelayerKeys => Vec::new(),
+            Self::PauseMethods(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::ResumeMethods(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::DenyAddress(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::AllowAddress(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::SetDeployAllowlistEnabled(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::AddDeployer(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::RemoveDeployer(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::SetSiloParams(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::AddFeeWaivedSenders(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::RemoveFeeWaivedSenders(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::SetWhitelistStatus(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::AddWhitelistedAddresses(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::RemoveWhitelistedAddresses(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::AddWhitelistedAccounts(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::RemoveWhitelistedAccounts(args) => serde_json::to_vec(args).unwrap_or_default(),
+            Self::ListLegacyRelayerKeys(args) => serde_json::to_vec(args).unwrap_or_default(),
         }
     }
 }
@@ -587,6 +775,33 @@ impl From<&TransactionKind> for TransactionKindTag {
             TransactionKind::RemoveRelayerKey(_) => Self::RemoveRelayerKey,
             TransactionKind::StartHashchain(_) => Self::StartHashchain,
             TransactionKind::SetErc20Metadata(_) => Self::SetErc20Metadata,
+            TransactionKind::SetNep141Code(_) => Self::SetNep141Code,
+            TransactionKind::DeployNep141(_) => Self::DeployNep141,
+            TransactionKind::RegisterNep141(_) => Self::RegisterNep141,
+            TransactionKind::SyncErc20Metadata(_) => Self::SyncErc20Metadata,
+            TransactionKind::SyncErc20MetadataCallback(_) => Self::SyncErc20MetadataCallback,
+            TransactionKind::SetErc20Code(_) => Self::SetErc20Code,
+            TransactionKind::MigrateErc20(_) => Self::MigrateErc20,
+            TransactionKind::GrantRole(_) => Self::GrantRole,
+            TransactionKind::RevokeRole(_) => Self::RevokeRole,
+            TransactionKind::StateMigration(_) => Self::StateMigration,
+            TransactionKind::RemoveExpiredRelayerKeys => Self::RemoveExpiredRelayerKeys,
+            TransactionKind::PauseMethods(_) => Self::PauseMethods,
+            TransactionKind::ResumeMethods(_) => Self::ResumeMethods,
+            TransactionKind::DenyAddress(_) => Self::DenyAddress,
+            TransactionKind::AllowAddress(_) => Self::AllowAddress,
+            TransactionKind::SetDeployAllowlistEnabled(_) => Self::SetDeployAllowlistEnabled,
+            TransactionKind::AddDeployer(_) => Self::AddDeployer,
+            TransactionKind::RemoveDeployer(_) => Self::RemoveDeployer,
+            TransactionKind::SetSiloParams(_) => Self::SetSiloParams,
+            TransactionKind::AddFeeWaivedSenders(_) => Self::AddFeeWaivedSenders,
+            TransactionKind::RemoveFeeWaivedSenders(_) => Self::RemoveFeeWaivedSenders,
+            TransactionKind::SetWhitelistStatus(_) => Self::SetWhitelistStatus,
+            TransactionKind::AddWhitelistedAddresses(_) => Self::AddWhitelistedAddresses,
+            TransactionKind::RemoveWhitelistedAddresses(_) => Self::RemoveWhitelistedAddresses,
+            TransactionKind::AddWhitelistedAccounts(_) => Self::AddWhitelistedAccounts,
+            TransactionKind::RemoveWhitelistedAccounts(_) => Self::RemoveWhitelistedAccounts,
+            TransactionKind::ListLegacyRelayerKeys(_) => Self::ListLegacyRelayerKeys,
             TransactionKind::Unknown => Self::Unknown,
         }
     }
@@ -603,7 +818,7 @@ impl From<&TransactionKind> for TransactionKindTag {
 ///
 /// V1 is an older version of `TransactionMessage`, before the addition of `promise_data`.
 ///
-/// V2 is a structurally identical message to `TransactionMessage` above.
+/// V4 is a structurally identical message to `TransactionMessage` above.
 ///
 /// For details of what the individual fields mean, see the comments on the main
 /// `TransactionMessage` type.
@@ -612,6 +827,7 @@ enum BorshableTransactionMessage<'a> {
     V1(BorshableTransactionMessageV1<'a>),
     V2(BorshableTransactionMessageV2<'a>),
     V3(BorshableTransactionMessageV3<'a>),
+    V4(BorshableTransactionMessageV4<'a>),
 }
 
 #[derive(BorshDeserialize, BorshSerialize)]
@@ -653,19 +869,37 @@ struct BorshableTransactionMessageV3<'a> {
     pub raw_input: Cow<'a, Vec<u8>>,
 }
 
+#[derive(BorshDeserialize, BorshSerialize)]
+struct BorshableTransactionMessageV4<'a> {
+    pub block_hash: [u8; 32],
+    pub near_receipt_id: [u8; 32],
+    pub position: u16,
+    pub succeeded: bool,
+    pub signer: Cow<'a, AccountId>,
+    pub signer_public_key: Option<PublicKey>,
+    pub caller: Cow<'a, AccountId>,
+    pub attached_near: u128,
+    pub transaction: BorshableTransactionKind<'a>,
+    pub promise_data: Cow<'a, Vec<Option<Vec<u8>>>>,
+    pub raw_input: Cow<'a, Vec<u8>>,
+    pub on_chain_outcome: Cow<'a, Option<OnChainOutcome>>,
+}
+
 impl<'a> From<&'a TransactionMessage> for BorshableTransactionMessage<'a> {
     fn from(t: &'a TransactionMessage) -> Self {
-        Self::V3(BorshableTransactionMessageV3 {
+        Self::V4(BorshableTransactionMessageV4 {
             block_hash: t.block_hash.0,
             near_receipt_id: t.near_receipt_id.0,
             position: t.position,
             succeeded: t.succeeded,
             signer: Cow::Borrowed(&t.signer),
+            signer_public_key: t.signer_public_key,
             caller: Cow::Borrowed(&t.caller),
             attached_near: t.attached_near,
             transaction: (&t.transaction).into(),
             promise_data: Cow::Borrowed(&t.promise_data),
             raw_input: Cow::Borrowed(&t.raw_input),
+            on_chain_outcome: Cow::Borrowed(&t.on_chain_outcome),
         })
     }
 }
@@ -684,11 +918,13 @@ impl<'a> TryFrom<BorshableTransactionMessage<'a>> for TransactionMessage {
                     position: t.position,
                     succeeded: t.succeeded,
                     signer: t.signer.into_owned(),
+                    signer_public_key: None,
                     caller: t.caller.into_owned(),
                     attached_near: t.attached_near,
                     transaction,
                     promise_data: Vec::new(),
                     raw_input,
+                    on_chain_outcome: None,
                 })
             }
             BorshableTransactionMessage::V2(t) => {
@@ -700,11 +936,13 @@ impl<'a> TryFrom<BorshableTransactionMessage<'a>> for TransactionMessage {
                     position: t.position,
                     succeeded: t.succeeded,
                     signer: t.signer.into_owned(),
+                    signer_public_key: None,
                     caller: t.caller.into_owned(),
                     attached_near: t.attached_near,
                     transaction,
                     promise_data: t.promise_data.into_owned(),
                     raw_input,
+                    on_chain_outcome: None,
                 })
             }
             BorshableTransactionMessage::V3(t) => Ok(Self {
@@ -713,11 +951,27 @@ impl<'a> TryFrom<BorshableTransactionMessage<'a>> for TransactionMessage {
                 position: t.position,
                 succeeded: t.succeeded,
                 signer: t.signer.into_owned(),
+                signer_public_key: None,
                 caller: t.caller.into_owned(),
                 attached_near: t.attached_near,
                 transaction: t.transaction.try_into()?,
                 promise_data: t.promise_data.into_owned(),
                 raw_input: t.raw_input.into_owned(),
+                on_chain_outcome: None,
+            }),
+            BorshableTransactionMessage::V4(t) => Ok(Self {
+                block_hash: H256(t.block_hash),
+                near_receipt_id: H256(t.near_receipt_id),
+                position: t.position,
+                succeeded: t.succeeded,
+                signer: t.signer.into_owned(),
+                signer_public_key: t.signer_public_key,
+                caller: t.caller.into_owned(),
+                attached_near: t.attached_near,
+                transaction: t.transaction.try_into()?,
+                promise_data: t.promise_data.into_owned(),
+                raw_input: t.raw_input.into_owned(),
+                on_chain_outcome: t.on_chain_outcome.into_owned(),
             }),
         }
     }
@@ -770,6 +1024,34 @@ enum BorshableTransactionKind<'a> {
     RemoveRelayerKey(Cow<'a, parameters::RelayerKeyArgs>),
     StartHashchain(Cow<'a, parameters::StartHashchainArgs>),
     SetErc20Metadata(Cow<'a, parameters::SetErc20MetadataArgs>),
+    SetNep141Code(Cow<'a, Vec<u8>>),
+    DeployNep141(Cow<'a, parameters::DeployNep141TokenArgs>),
+    RegisterNep141(Cow<'a, parameters::RegisterNep141TokenArgs>),
+    SyncErc20Metadata(Cow<'a, parameters::SyncErc20MetadataArgs>),
+    SyncErc20MetadataCallback(Cow<'a, parameters::SyncErc20MetadataArgs>),
+    SetErc20Code(Cow<'a, Vec<u8>>),
+    MigrateErc20(Address),
+    GrantRole(Cow<'a, parameters::RoleArgs>),
+    RevokeRole(Cow<'a, parameters::RoleArgs>),
+    StateMigration(Cow<'a, Vec<u8>>),
+    RemoveExpiredRelayerKeys,
+    PauseMethods(Cow<'a, parameters::PauseMethodsArgs>),
+    ResumeMethods(Cow<'a, parameters::PauseMethodsArgs>),
+    DenyAddress(Cow<'a, parameters::DenylistArgs>),
+    AllowAddress(Cow<'a, parameters::DenylistArgs>),
+    SetDeployAllowlistEnabled(Cow<'a, parameters::DeployAllowlistModeArgs>),
+    AddDeployer(Cow<'a, parameters::DeployerArgs>),
+    RemoveDeployer(Cow<'a, parameters::DeployerArgs>),
+    SetSiloParams(Cow<'a, parameters::SiloParams>),
+    AddFeeWaivedSenders(Cow<'a, parameters::AddressesArgs>),
+    RemoveFeeWaivedSenders(Cow<'a, parameters::AddressesArgs>),
+    SetWhitelistStatus(Cow<'a, parameters::WhitelistStatusArgs>),
+    AddWhitelistedAddresses(Cow<'a, parameters::AddressesArgs>),
+    RemoveWhitelistedAddresses(Cow<'a, parameters::AddressesArgs>),
+    AddWhitelistedAccounts(Cow<'a, parameters::AccountsArgs>),
+    RemoveWhitelistedAccounts(Cow<'a, parameters::AccountsArgs>),
+    AddRelayerKeyV2(Cow<'a, parameters::RelayerKeyArgsV2>),
+    ListLegacyRelayerKeys(Cow<'a, parameters::RelayerKeysArgs>),
 }
 
 impl<'a> From<&'a TransactionKind> for BorshableTransactionKind<'a> {
@@ -819,10 +1101,53 @@ impl<'a> From<&'a TransactionKind> for BorshableTransactionKind<'a> {
             TransactionKind::PauseContract => Self::PauseContract,
             TransactionKind::ResumeContract => Self::ResumeContract,
             TransactionKind::SetKeyManager(x) => Self::SetKeyManager(Cow::Borrowed(x)),
-            TransactionKind::AddRelayerKey(x) => Self::AddRelayerKey(Cow::Borrowed(x)),
+            TransactionKind::AddRelayerKey(x) => Self::AddRelayerKeyV2(Cow::Borrowed(x)),
             TransactionKind::RemoveRelayerKey(x) => Self::RemoveRelayerKey(Cow::Borrowed(x)),
             TransactionKind::StartHashchain(x) => Self::StartHashchain(Cow::Borrowed(x)),
             TransactionKind::SetErc20Metadata(x) => Self::SetErc20Metadata(Cow::Borrowed(x)),
+            TransactionKind::SetNep141Code(x) => Self::SetNep141Code(Cow::Borrowed(x)),
+            TransactionKind::DeployNep141(x) => Self::DeployNep141(Cow::Borrowed(x)),
+            TransactionKind::RegisterNep141(x) => Self::RegisterNep141(Cow::Borrowed(x)),
+            TransactionKind::SyncErc20Metadata(x) => Self::SyncErc20Metadata(Cow::Borrowed(x)),
+            TransactionKind::SyncErc20MetadataCallback(x) => {
+                Self::SyncErc20MetadataCallback(Cow::Borrowed(x))
+            }
+            TransactionKind::SetErc20Code(x) => Self::SetErc20Code(Cow::Borrowed(x)),
+            TransactionKind::MigrateErc20(address) => Self::MigrateErc20(*address),
+            TransactionKind::GrantRole(x) => Self::GrantRole(Cow::Borrowed(x)),
+            TransactionKind::RevokeRole(x) => Self::RevokeRole(Cow::Borrowed(x)),
+            TransactionKind::StateMigration(x) => Self::StateMigration(Cow::Borrowed(x)),
+            TransactionKind::RemoveExpiredRelayerKeys => Self::RemoveExpiredRelayerKeys,
+            TransactionKind::PauseMethods(x) => Self::PauseMethods(Cow::Borrowed(x)),
+            TransactionKind::ResumeMethods(x) => Self::ResumeMethods(Cow::Borrowed(x)),
+            TransactionKind::DenyAddress(x) => Self::DenyAddress(Cow::Borrowed(x)),
+            TransactionKind::AllowAddress(x) => Self::AllowAddress(Cow::Borrowed(x)),
+            TransactionKind::SetDeployAllowlistEnabled(x) => {
+                Self::SetDeployAllowlistEnabled(Cow::Borrowed(x))
+            }
+            TransactionKind::AddDeployer(x) => Self::AddDeployer(Cow::Borrowed(x)),
+            TransactionKind::RemoveDeployer(x) => Self::RemoveDeployer(Cow::Borrowed(x)),
+            TransactionKind::SetSiloParams(x) => Self::SetSiloParams(Cow::Borrowed(x)),
+            TransactionKind::AddFeeWaivedSenders(x) => Self::AddFeeWaivedSenders(Cow::Borrowed(x)),
+            TransactionKind::RemoveFeeWaivedSenders(x) => {
+                Self::RemoveFeeWaivedSenders(Cow::Borrowed(x))
+            }
+            TransactionKind::SetWhitelistStatus(x) => Self::SetWhitelistStatus(Cow::Borrowed(x)),
+            TransactionKind::AddWhitelistedAddresses(x) => {
+                Self::AddWhitelistedAddresses(Cow::Borrowed(x))
+            }
+            TransactionKind::RemoveWhitelistedAddresses(x) => {
+                Self::RemoveWhitelistedAddresses(Cow::Borrowed(x))
+            }
+            TransactionKind::AddWhitelistedAccounts(x) => {
+                Self::AddWhitelistedAccounts(Cow::Borrowed(x))
+            }
+            TransactionKind::RemoveWhitelistedAccounts(x) => {
+                Self::RemoveWhitelistedAccounts(Cow::Borrowed(x))
+            }
+            TransactionKind::ListLegacyRelayerKeys(x) => {
+                Self::ListLegacyRelayerKeys(Cow::Borrowed(x))
+            }
         }
     }
 }
@@ -891,7 +1216,9 @@ impl<'a> TryFrom<BorshableTransactionKind<'a>> for TransactionKind {
             BorshableTransactionKind::PauseContract => Ok(Self::PauseContract),
             BorshableTransactionKind::ResumeContract => Ok(Self::ResumeContract),
             BorshableTransactionKind::SetKeyManager(x) => Ok(Self::SetKeyManager(x.into_owned())),
-            BorshableTransactionKind::AddRelayerKey(x) => Ok(Self::AddRelayerKey(x.into_owned())),
+            BorshableTransactionKind::AddRelayerKey(x) => {
+                Ok(Self::AddRelayerKey(x.into_owned().into()))
+            }
             BorshableTransactionKind::RemoveRelayerKey(x) => {
                 Ok(Self::RemoveRelayerKey(x.into_owned()))
             }
@@ -899,6 +1226,58 @@ impl<'a> TryFrom<BorshableTransactionKind<'a>> for TransactionKind {
             BorshableTransactionKind::SetErc20Metadata(x) => {
                 Ok(Self::SetErc20Metadata(x.into_owned()))
             }
+            BorshableTransactionKind::SetNep141Code(x) => Ok(Self::SetNep141Code(x.into_owned())),
+            BorshableTransactionKind::DeployNep141(x) => Ok(Self::DeployNep141(x.into_owned())),
+            BorshableTransactionKind::RegisterNep141(x) => Ok(Self::RegisterNep141(x.into_owned())),
+            BorshableTransactionKind::SyncErc20Metadata(x) => {
+                Ok(Self::SyncErc20Metadata(x.into_owned()))
+            }
+            BorshableTransactionKind::SyncErc20MetadataCallback(x) => {
+                Ok(Self::SyncErc20MetadataCallback(x.into_owned()))
+            }
+            BorshableTransactionKind::SetErc20Code(x) => Ok(Self::SetErc20Code(x.into_owned())),
+            BorshableTransactionKind::MigrateErc20(address) => Ok(Self::MigrateErc20(address)),
+            BorshableTransactionKind::GrantRole(x) => Ok(Self::GrantRole(x.into_owned())),
+            BorshableTransactionKind::RevokeRole(x) => Ok(Self::RevokeRole(x.into_owned())),
+            BorshableTransactionKind::StateMigration(x) => Ok(Self::StateMigration(x.into_owned())),
+            BorshableTransactionKind::RemoveExpiredRelayerKeys => {
+                Ok(Self::RemoveExpiredRelayerKeys)
+            }
+            BorshableTransactionKind::PauseMethods(x) => Ok(Self::PauseMethods(x.into_owned())),
+            BorshableTransactionKind::ResumeMethods(x) => Ok(Self::ResumeMethods(x.into_owned())),
+            BorshableTransactionKind::DenyAddress(x) => Ok(Self::DenyAddress(x.into_owned())),
+            BorshableTransactionKind::AllowAddress(x) => Ok(Self::AllowAddress(x.into_owned())),
+            BorshableTransactionKind::SetDeployAllowlistEnabled(x) => {
+                Ok(Self::SetDeployAllowlistEnabled(x.into_owned()))
+            }
+            BorshableTransactionKind::AddDeployer(x) => Ok(Self::AddDeployer(x.into_owned())),
+            BorshableTransactionKind::RemoveDeployer(x) => Ok(Self::RemoveDeployer(x.into_owned())),
+            BorshableTransactionKind::SetSiloParams(x) => Ok(Self::SetSiloParams(x.into_owned())),
+            BorshableTransactionKind::AddFeeWaivedSenders(x) => {
+                Ok(Self::AddFeeWaivedSenders(x.into_owned()))
+            }
+            BorshableTransactionKind::RemoveFeeWaivedSenders(x) => {
+                Ok(Self::RemoveFeeWaivedSenders(x.into_owned()))
+            }
+            BorshableTransactionKind::SetWhitelistStatus(x) => {
+                Ok(Self::SetWhitelistStatus(x.into_owned()))
+            }
+            BorshableTransactionKind::AddWhitelistedAddresses(x) => {
+                Ok(Self::AddWhitelistedAddresses(x.into_owned()))
+            }
+            BorshableTransactionKind::RemoveWhitelistedAddresses(x) => {
+                Ok(Self::RemoveWhitelistedAddresses(x.into_owned()))
+            }
+            BorshableTransactionKind::AddWhitelistedAccounts(x) => {
+                Ok(Self::AddWhitelistedAccounts(x.into_owned()))
+            }
+            BorshableTransactionKind::RemoveWhitelistedAccounts(x) => {
+                Ok(Self::RemoveWhitelistedAccounts(x.into_owned()))
+            }
+            BorshableTransactionKind::AddRelayerKeyV2(x) => Ok(Self::AddRelayerKey(x.into_owned())),
+            BorshableTransactionKind::ListLegacyRelayerKeys(x) => {
+                Ok(Self::ListLegacyRelayerKeys(x.into_owned()))
+            }
         }
     }
 }
@@ -0,0 +1,130 @@
+use crate::prelude::{Address, Wei, U256};
+use crate::utils;
+use aurora_engine::engine::EngineErrorKind;
+use aurora_engine::parameters::DenylistArgs;
+use aurora_engine_precompiles::native::exit_to_near;
+use aurora_engine_transactions::legacy::TransactionLegacy;
+
+const TRANSFER_AMOUNT: Wei = Wei::new_u64(10);
+
+fn denylist_args(address: Address) -> Vec<u8> {
+    serde_json::to_vec(&DenylistArgs { address }).unwrap()
+}
+
+fn denylist(runner: &utils::AuroraRunner) -> Vec<Address> {
+    let result = runner
+        .one_shot()
+        .call("get_denylist", "viewer", Vec::new())
+        .unwrap();
+    serde_json::from_slice(&result.return_data.as_value().unwrap()).unwrap()
+}
+
+fn is_address_denied(runner: &utils::AuroraRunner, address: Address) -> bool {
+    let result = runner
+        .one_shot()
+        .call("is_address_denied", "viewer", denylist_args(address))
+        .unwrap();
+    serde_json::from_slice(&result.return_data.as_value().unwrap()).unwrap()
+}
+
+#[test]
+fn test_denied_signer_is_rejected() {
+    let (mut runner, mut signer, receiver) = crate::tests::sanity::initialize_transfer();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let sender = utils::address_from_secret_key(&signer.secret_key);
+
+    let result = runner.call("deny_address", &aurora_account_id, denylist_args(sender));
+    assert!(result.is_ok(), "{result:?}");
+    assert!(is_address_denied(&runner, sender));
+    assert_eq!(denylist(&runner), vec![sender]);
+
+    utils::assert_submit_rejected(
+        &mut runner,
+        &mut signer,
+        |nonce| utils::transfer(receiver, TRANSFER_AMOUNT, nonce),
+        &EngineErrorKind::DeniedSender,
+    );
+
+    let result = runner.call("allow_address", &aurora_account_id, denylist_args(sender));
+    assert!(result.is_ok(), "{result:?}");
+    assert!(!is_address_denied(&runner, sender));
+    assert!(denylist(&runner).is_empty());
+
+    let result = runner.submit_with_signer(&mut signer, |nonce| {
+        utils::transfer(receiver, TRANSFER_AMOUNT, nonce)
+    });
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_exit_from_denied_sender_aborts_the_transaction() {
+    let (mut runner, mut signer, _) = crate::tests::sanity::initialize_transfer();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let sender = utils::address_from_secret_key(&signer.secret_key);
+    // The denied signers are rejected before the execution, so the exit is made by a contract
+    // forwarding its input and value to the precompile.
+    let forwarder = Address::from_array([0xfe; 20]);
+    runner.create_address_with_code(forwarder, Wei::zero(), U256::zero(), forwarder_code());
+
+    let result = runner.call("deny_address", &aurora_account_id, denylist_args(forwarder));
+    assert!(result.is_ok(), "{result:?}");
+    let balance = runner.get_balance(sender);
+
+    // Flag of the ETH exit followed by the refund address (if enabled) and the recipient.
+    let mut input = vec![0];
+    if cfg!(feature = "error_refund") {
+        input.extend_from_slice(sender.as_bytes());
+    }
+    input.extend_from_slice(b"recipient.near");
+    utils::assert_submit_rejected(
+        &mut runner,
+        &mut signer,
+        |nonce| TransactionLegacy {
+            nonce,
+            gas_price: U256::zero(),
+            gas_limit: u64::MAX.into(),
+            to: Some(forwarder),
+            value: TRANSFER_AMOUNT,
+            data: input,
+        },
+        &EngineErrorKind::DeniedSender,
+    );
+    assert_eq!(runner.get_balance(sender), balance);
+}
+
+/// Runtime code of a contract which calls the `ExitToNear` precompile with its input and value.
+fn forwarder_code() -> Vec<u8> {
+    let mut code = vec![
+        // CALLDATACOPY(0, 0, CALLDATASIZE)
+        0x36, 0x60, 0x00, 0x60, 0x00, 0x37,
+        // CALL(GAS, exit_to_near::ADDRESS, CALLVALUE, 0, CALLDATASIZE, 0, 0)
+        0x60, 0x00, 0x60, 0x00, 0x36, 0x60, 0x00, 0x34, 0x73,
+    ];
+    code.extend_from_slice(exit_to_near::ADDRESS.as_bytes());
+    code.extend_from_slice(&[0x5a, 0xf1, 0x00]);
+    code
+}
+
+#[test]
+fn test_deny_address_requires_denylist_manager() {
+    let mut runner = utils::deploy_runner();
+    let address = Address::from_array([1; 20]);
+
+    let result = runner.call("deny_address", "stranger.near", denylist_args(address));
+    assert!(result.is_err());
+    assert!(!is_address_denied(&runner, address));
+    assert!(denylist(&runner).is_empty());
+}
+
+#[test]
+fn test_allow_not_denied_address() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let address = Address::from_array([1; 20]);
+
+    let result = runner.call("allow_address", &aurora_account_id, denylist_args(address));
+    assert!(matches!(
+        result.unwrap_err().kind,
+        EngineErrorKind::EvmFatal(evm::ExitFatal::Other(e)) if e == "ERR_NOT_DENIED"
+    ));
+}
@@ -0,0 +1,231 @@
+use crate::prelude::{Address, Wei, U256};
+use crate::utils;
+use aurora_engine::engine::EngineErrorKind;
+use aurora_engine::parameters::{DeployAllowlistModeArgs, DeployerArgs};
+use aurora_engine_transactions::legacy::TransactionLegacy;
+
+/// Runtime code of a contract which creates a contract with one byte of code and returns the
+/// address of the created contract, or zero if the creation failed.
+#[rustfmt::skip]
+const FACTORY_CODE: [u8; 29] = [
+    // PUSH10 <init code returning one byte of code>, PUSH1 0, MSTORE
+    0x69, 0x60, 0x00, 0x60, 0x00, 0x53, 0x60, 0x01, 0x60, 0x00, 0xf3, 0x60, 0x00, 0x52,
+    // CREATE(0, 22, 10)
+    0x60, 0x0a, 0x60, 0x16, 0x60, 0x00, 0xf0,
+    // MSTORE(0, address), RETURN(0, 32)
+    0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+];
+
+/// Runtime code of a contract which creates a contract without code and returns the address of
+/// the created contract, or zero if the creation failed.
+#[rustfmt::skip]
+const EMPTY_FACTORY_CODE: [u8; 15] = [
+    // CREATE(0, 0, 0)
+    0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf0,
+    // MSTORE(0, address), RETURN(0, 32)
+    0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+];
+
+/// Runtime code of a contract which creates a contract without code with `CREATE2` and returns
+/// the address of the created contract, or zero if the creation failed.
+#[rustfmt::skip]
+const CREATE2_FACTORY_CODE: [u8; 17] = [
+    // CREATE2(0, 0, 0, 0)
+    0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0x60, 0x00, 0xf5,
+    // MSTORE(0, address), RETURN(0, 32)
+    0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+];
+
+/// Runtime code of a contract which creates a contract without code endowed with 1 wei and
+/// returns the address of the created contract, or zero if the creation failed.
+#[rustfmt::skip]
+const VALUE_FACTORY_CODE: [u8; 15] = [
+    // CREATE(1, 0, 0)
+    0x60, 0x00, 0x60, 0x00, 0x60, 0x01, 0xf0,
+    // MSTORE(0, address), RETURN(0, 32)
+    0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+];
+
+fn set_enabled(runner: &mut utils::AuroraRunner, enabled: bool) {
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let args = serde_json::to_vec(&DeployAllowlistModeArgs { enabled }).unwrap();
+    let result = runner.call("set_deploy_allowlist_enabled", &aurora_account_id, args);
+    assert!(result.is_ok(), "{result:?}");
+}
+
+fn add_deployer(runner: &mut utils::AuroraRunner, address: Address) {
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let args = serde_json::to_vec(&DeployerArgs { address }).unwrap();
+    let result = runner.call("add_deployer", &aurora_account_id, args);
+    assert!(result.is_ok(), "{result:?}");
+}
+
+fn deployers(runner: &utils::AuroraRunner) -> Vec<Address> {
+    let result = runner
+        .one_shot()
+        .call("get_deployers", "viewer", Vec::new())
+        .unwrap();
+    serde_json::from_slice(&result.return_data.as_value().unwrap()).unwrap()
+}
+
+fn call_factory(nonce: U256, factory: Address) -> TransactionLegacy {
+    TransactionLegacy {
+        nonce,
+        gas_price: U256::zero(),
+        gas_limit: u64::MAX.into(),
+        to: Some(factory),
+        value: Wei::zero(),
+        data: Vec::new(),
+    }
+}
+
+#[test]
+fn test_deploy_requires_approved_deployer() {
+    let (mut runner, mut signer, _) = crate::tests::sanity::initialize_transfer();
+    let deployer = utils::address_from_secret_key(&signer.secret_key);
+    set_enabled(&mut runner, true);
+
+    utils::assert_submit_rejected(
+        &mut runner,
+        &mut signer,
+        |nonce| utils::create_deploy_transaction(FACTORY_CODE.to_vec(), nonce),
+        &EngineErrorKind::DeployerNotAllowed,
+    );
+
+    add_deployer(&mut runner, deployer);
+    assert_eq!(deployers(&runner), vec![deployer]);
+
+    let result = runner.submit_with_signer(&mut signer, |nonce| {
+        utils::create_deploy_transaction(FACTORY_CODE.to_vec(), nonce)
+    });
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_nested_create_requires_approved_deployer() {
+    let (mut runner, mut signer, _) = crate::tests::sanity::initialize_transfer();
+    let deployer = utils::address_from_secret_key(&signer.secret_key);
+    let factory = Address::from_array([0xfa; 20]);
+    runner.create_address_with_code(factory, Wei::zero(), U256::zero(), FACTORY_CODE.to_vec());
+    set_enabled(&mut runner, true);
+
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| call_factory(nonce, factory))
+        .unwrap();
+    assert_eq!(utils::unwrap_success(result), vec![0; 32]);
+
+    add_deployer(&mut runner, deployer);
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| call_factory(nonce, factory))
+        .unwrap();
+    assert_ne!(utils::unwrap_success(result), vec![0; 32]);
+}
+
+#[test]
+fn test_nested_create_without_code_requires_approved_deployer() {
+    let (mut runner, mut signer, _) = crate::tests::sanity::initialize_transfer();
+    let deployer = utils::address_from_secret_key(&signer.secret_key);
+    let factory = Address::from_array([0xfa; 20]);
+    runner.create_address_with_code(
+        factory,
+        Wei::zero(),
+        U256::zero(),
+        EMPTY_FACTORY_CODE.to_vec(),
+    );
+    set_enabled(&mut runner, true);
+
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| call_factory(nonce, factory))
+        .unwrap();
+    assert_eq!(utils::unwrap_success(result), vec![0; 32]);
+
+    add_deployer(&mut runner, deployer);
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| call_factory(nonce, factory))
+        .unwrap();
+    assert_ne!(utils::unwrap_success(result), vec![0; 32]);
+}
+
+#[test]
+fn test_nested_create2_requires_approved_deployer() {
+    let (mut runner, mut signer, _) = crate::tests::sanity::initialize_transfer();
+    let deployer = utils::address_from_secret_key(&signer.secret_key);
+    let factory = Address::from_array([0xfa; 20]);
+    runner.create_address_with_code(
+        factory,
+        Wei::zero(),
+        U256::zero(),
+        CREATE2_FACTORY_CODE.to_vec(),
+    );
+    set_enabled(&mut runner, true);
+
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| call_factory(nonce, factory))
+        .unwrap();
+    assert_eq!(utils::unwrap_success(result), vec![0; 32]);
+
+    add_deployer(&mut runner, deployer);
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| call_factory(nonce, factory))
+        .unwrap();
+    assert_ne!(utils::unwrap_success(result), vec![0; 32]);
+}
+
+#[test]
+fn test_nested_create_with_value_requires_approved_deployer() {
+    let (mut runner, mut signer, _) = crate::tests::sanity::initialize_transfer();
+    let deployer = utils::address_from_secret_key(&signer.secret_key);
+    let factory = Address::from_array([0xfa; 20]);
+    runner.create_address_with_code(
+        factory,
+        Wei::new_u64(1),
+        U256::zero(),
+        VALUE_FACTORY_CODE.to_vec(),
+    );
+    set_enabled(&mut runner, true);
+
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| call_factory(nonce, factory))
+        .unwrap();
+    assert_eq!(utils::unwrap_success(result), vec![0; 32]);
+    assert_eq!(runner.get_balance(factory), Wei::new_u64(1));
+
+    add_deployer(&mut runner, deployer);
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| call_factory(nonce, factory))
+        .unwrap();
+    assert_ne!(utils::unwrap_success(result), vec![0; 32]);
+    assert_eq!(runner.get_balance(factory), Wei::zero());
+}
+
+#[test]
+fn test_engine_deployments_are_not_restricted() {
+    let mut runner = utils::deploy_runner();
+    set_enabled(&mut runner, true);
+
+    let token = runner.deploy_erc20_token("tt.testnet");
+    assert!(!runner.get_code(token).is_empty());
+}
+
+#[test]
+fn test_deploy_allowlist_disabled_by_default() {
+    let (mut runner, mut signer, _) = crate::tests::sanity::initialize_transfer();
+
+    let result = runner.submit_with_signer(&mut signer, |nonce| {
+        utils::create_deploy_transaction(FACTORY_CODE.to_vec(), nonce)
+    });
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_add_deployer_requires_deployer_manager() {
+    let mut runner = utils::deploy_runner();
+    let args = serde_json::to_vec(&DeployerArgs {
+        address: Address::from_array([1; 20]),
+    })
+    .unwrap();
+
+    let result = runner.call("add_deployer", "stranger.near", args);
+    assert!(result.is_err());
+    assert!(deployers(&runner).is_empty());
+}
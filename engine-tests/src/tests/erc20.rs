@@ -8,7 +8,10 @@ use crate::utils::{
 use aurora_engine::engine::EngineErrorKind;
 use aurora_engine::parameters::TransactionStatus;
 use aurora_engine_sdk as sdk;
+use aurora_engine_types::borsh::{BorshDeserialize, BorshSerialize};
 use aurora_engine_types::parameters::connector::{Erc20Metadata, SetErc20MetadataArgs};
+use aurora_engine_types::parameters::engine::{DeployErc20TokenArgsV2, SyncErc20MetadataArgs};
+use aurora_engine_types::types::PromiseResult;
 use bstr::ByteSlice;
 use libsecp256k1::SecretKey;
 
@@ -284,6 +287,115 @@ fn test_erc20_get_and_set_metadata() {
     assert_eq!(metadata, new_metadata);
 }
 
+#[test]
+fn test_sync_erc20_metadata() {
+    let mut runner = utils::deploy_runner();
+    let caller = runner.aurora_account_id.clone();
+    let args = DeployErc20TokenArgsV2 {
+        nep141: "token.near".parse().unwrap(),
+        sync_metadata: true,
+    };
+    let outcome = runner
+        .call("deploy_erc20_token", &caller, args.try_to_vec().unwrap())
+        .unwrap();
+    let erc20_address = Vec::<u8>::try_from_slice(&outcome.return_data.as_value().unwrap())
+        .map(|bytes| Address::try_from_slice(&bytes).unwrap())
+        .unwrap();
+
+    // The metadata of the NEP-141 token is read and then written by the callback.
+    let callback_args = SyncErc20MetadataArgs {
+        nep141: args.nep141,
+    }
+    .try_to_vec()
+    .unwrap();
+    let calls: Vec<_> = outcome
+        .action_receipts
+        .iter()
+        .flat_map(|(target_account_id, receipt)| {
+            receipt.actions.iter().map(move |action| match action {
+                near_primitives::transaction::Action::FunctionCall(call) => (
+                    target_account_id.to_string(),
+                    call.method_name.clone(),
+                    call.args.clone(),
+                ),
+                other => panic!("Unexpected action {other:?}"),
+            })
+        })
+        .collect();
+    assert_eq!(
+        calls,
+        vec![
+            (
+                "token.near".to_string(),
+                "ft_metadata".to_string(),
+                Vec::new()
+            ),
+            (
+                caller.clone(),
+                "sync_erc20_metadata_callback".to_string(),
+                callback_args.clone()
+            ),
+        ]
+    );
+
+    let ft_metadata = serde_json::json!({
+        "spec": "ft-1.0.0",
+        "name": "USD Token",
+        "symbol": "USDT",
+        "icon": null,
+        "reference": null,
+        "reference_hash": null,
+        "decimals": 6,
+    });
+    runner.promise_results = vec![PromiseResult::Successful(
+        serde_json::to_vec(&ft_metadata).unwrap(),
+    )];
+    let error = runner
+        .call(
+            "sync_erc20_metadata_callback",
+            "alice.near",
+            callback_args.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_PRIVATE_CALL");
+
+    runner.promise_results = vec![PromiseResult::Failed];
+    let error = runner
+        .call(
+            "sync_erc20_metadata_callback",
+            &caller,
+            callback_args.clone(),
+        )
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_PROMISE_FAILED");
+
+    runner.promise_results = vec![PromiseResult::Successful(
+        serde_json::to_vec(&ft_metadata).unwrap(),
+    )];
+    let result = runner.call("sync_erc20_metadata_callback", &caller, callback_args);
+    assert!(result.is_ok());
+    runner.promise_results = Vec::new();
+
+    let result = runner
+        .one_shot()
+        .call(
+            "get_erc20_metadata",
+            &caller,
+            erc20_address.as_bytes().to_vec(),
+        )
+        .unwrap();
+    let metadata: Erc20Metadata =
+        serde_json::from_slice(&result.return_data.as_value().unwrap()).unwrap();
+    assert_eq!(
+        metadata,
+        Erc20Metadata {
+            name: "USD Token".to_string(),
+            symbol: "USDT".to_string(),
+            decimals: 6,
+        }
+    );
+}
+
 fn get_address_erc20_balance(
     runner: &utils::AuroraRunner,
     signer: &Signer,
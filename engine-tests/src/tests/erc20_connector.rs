@@ -1,12 +1,14 @@
 use crate::prelude::{Address, Balance, Wei, WeiU256, U256};
 use crate::utils::{self, create_eth_transaction, AuroraRunner, DEFAULT_AURORA_ACCOUNT_ID};
 use aurora_engine::engine::EngineError;
+use aurora_engine::erc20_code::CodeVersion;
 use aurora_engine::parameters::{CallArgs, FunctionCallArgsV2};
 use aurora_engine_transactions::legacy::LegacyEthSignedTransaction;
 use aurora_engine_types::borsh::{BorshDeserialize, BorshSerialize};
 use aurora_engine_types::parameters::engine::{SubmitResult, TransactionStatus};
+use aurora_engine_types::parameters::events::{EngineEvent, FtOnTransferInfo, TokenPair};
 use ethabi::Token;
-use libsecp256k1::SecretKey;
+use libsecp256k1::{Message, SecretKey};
 use near_vm_logic::VMOutcome;
 use serde_json::json;
 use sha3::Digest;
@@ -14,6 +16,15 @@ use sha3::Digest;
 const INITIAL_BALANCE: Wei = Wei::new_u64(1000);
 const INITIAL_NONCE: u64 = 0;
 
+#[cfg(feature = "error_refund")]
+const LEGACY_ERC20_CODE: &[u8] = include_bytes!("../../../etc/eth-contracts/res/EvmErc20V2.bin");
+#[cfg(not(feature = "error_refund"))]
+const LEGACY_ERC20_CODE: &[u8] = include_bytes!("../../../etc/eth-contracts/res/EvmErc20.bin");
+#[cfg(feature = "error_refund")]
+const ERC20_V3_CODE: &[u8] = include_bytes!("../../../etc/eth-contracts/res/EvmErc20V3Refund.bin");
+#[cfg(not(feature = "error_refund"))]
+const ERC20_V3_CODE: &[u8] = include_bytes!("../../../etc/eth-contracts/res/EvmErc20V3.bin");
+
 fn keccak256(input: &[u8]) -> Vec<u8> {
     sha3::Keccak256::digest(input).to_vec()
 }
@@ -129,6 +140,26 @@ impl AuroraRunner {
         }
     }
 
+    pub fn evm_call_status(&mut self, contract: Address, input: Vec<u8>) -> TransactionStatus {
+        let result = self
+            .evm_call(contract, input, DEFAULT_AURORA_ACCOUNT_ID)
+            .unwrap();
+        let output = result.return_data.as_value().unwrap();
+        SubmitResult::try_from_slice(&output).unwrap().status
+    }
+
+    pub fn erc20_code_version(&mut self, token: Address) -> CodeVersion {
+        let result = self
+            .one_shot()
+            .call(
+                "get_erc20_code_version",
+                DEFAULT_AURORA_ACCOUNT_ID,
+                token.as_bytes().to_vec(),
+            )
+            .unwrap();
+        CodeVersion::try_from_slice(&result.return_data.as_value().unwrap()).unwrap()
+    }
+
     pub fn mint(
         &mut self,
         token: Address,
@@ -224,7 +255,14 @@ impl AuroraRunner {
 #[test]
 fn test_deploy_erc20_token() {
     let mut runner = AuroraRunner::new();
-    runner.deploy_erc20_token("tt.testnet");
+    let erc20 = runner.deploy_erc20_token("tt.testnet");
+    assert_eq!(
+        runner.previous_events(),
+        vec![EngineEvent::DeployErc20Token(TokenPair {
+            nep141: "tt.testnet".parse().unwrap(),
+            erc20,
+        })]
+    );
 }
 
 #[test]
@@ -270,11 +308,135 @@ fn test_ft_on_transfer() {
     let res = runner.ft_on_transfer(nep141, alice, alice, amount, &recipient.encode());
     // Transaction should succeed so return amount is 0
     assert_eq!(res, "\"0\"");
+    assert_eq!(
+        runner.previous_events(),
+        vec![EngineEvent::FtOnTransfer(FtOnTransferInfo {
+            token_id: nep141.parse().unwrap(),
+            sender_id: alice.parse().unwrap(),
+            amount,
+            msg: recipient.encode(),
+        })]
+    );
 
     let balance = runner.balance_of(token, recipient, DEFAULT_AURORA_ACCOUNT_ID);
     assert_eq!(balance, U256::from(amount.as_u128()));
 }
 
+#[test]
+fn test_ft_on_transfer_with_call() {
+    let mut runner = AuroraRunner::new();
+    // Standalone runner presently does not support ft_on_transfer
+    runner.standalone_runner = None;
+    let nep141 = "tt.testnet";
+    let alice = "alice";
+    let alice_address = aurora_engine_sdk::types::near_account_to_evm_address(alice.as_bytes());
+    let token = runner.deploy_erc20_token(nep141);
+    let amount = Balance::new(10);
+
+    let mut signer = utils::Signer::random();
+    runner.create_address(
+        utils::address_from_secret_key(&signer.secret_key),
+        Wei::zero(),
+        0.into(),
+    );
+    let constructor = utils::solidity::ContractConstructor::compile_from_source(
+        "src/tests/res",
+        "target/solidity_build",
+        "deposit_receiver.sol",
+        "DepositReceiver",
+    );
+    let nonce = signer.use_nonce();
+    let receiver = runner.deploy_contract(
+        &signer.secret_key,
+        |c| c.deploy_with_args(nonce.into(), &[Token::Address(token.raw())]),
+        constructor,
+    );
+    let staked_input = build_input("staked(address)", &[Token::Address(alice_address.raw())]);
+
+    // The tokens are minted to the receiver, which is then called with the calldata.
+    let stake_input = build_input(
+        "stake(address,uint256)",
+        &[
+            Token::Address(alice_address.raw()),
+            Token::Uint(amount.as_u128().into()),
+        ],
+    );
+    let msg = format!("{}:{}", receiver.address.encode(), hex::encode(stake_input));
+    let res = runner.ft_on_transfer(nep141, alice, alice, amount, &msg);
+    assert_eq!(res, "\"0\"");
+    assert_eq!(
+        runner.balance_of(token, receiver.address, DEFAULT_AURORA_ACCOUNT_ID),
+        U256::from(amount.as_u128())
+    );
+    let staked = runner.evm_call_status(receiver.address, staked_input.clone());
+    assert_eq!(
+        staked,
+        TransactionStatus::Succeed(u256_to_vec(amount.as_u128().into()))
+    );
+
+    // If the call reverts, the tokens are not minted and the whole amount is refunded.
+    let stake_input = build_input(
+        "stake(address,uint256)",
+        &[
+            Token::Address(alice_address.raw()),
+            Token::Uint((amount.as_u128() + 1).into()),
+        ],
+    );
+    let msg = format!("{}:{}", receiver.address.encode(), hex::encode(stake_input));
+    let res = runner.ft_on_transfer(nep141, alice, alice, amount, &msg);
+    assert_eq!(res, format!("\"{amount}\""));
+    assert!(runner.previous_events().is_empty());
+    assert_eq!(
+        runner.balance_of(token, receiver.address, DEFAULT_AURORA_ACCOUNT_ID),
+        U256::from(amount.as_u128())
+    );
+    let staked = runner.evm_call_status(receiver.address, staked_input);
+    assert_eq!(
+        staked,
+        TransactionStatus::Succeed(u256_to_vec(amount.as_u128().into()))
+    );
+}
+
+#[test]
+fn test_ft_on_transfer_with_call_forged_sender() {
+    let mut runner = AuroraRunner::new();
+    // Standalone runner presently does not support ft_on_transfer
+    runner.standalone_runner = None;
+    let nep141 = "tt.testnet";
+    let alice = "alice";
+    let alice_address = aurora_engine_sdk::types::near_account_to_evm_address(alice.as_bytes());
+    let attacker = "attacker";
+    let attacker_address =
+        aurora_engine_sdk::types::near_account_to_evm_address(attacker.as_bytes());
+    let token = runner.deploy_erc20_token(nep141);
+    let amount = Balance::new(10);
+    let res = runner.ft_on_transfer(nep141, alice, alice, amount, &alice_address.encode());
+    assert_eq!(res, "\"0\"");
+
+    // Anyone can register a NEP-141 contract claiming any sender, so the call must not be
+    // made on behalf of the sender.
+    let fake_nep141 = "fake.testnet";
+    runner.deploy_erc20_token(fake_nep141);
+    let transfer_input = build_input(
+        "transfer(address,uint256)",
+        &[
+            Token::Address(attacker_address.raw()),
+            Token::Uint(amount.as_u128().into()),
+        ],
+    );
+    let msg = format!("{}:{}", token.encode(), hex::encode(transfer_input));
+    let res = runner.ft_on_transfer(fake_nep141, alice, attacker, amount, &msg);
+    assert_eq!(res, format!("\"{amount}\""));
+    assert_eq!(
+        runner.balance_of(token, alice_address, DEFAULT_AURORA_ACCOUNT_ID),
+        U256::from(amount.as_u128())
+    );
+    assert_eq!(
+        runner.balance_of(token, attacker_address, DEFAULT_AURORA_ACCOUNT_ID),
+        U256::zero()
+    );
+}
+
 #[test]
 fn test_ft_on_transfer_fail() {
     let mut runner = AuroraRunner::new();
@@ -286,6 +448,7 @@ fn test_ft_on_transfer_fail() {
 
     // Transaction should fail so it must return everything
     assert_eq!(res, format!("\"{amount}\""));
+    assert!(runner.previous_events().is_empty());
 }
 
 #[ignore]
@@ -383,6 +546,236 @@ fn test_transfer_erc20_token() {
     );
 }
 
+#[test]
+fn test_deploy_erc20_token_v3_by_default() {
+    let mut runner = AuroraRunner::new();
+    let token = runner.deploy_erc20_token("tt.testnet");
+    let owner = runner.create_account().address;
+
+    // `nonces` is only implemented by `EvmErc20V3`.
+    let status = runner.evm_call_status(
+        token,
+        build_input("nonces(address)", &[Token::Address(owner.raw())]),
+    );
+    assert_eq!(status, TransactionStatus::Succeed(vec![0; 32]));
+    assert_eq!(runner.erc20_code_version(token), CodeVersion(0));
+}
+
+#[test]
+fn test_permit() {
+    let mut runner = AuroraRunner::new();
+    let token = runner.deploy_erc20_token("tt.testnet");
+    let owner = runner.create_account();
+    let spender = runner.create_account().address;
+    runner
+        .mint(token, owner.address, 100, DEFAULT_AURORA_ACCOUNT_ID)
+        .unwrap();
+
+    let value = U256::from(10);
+    let deadline = U256::MAX;
+    let permit = permit_input(&mut runner, token, &owner, spender, value, deadline);
+    let status = runner.evm_call_status(token, permit.clone());
+    assert!(status.is_ok(), "{status:?}");
+
+    let allowance = runner.evm_call_status(
+        token,
+        build_input(
+            "allowance(address,address)",
+            &[
+                Token::Address(owner.address.raw()),
+                Token::Address(spender.raw()),
+            ],
+        ),
+    );
+    assert_eq!(allowance, TransactionStatus::Succeed(u256_to_vec(value)));
+    let nonce = runner.evm_call_status(
+        token,
+        build_input("nonces(address)", &[Token::Address(owner.address.raw())]),
+    );
+    assert_eq!(nonce, TransactionStatus::Succeed(u256_to_vec(U256::one())));
+
+    // The signature can't be reused because the nonce of the owner has changed.
+    let status = runner.evm_call_status(token, permit);
+    assert_revert_with(&status, "ERC20Permit: invalid signature");
+
+    // The block timestamp is greater than 0 after the calls above.
+    let permit = permit_input(&mut runner, token, &owner, spender, value, U256::zero());
+    let status = runner.evm_call_status(token, permit);
+    assert_revert_with(&status, "ERC20Permit: expired deadline");
+}
+
+#[test]
+fn test_migrate_erc20_token() {
+    let mut runner = AuroraRunner::new();
+
+    // Only the owner or a token admin can set the ERC-20 code.
+    let error = runner
+        .make_call("set_erc20_code", "alice.near", LEGACY_ERC20_CODE.to_vec())
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_NOT_ALLOWED");
+
+    // Deploy a token with the code that was used before `EvmErc20V3`.
+    runner
+        .make_call(
+            "set_erc20_code",
+            DEFAULT_AURORA_ACCOUNT_ID,
+            LEGACY_ERC20_CODE.to_vec(),
+        )
+        .unwrap();
+    let token = runner.deploy_erc20_token("tt.testnet");
+    assert_eq!(runner.erc20_code_version(token), CodeVersion(1));
+    let holder = runner.create_account().address;
+    runner
+        .mint(token, holder, 100, DEFAULT_AURORA_ACCOUNT_ID)
+        .unwrap();
+    let nonces_input = build_input("nonces(address)", &[Token::Address(holder.raw())]);
+    let status = runner.evm_call_status(token, nonces_input.clone());
+    assert!(matches!(status, TransactionStatus::Revert(_)), "{status:?}");
+
+    let error = runner
+        .make_call(
+            "migrate_erc20_token",
+            DEFAULT_AURORA_ACCOUNT_ID,
+            token.as_bytes().to_vec(),
+        )
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_ERC20_CODE_UP_TO_DATE");
+
+    runner
+        .make_call(
+            "set_erc20_code",
+            DEFAULT_AURORA_ACCOUNT_ID,
+            ERC20_V3_CODE.to_vec(),
+        )
+        .unwrap();
+
+    // Only the owner or a token admin can migrate a token.
+    let error = runner
+        .make_call(
+            "migrate_erc20_token",
+            "alice.near",
+            token.as_bytes().to_vec(),
+        )
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_NOT_ALLOWED");
+
+    // Only bridged tokens can be migrated.
+    let error = runner
+        .make_call(
+            "migrate_erc20_token",
+            DEFAULT_AURORA_ACCOUNT_ID,
+            holder.as_bytes().to_vec(),
+        )
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_NOT_BRIDGED_ERC20");
+
+    runner
+        .make_call(
+            "migrate_erc20_token",
+            DEFAULT_AURORA_ACCOUNT_ID,
+            token.as_bytes().to_vec(),
+        )
+        .unwrap();
+    assert_eq!(runner.erc20_code_version(token), CodeVersion(2));
+
+    // The storage of the token is preserved and the new code is used.
+    assert_eq!(
+        runner.balance_of(token, holder, DEFAULT_AURORA_ACCOUNT_ID),
+        U256::from(100)
+    );
+    let status = runner.evm_call_status(token, nonces_input);
+    assert_eq!(status, TransactionStatus::Succeed(vec![0; 32]));
+
+    let error = runner
+        .make_call(
+            "migrate_erc20_token",
+            DEFAULT_AURORA_ACCOUNT_ID,
+            token.as_bytes().to_vec(),
+        )
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_ERC20_CODE_UP_TO_DATE");
+}
+
+fn u256_to_vec(value: U256) -> Vec<u8> {
+    let mut bytes = vec![0; 32];
+    value.to_big_endian(&mut bytes);
+    bytes
+}
+
+fn assert_revert_with(status: &TransactionStatus, message: &str) {
+    let TransactionStatus::Revert(bytes) = status else {
+        panic!("Unexpected status {status:?}");
+    };
+    assert!(
+        bytes
+            .windows(message.len())
+            .any(|window| window == message.as_bytes()),
+        "Unexpected revert reason {bytes:?}"
+    );
+}
+
+/// Input of the `permit` method of the token signed by `owner` according to EIP-712.
+fn permit_input(
+    runner: &mut AuroraRunner,
+    token: Address,
+    owner: &EthereumAddress,
+    spender: Address,
+    value: U256,
+    deadline: U256,
+) -> Vec<u8> {
+    let TransactionStatus::Succeed(name) = runner.evm_call_status(token, build_input("name()", &[]))
+    else {
+        panic!("Failed to get the name of the token");
+    };
+    let name = ethabi::decode(&[ethabi::ParamType::String], &name)
+        .unwrap()
+        .pop()
+        .and_then(Token::into_string)
+        .unwrap();
+    let TransactionStatus::Succeed(nonce) = runner.evm_call_status(
+        token,
+        build_input("nonces(address)", &[Token::Address(owner.address.raw())]),
+    ) else {
+        panic!("Failed to get the nonce of the owner");
+    };
+
+    let domain_separator = keccak256(&ethabi::encode(&[
+        Token::FixedBytes(keccak256(
+            b"EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)",
+        )),
+        Token::FixedBytes(keccak256(name.as_bytes())),
+        Token::FixedBytes(keccak256(b"1")),
+        Token::Uint(runner.chain_id.into()),
+        Token::Address(token.raw()),
+    ]));
+    let struct_hash = keccak256(&ethabi::encode(&[
+        Token::FixedBytes(keccak256(
+            b"Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)",
+        )),
+        Token::Address(owner.address.raw()),
+        Token::Address(spender.raw()),
+        Token::Uint(value),
+        Token::Uint(U256::from_big_endian(&nonce)),
+        Token::Uint(deadline),
+    ]));
+    let digest = keccak256(&[b"\x19\x01".as_slice(), &domain_separator, &struct_hash].concat());
+    let message = Message::parse_slice(&digest).unwrap();
+    let (signature, recovery_id) = libsecp256k1::sign(&message, &owner.secret_key);
+
+    build_input(
+        "permit(address,address,uint256,uint256,uint8,bytes32,bytes32)",
+        &[
+            Token::Address(owner.address.raw()),
+            Token::Address(spender.raw()),
+            Token::Uint(value),
+            Token::Uint(deadline),
+            Token::Uint((recovery_id.serialize() + 27).into()),
+            Token::FixedBytes(signature.r.b32().to_vec()),
+            Token::FixedBytes(signature.s.b32().to_vec()),
+        ],
+    )
+}
+
 mod workspace {
     use super::build_input;
     use crate::prelude::{Address, Wei, WeiU256, U256};
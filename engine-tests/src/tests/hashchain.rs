@@ -1,12 +1,15 @@
 use crate::utils;
 use aurora_engine::parameters::{StartHashchainArgs, SubmitResult, TransactionStatus};
-use aurora_engine_hashchain::bloom::Bloom;
+use aurora_engine_hashchain::{bloom::Bloom, proof::verify_tx_inclusion};
+use aurora_engine_modexp::AuroraModExp;
 use aurora_engine_transactions::legacy::TransactionLegacy;
 use aurora_engine_types::{
     borsh::BorshSerialize,
     types::{Address, Wei},
     H256, U256,
 };
+use engine_standalone_storage::hashchain_verification::verify_block_hashchains;
+use std::collections::BTreeMap;
 
 #[test]
 fn test_hashchain() {
@@ -38,6 +41,7 @@ fn test_hashchain() {
         .try_to_vec()
         .unwrap();
 
+    let submit_block_height = hc.block_height + 2;
     let expected_hc = {
         let start_hc_args = StartHashchainArgs {
             block_height: hc.block_height,
@@ -87,6 +91,77 @@ fn test_hashchain() {
     let hc = get_latest_hashchain(&runner);
     assert_eq!(hc.block_height, runner.context.block_height - 1);
     assert_eq!(hc.hashchain, hex::encode(expected_hc));
+
+    // The standalone engine proves the inclusion of the `submit` transaction in its block
+    let storage = &runner.standalone_runner.as_ref().unwrap().storage;
+    let proof = engine_standalone_storage::hashchain_proof::compute_tx_inclusion_proof::<
+        AuroraModExp,
+    >(storage, submit_block_height, 0)
+    .unwrap();
+    assert_eq!(proof.method_name, "submit");
+    assert_eq!(proof.input, input);
+    assert_eq!(proof.output, output);
+    assert!(verify_tx_inclusion(&proof, &expected_hc));
+    assert!(!verify_tx_inclusion(&proof, &H256::default().0));
+
+    // There is only one hashchain transaction in the block
+    let result = engine_standalone_storage::hashchain_proof::compute_tx_inclusion_proof::<
+        AuroraModExp,
+    >(storage, submit_block_height, 1);
+    assert_eq!(
+        result,
+        Err(engine_standalone_storage::Error::HashchainTxNotFound(
+            submit_block_height,
+            1
+        ))
+    );
+
+    // The standalone storage records the hashchain of the finalized blocks
+    assert_eq!(
+        storage.get_block_hashchain(submit_block_height),
+        Ok(expected_hc)
+    );
+    assert_eq!(
+        storage.get_block_hashchain(submit_block_height + 1),
+        Err(engine_standalone_storage::Error::BlockHashchainNotFound(
+            submit_block_height + 1
+        ))
+    );
+
+    // Replaying the blocks gives the same hashchains, and a wrong on-chain value is located.
+    // The replay doesn't modify the storage.
+    let snapshot = storage.get_snapshot(submit_block_height + 1).unwrap();
+    let mut on_chain = BTreeMap::new();
+    on_chain.insert(submit_block_height, expected_hc);
+    let divergence = verify_block_hashchains::<AuroraModExp>(
+        storage,
+        submit_block_height - 1,
+        submit_block_height + 1,
+        &on_chain,
+    )
+    .unwrap();
+    assert_eq!(divergence, None);
+    assert_eq!(
+        storage.get_block_hashchain(submit_block_height),
+        Ok(expected_hc)
+    );
+
+    on_chain.insert(submit_block_height - 1, [1u8; 32]);
+    let divergence = verify_block_hashchains::<AuroraModExp>(
+        storage,
+        submit_block_height - 1,
+        submit_block_height + 1,
+        &on_chain,
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(divergence.block_height, submit_block_height - 1);
+    assert_eq!(divergence.expected, [1u8; 32]);
+    assert!(divergence.computed.is_some());
+    assert_eq!(
+        storage.get_snapshot(submit_block_height + 1).unwrap(),
+        snapshot
+    );
 }
 
 fn get_latest_hashchain(runner: &utils::AuroraRunner) -> HashchainView {
@@ -1,5 +1,7 @@
 mod account_id_precompiles;
 mod contract_call;
+mod denylist;
+mod deploy_allowlist;
 mod ecrecover;
 mod erc20;
 mod erc20_connector;
@@ -10,7 +12,9 @@ mod hashchain;
 mod meta_parsing;
 pub mod modexp;
 mod multisender;
+mod native_erc20;
 mod one_inch;
+mod pausable_methods;
 mod pausable_precompiles;
 mod pause_contract;
 mod prepaid_gas_precompile;
@@ -18,12 +22,15 @@ mod promise_results_precompile;
 mod random;
 mod relayer_keys;
 mod repro;
+mod roles;
 pub mod sanity;
 mod self_destruct_state;
 mod serde;
+mod silo_params;
 mod standalone;
 mod standard_precompiles;
 mod transaction;
 pub mod uniswap;
 mod upgrade;
+mod whitelist;
 pub mod xcc;
@@ -0,0 +1,249 @@
+use crate::prelude::{Wei, U256};
+use crate::utils::{
+    self,
+    solidity::{
+        erc20::{ERC20Constructor, ERC20},
+        ContractConstructor,
+    },
+    AuroraRunner, Signer,
+};
+use aurora_engine::native_erc20;
+use aurora_engine::parameters::{
+    DeployNep141TokenArgs, RegisterNep141TokenArgs, TransactionStatus,
+};
+use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::borsh::BorshSerialize;
+use aurora_engine_types::types::PromiseResult;
+use near_primitives::transaction::Action;
+
+const NEP141_CODE: &[u8] = b"nep141 wasm";
+
+#[test]
+fn test_deploy_nep141_token() {
+    let (mut runner, erc20) = initialize();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let erc20_address = erc20.0.address;
+    let nep141: AccountId = format!("{}-nep141.{aurora_account_id}", erc20_address.encode())
+        .parse()
+        .unwrap();
+    let args = DeployNep141TokenArgs { erc20_address }
+        .try_to_vec()
+        .unwrap();
+
+    // Only the owner or a token admin can deploy the NEP-141 token.
+    runner.context.attached_deposit = native_erc20::STORAGE_AMOUNT.as_u128();
+    let error = runner
+        .call("deploy_nep141_token", "alice.near", args.clone())
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_NOT_ALLOWED");
+
+    // The deposit must cover the storage of the new account.
+    runner.context.attached_deposit = native_erc20::STORAGE_AMOUNT.as_u128() - 1;
+    let error = runner
+        .call("deploy_nep141_token", &aurora_account_id, args.clone())
+        .unwrap_err();
+    assert_eq!(
+        error.kind.as_bytes(),
+        b"ERR_INSUFFICIENT_FUNDING_OF_NEW_NEP141_ACCOUNT"
+    );
+
+    runner.context.attached_deposit = native_erc20::STORAGE_AMOUNT.as_u128();
+    let outcome = runner
+        .call("deploy_nep141_token", &aurora_account_id, args.clone())
+        .unwrap();
+    runner.context.attached_deposit = 0;
+
+    // The NEP-141 account is created and initialized, then the token is registered by a callback.
+    assert_eq!(outcome.action_receipts.len(), 2);
+    let (target_account_id, receipt) = &outcome.action_receipts[0];
+    assert_eq!(target_account_id.as_str(), nep141.as_ref());
+    assert!(matches!(receipt.actions[0], Action::CreateAccount(_)));
+    let Action::Transfer(transfer) = &receipt.actions[1] else {
+        panic!("Unexpected action {:?}", receipt.actions[1]);
+    };
+    assert_eq!(transfer.deposit, native_erc20::STORAGE_AMOUNT.as_u128());
+    let Action::DeployContract(deploy) = &receipt.actions[2] else {
+        panic!("Unexpected action {:?}", receipt.actions[2]);
+    };
+    assert_eq!(deploy.code, NEP141_CODE);
+    let Action::FunctionCall(init) = &receipt.actions[3] else {
+        panic!("Unexpected action {:?}", receipt.actions[3]);
+    };
+    assert_eq!(init.method_name, "new");
+    let init_args: serde_json::Value = serde_json::from_slice(&init.args).unwrap();
+    assert_eq!(init_args["owner_id"], aurora_account_id.as_str());
+    assert_eq!(init_args["metadata"]["name"], "TestToken");
+    assert_eq!(init_args["metadata"]["symbol"], "TEST");
+
+    let (target_account_id, receipt) = &outcome.action_receipts[1];
+    assert_eq!(target_account_id.as_str(), aurora_account_id.as_str());
+    let Action::FunctionCall(callback) = &receipt.actions[0] else {
+        panic!("Unexpected action {:?}", receipt.actions[0]);
+    };
+    assert_eq!(callback.method_name, "register_nep141_token");
+    let expected_callback_args = RegisterNep141TokenArgs {
+        erc20_address,
+        nep141: nep141.clone(),
+    }
+    .try_to_vec()
+    .unwrap();
+    assert_eq!(callback.args, expected_callback_args);
+
+    // The callback returns the id of the registered NEP-141 token.
+    runner.promise_results = vec![PromiseResult::Successful(Vec::new())];
+    let outcome = runner
+        .call(
+            "register_nep141_token",
+            &aurora_account_id,
+            expected_callback_args,
+        )
+        .unwrap();
+    runner.promise_results = Vec::new();
+    assert_eq!(
+        outcome.return_data.as_value().unwrap(),
+        nep141.as_bytes().to_vec()
+    );
+
+    let outcome = runner
+        .one_shot()
+        .call(
+            "get_nep141_from_erc20",
+            "alice.near",
+            erc20_address.as_bytes().to_vec(),
+        )
+        .unwrap();
+    assert_eq!(
+        outcome.return_data.as_value().unwrap(),
+        nep141.as_bytes().to_vec()
+    );
+
+    // The token can't be deployed twice.
+    runner.context.attached_deposit = native_erc20::STORAGE_AMOUNT.as_u128();
+    let error = runner
+        .call("deploy_nep141_token", &aurora_account_id, args)
+        .unwrap_err();
+    runner.context.attached_deposit = 0;
+    assert_eq!(
+        error.kind.as_bytes(),
+        b"ERR_NEP141_TOKEN_ALREADY_REGISTERED"
+    );
+}
+
+#[test]
+fn test_register_nep141_token_after_failed_deploy() {
+    let (mut runner, erc20) = initialize();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let erc20_address = erc20.0.address;
+    let args = RegisterNep141TokenArgs {
+        erc20_address,
+        nep141: format!("{}-nep141.{aurora_account_id}", erc20_address.encode())
+            .parse()
+            .unwrap(),
+    }
+    .try_to_vec()
+    .unwrap();
+
+    // The callback is private.
+    runner.promise_results = vec![PromiseResult::Successful(Vec::new())];
+    let error = runner
+        .call("register_nep141_token", "alice.near", args.clone())
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_PRIVATE_CALL");
+
+    // Nothing is registered if the deployment failed.
+    runner.promise_results = vec![PromiseResult::Failed];
+    let error = runner
+        .call("register_nep141_token", &aurora_account_id, args)
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_NEP141_DEPLOY_FAILED");
+
+    let error = runner
+        .one_shot()
+        .call(
+            "get_nep141_from_erc20",
+            "alice.near",
+            erc20_address.as_bytes().to_vec(),
+        )
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERC20_NOT_FOUND");
+}
+
+#[test]
+fn test_native_erc20_cannot_exit_as_bridged_token() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let mut signer = Signer::random();
+    let signer_address = utils::address_from_secret_key(&signer.secret_key);
+    runner.create_address(signer_address, Wei::zero(), 0.into());
+    let constructor = ContractConstructor::compile_from_source(
+        "src/tests/res",
+        "target/solidity_build",
+        "exit_caller.sol",
+        "ExitCaller",
+    );
+    let nonce = signer.use_nonce();
+    let token = runner.deploy_contract(
+        &signer.secret_key,
+        |c| c.deploy_without_constructor(nonce.into()),
+        constructor,
+    );
+
+    // The token is registered as native to Aurora.
+    runner.promise_results = vec![PromiseResult::Successful(Vec::new())];
+    let args = RegisterNep141TokenArgs {
+        erc20_address: token.address,
+        nep141: format!("{}-nep141.{aurora_account_id}", token.address.encode())
+            .parse()
+            .unwrap(),
+    };
+    let result = runner.call(
+        "register_nep141_token",
+        &aurora_account_id,
+        args.try_to_vec().unwrap(),
+    );
+    runner.promise_results = Vec::new();
+    assert!(result.is_ok(), "{result:?}");
+
+    // Flag of the bridged ERC-20 exit followed by the refund address (if enabled), the amount
+    // and the recipient. The NEP-141 tokens of the engine must not be transferred without
+    // locking the native tokens.
+    let mut input = vec![1];
+    if cfg!(feature = "error_refund") {
+        input.extend_from_slice(signer_address.as_bytes());
+    }
+    let mut amount = [0; 32];
+    U256::from(100).to_big_endian(&mut amount);
+    input.extend_from_slice(&amount);
+    input.extend_from_slice(b"bob.near");
+    let result = runner
+        .submit_with_signer(&mut signer, |nonce| {
+            token.call_method_with_args("exit", &[ethabi::Token::Bytes(input)], nonce)
+        })
+        .unwrap();
+    assert!(
+        matches!(result.status, TransactionStatus::Revert(_)),
+        "{result:?}"
+    );
+}
+
+fn initialize() -> (AuroraRunner, ERC20) {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let result = runner.call("set_nep141_code", &aurora_account_id, NEP141_CODE.to_vec());
+    assert!(result.is_ok());
+
+    let mut signer = Signer::random();
+    runner.create_address(
+        utils::address_from_secret_key(&signer.secret_key),
+        Wei::zero(),
+        0.into(),
+    );
+    let nonce = signer.use_nonce();
+    let erc20 = ERC20(runner.deploy_contract(
+        &signer.secret_key,
+        |c| c.deploy("TestToken", "TEST", nonce.into()),
+        ERC20Constructor::load(),
+    ));
+
+    (runner, erc20)
+}
@@ -0,0 +1,144 @@
+use crate::prelude::Wei;
+use crate::utils;
+use aurora_engine::engine::EngineErrorKind;
+use aurora_engine::parameters::{PauseMethodsArgs, StartHashchainArgs};
+use aurora_engine_types::borsh::BorshSerialize;
+
+const TRANSFER_AMOUNT: Wei = Wei::new_u64(10);
+
+fn methods_args(methods: &[&str]) -> Vec<u8> {
+    serde_json::to_vec(&PauseMethodsArgs {
+        methods: methods.iter().map(ToString::to_string).collect(),
+    })
+    .unwrap()
+}
+
+fn paused_methods(runner: &utils::AuroraRunner) -> Vec<String> {
+    let result = runner
+        .one_shot()
+        .call("get_paused_methods", "viewer", Vec::new())
+        .unwrap();
+    serde_json::from_slice(&result.return_data.as_value().unwrap()).unwrap()
+}
+
+#[test]
+fn test_paused_method_is_rejected() {
+    let (mut runner, mut signer, receiver) = crate::tests::sanity::initialize_transfer();
+    let aurora_account_id = runner.aurora_account_id.clone();
+
+    let result = runner.call(
+        "pause_methods",
+        &aurora_account_id,
+        methods_args(&["submit"]),
+    );
+    assert!(result.is_ok(), "{result:?}");
+    assert_eq!(paused_methods(&runner), vec!["submit".to_string()]);
+
+    utils::assert_submit_rejected(
+        &mut runner,
+        &mut signer,
+        |nonce| utils::transfer(receiver, TRANSFER_AMOUNT, nonce),
+        &EngineErrorKind::EvmFatal(evm::ExitFatal::Other("ERR_METHOD_PAUSED".into())),
+    );
+
+    let result = runner.call(
+        "resume_methods",
+        &aurora_account_id,
+        methods_args(&["submit"]),
+    );
+    assert!(result.is_ok(), "{result:?}");
+    assert!(paused_methods(&runner).is_empty());
+
+    let result = runner.submit_with_signer(&mut signer, |nonce| {
+        utils::transfer(receiver, TRANSFER_AMOUNT, nonce)
+    });
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_paused_start_hashchain_is_rejected() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+
+    let result = runner.call(
+        "pause_methods",
+        &aurora_account_id,
+        methods_args(&["start_hashchain"]),
+    );
+    assert!(result.is_ok(), "{result:?}");
+    let result = runner.call("pause_contract", &aurora_account_id, Vec::new());
+    assert!(result.is_ok(), "{result:?}");
+
+    let args = StartHashchainArgs {
+        block_height: runner.context.block_height,
+        block_hashchain: [0u8; 32],
+    };
+    let result = runner.call(
+        "start_hashchain",
+        &aurora_account_id,
+        args.try_to_vec().unwrap(),
+    );
+    assert!(matches!(
+        result.unwrap_err().kind,
+        EngineErrorKind::EvmFatal(evm::ExitFatal::Other(e)) if e == "ERR_METHOD_PAUSED"
+    ));
+}
+
+#[test]
+fn test_pause_methods_requires_pauser() {
+    let mut runner = utils::deploy_runner();
+    let args = methods_args(&["deploy_erc20_token"]);
+
+    let result = runner.call("pause_methods", "stranger.near", args);
+    assert!(result.is_err());
+    assert!(paused_methods(&runner).is_empty());
+}
+
+#[test]
+fn test_pausing_methods_can_not_be_paused() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+
+    let result = runner.call(
+        "pause_methods",
+        &aurora_account_id,
+        methods_args(&["submit", "resume_methods"]),
+    );
+    assert!(matches!(
+        result.unwrap_err().kind,
+        EngineErrorKind::EvmFatal(evm::ExitFatal::Other(e)) if e == "ERR_METHOD_NOT_PAUSABLE"
+    ));
+    assert!(paused_methods(&runner).is_empty());
+}
+
+#[test]
+fn test_callbacks_can_not_be_paused() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+
+    for method in ["register_nep141_token", "finish_deposit", "state_migration"] {
+        let result = runner.call("pause_methods", &aurora_account_id, methods_args(&[method]));
+        assert!(matches!(
+            result.unwrap_err().kind,
+            EngineErrorKind::EvmFatal(evm::ExitFatal::Other(e)) if e == "ERR_METHOD_NOT_PAUSABLE"
+        ));
+    }
+    assert!(paused_methods(&runner).is_empty());
+}
+
+#[test]
+fn test_unknown_methods_can_not_be_paused() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+
+    let result = runner.call(
+        "pause_methods",
+        &aurora_account_id,
+        methods_args(&["submit", "sumbit"]),
+    );
+    assert!(matches!(
+        result.unwrap_err().kind,
+        EngineErrorKind::EvmFatal(evm::ExitFatal::Other(e)) if e == "ERR_UNKNOWN_METHOD"
+    ));
+    assert!(paused_methods(&runner).is_empty());
+}
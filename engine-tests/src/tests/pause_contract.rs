@@ -1,5 +1,5 @@
 use crate::utils;
-use aurora_engine::parameters::SetUpgradeDelayBlocksArgs;
+use aurora_engine::parameters::{DenylistArgs, DeployAllowlistModeArgs, SetUpgradeDelayBlocksArgs};
 use aurora_engine_types::borsh::BorshSerialize;
 
 #[test]
@@ -95,6 +95,29 @@ fn test_pause_contract() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_admin_settings_require_running() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let result = runner.call("pause_contract", &aurora_account_id, vec![]);
+    assert!(result.is_ok());
+
+    let args = serde_json::to_vec(&DenylistArgs {
+        address: utils::address_from_secret_key(&utils::Signer::random().secret_key),
+    })
+    .unwrap();
+    let error = runner
+        .call("deny_address", &aurora_account_id, args)
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_PAUSED");
+
+    let args = serde_json::to_vec(&DeployAllowlistModeArgs { enabled: true }).unwrap();
+    let error = runner
+        .call("set_deploy_allowlist_enabled", &aurora_account_id, args)
+        .unwrap_err();
+    assert_eq!(error.kind.as_bytes(), b"ERR_PAUSED");
+}
+
 #[test]
 fn test_resume_contract() {
     let mut runner = utils::deploy_runner();
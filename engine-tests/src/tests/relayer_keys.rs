@@ -1,5 +1,7 @@
 use crate::utils::workspace::deploy_engine;
-use aurora_engine_types::parameters::engine::{RelayerKeyArgs, RelayerKeyManagerArgs};
+use aurora_engine_types::parameters::engine::{
+    RelayerKeyArgs, RelayerKeyArgsV2, RelayerKeyManagerArgs,
+};
 use aurora_engine_types::public_key::PublicKey;
 use aurora_engine_types::types::Address;
 use aurora_engine_workspace::parse_near;
@@ -275,6 +277,119 @@ async fn test_call_not_allowed_contract() {
     assert_error_message(&err, "unable to broadcast the transaction to the network");
 }
 
+#[tokio::test]
+async fn test_expired_relayer_key() {
+    let aurora = deploy_engine().await;
+    let secret_key = SecretKey::from_random(KeyType::ED25519);
+    let public_key = public_key(&secret_key);
+    let relayer = aurora.create_account(&aurora.id(), secret_key);
+
+    let manager = aurora
+        .root()
+        .create_subaccount("key_manager", parse_near!("10 N"))
+        .await
+        .unwrap();
+    let result = aurora
+        .set_key_manager(RelayerKeyManagerArgs {
+            key_manager: Some(manager.id().clone()),
+        })
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+
+    let block_height = aurora.node.worker().view_block().await.unwrap().height();
+    let relayer_key_args = RelayerKeyArgsV2 {
+        public_key,
+        allowed_methods: vec!["submit".into()],
+        expires_at: Some(block_height + 100),
+    };
+    let result = manager
+        .call(&aurora.id(), "add_relayer_key")
+        .args_json(relayer_key_args.clone())
+        .max_gas()
+        .deposit(parse_near!("0.5 N"))
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+    let keys = aurora.get_relayer_keys().await.unwrap().result;
+    assert_eq!(keys, vec![relayer_key_args]);
+
+    // Only the `submit` method is allowed.
+    let err = relayer
+        .call(&aurora.id(), "call")
+        .max_gas()
+        .transact()
+        .await
+        .err()
+        .unwrap();
+    assert_error_message(&err, "unable to broadcast the transaction to the network");
+
+    aurora.node.worker().fast_forward(200).await.unwrap();
+
+    let result = relayer
+        .call(&aurora.id(), "submit")
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_failure());
+    let err = result.into_result().err().unwrap();
+    assert_error_message(&err, "Smart contract panicked: ERR_RELAYER_KEY_EXPIRED");
+
+    // Anyone can remove the expired keys.
+    let result = aurora
+        .root()
+        .call(&aurora.id(), "remove_expired_relayer_keys")
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+    let removed: Vec<PublicKey> = result.json().unwrap();
+    assert_eq!(removed, vec![public_key]);
+    assert!(aurora.get_relayer_keys().await.unwrap().result.is_empty());
+
+    let err = relayer
+        .call(&aurora.id(), "submit")
+        .max_gas()
+        .transact()
+        .await
+        .err()
+        .unwrap();
+    assert_error_message(&err, "unable to broadcast the transaction to the network");
+}
+
+#[tokio::test]
+async fn test_add_relayer_key_with_invalid_methods() {
+    let aurora = deploy_engine().await;
+    let pk = PublicKey::from_str("ed25519:DcA2MzgpJbrUATQLLceocVckhhAqrkingax4oJ9kZ847").unwrap();
+    let result = aurora
+        .set_key_manager(RelayerKeyManagerArgs {
+            key_manager: Some(aurora.id()),
+        })
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+
+    let result = aurora
+        .add_relayer_key(RelayerKeyArgsV2 {
+            public_key: pk,
+            allowed_methods: vec!["deploy_code".into()],
+            expires_at: None,
+        })
+        .max_gas()
+        .deposit(parse_near!("0.5 N"))
+        .transact()
+        .await;
+    let err = result.err().unwrap();
+    assert_error_message(&err, "ERR_INVALID_RELAYER_KEY_METHODS");
+}
+
 fn public_key(sk: &SecretKey) -> PublicKey {
     let pk_str = serde_json::to_string(&sk.public_key()).unwrap();
     PublicKey::from_str(pk_str.trim_matches('"')).unwrap()
@@ -0,0 +1,23 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+interface IERC20 {
+    function balanceOf(address account) external view returns (uint256);
+}
+
+contract DepositReceiver {
+    address public token;
+    uint256 public totalStaked;
+    mapping(address => uint256) public staked;
+
+    constructor(address _token) {
+        token = _token;
+    }
+
+    // Stakes tokens which were transferred to this contract beforehand for the account.
+    function stake(address account, uint256 amount) public {
+        require(IERC20(token).balanceOf(address(this)) >= totalStaked + amount, "Not enough tokens");
+        totalStaked += amount;
+        staked[account] += amount;
+    }
+}
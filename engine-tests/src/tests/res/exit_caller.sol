@@ -0,0 +1,10 @@
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+// Calls the exit to NEAR precompile itself, like the bridged ERC-20 tokens do when burning.
+contract ExitCaller {
+    function exit(bytes memory input) public {
+        (bool success, ) = address(0xe9217bc70b7ed1f598ddd3199e80b093fa71124f).call(input);
+        require(success, "ERR_EXIT");
+    }
+}
@@ -0,0 +1,114 @@
+use crate::utils;
+use aurora_engine::parameters::{PausePrecompilesCallArgs, Role, RoleArgs};
+use aurora_engine_types::borsh::BorshSerialize;
+
+fn role_args(role: Role, account_id: &str) -> Vec<u8> {
+    serde_json::to_vec(&RoleArgs {
+        role,
+        account_id: account_id.parse().unwrap(),
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_grant_role_require_owner() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let args = role_args(Role::Pauser, "pauser.near");
+
+    let result = runner.call("grant_role", "pauser.near", args.clone());
+    assert!(result.is_err());
+
+    let result = runner.call("grant_role", &aurora_account_id, args.clone());
+    assert!(result.is_ok());
+
+    // The role can't be granted twice.
+    let result = runner.call("grant_role", &aurora_account_id, args);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_pauser_and_unpauser_roles() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+
+    let result = runner.call("pause_contract", "pauser.near", vec![]);
+    assert!(result.is_err());
+
+    let result = runner.call(
+        "grant_role",
+        &aurora_account_id,
+        role_args(Role::Pauser, "pauser.near"),
+    );
+    assert!(result.is_ok());
+    let result = runner.call(
+        "grant_role",
+        &aurora_account_id,
+        role_args(Role::Unpauser, "unpauser.near"),
+    );
+    assert!(result.is_ok());
+
+    let result = runner.call("pause_contract", "pauser.near", vec![]);
+    assert!(result.is_ok());
+
+    // The pauser can't resume the contract.
+    let result = runner.call("resume_contract", "pauser.near", vec![]);
+    assert!(result.is_err());
+
+    let result = runner.call("resume_contract", "unpauser.near", vec![]);
+    assert!(result.is_ok());
+
+    // The pauser role also allows pausing precompiles.
+    let args = PausePrecompilesCallArgs { paused_mask: 1 }
+        .try_to_vec()
+        .unwrap();
+    let result = runner.call("pause_precompiles", "pauser.near", args.clone());
+    assert!(result.is_ok());
+    let result = runner.call("resume_precompiles", "pauser.near", args.clone());
+    assert!(result.is_err());
+    let result = runner.call("resume_precompiles", "unpauser.near", args);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_revoke_role() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let args = role_args(Role::Pauser, "pauser.near");
+
+    let result = runner.call("revoke_role", &aurora_account_id, args.clone());
+    assert!(result.is_err());
+
+    let result = runner.call("grant_role", &aurora_account_id, args.clone());
+    assert!(result.is_ok());
+
+    let result = runner.call("revoke_role", "pauser.near", args.clone());
+    assert!(result.is_err());
+
+    let result = runner.call("revoke_role", &aurora_account_id, args);
+    assert!(result.is_ok());
+
+    let result = runner.call("pause_contract", "pauser.near", vec![]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_get_roles() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+
+    let result = runner.call(
+        "grant_role",
+        &aurora_account_id,
+        role_args(Role::TokenAdmin, "admin.near"),
+    );
+    assert!(result.is_ok());
+
+    let outcome = runner
+        .one_shot()
+        .call("get_roles", &aurora_account_id, vec![])
+        .unwrap();
+    let roles: serde_json::Value =
+        serde_json::from_slice(&outcome.return_data.as_value().unwrap()).unwrap();
+    assert_eq!(roles, serde_json::json!({ "token_admin": ["admin.near"] }));
+}
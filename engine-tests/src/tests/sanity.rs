@@ -6,6 +6,7 @@ use aurora_engine::fungible_token::FungibleTokenMetadata;
 use aurora_engine::parameters::{SetOwnerArgs, SetUpgradeDelayBlocksArgs, TransactionStatus};
 use aurora_engine_sdk as sdk;
 use aurora_engine_types::borsh::{BorshDeserialize, BorshSerialize};
+use aurora_engine_types::parameters::events::{EngineEvent, OwnerChange};
 use aurora_engine_types::H160;
 use evm::ExitFatal;
 use libsecp256k1::SecretKey;
@@ -1110,6 +1111,15 @@ fn test_set_owner() {
     // setting owner from the owner with same owner id should succeed
     assert!(result.is_ok());
 
+    // the change is announced with a NEP-297 event
+    assert_eq!(
+        runner.previous_events(),
+        vec![EngineEvent::SetOwner(OwnerChange {
+            old_owner: aurora_account_id.clone(),
+            new_owner: str_to_account_id("new_owner.near"),
+        })]
+    );
+
     // get owner to see if the owner_id property has changed
     let outcome = runner
         .one_shot()
@@ -0,0 +1,90 @@
+use crate::prelude::{Address, Wei};
+use crate::utils;
+use aurora_engine::engine::{EngineErrorKind, GasPaymentError};
+use aurora_engine::parameters::{AddressesArgs, SiloParams};
+
+const TRANSFER_AMOUNT: Wei = Wei::new_u64(10);
+
+fn set_silo_params(runner: &mut utils::AuroraRunner, params: &SiloParams) {
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let args = serde_json::to_vec(params).unwrap();
+    let result = runner.call("set_silo_params", &aurora_account_id, args);
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_underpriced_transaction_is_rejected() {
+    let (mut runner, mut signer, receiver) = crate::tests::sanity::initialize_transfer();
+    let params = SiloParams {
+        min_gas_price: 1,
+        ..Default::default()
+    };
+    set_silo_params(&mut runner, &params);
+
+    let result = runner
+        .one_shot()
+        .call("get_silo_params", "viewer", Vec::new())
+        .unwrap();
+    let actual_params: SiloParams =
+        serde_json::from_slice(&result.return_data.as_value().unwrap()).unwrap();
+    assert_eq!(actual_params, params);
+
+    utils::assert_submit_rejected(
+        &mut runner,
+        &mut signer,
+        |nonce| utils::transfer(receiver, TRANSFER_AMOUNT, nonce),
+        &EngineErrorKind::GasPayment(GasPaymentError::GasPriceTooLow),
+    );
+
+    let sender = utils::address_from_secret_key(&signer.secret_key);
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let args = serde_json::to_vec(&AddressesArgs {
+        addresses: vec![sender],
+    })
+    .unwrap();
+    let result = runner.call("add_fee_waived_senders", &aurora_account_id, args);
+    assert!(result.is_ok(), "{result:?}");
+    let result = runner
+        .one_shot()
+        .call("get_fee_waived_senders", "viewer", Vec::new())
+        .unwrap();
+    let senders: Vec<Address> =
+        serde_json::from_slice(&result.return_data.as_value().unwrap()).unwrap();
+    assert_eq!(senders, vec![sender]);
+
+    let result = runner.submit_with_signer(&mut signer, |nonce| {
+        utils::transfer(receiver, TRANSFER_AMOUNT, nonce)
+    });
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_invalid_silo_params_are_rejected() {
+    let mut runner = utils::deploy_runner();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let args = serde_json::to_vec(&SiloParams {
+        min_gas_price: 2,
+        fixed_gas_price: Some(1),
+        fee_recipient: None,
+    })
+    .unwrap();
+
+    let result = runner.call("set_silo_params", &aurora_account_id, args);
+    assert!(matches!(
+        result.unwrap_err().kind,
+        EngineErrorKind::EvmFatal(evm::ExitFatal::Other(e)) if e == "ERR_INVALID_SILO_PARAMS"
+    ));
+}
+
+#[test]
+fn test_set_silo_params_requires_silo_admin() {
+    let mut runner = utils::deploy_runner();
+    let args = serde_json::to_vec(&SiloParams {
+        fee_recipient: Some(Address::from_array([1; 20])),
+        ..Default::default()
+    })
+    .unwrap();
+
+    let result = runner.call("set_silo_params", "stranger.near", args);
+    assert!(result.is_err());
+}
@@ -1,5 +1,7 @@
 mod call_tracer;
 mod json_snapshot;
+mod near_lake;
+mod relayer_db;
 mod sanity;
 mod storage;
 mod sync;
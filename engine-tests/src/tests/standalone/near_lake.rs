@@ -0,0 +1,183 @@
+use aurora_engine_modexp::AuroraModExp;
+use aurora_engine_types::types::{Address, Wei};
+use aurora_engine_types::{H160, H256, U256};
+use engine_standalone_storage::near_lake::{self, IngestionIssue};
+use engine_standalone_storage::sync::types::TransactionKind;
+use near_primitives::hash::CryptoHash;
+use serde_json::json;
+
+use crate::utils::standalone::StandaloneRunner;
+
+#[test]
+fn test_ingest_near_lake_directory() {
+    let mut runner = StandaloneRunner::default();
+    runner.init_evm();
+
+    let caller = "alice.near";
+    let initial_balance = Wei::new_u64(800_000);
+    let transfer_amount = Wei::new_u64(115_321);
+    let caller_address = aurora_engine_sdk::types::near_account_to_evm_address(caller.as_bytes());
+    let recipient_address = Address::new(H160([1u8; 20]));
+    runner.mint_account(caller_address, initial_balance, U256::zero(), None);
+
+    let block_height = runner.env.block_height + 1;
+    let engine_account = runner.env.current_account_id.to_string();
+    let transfer = TransactionKind::Call(aurora_engine::parameters::CallArgs::V2(
+        aurora_engine::parameters::FunctionCallArgsV2 {
+            contract: recipient_address,
+            value: transfer_amount.to_bytes(),
+            input: Vec::new(),
+        },
+    ));
+
+    let lake_dir = tempfile::tempdir().unwrap();
+    // The name of the block directory doesn't have to be padded
+    let block_dir = lake_dir.path().join(block_height.to_string());
+    std::fs::create_dir(&block_dir).unwrap();
+    let block = json!({
+        "author": "validator.near",
+        "header": {
+            "height": block_height,
+            "hash": encode_hash(0xb1),
+            "timestamp": 1_000_000_000_u64,
+            "random_value": encode_hash(0x02),
+        },
+        "chunks": [{ "shard_id": 0 }, { "shard_id": 1 }],
+    });
+    std::fs::write(block_dir.join("block.json"), block.to_string()).unwrap();
+    // A batched receipt with two transfers
+    let mut batch = receipt_outcome(
+        &engine_account,
+        0x16,
+        "call",
+        &transfer.raw_bytes(),
+        &[],
+        true,
+    );
+    let actions = batch["receipt"]["receipt"]["Action"]["actions"]
+        .as_array_mut()
+        .unwrap();
+    actions.push(actions[0].clone());
+    let shard = json!({
+        "shard_id": 0,
+        "chunk": { "receipts": [] },
+        "receipt_execution_outcomes": [
+            receipt_outcome(&engine_account, 0x11, "call", &transfer.raw_bytes(), &[], true),
+            // The proof verification result is not available
+            receipt_outcome(&engine_account, 0x12, "finish_deposit", &[], &[0x99], true),
+            receipt_outcome(&engine_account, 0x13, "not_an_engine_method", &[], &[], true),
+            // Failed receipts are not reported
+            receipt_outcome(&engine_account, 0x14, "submit", &[0xff], &[], false),
+            receipt_outcome("other.near", 0x15, "submit", &[0xff], &[], true),
+            batch,
+        ],
+    });
+    std::fs::write(block_dir.join("shard_0.json"), shard.to_string()).unwrap();
+
+    let report =
+        near_lake::ingest_directory::<AuroraModExp, _>(&mut runner.storage, lake_dir.path())
+            .unwrap();
+
+    assert_eq!(report.blocks, 1);
+    assert_eq!(report.transactions, 3);
+    assert_eq!(report.issues.len(), 3);
+    assert_eq!(
+        report.issues[0],
+        IngestionIssue::MissingShard {
+            block_height,
+            shard_id: 1
+        }
+    );
+    assert_eq!(
+        report.issues[1],
+        IngestionIssue::MissingPromiseData {
+            block_height,
+            receipt_id: H256([0x12; 32]),
+            data_id: H256([0x99; 32]),
+        }
+    );
+    assert!(matches!(
+        report.issues[2],
+        IngestionIssue::Unparseable {
+            receipt_id,
+            ..
+        } if receipt_id == H256([0x13; 32])
+    ));
+
+    let block_hash = H256([0xb1; 32]);
+    assert_eq!(
+        runner
+            .storage
+            .get_block_hash_by_height(block_height)
+            .unwrap(),
+        block_hash
+    );
+    let metadata = runner.storage.get_block_metadata(block_hash).unwrap();
+    assert_eq!(metadata.random_seed, H256([0x02; 32]));
+    assert_eq!(metadata.timestamp.nanos(), 1_000_000_000);
+
+    // The actions of the batched receipt are recorded as distinct transactions
+    let batch_id = H256([0x16; 32]);
+    let second_action_id = near_lake::action_receipt_id(batch_id, 1);
+    assert_ne!(second_action_id, batch_id);
+    for (id, position) in [(batch_id, 1), (second_action_id, 2)] {
+        let message = runner.storage.get_transaction_data(id).unwrap();
+        assert_eq!(message.near_receipt_id, id);
+        assert_eq!(message.position, position);
+    }
+
+    runner.env.block_height = block_height;
+    let total_amount = Wei::new_u64(3 * 115_321);
+    assert_eq!(runner.get_balance(&recipient_address), total_amount);
+    assert_eq!(
+        runner.get_balance(&caller_address),
+        initial_balance - total_amount
+    );
+    assert_eq!(runner.get_nonce(&caller_address), U256::from(3));
+
+    runner.close();
+}
+
+fn receipt_outcome(
+    receiver_id: &str,
+    receipt_id: u8,
+    method_name: &str,
+    args: &[u8],
+    input_data_ids: &[u8],
+    succeeded: bool,
+) -> serde_json::Value {
+    let status = if succeeded {
+        json!({ "SuccessValue": "" })
+    } else {
+        json!({ "Failure": { "ActionError": {} } })
+    };
+    json!({
+        "receipt": {
+            "predecessor_id": "alice.near",
+            "receiver_id": receiver_id,
+            "receipt_id": encode_hash(receipt_id),
+            "receipt": {
+                "Action": {
+                    "signer_id": "alice.near",
+                    "signer_public_key": "ed25519:DcA2MzgpJbrUATQLLceocVckhhAqrkingax4oJ9kZ847",
+                    "input_data_ids": input_data_ids.iter().copied().map(encode_hash).collect::<Vec<_>>(),
+                    "actions": [
+                        {
+                            "FunctionCall": {
+                                "method_name": method_name,
+                                "args": aurora_engine_sdk::base64::encode(args),
+                                "gas": 300_000_000_000_000_u64,
+                                "deposit": "0",
+                            }
+                        }
+                    ],
+                }
+            },
+        },
+        "execution_outcome": { "outcome": { "logs": [], "status": status } },
+    })
+}
+
+fn encode_hash(byte: u8) -> String {
+    CryptoHash([byte; 32]).to_string()
+}
@@ -0,0 +1,141 @@
+use aurora_engine_types::types::{Address, Wei};
+use aurora_engine_types::{H160, H256, U256};
+use engine_standalone_storage::relayer_db::{
+    self,
+    types::{BlockRow, TransactionRow},
+    MemoryRowSource,
+};
+use std::num::NonZeroUsize;
+
+use crate::utils::{self, standalone::StandaloneRunner};
+
+const TRANSFER_GAS_USED: u64 = 21_000;
+
+#[test]
+fn test_resumable_relayer_db_import() {
+    let mut runner = StandaloneRunner::default();
+    runner.init_evm();
+
+    let mut signer = utils::Signer::random();
+    let initial_balance = Wei::new_u64(800_000);
+    let transfer_amount = Wei::new_u64(115_321);
+    let signer_address = utils::address_from_secret_key(&signer.secret_key);
+    let recipient_address = Address::new(H160([1u8; 20]));
+    runner.mint_account(signer_address, initial_balance, signer.nonce.into(), None);
+
+    let engine_state = runner
+        .storage
+        .with_engine_access(runner.env.block_height + 1, 0, &[], |io| {
+            aurora_engine::state::get_state(&io)
+        })
+        .result
+        .unwrap();
+    let first_height = runner.env.block_height + 1;
+    let blocks: Vec<BlockRow> = (first_height..first_height + 3).map(block_row).collect();
+    let mut transfer = |block: u64, gas_used: u64| {
+        let transaction = utils::transfer(
+            recipient_address,
+            transfer_amount,
+            signer.use_nonce().into(),
+        );
+        let signed =
+            utils::sign_transaction(transaction, Some(runner.chain_id), &signer.secret_key);
+        TransactionRow {
+            block,
+            block_hash: block_hash(block),
+            index: 0,
+            id: block,
+            hash: H256::zero(),
+            near_hash: H256([u8::try_from(block % 256).unwrap(); 32]),
+            near_receipt_hash: H256::zero(),
+            from: signer_address,
+            to: signed.transaction.to,
+            nonce: signed.transaction.nonce,
+            gas_price: signed.transaction.gas_price,
+            gas_limit: signed.transaction.gas_limit,
+            gas_used,
+            value: signed.transaction.value,
+            input: signed.transaction.data,
+            v: signed.v,
+            r: signed.r,
+            s: signed.s,
+            status: true,
+            output: Vec::new(),
+        }
+    };
+    let transactions = vec![
+        transfer(first_height, TRANSFER_GAS_USED),
+        // The gas used recorded by the relayer is wrong
+        transfer(first_height + 2, 1),
+    ];
+    let page_size = NonZeroUsize::new(2).unwrap();
+
+    // Only the first block is available at first
+    let mut source = MemoryRowSource::new(blocks[..1].to_vec(), transactions[..1].to_vec());
+    let report =
+        relayer_db::import(&mut runner.storage, &mut source, &engine_state, page_size).unwrap();
+    assert_eq!(report.blocks, 1);
+    assert_eq!(report.transactions, 1);
+    assert_eq!(report.last_block_height, Some(first_height));
+    assert!(report.mismatches.is_empty());
+    assert_eq!(
+        runner.storage.get_relayer_import_checkpoint().unwrap(),
+        Some(first_height)
+    );
+    let metadata = runner
+        .storage
+        .get_block_metadata(block_hash(first_height))
+        .unwrap();
+    assert_eq!(metadata.random_seed, H256([0x5e; 32]));
+
+    // The import resumes after the first block
+    let mut source = MemoryRowSource::new(blocks, transactions);
+    let report =
+        relayer_db::import(&mut runner.storage, &mut source, &engine_state, page_size).unwrap();
+    assert_eq!(report.blocks, 2);
+    assert_eq!(report.transactions, 1);
+    assert_eq!(report.last_block_height, Some(first_height + 2));
+    assert_eq!(report.mismatches.len(), 1);
+    let mismatch = &report.mismatches[0];
+    assert_eq!(mismatch.block_height, first_height + 2);
+    assert!(mismatch.status);
+    assert_eq!(mismatch.gas_used, Some(TRANSFER_GAS_USED));
+    assert_eq!(mismatch.expected_gas_used, 1);
+
+    // Nothing left to import
+    let report =
+        relayer_db::import(&mut runner.storage, &mut source, &engine_state, page_size).unwrap();
+    assert_eq!(report.blocks, 0);
+    assert_eq!(report.last_block_height, None);
+
+    runner.env.block_height = first_height + 2;
+    assert_eq!(
+        runner.get_balance(&recipient_address),
+        Wei::new_u64(2 * 115_321)
+    );
+    assert_eq!(runner.get_nonce(&signer_address), U256::from(2));
+
+    runner.close();
+}
+
+fn block_hash(height: u64) -> H256 {
+    utils::standalone::mocks::compute_block_hash(height)
+}
+
+fn block_row(height: u64) -> BlockRow {
+    BlockRow {
+        chain: 1_313_161_554,
+        id: height,
+        hash: block_hash(height),
+        near_hash: None,
+        timestamp: Some(height * 1_000_000_000),
+        size: 0,
+        gas_limit: U256::zero(),
+        gas_used: U256::zero(),
+        parent_hash: block_hash(height - 1),
+        transactions_root: H256::zero(),
+        state_root: H256::zero(),
+        receipts_root: H256::zero(),
+        random_seed: Some(H256([0x5e; 32])),
+    }
+}
@@ -21,12 +21,16 @@ fn test_deploy_code() {
         upgrade_delay_blocks: 0,
         is_paused: false,
         key_manager: None,
+        roles: Default::default(),
+        is_deploy_allowlist_enabled: false,
+        silo_params: Default::default(),
     };
     let origin = Address::new(H160([0u8; 20]));
     let storage = RefCell::new(Storage::default());
     let io = StoragePointer(&storage);
     let env = aurora_engine_sdk::env::Fixed {
         signer_account_id: owner_id.clone(),
+        signer_account_pk: None,
         current_account_id: owner_id.clone(),
         predecessor_account_id: owner_id.clone(),
         block_height: 0,
@@ -1,9 +1,14 @@
 use aurora_engine_sdk::env::Timestamp;
+use aurora_engine_types::borsh::BorshSerialize;
+use aurora_engine_types::parameters::engine::WhitelistKind;
+use aurora_engine_types::storage::{address_to_key, bytes_to_key, storage_to_key, KeyPrefix};
 use aurora_engine_types::types::{Address, Wei};
 use aurora_engine_types::{H256, U256};
 use engine_standalone_storage::{
+    account_history::{CodeChange, StorageChange},
+    state_change::{self, AddressListKind, EngineChange},
     sync::types::{TransactionKind, TransactionMessage},
-    BlockMetadata,
+    BlockMetadata, Diff, DiffValue,
 };
 
 use crate::utils::standalone::{mocks, storage::create_db};
@@ -268,11 +273,13 @@ fn test_transaction_index() {
         position: tx_position,
         succeeded: true,
         signer: "placeholder.near".parse().unwrap(),
+        signer_public_key: None,
         caller: "placeholder.near".parse().unwrap(),
         attached_near: 0,
         transaction: TransactionKind::Unknown,
         promise_data: Vec::new(),
         raw_input: Vec::new(),
+        on_chain_outcome: None,
     };
     let tx_included = engine_standalone_storage::TransactionIncluded {
         block_hash,
@@ -393,3 +400,276 @@ fn test_track_key() {
 
     runner.close();
 }
+
+#[test]
+fn test_account_history() {
+    let mut signer = Signer::random();
+    let signer_address = utils::address_from_secret_key(&signer.secret_key);
+    let initial_balance = Wei::new_u64(1000);
+    let transfer_amount = Wei::new_u64(37);
+    let dest = Address::from_array([0x11; 20]);
+    let contract = Address::from_array([0x22; 20]);
+    let slot = H256([0x33; 32]);
+    let mut runner = utils::standalone::StandaloneRunner::default();
+
+    runner.init_evm();
+    runner.mint_account(signer_address, initial_balance, signer.nonce.into(), None);
+    let created_block_height = runner.env.block_height;
+    let result = runner
+        .transfer_with_signer(&mut signer, transfer_amount, dest)
+        .unwrap();
+    assert!(result.status.is_ok());
+    let transfer_block_height = runner.env.block_height;
+
+    let balance_history = runner
+        .storage
+        .balance_history(&signer_address, 0, transfer_block_height)
+        .unwrap();
+    assert_eq!(balance_history.len(), 2);
+    assert_eq!(balance_history[0].block_height, created_block_height);
+    assert_eq!(balance_history[0].value, initial_balance);
+    assert_eq!(balance_history[1].block_height, transfer_block_height);
+    assert_eq!(balance_history[1].value, initial_balance - transfer_amount);
+    let transfer_hash = runner
+        .storage
+        .get_transaction_by_position(engine_standalone_storage::TransactionIncluded {
+            block_hash: runner
+                .storage
+                .get_block_hash_by_height(transfer_block_height)
+                .unwrap(),
+            position: 0,
+        })
+        .unwrap();
+    assert_eq!(balance_history[1].tx_hash, transfer_hash);
+    // The history is paginated by height
+    let balance_history = runner
+        .storage
+        .balance_history(&signer_address, transfer_block_height, u64::MAX)
+        .unwrap();
+    assert_eq!(balance_history.len(), 1);
+    assert_eq!(balance_history[0].tx_hash, transfer_hash);
+    let nonce_history = runner
+        .storage
+        .nonce_history(
+            &signer_address,
+            transfer_block_height,
+            transfer_block_height,
+        )
+        .unwrap();
+    assert_eq!(nonce_history.len(), 1);
+    assert_eq!(nonce_history[0].value, U256::one());
+
+    // Deploy a contract, write a slot, then destroy the contract and write the slot again
+    let code_key = address_to_key(KeyPrefix::Code, &contract);
+    let generation_key = address_to_key(KeyPrefix::Generation, &contract);
+    let mut diffs = vec![Diff::default(), Diff::default(), Diff::default()];
+    diffs[0].modify(code_key.to_vec(), vec![0xfe]);
+    diffs[0].modify(
+        storage_to_key(&contract, &slot, 0).as_ref().to_vec(),
+        vec![1; 32],
+    );
+    diffs[1].delete(code_key.to_vec());
+    diffs[1].modify(generation_key.to_vec(), 1u32.to_be_bytes().to_vec());
+    diffs[2].modify(
+        storage_to_key(&contract, &slot, 1).as_ref().to_vec(),
+        vec![2; 32],
+    );
+    let first_height = runner.env.block_height + 1;
+    for (i, diff) in diffs.into_iter().enumerate() {
+        runner.env.block_height += 1;
+        mocks::insert_block(&mut runner.storage, runner.env.block_height);
+        let tx_hash = H256([u8::try_from(i).unwrap() + 1; 32]);
+        let message = TransactionMessage {
+            block_hash: mocks::compute_block_hash(runner.env.block_height),
+            near_receipt_id: tx_hash,
+            position: 0,
+            succeeded: true,
+            signer: runner.env.signer_account_id(),
+            signer_public_key: None,
+            caller: runner.env.predecessor_account_id(),
+            attached_near: 0,
+            transaction: TransactionKind::Unknown,
+            promise_data: Vec::new(),
+            raw_input: Vec::new(),
+            on_chain_outcome: None,
+        };
+        runner
+            .storage
+            .set_transaction_included(tx_hash, &message, &diff)
+            .unwrap();
+    }
+
+    let code_history = runner
+        .storage
+        .code_history(&contract, first_height, u64::MAX)
+        .unwrap();
+    let code_changes: Vec<_> = code_history
+        .iter()
+        .map(|entry| (entry.block_height, entry.value.clone()))
+        .collect();
+    assert_eq!(
+        code_changes,
+        vec![
+            (first_height, CodeChange::Deployed(vec![0xfe])),
+            (first_height + 1, CodeChange::Removed),
+            (first_height + 1, CodeChange::GenerationBumped(1)),
+        ]
+    );
+    assert_eq!(code_history[1].tx_hash, H256([2; 32]));
+    assert_eq!(code_history[2].tx_hash, H256([2; 32]));
+
+    let storage_history = runner
+        .storage
+        .storage_history(&contract, &slot, 0, u64::MAX)
+        .unwrap();
+    let storage_changes: Vec<_> = storage_history
+        .into_iter()
+        .map(|entry| (entry.block_height, entry.value))
+        .collect();
+    assert_eq!(
+        storage_changes,
+        vec![
+            (
+                first_height,
+                StorageChange {
+                    generation: 0,
+                    value: H256([1; 32])
+                }
+            ),
+            (
+                first_height + 1,
+                StorageChange {
+                    generation: 1,
+                    value: H256::zero()
+                }
+            ),
+            (
+                first_height + 2,
+                StorageChange {
+                    generation: 1,
+                    value: H256([2; 32])
+                }
+            ),
+        ]
+    );
+
+    runner.close();
+}
+
+#[test]
+fn test_transaction_changes() {
+    let mut signer = Signer::random();
+    let signer_address = utils::address_from_secret_key(&signer.secret_key);
+    let initial_balance = Wei::new_u64(1000);
+    let transfer_amount = Wei::new_u64(37);
+    let dest = Address::from_array([0x11; 20]);
+    let mut runner = utils::standalone::StandaloneRunner::default();
+
+    runner.init_evm();
+    runner.mint_account(signer_address, initial_balance, signer.nonce.into(), None);
+    let result = runner
+        .transfer_with_signer(&mut signer, transfer_amount, dest)
+        .unwrap();
+    assert!(result.status.is_ok());
+
+    let tx_included = engine_standalone_storage::TransactionIncluded {
+        block_hash: runner
+            .storage
+            .get_block_hash_by_height(runner.env.block_height)
+            .unwrap(),
+        position: 0,
+    };
+    let changes = runner.storage.get_transaction_changes(tx_included).unwrap();
+    let signer_balance = changes
+        .iter()
+        .find(|change| {
+            change.change
+                == EngineChange::Balance {
+                    address: signer_address,
+                    balance: initial_balance - transfer_amount,
+                }
+        })
+        .unwrap();
+    assert_eq!(
+        signer_balance.old_value,
+        Some(DiffValue::Modified(initial_balance.to_bytes().to_vec()))
+    );
+    let dest_balance = changes
+        .iter()
+        .find(|change| {
+            change.change
+                == EngineChange::Balance {
+                    address: dest,
+                    balance: transfer_amount,
+                }
+        })
+        .unwrap();
+    assert_eq!(dest_balance.old_value, Some(DiffValue::Deleted));
+    assert!(changes.iter().any(|change| change.change
+        == EngineChange::Nonce {
+            address: signer_address,
+            nonce: U256::one(),
+        }));
+
+    // Keys which are not understood are kept
+    let contract = Address::from_array([0x22; 20]);
+    let slot = H256([0x33; 32]);
+    let mut diff = Diff::default();
+    diff.modify(
+        storage_to_key(&contract, &slot, 3).as_ref().to_vec(),
+        vec![1; 32],
+    );
+    diff.modify(vec![0xff, 0xff], vec![1]);
+    let changes = state_change::decode_diff(&diff);
+    assert_eq!(changes.len(), 2);
+    assert_eq!(
+        changes[0].change,
+        EngineChange::Storage {
+            address: contract,
+            generation: 3,
+            slot,
+            value: H256([1; 32]),
+        }
+    );
+    assert_eq!(changes[0].old_value, None);
+    assert_eq!(changes[1].change, EngineChange::Unknown);
+
+    // The keys of the lists of the engine are decoded, the ones with an unknown prefix are kept
+    let denied = Address::from_array([0x44; 20]);
+    let mut diff = Diff::default();
+    diff.modify(
+        bytes_to_key(KeyPrefix::Denylist, denied.as_bytes()),
+        vec![1],
+    );
+    diff.modify(
+        bytes_to_key(KeyPrefix::Denylist, b"LIST"),
+        vec![denied].try_to_vec().unwrap(),
+    );
+    diff.delete(bytes_to_key(KeyPrefix::Whitelist, b"\x01STATUS"));
+    diff.modify(vec![0x07, 0xff, 0x01], vec![1]);
+    let changes: Vec<EngineChange> = state_change::decode_diff(&diff)
+        .into_iter()
+        .map(|change| change.change)
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            EngineChange::AddressListEntry {
+                list: AddressListKind::Denylist,
+                address: denied,
+                added: true,
+            },
+            EngineChange::AddressListEntries {
+                list: AddressListKind::Denylist,
+                addresses: vec![denied],
+            },
+            EngineChange::WhitelistStatus {
+                kind: WhitelistKind::Account,
+                enabled: false,
+            },
+            EngineChange::Unknown,
+        ]
+    );
+
+    runner.close();
+}
@@ -1,9 +1,16 @@
 use aurora_engine::deposit_event::TokenMessageData;
+use aurora_engine::parameters::{
+    RelayerKeyArgsV2, RelayerKeyManagerArgs, SubmitResult, TransactionStatus,
+};
 use aurora_engine_modexp::AuroraModExp;
 use aurora_engine_sdk::env::{Env, Timestamp};
 use aurora_engine_types::borsh::{BorshDeserialize, BorshSerialize};
+use aurora_engine_types::public_key::PublicKey;
+use aurora_engine_types::types::PromiseResult;
 use aurora_engine_types::types::{Address, Balance, Fee, NEP141Wei, Wei};
 use aurora_engine_types::{account_id::AccountId, H160, H256, U256};
+use engine_standalone_storage::divergence::Divergence;
+use engine_standalone_storage::promise_emulator::{self, PromiseEmulator};
 use engine_standalone_storage::sync;
 
 use crate::utils::solidity::erc20::{ERC20Constructor, ERC20};
@@ -54,11 +61,13 @@ fn test_consume_deposit_message() {
         position: 0,
         succeeded: true,
         signer: runner.env.signer_account_id(),
+        signer_public_key: None,
         caller: runner.env.predecessor_account_id(),
         attached_near: 0,
         transaction: tx_kind,
         promise_data: Vec::new(),
         raw_input,
+        on_chain_outcome: None,
     };
 
     let outcome = sync::consume_message::<AuroraModExp>(
@@ -90,6 +99,7 @@ fn test_consume_deposit_message() {
         position: 1,
         succeeded: true,
         signer: runner.env.signer_account_id(),
+        signer_public_key: None,
         caller: runner.env.predecessor_account_id(),
         attached_near: 0,
         transaction: tx_kind,
@@ -97,6 +107,7 @@ fn test_consume_deposit_message() {
         // (which is `true` because the proof is valid in this case).
         promise_data: vec![Some(true.try_to_vec().unwrap())],
         raw_input,
+        on_chain_outcome: None,
     };
 
     let outcome = sync::consume_message::<AuroraModExp>(
@@ -126,11 +137,13 @@ fn test_consume_deposit_message() {
         position: 2,
         succeeded: true,
         signer: runner.env.signer_account_id(),
+        signer_public_key: None,
         caller: runner.env.predecessor_account_id(),
         attached_near: 0,
         transaction: tx_kind,
         promise_data: Vec::new(),
         raw_input,
+        on_chain_outcome: None,
     };
 
     let outcome = sync::consume_message::<AuroraModExp>(
@@ -145,6 +158,94 @@ fn test_consume_deposit_message() {
     runner.close();
 }
 
+#[test]
+fn test_emulate_deposit_promises() {
+    let (runner, block_message) = initialize();
+
+    let recipient_address = Address::new(H160([22u8; 20]));
+    let deposit_amount = Wei::new_u64(123_456_789);
+    let proof = mock_proof(recipient_address, deposit_amount);
+    let tx_kind = sync::types::TransactionKind::Deposit(proof.try_to_vec().unwrap());
+    let raw_input = tx_kind.raw_bytes();
+
+    let transaction_message = sync::types::TransactionMessage {
+        block_hash: block_message.hash,
+        near_receipt_id: H256([0x11; 32]),
+        position: 0,
+        succeeded: true,
+        signer: runner.env.signer_account_id(),
+        signer_public_key: None,
+        caller: runner.env.predecessor_account_id(),
+        attached_near: 0,
+        transaction: tx_kind,
+        promise_data: Vec::new(),
+        raw_input,
+        on_chain_outcome: None,
+    };
+
+    let mut emulator = PromiseEmulator::new(
+        &runner.storage,
+        block_message.height,
+        block_message.metadata.clone(),
+    )
+    .unwrap();
+    // The proof is valid in this case
+    emulator.register_responder("prover.near".parse().unwrap(), "verify_log_entry", |_| {
+        PromiseResult::Successful(true.try_to_vec().unwrap())
+    });
+    let result = emulator
+        .execute::<AuroraModExp>(&transaction_message)
+        .unwrap();
+    // The result of `deposit` is the one of `ft_resolve_transfer`, i.e. the used amount
+    assert_eq!(
+        result,
+        PromiseResult::Successful(format!("\"{}\"", deposit_amount.raw()).into_bytes())
+    );
+
+    let methods: Vec<_> = emulator
+        .receipts()
+        .iter()
+        .map(|receipt| receipt.method.as_str())
+        .collect();
+    assert_eq!(
+        methods,
+        [
+            "verify_log_entry",
+            "ft_on_transfer",
+            "ft_resolve_transfer",
+            "finish_deposit",
+            "deposit"
+        ]
+    );
+
+    // The deposit is only visible in the emulated state, the storage is not modified
+    let emulated_balance = runner
+        .storage
+        .with_engine_access_over_diff(block_message.height + 1, 0, &[], emulator.diff(), |io| {
+            aurora_engine::engine::get_balance(&io, &recipient_address)
+        })
+        .result;
+    assert_eq!(emulated_balance, deposit_amount);
+    assert_eq!(runner.get_balance(&recipient_address), Wei::zero());
+
+    // External calls without a responder are reported
+    let mut emulator = PromiseEmulator::new(
+        &runner.storage,
+        block_message.height,
+        block_message.metadata.clone(),
+    )
+    .unwrap();
+    let error = emulator
+        .execute::<AuroraModExp>(&transaction_message)
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        promise_emulator::Error::MissingResponder { method, .. } if method == "verify_log_entry"
+    ));
+
+    runner.close();
+}
+
 #[test]
 fn test_consume_deploy_message() {
     let (mut runner, block_message) = initialize();
@@ -160,11 +261,13 @@ fn test_consume_deploy_message() {
         position: 0,
         succeeded: true,
         signer: runner.env.signer_account_id(),
+        signer_public_key: None,
         caller: runner.env.predecessor_account_id(),
         attached_near: 0,
         transaction: tx_kind,
         promise_data: Vec::new(),
         raw_input,
+        on_chain_outcome: None,
     };
 
     let outcome = sync::consume_message::<AuroraModExp>(
@@ -216,11 +319,13 @@ fn test_consume_deploy_erc20_message() {
         position: 0,
         succeeded: true,
         signer: runner.env.signer_account_id(),
+        signer_public_key: None,
         caller: runner.env.predecessor_account_id(),
         attached_near: 0,
         transaction: tx_kind,
         promise_data: Vec::new(),
         raw_input,
+        on_chain_outcome: None,
     };
 
     // Deploy ERC-20 (this would be the flow for bridging a new NEP-141 to Aurora)
@@ -258,11 +363,13 @@ fn test_consume_deploy_erc20_message() {
         position: 0,
         succeeded: true,
         signer: runner.env.signer_account_id(),
+        signer_public_key: None,
         caller: runner.env.predecessor_account_id(),
         attached_near: 0,
         transaction: tx_kind,
         promise_data: Vec::new(),
         raw_input,
+        on_chain_outcome: None,
     };
 
     // Mint new tokens (via ft_on_transfer flow, same as the bridge)
@@ -319,11 +426,13 @@ fn test_consume_ft_on_transfer_message() {
         position: 0,
         succeeded: true,
         signer: runner.env.signer_account_id(),
+        signer_public_key: None,
         caller: runner.env.predecessor_account_id(),
         attached_near: 0,
         transaction: tx_kind,
         promise_data: Vec::new(),
         raw_input,
+        on_chain_outcome: None,
     };
 
     let outcome = sync::consume_message::<AuroraModExp>(
@@ -367,11 +476,13 @@ fn test_consume_call_message() {
         position: 0,
         succeeded: true,
         signer: runner.env.signer_account_id(),
+        signer_public_key: None,
         caller: runner.env.predecessor_account_id(),
         attached_near: 0,
         transaction: tx_kind,
         promise_data: Vec::new(),
         raw_input,
+        on_chain_outcome: None,
     };
 
     let outcome = sync::consume_message::<AuroraModExp>(
@@ -389,6 +500,245 @@ fn test_consume_call_message() {
     assert_eq!(runner.get_nonce(&caller_address), U256::one());
 }
 
+#[test]
+fn test_consume_block() {
+    let (mut runner, _) = initialize();
+
+    let caller = "some_account.near";
+    let initial_balance = Wei::new_u64(800_000);
+    let transfer_amount = Wei::new_u64(115_321);
+    let caller_address = aurora_engine_sdk::types::near_account_to_evm_address(caller.as_bytes());
+    let recipient_address = Address::new(H160([1u8; 20]));
+    runner.mint_account(caller_address, initial_balance, U256::zero(), None);
+
+    runner.env.block_height += 1;
+    runner.env.signer_account_id = caller.parse().unwrap();
+    runner.env.predecessor_account_id = caller.parse().unwrap();
+    let block_message = sync::types::BlockMessage {
+        height: runner.env.block_height,
+        hash: utils::standalone::mocks::compute_block_hash(runner.env.block_height),
+        metadata: engine_standalone_storage::BlockMetadata {
+            timestamp: runner.env.block_timestamp,
+            random_seed: runner.env.random_seed,
+        },
+    };
+
+    let signer = runner.env.signer_account_id();
+    let predecessor = runner.env.predecessor_account_id();
+    let transfer_message = |position: u16| {
+        let tx_kind = sync::types::TransactionKind::Call(simple_transfer_args(
+            recipient_address,
+            transfer_amount,
+        ));
+        let raw_input = tx_kind.raw_bytes();
+        sync::types::TransactionMessage {
+            block_hash: block_message.hash,
+            near_receipt_id: H256([u8::try_from(position).unwrap(); 32]),
+            position,
+            succeeded: true,
+            signer: signer.clone(),
+            signer_public_key: None,
+            caller: predecessor.clone(),
+            attached_near: 0,
+            transaction: tx_kind,
+            promise_data: Vec::new(),
+            raw_input,
+            on_chain_outcome: None,
+        }
+    };
+
+    let on_chain_outcome = |logs: Vec<String>| sync::types::OnChainOutcome {
+        output: Some(Vec::new()),
+        logs,
+    };
+
+    // The Engine cannot parse the input of the second transaction, so the whole block is rolled
+    // back. Only the divergence with the on-chain outcome is recorded.
+    let mut invalid_message = transfer_message(1);
+    invalid_message.raw_input = vec![0xff];
+    invalid_message.on_chain_outcome = Some(on_chain_outcome(Vec::new()));
+    let outcome = sync::consume_block::<AuroraModExp>(
+        &mut runner.storage,
+        &block_message,
+        vec![transfer_message(0), invalid_message],
+    )
+    .unwrap();
+    match outcome {
+        sync::ConsumeBlockOutcome::RolledBack(outcome) => assert_eq!(outcome.info.position, 1),
+        other => panic!("Unexpected outcome {other:?}"),
+    }
+    assert!(runner
+        .storage
+        .get_block_height_by_hash(block_message.hash)
+        .is_err());
+    assert_eq!(runner.get_balance(&recipient_address), Wei::zero());
+    assert_eq!(runner.get_nonce(&caller_address), U256::zero());
+    let records = runner
+        .storage
+        .get_divergence_records(block_message.height, block_message.height)
+        .unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].transaction_position, 1);
+    assert_eq!(
+        records[0].divergences,
+        vec![Divergence::Status {
+            on_chain: true,
+            local: false,
+        }]
+    );
+
+    // The second transaction sees the changes of the first one. The third one failed on NEAR,
+    // so it is not committed, but the divergence of its local execution is recorded.
+    let mut failed_message = transfer_message(2);
+    failed_message.succeeded = false;
+    failed_message.on_chain_outcome =
+        Some(on_chain_outcome(vec!["Exceeded the prepaid gas.".into()]));
+    let outcome = sync::consume_block::<AuroraModExp>(
+        &mut runner.storage,
+        &block_message,
+        vec![transfer_message(0), transfer_message(1), failed_message],
+    )
+    .unwrap();
+    let outcomes = match outcome {
+        sync::ConsumeBlockOutcome::Committed(outcomes) => outcomes,
+        other => panic!("Unexpected outcome {other:?}"),
+    };
+    assert_eq!(outcomes.len(), 3);
+    assert!(!outcomes[2].is_included());
+    let records = runner
+        .storage
+        .get_divergence_records(block_message.height, block_message.height)
+        .unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].transaction_position, 2);
+    assert_eq!(
+        records[1].divergences,
+        vec![Divergence::Status {
+            on_chain: false,
+            local: true,
+        }]
+    );
+    for outcome in outcomes
+        .into_iter()
+        .filter(sync::TransactionIncludedOutcome::is_included)
+    {
+        let diff = runner
+            .storage
+            .get_transaction_diff(engine_standalone_storage::TransactionIncluded {
+                block_hash: block_message.hash,
+                position: outcome.info.position,
+            })
+            .unwrap();
+        assert_eq!(diff, outcome.diff);
+    }
+
+    assert_eq!(
+        runner.get_balance(&recipient_address),
+        Wei::new_u64(2 * 115_321)
+    );
+    assert_eq!(
+        runner.get_balance(&caller_address),
+        initial_balance - Wei::new_u64(2 * 115_321)
+    );
+    assert_eq!(runner.get_nonce(&caller_address), U256::from(2));
+}
+
+#[test]
+fn test_parallel_block_execution() {
+    // The block is executed in parallel by the first runner and sequentially by the second one.
+    let (mut runner, _) = initialize();
+    let (mut sequential_runner, _) = initialize();
+
+    let initial_balance = Wei::new_u64(800_000);
+    let transfer_amount = Wei::new_u64(115_321);
+    let callers = ["alice.near", "bob.near"];
+    for caller in callers {
+        let address = aurora_engine_sdk::types::near_account_to_evm_address(caller.as_bytes());
+        runner.mint_account(address, initial_balance, U256::zero(), None);
+        sequential_runner.mint_account(address, initial_balance, U256::zero(), None);
+    }
+
+    runner.env.block_height += 1;
+    let block_message = sync::types::BlockMessage {
+        height: runner.env.block_height,
+        hash: utils::standalone::mocks::compute_block_hash(runner.env.block_height),
+        metadata: engine_standalone_storage::BlockMetadata {
+            timestamp: runner.env.block_timestamp,
+            random_seed: runner.env.random_seed,
+        },
+    };
+
+    // Alice sends two transfers, which conflict with each other, and Bob sends one.
+    let transactions: Vec<_> = ["alice.near", "bob.near", "alice.near"]
+        .into_iter()
+        .enumerate()
+        .map(|(position, caller)| {
+            let position = u16::try_from(position).unwrap();
+            let recipient_address = Address::new(H160([u8::try_from(position).unwrap() + 1; 20]));
+            let tx_kind = sync::types::TransactionKind::Call(simple_transfer_args(
+                recipient_address,
+                transfer_amount,
+            ));
+            let raw_input = tx_kind.raw_bytes();
+            sync::types::TransactionMessage {
+                block_hash: block_message.hash,
+                near_receipt_id: H256([u8::try_from(position).unwrap(); 32]),
+                position,
+                succeeded: true,
+                signer: caller.parse().unwrap(),
+                signer_public_key: None,
+                caller: caller.parse().unwrap(),
+                attached_near: 0,
+                transaction: tx_kind,
+                promise_data: Vec::new(),
+                raw_input,
+                on_chain_outcome: None,
+            }
+        })
+        .collect();
+
+    let parallel_outcome = sync::parallel::execute_block::<AuroraModExp>(
+        &runner.storage,
+        &block_message,
+        transactions.clone(),
+        std::num::NonZeroUsize::new(3).unwrap(),
+    )
+    .unwrap();
+    // Only the second transfer of Alice is executed again.
+    assert_eq!(parallel_outcome.re_executed, 1);
+    parallel_outcome
+        .commit(&mut runner.storage, &block_message)
+        .unwrap();
+
+    // The outcomes are the same as the ones of the sequential execution
+    let sequential_outcomes = match sync::consume_block::<AuroraModExp>(
+        &mut sequential_runner.storage,
+        &block_message,
+        transactions,
+    )
+    .unwrap()
+    {
+        sync::ConsumeBlockOutcome::Committed(outcomes) => outcomes,
+        other => panic!("Unexpected outcome {other:?}"),
+    };
+    assert_eq!(parallel_outcome.outcomes.len(), sequential_outcomes.len());
+    for (parallel, sequential) in parallel_outcome.outcomes.iter().zip(&sequential_outcomes) {
+        assert_eq!(parallel.hash, sequential.hash);
+        assert_eq!(parallel.diff, sequential.diff);
+    }
+
+    // So is the committed state
+    assert_eq!(
+        runner.storage.get_snapshot(block_message.height).unwrap(),
+        sequential_runner
+            .storage
+            .get_snapshot(block_message.height)
+            .unwrap()
+    );
+    let alice_address = aurora_engine_sdk::types::near_account_to_evm_address(b"alice.near");
+    assert_eq!(runner.get_nonce(&alice_address), U256::from(2));
+}
+
 #[test]
 fn test_consume_submit_message() {
     let (mut runner, _) = initialize();
@@ -421,11 +771,13 @@ fn test_consume_submit_message() {
         position: 0,
         succeeded: true,
         signer: runner.env.signer_account_id(),
+        signer_public_key: None,
         caller: runner.env.predecessor_account_id(),
         attached_near: 0,
         transaction: tx_kind,
         promise_data: Vec::new(),
         raw_input,
+        on_chain_outcome: None,
     };
 
     let outcome = sync::consume_message::<AuroraModExp>(
@@ -443,6 +795,203 @@ fn test_consume_submit_message() {
     assert_eq!(runner.get_nonce(&signer_address), U256::one());
 }
 
+#[test]
+fn test_expired_relayer_key() {
+    let (mut runner, block_message) = initialize();
+
+    let public_key: PublicKey = "ed25519:DcA2MzgpJbrUATQLLceocVckhhAqrkingax4oJ9kZ847"
+        .parse()
+        .unwrap();
+    let expires_at = block_message.height + 2;
+    let signer = runner.env.signer_account_id();
+    let predecessor = runner.env.predecessor_account_id();
+    let message = |position: u16, signer_public_key, tx_kind: sync::types::TransactionKind| {
+        let raw_input = tx_kind.raw_bytes();
+        sync::types::TransactionMessage {
+            block_hash: block_message.hash,
+            near_receipt_id: H256([u8::try_from(position).unwrap(); 32]),
+            position,
+            succeeded: true,
+            signer: signer.clone(),
+            signer_public_key,
+            caller: predecessor.clone(),
+            attached_near: 100,
+            transaction: tx_kind,
+            promise_data: Vec::new(),
+            raw_input,
+            on_chain_outcome: None,
+        }
+    };
+    let set_key_manager = sync::types::TransactionKind::SetKeyManager(RelayerKeyManagerArgs {
+        key_manager: Some(predecessor.clone()),
+    });
+    let add_relayer_key = sync::types::TransactionKind::AddRelayerKey(RelayerKeyArgsV2 {
+        public_key,
+        allowed_methods: Vec::new(),
+        expires_at: Some(expires_at),
+    });
+    let messages = vec![
+        message(0, None, set_key_manager),
+        message(1, None, add_relayer_key),
+    ];
+    for transaction_message in messages {
+        let outcome = sync::consume_message::<AuroraModExp>(
+            &mut runner.storage,
+            sync::types::Message::Transaction(Box::new(transaction_message)),
+        )
+        .unwrap();
+        outcome.commit(&mut runner.storage).unwrap();
+    }
+
+    let call = || {
+        sync::types::TransactionKind::Call(simple_transfer_args(
+            Address::new(H160([1u8; 20])),
+            Wei::zero(),
+        ))
+    };
+    let call_at_height = |runner: &mut StandaloneRunner, block_height: u64| {
+        utils::standalone::mocks::insert_block(&mut runner.storage, block_height);
+        let mut transaction_message = message(0, Some(public_key), call());
+        transaction_message.block_hash = utils::standalone::mocks::compute_block_hash(block_height);
+        let outcome = sync::consume_message::<AuroraModExp>(
+            &mut runner.storage,
+            sync::types::Message::Transaction(Box::new(transaction_message)),
+        )
+        .unwrap();
+        match outcome {
+            sync::ConsumeMessageOutcome::TransactionIncluded(outcome) => outcome.maybe_result,
+            other => panic!("Unexpected outcome {other:?}"),
+        }
+    };
+
+    // The key is valid until its expiration height.
+    assert!(call_at_height(&mut runner, expires_at - 1).is_ok());
+
+    // The standalone rejects the transactions signed with the expired key, like the contract.
+    match call_at_height(&mut runner, expires_at) {
+        Err(sync::error::Error::ContractError(e)) => {
+            assert_eq!(
+                e.message.as_ref().as_ref(),
+                aurora_engine::errors::ERR_RELAYER_KEY_EXPIRED
+            );
+        }
+        other => panic!("Unexpected result {other:?}"),
+    }
+}
+
+#[test]
+fn test_detect_divergence() {
+    let (mut runner, _) = initialize();
+
+    let mut signer = utils::Signer::random();
+    let initial_balance = Wei::new_u64(800_000);
+    let transfer_amount = Wei::new_u64(115_321);
+    let signer_address = utils::address_from_secret_key(&signer.secret_key);
+    let recipient_address = Address::new(H160([1u8; 20]));
+    runner.mint_account(signer_address, initial_balance, signer.nonce.into(), None);
+
+    runner.env.block_height += 1;
+    let block_height = runner.env.block_height;
+    utils::standalone::mocks::insert_block(&mut runner.storage, block_height);
+    let block_hash = utils::standalone::mocks::compute_block_hash(block_height);
+    let mut transfer_message = |position: u16, succeeded: bool, on_chain_outcome| {
+        let transaction = utils::transfer(
+            recipient_address,
+            transfer_amount,
+            signer.use_nonce().into(),
+        );
+        let signed_transaction =
+            utils::sign_transaction(transaction, Some(runner.chain_id), &signer.secret_key);
+        let tx_kind = sync::types::TransactionKind::Submit(
+            crate::prelude::transactions::EthTransactionKind::Legacy(signed_transaction),
+        );
+        sync::types::TransactionMessage {
+            block_hash,
+            near_receipt_id: H256([8u8 + u8::try_from(position).unwrap(); 32]),
+            position,
+            succeeded,
+            signer: runner.env.signer_account_id(),
+            signer_public_key: None,
+            caller: runner.env.predecessor_account_id(),
+            attached_near: 0,
+            raw_input: tx_kind.raw_bytes(),
+            transaction: tx_kind,
+            promise_data: Vec::new(),
+            on_chain_outcome: Some(on_chain_outcome),
+        }
+    };
+
+    // The gas used on-chain is wrong
+    let on_chain_result = SubmitResult::new(TransactionStatus::Succeed(Vec::new()), 1, Vec::new());
+    let transaction_message = transfer_message(
+        0,
+        true,
+        sync::types::OnChainOutcome {
+            output: Some(on_chain_result.try_to_vec().unwrap()),
+            logs: Vec::new(),
+        },
+    );
+    let outcome = sync::consume_message::<AuroraModExp>(
+        &mut runner.storage,
+        sync::types::Message::Transaction(Box::new(transaction_message)),
+    )
+    .unwrap();
+    outcome.commit(&mut runner.storage).unwrap();
+
+    // The receipt failed on-chain, but the transaction succeeds locally
+    let transaction_message = transfer_message(
+        1,
+        false,
+        sync::types::OnChainOutcome {
+            output: Some(Vec::new()),
+            logs: vec!["Exceeded the prepaid gas.".into()],
+        },
+    );
+    let outcome = sync::consume_message::<AuroraModExp>(
+        &mut runner.storage,
+        sync::types::Message::Transaction(Box::new(transaction_message)),
+    )
+    .unwrap();
+    assert!(matches!(
+        outcome,
+        sync::ConsumeMessageOutcome::FailedTransactionDiverged(_)
+    ));
+    outcome.commit(&mut runner.storage).unwrap();
+
+    let records = runner
+        .storage
+        .get_divergence_records(block_height, block_height)
+        .unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].transaction_position, 0);
+    assert!(records[0].divergences.contains(&Divergence::Gas {
+        on_chain: 1,
+        local: 21_000,
+    }));
+    assert!(records[0]
+        .divergences
+        .iter()
+        .any(|divergence| matches!(divergence, Divergence::Output { .. })));
+    assert_eq!(records[1].transaction_position, 1);
+    assert_eq!(
+        records[1].divergences,
+        vec![Divergence::Status {
+            on_chain: false,
+            local: true,
+        }]
+    );
+    assert_eq!(records[1].on_chain_logs, vec!["Exceeded the prepaid gas."]);
+    assert!(runner
+        .storage
+        .get_divergence_records(block_height + 1, block_height + 1)
+        .unwrap()
+        .is_empty());
+
+    // The failed transaction is not included
+    assert_eq!(runner.get_balance(&recipient_address), transfer_amount);
+    assert_eq!(runner.get_nonce(&signer_address), U256::one());
+}
+
 fn mock_proof(recipient_address: Address, deposit_amount: Wei) -> aurora_engine::proof::Proof {
     let eth_custodian_address = utils::standalone::mocks::ETH_CUSTODIAN_ADDRESS;
 
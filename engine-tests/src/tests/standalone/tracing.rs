@@ -68,14 +68,17 @@ fn test_evm_tracing_with_storage() {
             position: 0,
             succeeded: true,
             signer: "system".parse().unwrap(),
+            signer_public_key: None,
             caller: "system".parse().unwrap(),
             attached_near: 0,
             transaction: engine_standalone_storage::sync::types::TransactionKind::Unknown,
             promise_data: Vec::new(),
             raw_input: Vec::new(),
+            on_chain_outcome: None,
         },
         diff,
         maybe_result: Ok(None),
+        divergence: None,
     };
     standalone::storage::commit(&mut runner.storage, &tx);
 
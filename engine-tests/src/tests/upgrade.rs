@@ -1,5 +1,9 @@
 use std::{fs, path::Path};
 
+use aurora_engine_types::parameters::engine::DeployUpgradeArgs;
+use aurora_engine_types::parameters::events::{EngineEvent, UpgradeInfo};
+use aurora_engine_workspace::parse_near;
+
 use crate::utils::workspace::deploy_engine;
 
 #[tokio::test]
@@ -54,6 +58,110 @@ async fn test_repeated_calls_to_upgrade_should_fail() {
     assert!(result.is_err());
 }
 
+#[tokio::test]
+async fn test_staged_upgrade_info_and_cancel() {
+    let aurora = deploy_engine().await;
+    let code = contract_bytes();
+    let code_hash = hex::encode(aurora_engine_sdk::sha256(&code));
+
+    let result = aurora
+        .stage_upgrade(code.clone())
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+
+    let info = aurora.get_staged_upgrade().await.unwrap().result;
+    assert_eq!(info.code_hash, code_hash);
+    assert_eq!(info.code_size, u64::try_from(code.len()).unwrap());
+    let events: Vec<_> = result
+        .logs()
+        .into_iter()
+        .filter_map(EngineEvent::from_log)
+        .collect();
+    assert_eq!(events, vec![EngineEvent::StageUpgrade(info.clone())]);
+
+    let result = aurora.cancel_upgrade().max_gas().transact().await.unwrap();
+    assert!(result.is_success());
+    let events: Vec<_> = result
+        .logs()
+        .into_iter()
+        .filter_map(EngineEvent::from_log)
+        .collect();
+    assert_eq!(events, vec![EngineEvent::CancelUpgrade(info)]);
+
+    // Nothing is staged anymore.
+    assert!(aurora.get_staged_upgrade().await.is_err());
+    let result = aurora.deploy_upgrade().max_gas().transact().await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_deploy_upgrade_with_expected_code_hash() {
+    let aurora = deploy_engine().await;
+    let code = contract_bytes();
+
+    let result = aurora
+        .stage_upgrade(code)
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+
+    let result = aurora
+        .deploy_upgrade()
+        .args_json(DeployUpgradeArgs {
+            code_hash: hex::encode([0u8; 32]),
+        })
+        .max_gas()
+        .transact()
+        .await;
+    assert!(result.is_err());
+
+    let UpgradeInfo { code_hash, .. } = aurora.get_staged_upgrade().await.unwrap().result;
+    let result = aurora
+        .deploy_upgrade()
+        .args_json(DeployUpgradeArgs { code_hash })
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_success());
+}
+
+#[tokio::test]
+async fn test_state_migration_of_new_engine() {
+    let aurora = deploy_engine().await;
+    let schema_version: u32 = aurora
+        .as_raw_contract()
+        .view("get_schema_version")
+        .await
+        .unwrap()
+        .borsh()
+        .unwrap();
+    assert_eq!(schema_version, 1);
+
+    // A new engine doesn't have pending migrations.
+    let result = aurora.state_migration().max_gas().transact().await.unwrap();
+    assert!(result.is_success());
+
+    // Only the engine itself and upgraders are allowed to run migrations.
+    let user = aurora
+        .root()
+        .create_subaccount("user", parse_near!("1 N"))
+        .await
+        .unwrap();
+    let result = user
+        .call(&aurora.id(), "state_migration")
+        .max_gas()
+        .transact()
+        .await
+        .unwrap();
+    assert!(result.is_failure());
+}
+
 fn contract_bytes() -> Vec<u8> {
     let base_path = Path::new("../etc")
         .join("tests")
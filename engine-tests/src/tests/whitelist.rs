@@ -0,0 +1,103 @@
+use crate::prelude::Wei;
+use crate::utils;
+use aurora_engine::engine::EngineErrorKind;
+use aurora_engine::parameters::{
+    AccountsArgs, AddressesArgs, Whitelist, WhitelistKind, WhitelistStatusArgs,
+};
+use aurora_engine_types::account_id::AccountId;
+
+const TRANSFER_AMOUNT: Wei = Wei::new_u64(10);
+/// The account submitting the transactions in the runner.
+const RELAYER_ACCOUNT_ID: &str = "some-account.near";
+
+fn set_whitelist_status(runner: &mut utils::AuroraRunner, kind: WhitelistKind, enabled: bool) {
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let args = serde_json::to_vec(&WhitelistStatusArgs { kind, enabled }).unwrap();
+    let result = runner.call("set_whitelist_status", &aurora_account_id, args);
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_address_whitelist() {
+    let (mut runner, mut signer, receiver) = crate::tests::sanity::initialize_transfer();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    let sender = utils::address_from_secret_key(&signer.secret_key);
+    set_whitelist_status(&mut runner, WhitelistKind::Address, true);
+
+    utils::assert_submit_rejected(
+        &mut runner,
+        &mut signer,
+        |nonce| utils::transfer(receiver, TRANSFER_AMOUNT, nonce),
+        &EngineErrorKind::EvmFatal(evm::ExitFatal::Other("ERR_NOT_WHITELISTED_ADDRESS".into())),
+    );
+
+    let args = serde_json::to_vec(&AddressesArgs {
+        addresses: vec![sender],
+    })
+    .unwrap();
+    let result = runner.call("add_whitelisted_addresses", &aurora_account_id, args);
+    assert!(result.is_ok(), "{result:?}");
+
+    let result = runner
+        .one_shot()
+        .call("get_address_whitelist", "viewer", Vec::new())
+        .unwrap();
+    let whitelist: Whitelist<_> =
+        serde_json::from_slice(&result.return_data.as_value().unwrap()).unwrap();
+    assert_eq!(
+        whitelist,
+        Whitelist {
+            enabled: true,
+            entries: vec![sender],
+        }
+    );
+
+    let result = runner.submit_with_signer(&mut signer, |nonce| {
+        utils::transfer(receiver, TRANSFER_AMOUNT, nonce)
+    });
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_account_whitelist() {
+    let (mut runner, mut signer, receiver) = crate::tests::sanity::initialize_transfer();
+    let aurora_account_id = runner.aurora_account_id.clone();
+    set_whitelist_status(&mut runner, WhitelistKind::Account, true);
+
+    utils::assert_submit_rejected(
+        &mut runner,
+        &mut signer,
+        |nonce| utils::transfer(receiver, TRANSFER_AMOUNT, nonce),
+        &EngineErrorKind::EvmFatal(evm::ExitFatal::Other("ERR_NOT_WHITELISTED_ACCOUNT".into())),
+    );
+
+    let relayer: AccountId = RELAYER_ACCOUNT_ID.parse().unwrap();
+    let args = serde_json::to_vec(&AccountsArgs {
+        accounts: vec![relayer],
+    })
+    .unwrap();
+    let result = runner.call("add_whitelisted_accounts", &aurora_account_id, args);
+    assert!(result.is_ok(), "{result:?}");
+
+    let result = runner.submit_with_signer(&mut signer, |nonce| {
+        utils::transfer(receiver, TRANSFER_AMOUNT, nonce)
+    });
+    assert!(result.is_ok(), "{result:?}");
+
+    set_whitelist_status(&mut runner, WhitelistKind::Account, false);
+    let result = runner.call("deploy_code", "other.near", Vec::new());
+    assert!(result.is_ok(), "{result:?}");
+}
+
+#[test]
+fn test_set_whitelist_status_requires_silo_admin() {
+    let mut runner = utils::deploy_runner();
+    let args = serde_json::to_vec(&WhitelistStatusArgs {
+        kind: WhitelistKind::Address,
+        enabled: true,
+    })
+    .unwrap();
+
+    let result = runner.call("set_whitelist_status", "stranger.near", args);
+    assert!(result.is_err());
+}
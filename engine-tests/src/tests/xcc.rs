@@ -1,14 +1,20 @@
 use crate::utils::solidity::erc20::{ERC20Constructor, ERC20};
 use crate::utils::{self, AuroraRunner, DEFAULT_AURORA_ACCOUNT_ID};
-use aurora_engine_precompiles::xcc::{costs, cross_contract_call};
+use aurora_engine_precompiles::xcc::{costs, cross_contract_call, state};
 use aurora_engine_transactions::legacy::TransactionLegacy;
 use aurora_engine_types::account_id::AccountId;
 use aurora_engine_types::borsh::{BorshDeserialize, BorshSerialize};
+use aurora_engine_types::parameters::engine::SubmitResult;
+use aurora_engine_types::parameters::events::{AddressInfo, CodeInfo, EngineEvent};
+use aurora_engine_types::parameters::xcc::{
+    AddressVersionUpdateArgs, CreateSubAccountArgs, SubAccountKey, UpdateSubAccountKeysArgs,
+};
 use aurora_engine_types::parameters::{
-    CrossContractCallArgs, NearPromise, PromiseArgs, PromiseCreateArgs, PromiseWithCallbackArgs,
-    SimpleNearPromise,
+    CrossContractCallArgs, NearPromise, PromiseAction, PromiseArgs, PromiseBatchAction,
+    PromiseCreateArgs, PromiseWithCallbackArgs, SimpleNearPromise,
 };
-use aurora_engine_types::types::{Address, EthGas, NearGas, Wei, Yocto};
+use aurora_engine_types::public_key::PublicKey;
+use aurora_engine_types::types::{Address, EthGas, NearGas, PromiseResult, Wei, Yocto};
 use aurora_engine_types::U256;
 use near_primitives::transaction::Action;
 use near_primitives_core::contract::ContractCode;
@@ -278,6 +284,277 @@ fn test_xcc_exec_gas() {
     }
 }
 
+#[test]
+#[allow(clippy::too_many_lines)]
+fn test_xcc_sub_account() {
+    let mut runner = utils::deploy_runner();
+    runner.standalone_runner = None;
+    let xcc_wasm_bytes = contract_bytes();
+    let _res = runner.call(
+        "factory_update",
+        DEFAULT_AURORA_ACCOUNT_ID,
+        xcc_wasm_bytes.clone(),
+    );
+    assert_eq!(
+        runner.previous_events(),
+        vec![EngineEvent::FactoryUpdate(CodeInfo {
+            code_hash: hex::encode(aurora_engine_sdk::sha256(&xcc_wasm_bytes)),
+            code_size: xcc_wasm_bytes.len().try_into().unwrap(),
+        })]
+    );
+    let mut signer = utils::Signer::random();
+    let signer_address = utils::address_from_secret_key(&signer.secret_key);
+    let wnear_erc20 = deploy_erc20(&mut runner, &signer);
+    approve_erc20(
+        &wnear_erc20,
+        cross_contract_call::ADDRESS,
+        &mut runner,
+        &mut signer,
+    );
+    let _res = runner.call(
+        "factory_set_wnear_address",
+        DEFAULT_AURORA_ACCOUNT_ID,
+        wnear_erc20.0.address.as_bytes().to_vec(),
+    );
+    assert_eq!(
+        runner.previous_events(),
+        vec![EngineEvent::FactorySetWnearAddress(AddressInfo {
+            address: wnear_erc20.0.address,
+        })]
+    );
+
+    let router_account_id: AccountId =
+        format!("{}.{}", signer_address.encode(), DEFAULT_AURORA_ACCOUNT_ID)
+            .parse()
+            .unwrap();
+    let sub_account_id: AccountId = format!("dao.{router_account_id}").parse().unwrap();
+    let full_access_key = PublicKey::Ed25519([1u8; 32]);
+    let function_call_key = SubAccountKey::FunctionCall {
+        public_key: PublicKey::Ed25519([2u8; 32]),
+        allowance: Yocto::new(0),
+        receiver_id: "dao.near".parse().unwrap(),
+        function_names: "vote".into(),
+    };
+
+    // Create the sub-account with a full access key.
+    let create_args = CrossContractCallArgs::CreateSubAccount(CreateSubAccountArgs {
+        name: "dao".into(),
+        initial_balance: state::STORAGE_AMOUNT,
+        keys: vec![SubAccountKey::FullAccess(full_access_key)],
+    });
+    let (result, calls) = submit_xcc_and_get_calls(&mut runner, &mut signer, &create_args);
+    assert!(result.status.is_ok(), "{result:?}");
+    let sub_account_log = result
+        .logs
+        .iter()
+        .find(|log| log.topics[0] == cross_contract_call::SUB_ACCOUNT_TOPIC.0)
+        .unwrap();
+    assert_eq!(sub_account_log.data, sub_account_id.as_bytes());
+
+    // The router creates the sub-account, then deploys the router code to it.
+    let create_call = PromiseArgs::Recursive(NearPromise::Simple(SimpleNearPromise::Batch(
+        PromiseBatchAction {
+            target_account_id: sub_account_id.clone(),
+            actions: vec![
+                PromiseAction::CreateAccount,
+                PromiseAction::Transfer {
+                    amount: state::STORAGE_AMOUNT,
+                },
+                PromiseAction::AddFullAccessKey {
+                    public_key: full_access_key,
+                    nonce: 0,
+                },
+            ],
+        },
+    )));
+    let deploy_call = PromiseArgs::Recursive(NearPromise::Simple(SimpleNearPromise::Batch(
+        PromiseBatchAction {
+            target_account_id: sub_account_id.clone(),
+            actions: vec![
+                PromiseAction::DeployContract {
+                    code: xcc_wasm_bytes.clone(),
+                },
+                PromiseAction::FunctionCall {
+                    name: "initialize".into(),
+                    args: br#"{"wnear_account": "wrap.near", "must_register": false}"#.to_vec(),
+                    attached_yocto: Yocto::new(0),
+                    gas: costs::ROUTER_INITIALIZE,
+                },
+            ],
+        },
+    )));
+    assert_eq!(
+        router_executions(&calls, &router_account_id),
+        vec![create_call, deploy_call.clone()]
+    );
+
+    // The version of the router deployed to the sub-account is recorded by the engine.
+    let version_args = calls
+        .iter()
+        .find(|(_, method, _)| method == "factory_update_address_version")
+        .map(|(_, _, args)| AddressVersionUpdateArgs::try_from_slice(args).unwrap())
+        .filter(|args| args.address == state::sub_account_address(&sub_account_id))
+        .unwrap();
+    runner.promise_results = vec![PromiseResult::Successful(Vec::new())];
+    let _res = runner
+        .call(
+            "factory_update_address_version",
+            DEFAULT_AURORA_ACCOUNT_ID,
+            version_args.try_to_vec().unwrap(),
+        )
+        .unwrap();
+    runner.promise_results = Vec::new();
+
+    // Replace the full access key of the existing sub-account with a function call key.
+    let update_args = CrossContractCallArgs::UpdateSubAccountKeys(UpdateSubAccountKeysArgs {
+        name: "dao".into(),
+        add_keys: vec![function_call_key.clone()],
+        delete_keys: vec![full_access_key],
+    });
+    let (result, calls) = submit_xcc_and_get_calls(&mut runner, &mut signer, &update_args);
+    assert!(result.status.is_ok(), "{result:?}");
+
+    // The router of the sub-account is asked to change its own keys.
+    let batch = PromiseArgs::Recursive(NearPromise::Simple(SimpleNearPromise::Batch(
+        PromiseBatchAction {
+            target_account_id: sub_account_id.clone(),
+            actions: vec![
+                function_call_key.to_action(),
+                PromiseAction::DeleteKey {
+                    public_key: full_access_key,
+                },
+            ],
+        },
+    )));
+    let update_call = PromiseArgs::Create(PromiseCreateArgs {
+        target_account_id: sub_account_id.clone(),
+        method: "execute".into(),
+        args: batch.try_to_vec().unwrap(),
+        attached_balance: Yocto::new(0),
+        attached_gas: costs::ROUTER_EXEC_BASE,
+    });
+    assert_eq!(
+        router_executions(&calls, &router_account_id),
+        vec![update_call.clone()]
+    );
+
+    // Once the router code is updated, the router of the sub-account is upgraded before
+    // changing its keys.
+    let _res = runner.call(
+        "factory_update",
+        DEFAULT_AURORA_ACCOUNT_ID,
+        xcc_wasm_bytes.clone(),
+    );
+    let (result, calls) = submit_xcc_and_get_calls(&mut runner, &mut signer, &update_args);
+    assert!(result.status.is_ok(), "{result:?}");
+    assert_eq!(
+        router_executions(&calls, &router_account_id),
+        vec![deploy_call, update_call]
+    );
+
+    // The initial balance must cover the storage of the router code.
+    let poor_args = CrossContractCallArgs::CreateSubAccount(CreateSubAccountArgs {
+        name: "poor".into(),
+        initial_balance: Yocto::new(1),
+        keys: Vec::new(),
+    });
+    let result = submit_xcc_with_signer(&mut runner, &mut signer, &poor_args);
+    assert_eq!(
+        utils::unwrap_revert_slice(&result),
+        b"ERR_INSUFFICIENT_XCC_SUB_ACCOUNT_BALANCE"
+    );
+
+    // Names which are not a single account id part are rejected.
+    let invalid_args = CrossContractCallArgs::UpdateSubAccountKeys(UpdateSubAccountKeysArgs {
+        name: "a.dao".into(),
+        add_keys: vec![function_call_key],
+        delete_keys: Vec::new(),
+    });
+    let result = submit_xcc_with_signer(&mut runner, &mut signer, &invalid_args);
+    assert_eq!(
+        utils::unwrap_revert_slice(&result),
+        b"ERR_INVALID_XCC_SUB_ACCOUNT"
+    );
+}
+
+fn submit_xcc_with_signer(
+    runner: &mut AuroraRunner,
+    signer: &mut utils::Signer,
+    args: &CrossContractCallArgs,
+) -> SubmitResult {
+    runner
+        .submit_with_signer(signer, |nonce| TransactionLegacy {
+            nonce,
+            gas_price: U256::zero(),
+            gas_limit: u64::MAX.into(),
+            to: Some(cross_contract_call::ADDRESS),
+            value: Wei::zero(),
+            data: args.try_to_vec().unwrap(),
+        })
+        .unwrap()
+}
+
+/// Submits a call to the xcc precompile and returns the result together with the
+/// `(receiver, method, args)` of the function calls scheduled by the engine, in order.
+fn submit_xcc_and_get_calls(
+    runner: &mut AuroraRunner,
+    signer: &mut utils::Signer,
+    args: &CrossContractCallArgs,
+) -> (SubmitResult, Vec<(String, String, Vec<u8>)>) {
+    let tx = TransactionLegacy {
+        nonce: signer.use_nonce().into(),
+        gas_price: U256::zero(),
+        gas_limit: u64::MAX.into(),
+        to: Some(cross_contract_call::ADDRESS),
+        value: Wei::zero(),
+        data: args.try_to_vec().unwrap(),
+    };
+    let signed_tx = utils::sign_transaction(tx, Some(runner.chain_id), &signer.secret_key);
+    let outcome = runner
+        .call(
+            utils::SUBMIT,
+            "some-account.near",
+            rlp::encode(&signed_tx).to_vec(),
+        )
+        .unwrap();
+    let result =
+        SubmitResult::try_from_slice(outcome.return_data.as_value().as_ref().unwrap()).unwrap();
+
+    let calls = outcome
+        .action_receipts
+        .into_iter()
+        .flat_map(|(target_account_id, receipt)| {
+            receipt
+                .actions
+                .into_iter()
+                .filter_map(move |action| match action {
+                    Action::FunctionCall(function_call) => Some((
+                        target_account_id.clone(),
+                        function_call.method_name,
+                        function_call.args,
+                    )),
+                    _ => None,
+                })
+        })
+        .collect();
+
+    (result, calls)
+}
+
+/// The promises the given router is asked to execute.
+fn router_executions(
+    calls: &[(String, String, Vec<u8>)],
+    router_account_id: &AccountId,
+) -> Vec<PromiseArgs> {
+    calls
+        .iter()
+        .filter(|(target_account_id, method, _)| {
+            target_account_id.as_str() == router_account_id.as_ref() && method == "execute"
+        })
+        .map(|(_, _, args)| PromiseArgs::try_from_slice(args).unwrap())
+        .collect()
+}
+
 fn deploy_router() -> AuroraRunner {
     let mut router = AuroraRunner {
         code: ContractCode::new(contract_bytes(), None),
@@ -389,15 +666,19 @@ pub mod workspace {
         create_sub_account, deploy_engine, deploy_erc20_from_nep_141, deploy_nep_141,
         nep_141_balance_of, transfer_nep_141_to_erc_20,
     };
-    use aurora_engine_precompiles::xcc::cross_contract_call;
+    use aurora_engine_precompiles::xcc::{cross_contract_call, state};
     use aurora_engine_transactions::legacy::TransactionLegacy;
     use aurora_engine_types::account_id::AccountId;
     use aurora_engine_types::borsh::BorshSerialize;
     use aurora_engine_types::parameters::engine::TransactionStatus;
+    use aurora_engine_types::parameters::xcc::{
+        CreateSubAccountArgs, SubAccountKey, UpdateSubAccountKeysArgs,
+    };
     use aurora_engine_types::parameters::{
         CrossContractCallArgs, NearPromise, PromiseArgs, PromiseCreateArgs,
         PromiseWithCallbackArgs, SimpleNearPromise,
     };
+    use aurora_engine_types::public_key::PublicKey;
     use aurora_engine_types::types::{Address, NearGas, Wei, Yocto};
     use aurora_engine_types::U256;
     use aurora_engine_workspace::{parse_near, EngineContract, RawContract};
@@ -605,6 +886,59 @@ pub mod workspace {
         check_fib_result(&output, usize::try_from(n).unwrap());
     }
 
+    #[tokio::test]
+    async fn test_xcc_sub_account() {
+        let XccTestContext {
+            aurora,
+            mut signer,
+            signer_address,
+            chain_id,
+            ..
+        } = init_xcc().await.unwrap();
+
+        let sub_account_id: AccountId = format!(
+            "dao.{}.{}",
+            hex::encode(signer_address.as_bytes()),
+            aurora.id().as_ref()
+        )
+        .parse()
+        .unwrap();
+        let full_access_key = PublicKey::Ed25519([1u8; 32]);
+        let function_call_key = PublicKey::Ed25519([2u8; 32]);
+
+        // 1. Create the sub-account with a full access key
+        let xcc_args = CrossContractCallArgs::CreateSubAccount(CreateSubAccountArgs {
+            name: "dao".into(),
+            initial_balance: state::STORAGE_AMOUNT,
+            keys: vec![SubAccountKey::FullAccess(full_access_key)],
+        });
+        let result = submit_xcc_transaction(&xcc_args, &aurora, &mut signer, chain_id).await;
+        assert!(result.is_ok(), "{:?}", result.err());
+
+        // 2. Check the router was deployed to it
+        let code = aurora.node.get_code(&sub_account_id).await.unwrap();
+        assert_eq!(code, super::contract_bytes());
+        let keys = aurora.node.get_access_keys(&sub_account_id).await.unwrap();
+        assert_eq!(keys, vec![full_access_key]);
+
+        // 3. Have the router of the sub-account replace its full access key
+        let xcc_args = CrossContractCallArgs::UpdateSubAccountKeys(UpdateSubAccountKeysArgs {
+            name: "dao".into(),
+            add_keys: vec![SubAccountKey::FunctionCall {
+                public_key: function_call_key,
+                allowance: Yocto::new(0),
+                receiver_id: aurora.id(),
+                function_names: "submit".into(),
+            }],
+            delete_keys: vec![full_access_key],
+        });
+        let result = submit_xcc_transaction(&xcc_args, &aurora, &mut signer, chain_id).await;
+        assert!(result.is_ok(), "{:?}", result.err());
+
+        let keys = aurora.node.get_access_keys(&sub_account_id).await.unwrap();
+        assert_eq!(keys, vec![function_call_key]);
+    }
+
     #[allow(clippy::too_many_lines)]
     async fn test_xcc_precompile_common(is_scheduled: bool) {
         let XccTestContext {
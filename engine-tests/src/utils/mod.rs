@@ -3,6 +3,7 @@ use aurora_engine::parameters::{SubmitArgs, ViewCallArgs};
 use aurora_engine_types::account_id::AccountId;
 use aurora_engine_types::borsh::{BorshDeserialize, BorshSerialize};
 use aurora_engine_types::parameters::engine::{NewCallArgs, NewCallArgsV4};
+use aurora_engine_types::parameters::events::EngineEvent;
 use aurora_engine_types::types::{NEP141Wei, PromiseResult};
 use evm::ExitFatal;
 use libsecp256k1::{self, Message, PublicKey, SecretKey};
@@ -485,6 +486,14 @@ impl AuroraRunner {
         self.u256_getter_method_call("get_nonce", address)
     }
 
+    /// Returns the engine events logged by the last call.
+    pub fn previous_events(&self) -> Vec<EngineEvent> {
+        self.previous_logs
+            .iter()
+            .filter_map(|log| EngineEvent::from_log(log))
+            .collect()
+    }
+
     pub fn get_code(&self, address: Address) -> Vec<u8> {
         self.getter_method_call("get_code", address)
     }
@@ -876,6 +885,19 @@ pub fn panic_on_fail(status: TransactionStatus) {
     }
 }
 
+/// Submits a transaction which must be rejected by the engine with the `expected` error.
+/// The rejected transaction doesn't use the nonce, so it is given back to the signer.
+pub fn assert_submit_rejected<F: FnOnce(U256) -> TransactionLegacy>(
+    runner: &mut AuroraRunner,
+    signer: &mut Signer,
+    make_tx: F,
+    expected: &EngineErrorKind,
+) {
+    let error = runner.submit_with_signer(signer, make_tx).unwrap_err();
+    assert_eq!(&error.kind, expected);
+    signer.nonce -= 1;
+}
+
 /// Checks if `total_gas` is within 1 Tgas of `tgas_bound`.
 pub fn assert_gas_bound(total_gas: u64, tgas_bound: u64) {
     const TERA: i128 = 1_000_000_000_000;
@@ -905,9 +927,15 @@ fn into_engine_error(gas_used: u64, aborted: &FunctionCallError) -> EngineError
             match panic_msg.as_str() {
                 "ERR_INVALID_CHAIN_ID" => EngineErrorKind::InvalidChainId,
                 "ERR_OUT_OF_FUND" => EngineErrorKind::GasPayment(GasPaymentError::OutOfFund),
+                "ERR_GAS_PRICE_TOO_LOW" => {
+                    EngineErrorKind::GasPayment(GasPaymentError::GasPriceTooLow)
+                }
                 "ERR_GAS_OVERFLOW" => EngineErrorKind::GasOverflow,
                 "ERR_INTRINSIC_GAS" => EngineErrorKind::IntrinsicGasNotMet,
                 "ERR_INCORRECT_NONCE" => EngineErrorKind::IncorrectNonce,
+                "ERR_DENIED_SENDER" => EngineErrorKind::DeniedSender,
+                "ERR_DENIED_RECIPIENT" => EngineErrorKind::DeniedRecipient,
+                "ERR_DEPLOYER_NOT_ALLOWED" => EngineErrorKind::DeployerNotAllowed,
                 "ERR_PAUSED" => EngineErrorKind::EvmFatal(ExitFatal::Other("ERR_PAUSED".into())),
                 msg => EngineErrorKind::EvmFatal(ExitFatal::Other(Cow::Owned(msg.into()))),
             }
@@ -35,6 +35,7 @@ pub fn default_env(block_height: u64) -> aurora_engine_sdk::env::Fixed {
     let aurora_id: AccountId = utils::DEFAULT_AURORA_ACCOUNT_ID.parse().unwrap();
     aurora_engine_sdk::env::Fixed {
         signer_account_id: aurora_id.clone(),
+        signer_account_pk: None,
         current_account_id: aurora_id.clone(),
         predecessor_account_id: aurora_id,
         block_height,
@@ -3,6 +3,8 @@ use aurora_engine::parameters::{SubmitResult, TransactionStatus};
 use aurora_engine_modexp::AuroraModExp;
 use aurora_engine_sdk::env::{self, Env};
 use aurora_engine_transactions::legacy::{LegacyEthSignedTransaction, TransactionLegacy};
+use aurora_engine_types::borsh::BorshDeserialize;
+use aurora_engine_types::public_key::PublicKey;
 use aurora_engine_types::types::{Address, NearGas, PromiseResult, Wei};
 use aurora_engine_types::{H256, U256};
 use engine_standalone_storage::{
@@ -52,6 +54,7 @@ impl StandaloneRunner {
             info: tx_msg,
             diff: result.diff,
             maybe_result: Ok(None),
+            divergence: None,
         };
         self.cumulative_diff.append(outcome.diff.clone());
         storage::commit(storage, &outcome);
@@ -87,6 +90,7 @@ impl StandaloneRunner {
             info: tx_msg,
             diff: result.diff,
             maybe_result: Ok(None),
+            divergence: None,
         };
         self.cumulative_diff.append(outcome.diff.clone());
         storage::commit(storage, &outcome);
@@ -202,6 +206,7 @@ impl StandaloneRunner {
         env.predecessor_account_id = ctx.predecessor_account_id.as_ref().parse().unwrap();
         env.current_account_id = ctx.current_account_id.as_ref().parse().unwrap();
         env.signer_account_id = ctx.signer_account_id.as_ref().parse().unwrap();
+        env.signer_account_pk = PublicKey::try_from_slice(&ctx.signer_account_pk).ok();
         env.prepaid_gas = NearGas::new(ctx.prepaid_gas);
         if ctx.random_seed.len() == 32 {
             env.random_seed = H256::from_slice(&ctx.random_seed);
@@ -319,11 +324,13 @@ impl StandaloneRunner {
             position: transaction_position,
             succeeded: true,
             signer: env.signer_account_id(),
+            signer_public_key: env.signer_account_pk,
             caller: env.predecessor_account_id(),
             attached_near: env.attached_deposit,
             transaction: TransactionKind::Unknown,
             promise_data,
             raw_input,
+            on_chain_outcome: None,
         }
     }
 
@@ -2,7 +2,7 @@ use crate::{
     account_id::AccountId,
     public_key::PublicKey,
     types::{Address, RawH256, RawU256, WeiU256, Yocto},
-    Vec,
+    String, Vec,
 };
 #[cfg(not(feature = "borsh-compat"))]
 use borsh::{BorshDeserialize, BorshSerialize};
@@ -327,6 +327,56 @@ pub struct DeployErc20TokenArgs {
 /// Borsh-encoded parameters for `get_erc20_from_nep141` function.
 pub type GetErc20FromNep141CallArgs = DeployErc20TokenArgs;
 
+/// Borsh-encoded parameters for `deploy_erc20_token` function, which additionally allow
+/// synchronizing the metadata of the new ERC-20 token with the NEP-141 `ft_metadata`.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Eq, PartialEq, Clone)]
+pub struct DeployErc20TokenArgsV2 {
+    pub nep141: AccountId,
+    /// Schedule the synchronization of the ERC-20 metadata after the deployment.
+    pub sync_metadata: bool,
+}
+
+impl DeployErc20TokenArgsV2 {
+    /// Deserialize the arguments of `deploy_erc20_token` in either of the supported formats.
+    #[must_use]
+    pub fn deserialize(bytes: &[u8]) -> Option<Self> {
+        Self::try_from_slice(bytes).map_or_else(
+            |_| {
+                DeployErc20TokenArgs::try_from_slice(bytes)
+                    .ok()
+                    .map(Into::into)
+            },
+            Some,
+        )
+    }
+}
+
+impl From<DeployErc20TokenArgs> for DeployErc20TokenArgsV2 {
+    fn from(value: DeployErc20TokenArgs) -> Self {
+        Self {
+            nep141: value.nep141,
+            sync_metadata: false,
+        }
+    }
+}
+
+/// Borsh-encoded parameters for `sync_erc20_metadata` function and its callback.
+pub type SyncErc20MetadataArgs = DeployErc20TokenArgs;
+
+/// Borsh-encoded parameters for `deploy_nep141_token` function.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Eq, PartialEq, Clone)]
+pub struct DeployNep141TokenArgs {
+    /// Address of the ERC-20 contract native to Aurora.
+    pub erc20_address: Address,
+}
+
+/// Borsh-encoded parameters for `register_nep141_token` callback.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Eq, PartialEq, Clone)]
+pub struct RegisterNep141TokenArgs {
+    pub erc20_address: Address,
+    pub nep141: AccountId,
+}
+
 /// Borsh-encoded parameters for the `get_storage_at` function.
 #[derive(BorshSerialize, BorshDeserialize)]
 pub struct GetStorageAtArgs {
@@ -334,18 +384,239 @@ pub struct GetStorageAtArgs {
     pub key: RawH256,
 }
 
+/// Parameters of the `pause_methods` and `resume_methods` functions.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct PauseMethodsArgs {
+    /// Names of the engine methods, e.g. `submit` or `deploy_erc20_token`.
+    pub methods: Vec<String>,
+}
+
+/// Parameters of the `deny_address`, `allow_address` and `is_address_denied` functions.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct DenylistArgs {
+    pub address: Address,
+}
+
+/// Parameters of the `add_deployer`, `remove_deployer` and `is_deployer_allowed` functions.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct DeployerArgs {
+    pub address: Address,
+}
+
+/// Parameters of the `set_deploy_allowlist_enabled` function.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct DeployAllowlistModeArgs {
+    /// If true, only the approved deployers can create contracts.
+    pub enabled: bool,
+}
+
+/// Economic settings of a silo, applied to the transactions submitted to the engine.
+#[derive(
+    Debug, Default, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct SiloParams {
+    /// Transactions with a lower gas price are rejected.
+    pub min_gas_price: u128,
+    /// If set, the transactions are charged this gas price and those offering less are rejected.
+    pub fixed_gas_price: Option<u128>,
+    /// If set, receives the transaction fees instead of the relayer.
+    pub fee_recipient: Option<Address>,
+}
+
+/// Parameters of the functions adding or removing several EVM addresses at once.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct AddressesArgs {
+    pub addresses: Vec<Address>,
+}
+
+/// Status and entries of a whitelist of a permissioned silo, returned by the views.
+#[derive(
+    Debug, Default, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct Whitelist<T> {
+    /// If false, everyone is allowed to transact.
+    pub enabled: bool,
+    pub entries: Vec<T>,
+}
+
+/// Kinds of the whitelists of a permissioned silo.
+#[derive(
+    Debug, Clone, Copy, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum WhitelistKind {
+    /// EVM addresses which can sign the transactions passed to `submit`.
+    Address,
+    /// NEAR accounts which can call `call`, `deploy_code` and `submit`.
+    Account,
+}
+
+/// Parameters of the `set_whitelist_status` function.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct WhitelistStatusArgs {
+    pub kind: WhitelistKind,
+    pub enabled: bool,
+}
+
+/// Parameters of the functions adding or removing several NEAR accounts at once.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct AccountsArgs {
+    pub accounts: Vec<AccountId>,
+}
+
 /// Parameters for setting relayer keys manager.
 #[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct RelayerKeyManagerArgs {
     pub key_manager: Option<AccountId>,
 }
 
+/// Methods of the engine which can be called with relayer function call keys.
+pub const RELAYER_KEY_METHODS: [&str; 3] = ["call", "submit", "submit_with_args"];
+
 /// Parameters for adding or removing relayer function all keys.
 #[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
 pub struct RelayerKeyArgs {
     pub public_key: PublicKey,
 }
 
+/// Parameters for adding relayer function call keys with restrictions. Its JSON representation
+/// is compatible with `RelayerKeyArgs`, the new fields are optional.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct RelayerKeyArgsV2 {
+    pub public_key: PublicKey,
+    /// Methods the key is allowed to call, a subset of `RELAYER_KEY_METHODS`.
+    /// All of them are allowed if the list is empty.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_methods: Vec<String>,
+    /// Block height starting from which the key can't be used. The key doesn't expire if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+impl RelayerKeyArgsV2 {
+    #[must_use]
+    pub fn metadata(&self) -> RelayerKeyMetadata {
+        RelayerKeyMetadata {
+            allowed_methods: self.allowed_methods.clone(),
+            expires_at: self.expires_at,
+        }
+    }
+}
+
+impl From<RelayerKeyArgs> for RelayerKeyArgsV2 {
+    fn from(args: RelayerKeyArgs) -> Self {
+        Self {
+            public_key: args.public_key,
+            allowed_methods: Vec::new(),
+            expires_at: None,
+        }
+    }
+}
+
+/// Parameters for adding the relayer function call keys created before the list of the keys
+/// was introduced to the list.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct RelayerKeysArgs {
+    pub public_keys: Vec<PublicKey>,
+}
+
+/// Metadata the engine keeps for each relayer function call key.
+#[derive(
+    Debug, Default, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct RelayerKeyMetadata {
+    /// Methods the key is allowed to call. All of `RELAYER_KEY_METHODS` if empty.
+    pub allowed_methods: Vec<String>,
+    /// Block height starting from which the key can't be used.
+    pub expires_at: Option<u64>,
+}
+
+impl RelayerKeyMetadata {
+    /// Returns `true` if the key can't be used at the given block height.
+    #[must_use]
+    pub fn is_expired(&self, block_height: u64) -> bool {
+        self.expires_at
+            .map_or(false, |expires_at| block_height >= expires_at)
+    }
+
+    /// Returns `true` if the methods are a non-repeating subset of `RELAYER_KEY_METHODS`.
+    #[must_use]
+    pub fn has_valid_methods(&self) -> bool {
+        self.allowed_methods.iter().enumerate().all(|(i, method)| {
+            RELAYER_KEY_METHODS.contains(&method.as_str())
+                && !self.allowed_methods[..i].contains(method)
+        })
+    }
+
+    /// Comma separated list of the methods allowed by the function call key.
+    #[must_use]
+    pub fn function_names(&self) -> String {
+        if self.allowed_methods.is_empty() {
+            RELAYER_KEY_METHODS.join(",")
+        } else {
+            self.allowed_methods.join(",")
+        }
+    }
+}
+
+/// Optional parameters for `deploy_upgrade`.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DeployUpgradeArgs {
+    /// Hex encoded sha256 hash the staged code is expected to have.
+    pub code_hash: String,
+}
+
+/// Optional parameters for `state_migration`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+pub struct StateMigrationArgs {
+    /// The maximum number of items processed by a batched migration step.
+    pub batch_size: u32,
+}
+
+/// Roles which can be granted to accounts to perform a part of the owner's duties.
+/// The owner implicitly has all the roles.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Can stage upgrades of the contract and set the upgrade delay.
+    Upgrader,
+    /// Can pause the contract and precompiles.
+    Pauser,
+    /// Can resume the contract and precompiles.
+    Unpauser,
+    /// Can manage bridged tokens: metadata, code and migrations.
+    TokenAdmin,
+    /// Can manage the cross contract call factory.
+    XccAdmin,
+    /// Can add and remove relayer keys.
+    RelayerKeyManager,
+    /// Can add and remove addresses in the denylist.
+    DenylistManager,
+    /// Can enable the deploy allowlist and manage the approved deployers.
+    DeployerManager,
+    /// Can change the economic settings and the whitelists of the silo.
+    SiloAdmin,
+}
+
+/// Parameters for granting or revoking a role.
+#[derive(Debug, Clone, Eq, PartialEq, BorshSerialize, BorshDeserialize, Serialize, Deserialize)]
+pub struct RoleArgs {
+    pub role: Role,
+    pub account_id: AccountId,
+}
+
 pub mod errors {
     use crate::{account_id::ParseAccountError, String, ToString};
 
@@ -449,6 +720,34 @@ mod tests {
         assert_eq!(parsed_data, None);
     }
 
+    #[test]
+    fn test_deploy_erc20_token_args_deserialize() {
+        let nep141: AccountId = "token.near".parse().unwrap();
+        let new_input = DeployErc20TokenArgsV2 {
+            nep141: nep141.clone(),
+            sync_metadata: true,
+        };
+        let legacy_input = DeployErc20TokenArgs {
+            nep141: nep141.clone(),
+        };
+
+        let input_bytes = new_input.try_to_vec().unwrap();
+        let parsed_data = DeployErc20TokenArgsV2::deserialize(&input_bytes);
+        assert_eq!(parsed_data, Some(new_input));
+
+        let input_bytes = legacy_input.try_to_vec().unwrap();
+        let parsed_data = DeployErc20TokenArgsV2::deserialize(&input_bytes);
+        assert_eq!(
+            parsed_data,
+            Some(DeployErc20TokenArgsV2 {
+                nep141,
+                sync_metadata: false,
+            })
+        );
+
+        assert_eq!(DeployErc20TokenArgsV2::deserialize(&[1, 2, 3]), None);
+    }
+
     #[test]
     fn test_deserialize_relayer_key_args() {
         let json = r#"{"public_key": "ed25519:DcA2MzgpJbrUATQLLceocVckhhAqrkingax4oJ9kZ847"}"#;
@@ -458,5 +757,61 @@ mod tests {
         let args = serde_json::from_str::<RelayerKeyArgs>(json).unwrap();
 
         assert_eq!(args.public_key, public_key);
+
+        let args_v2 = serde_json::from_str::<RelayerKeyArgsV2>(json).unwrap();
+        assert_eq!(args_v2, RelayerKeyArgsV2::from(args));
+
+        let json = r#"{"public_key": "ed25519:DcA2MzgpJbrUATQLLceocVckhhAqrkingax4oJ9kZ847", "allowed_methods": ["submit"], "expires_at": 100}"#;
+        let args = serde_json::from_str::<RelayerKeyArgsV2>(json).unwrap();
+
+        assert_eq!(args.public_key, public_key);
+        assert_eq!(args.allowed_methods, vec!["submit".to_string()]);
+        assert_eq!(args.expires_at, Some(100));
+    }
+
+    #[test]
+    fn test_relayer_key_metadata() {
+        let metadata = RelayerKeyMetadata::default();
+        assert!(metadata.has_valid_methods());
+        assert!(!metadata.is_expired(u64::MAX));
+        assert_eq!(metadata.function_names(), "call,submit,submit_with_args");
+
+        let metadata = RelayerKeyMetadata {
+            allowed_methods: vec!["submit".into(), "call".into()],
+            expires_at: Some(10),
+        };
+        assert!(metadata.has_valid_methods());
+        assert!(!metadata.is_expired(9));
+        assert!(metadata.is_expired(10));
+        assert_eq!(metadata.function_names(), "submit,call");
+
+        let repeated = RelayerKeyMetadata {
+            allowed_methods: vec!["submit".into(), "submit".into()],
+            expires_at: None,
+        };
+        assert!(!repeated.has_valid_methods());
+        let unknown = RelayerKeyMetadata {
+            allowed_methods: vec!["deploy_code".into()],
+            expires_at: None,
+        };
+        assert!(!unknown.has_valid_methods());
+    }
+
+    #[test]
+    fn test_deserialize_role_args() {
+        let json = r#"{"role": "token_admin", "account_id": "admin.near"}"#;
+        let args = serde_json::from_str::<RoleArgs>(json).unwrap();
+
+        assert_eq!(args.role, Role::TokenAdmin);
+        assert_eq!(args.account_id.as_ref(), "admin.near");
+    }
+
+    #[test]
+    fn test_deserialize_whitelist_status_args() {
+        let json = r#"{"kind": "account", "enabled": true}"#;
+        let args = serde_json::from_str::<WhitelistStatusArgs>(json).unwrap();
+
+        assert_eq!(args.kind, WhitelistKind::Account);
+        assert!(args.enabled);
     }
 }
@@ -0,0 +1,404 @@
+//! Structured events emitted by the engine as NEAR logs following the NEP-297 standard,
+//! i.e. `EVENT_JSON:{"standard":"aurora-engine","version":"1.0.0","event":...,"data":...}`.
+//!
+//! Every state-changing admin, bridge and cross-contract call method emits one of these events,
+//! so indexers can follow them without parsing the receipts. The name of an event is the name of
+//! the method which emitted it.
+use crate::account_id::AccountId;
+use crate::parameters::engine::{Role, SiloParams, WhitelistKind};
+use crate::public_key::PublicKey;
+use crate::types::{Address, Balance, NEP141Wei, Wei};
+use crate::{String, ToString, Vec};
+use serde::{Deserialize, Serialize};
+
+/// Prefix of the NEAR logs containing NEP-297 events.
+pub const EVENT_JSON_PREFIX: &str = "EVENT_JSON:";
+/// Name of the standard of the events emitted by the engine.
+pub const EVENT_STANDARD: &str = "aurora-engine";
+/// Version of the schema of the events emitted by the engine.
+pub const EVENT_VERSION: &str = "1.0.0";
+
+/// Events emitted by the engine.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", content = "data", rename_all = "snake_case")]
+pub enum EngineEvent {
+    /// New code was staged for the upgrade of the contract.
+    StageUpgrade(UpgradeInfo),
+    /// The staged upgrade was cancelled.
+    CancelUpgrade(UpgradeInfo),
+    /// The staged code was deployed.
+    DeployUpgrade(UpgradeInfo),
+    /// The owner of the contract was changed.
+    SetOwner(OwnerChange),
+    /// The number of blocks between staging and deploying an upgrade was changed.
+    SetUpgradeDelayBlocks(UpgradeDelayBlocks),
+    /// The precompiles of the mask were paused.
+    PausePrecompiles(PrecompilesMask),
+    /// The precompiles of the mask were resumed.
+    ResumePrecompiles(PrecompilesMask),
+    /// The methods were paused.
+    PauseMethods(Methods),
+    /// The methods were resumed.
+    ResumeMethods(Methods),
+    /// The contract was paused.
+    PauseContract,
+    /// The contract was resumed.
+    ResumeContract,
+    /// The relayer key manager was changed.
+    SetKeyManager(KeyManager),
+    /// The role was granted to the account.
+    GrantRole(RoleChange),
+    /// The role was revoked from the account.
+    RevokeRole(RoleChange),
+    /// The address was added to the denylist.
+    DenyAddress(AddressInfo),
+    /// The address was removed from the denylist.
+    AllowAddress(AddressInfo),
+    /// The deploy allowlist mode was enabled or disabled.
+    SetDeployAllowlistEnabled(Enabled),
+    /// The address was allowed to deploy contracts.
+    AddDeployer(AddressInfo),
+    /// The address was disallowed to deploy contracts.
+    RemoveDeployer(AddressInfo),
+    /// The gas price and fee parameters of the silo were changed.
+    SetSiloParams(SiloParams),
+    /// The senders were exempted from paying gas fees.
+    AddFeeWaivedSenders(Addresses),
+    /// The senders were required to pay gas fees again.
+    RemoveFeeWaivedSenders(Addresses),
+    /// The whitelist was enabled or disabled.
+    SetWhitelistStatus(WhitelistStatus),
+    /// The addresses were added to the signer whitelist.
+    AddWhitelistedAddresses(Addresses),
+    /// The addresses were removed from the signer whitelist.
+    RemoveWhitelistedAddresses(Addresses),
+    /// The accounts were added to the account whitelist.
+    AddWhitelistedAccounts(Accounts),
+    /// The accounts were removed from the account whitelist.
+    RemoveWhitelistedAccounts(Accounts),
+    /// The relayer function call key was added.
+    AddRelayerKey(RelayerKey),
+    /// The relayer function call key was removed.
+    RemoveRelayerKey(RelayerKey),
+    /// The expired relayer function call keys were removed.
+    RemoveExpiredRelayerKeys(RelayerKeys),
+    /// The account registered the address which receives its relayer fees.
+    RegisterRelayer(Relayer),
+    /// The hashchain was started from the given block.
+    StartHashchain(HashchainStart),
+    /// The ERC-20 token was deployed for the NEP-141 token.
+    DeployErc20Token(TokenPair),
+    /// The deployment of the NEP-141 token for the ERC-20 token was started.
+    DeployNep141Token(TokenPair),
+    /// The NEP-141 token was registered for the ERC-20 token.
+    RegisterNep141Token(TokenPair),
+    /// The code of the NEP-141 tokens deployed for ERC-20 tokens was changed.
+    SetNep141Code(CodeInfo),
+    /// The code of the ERC-20 tokens deployed for NEP-141 tokens was changed.
+    SetErc20Code(CodeInfo),
+    /// The ERC-20 token was migrated to the current ERC-20 code.
+    MigrateErc20Token(Erc20Migration),
+    /// The metadata of the ERC-20 token was changed.
+    SetErc20Metadata(Erc20MetadataChange),
+    /// The eth-connector was initialized.
+    NewEthConnector(EthConnectorData),
+    /// The eth-connector data was changed.
+    SetEthConnectorContractData(EthConnectorData),
+    /// The paused flags of the eth-connector were changed.
+    SetPausedFlags(PausedFlags),
+    /// The deposited ETH was minted after the verification of the proof.
+    FinishDeposit(FinishDepositInfo),
+    /// The ETH was withdrawn to Ethereum.
+    Withdraw(WithdrawInfo),
+    /// The NEP-141 tokens were transferred to the engine.
+    FtOnTransfer(FtOnTransferInfo),
+    /// The tokens of a failed exit to NEAR were refunded.
+    RefundOnError(RefundInfo),
+    /// The code of the cross-contract call router was changed.
+    FactoryUpdate(CodeInfo),
+    /// The router deployed for the address was updated to the version.
+    FactoryUpdateAddressVersion(RouterVersion),
+    /// The address of the wNEAR ERC-20 token was changed.
+    FactorySetWnearAddress(AddressInfo),
+    /// The cross-contract call sub-account of the address was funded.
+    FundXccSubAccount(XccSubAccount),
+}
+
+/// Information about the code staged for the upgrade of the contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeInfo {
+    /// Hex encoded sha256 hash of the staged code.
+    pub code_hash: String,
+    /// Size of the staged code in bytes.
+    pub code_size: u64,
+    /// The upgrade can be deployed at block heights greater than this one.
+    pub deploy_after: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OwnerChange {
+    pub old_owner: AccountId,
+    pub new_owner: AccountId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeDelayBlocks {
+    pub upgrade_delay_blocks: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrecompilesMask {
+    pub paused_mask: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Methods {
+    pub methods: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyManager {
+    pub key_manager: Option<AccountId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoleChange {
+    pub role: Role,
+    pub account_id: AccountId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressInfo {
+    pub address: Address,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Addresses {
+    pub addresses: Vec<Address>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Accounts {
+    pub accounts: Vec<AccountId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Enabled {
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WhitelistStatus {
+    pub kind: WhitelistKind,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayerKey {
+    pub public_key: PublicKey,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayerKeys {
+    pub public_keys: Vec<PublicKey>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Relayer {
+    pub account_id: AccountId,
+    pub address: Address,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashchainStart {
+    pub block_height: u64,
+}
+
+/// A NEP-141 token and the ERC-20 token mapped to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenPair {
+    pub nep141: AccountId,
+    pub erc20: Address,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CodeInfo {
+    /// Hex encoded sha256 hash of the code.
+    pub code_hash: String,
+    /// Size of the code in bytes.
+    pub code_size: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Erc20Migration {
+    pub erc20: Address,
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Erc20MetadataChange {
+    pub erc20: Address,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EthConnectorData {
+    pub prover_account: AccountId,
+    pub eth_custodian_address: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PausedFlags {
+    pub paused_mask: u8,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FinishDepositInfo {
+    pub new_owner_id: AccountId,
+    pub amount: NEP141Wei,
+    pub fee: NEP141Wei,
+    pub relayer_id: AccountId,
+    pub proof_key: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WithdrawInfo {
+    pub sender_id: AccountId,
+    pub recipient_address: Address,
+    pub amount: NEP141Wei,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FtOnTransferInfo {
+    /// Account of the NEP-141 token.
+    pub token_id: AccountId,
+    pub sender_id: AccountId,
+    pub amount: Balance,
+    pub msg: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefundInfo {
+    pub recipient_address: Address,
+    /// Address of the refunded ERC-20 token or `None` for ETH.
+    pub erc20_address: Option<Address>,
+    pub amount: Wei,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouterVersion {
+    pub address: Address,
+    pub version: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XccSubAccount {
+    pub target: Address,
+    pub wnear_account_id: Option<AccountId>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Nep297Event<'a, T> {
+    standard: &'a str,
+    version: &'a str,
+    #[serde(flatten)]
+    event: T,
+}
+
+impl EngineEvent {
+    /// Returns the NEAR log representation of the event.
+    #[must_use]
+    pub fn to_log(&self) -> String {
+        let event = Nep297Event {
+            standard: EVENT_STANDARD,
+            version: EVENT_VERSION,
+            event: self,
+        };
+        // The serialization can't fail since the event only contains strings and numbers.
+        let json = serde_json::to_string(&event).unwrap_or_default();
+        EVENT_JSON_PREFIX.to_string() + &json
+    }
+
+    /// Parses the event from a NEAR log. Returns `None` if the log is not an event emitted by
+    /// the engine.
+    #[must_use]
+    pub fn from_log(log: &str) -> Option<Self> {
+        let json = log.strip_prefix(EVENT_JSON_PREFIX)?;
+        let event: Nep297Event<Self> = serde_json::from_str(json).ok()?;
+        (event.standard == EVENT_STANDARD).then_some(event.event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_engine_event_log_roundtrip() {
+        let event = EngineEvent::StageUpgrade(UpgradeInfo {
+            code_hash: "ab".repeat(32),
+            code_size: 1024,
+            deploy_after: 100,
+        });
+        let log = event.to_log();
+
+        assert_eq!(
+            log,
+            format!(
+                r#"EVENT_JSON:{{"standard":"aurora-engine","version":"1.0.0","event":"stage_upgrade","data":{{"code_hash":"{}","code_size":1024,"deploy_after":100}}}}"#,
+                "ab".repeat(32)
+            )
+        );
+        assert_eq!(EngineEvent::from_log(&log), Some(event));
+        assert_eq!(EngineEvent::from_log("some log"), None);
+    }
+
+    #[test]
+    fn test_engine_event_data_serialization() {
+        let event = EngineEvent::SetOwner(OwnerChange {
+            old_owner: "owner.near".parse().unwrap(),
+            new_owner: "new-owner.near".parse().unwrap(),
+        });
+        let log = event.to_log();
+        assert_eq!(
+            log,
+            r#"EVENT_JSON:{"standard":"aurora-engine","version":"1.0.0","event":"set_owner","data":{"old_owner":"owner.near","new_owner":"new-owner.near"}}"#
+        );
+        assert_eq!(EngineEvent::from_log(&log), Some(event));
+
+        let event = EngineEvent::GrantRole(RoleChange {
+            role: Role::Pauser,
+            account_id: "pauser.near".parse().unwrap(),
+        });
+        assert_eq!(EngineEvent::from_log(&event.to_log()), Some(event));
+
+        let event = EngineEvent::Withdraw(WithdrawInfo {
+            sender_id: "alice.near".parse().unwrap(),
+            recipient_address: Address::zero(),
+            amount: NEP141Wei::new(100),
+        });
+        assert_eq!(EngineEvent::from_log(&event.to_log()), Some(event));
+    }
+
+    #[test]
+    fn test_engine_event_without_data() {
+        let log = EngineEvent::PauseContract.to_log();
+        assert_eq!(
+            log,
+            r#"EVENT_JSON:{"standard":"aurora-engine","version":"1.0.0","event":"pause_contract"}"#
+        );
+        assert_eq!(
+            EngineEvent::from_log(&log),
+            Some(EngineEvent::PauseContract)
+        );
+    }
+
+    #[test]
+    fn test_engine_event_of_other_standard_is_ignored() {
+        let log =
+            r#"EVENT_JSON:{"standard":"nep141","version":"1.0.0","event":"ft_mint","data":[]}"#;
+        assert_eq!(EngineEvent::from_log(log), None);
+    }
+}
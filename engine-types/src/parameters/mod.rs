@@ -2,5 +2,6 @@ pub use promise::*;
 
 pub mod connector;
 pub mod engine;
+pub mod events;
 pub mod promise;
 pub mod xcc;
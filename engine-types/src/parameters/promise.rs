@@ -331,4 +331,11 @@ pub enum CrossContractCallArgs {
     /// much gas left to perform it. In this case, the promise could be `Delayed` (stored in the router)
     /// and executed in a separate transaction with a fresh 300 Tgas available for it.
     Delayed(PromiseArgs),
+    /// Create a named sub-account of the caller's router account (`{name}.{address}.{aurora}`)
+    /// with the given access keys. The router executes the batch immediately, then the engine
+    /// deploys the router contract to the new account. The new account id is emitted in a log
+    /// with the `SUB_ACCOUNT_TOPIC` topic of the cross contract call precompile.
+    CreateSubAccount(crate::parameters::xcc::CreateSubAccountArgs),
+    /// Add and delete access keys of a sub-account previously created with `CreateSubAccount`.
+    UpdateSubAccountKeys(crate::parameters::xcc::UpdateSubAccountKeysArgs),
 }
@@ -1,6 +1,9 @@
 use crate::account_id::AccountId;
 use crate::borsh::{self, BorshDeserialize, BorshSerialize};
-use crate::types::Address;
+use crate::parameters::promise::PromiseAction;
+use crate::public_key::PublicKey;
+use crate::types::{Address, Yocto};
+use crate::{String, Vec};
 
 #[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
 pub struct AddressVersionUpdateArgs {
@@ -26,3 +29,84 @@ impl CodeVersion {
         Self(self.0 + 1)
     }
 }
+
+/// Args for creating a named sub-account of the caller's router account,
+/// i.e. the account `{name}.{address}.{aurora}`.
+///
+/// The account is created, funded with `initial_balance` and given the listed access keys in
+/// a single batch executed by the router. The engine then deploys the router contract to the
+/// new account, so that its keys can later be changed by the caller with
+/// `UpdateSubAccountKeysArgs`. The `initial_balance` must cover the storage of the router.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct CreateSubAccountArgs {
+    /// The sub-account name without any `.` separators.
+    pub name: String,
+    pub initial_balance: Yocto,
+    pub keys: Vec<SubAccountKey>,
+}
+
+/// Args for changing the access keys of an existing sub-account of the caller's router account.
+///
+/// NEAR only allows an account to change its own keys, so the caller's router asks the router
+/// deployed on the sub-account (see `CreateSubAccountArgs`) to execute the changes.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub struct UpdateSubAccountKeysArgs {
+    /// The sub-account name without any `.` separators.
+    pub name: String,
+    pub add_keys: Vec<SubAccountKey>,
+    pub delete_keys: Vec<PublicKey>,
+}
+
+impl UpdateSubAccountKeysArgs {
+    /// The batch of actions the sub-account executes on itself.
+    #[must_use]
+    pub fn to_actions(&self) -> Vec<PromiseAction> {
+        self.add_keys
+            .iter()
+            .map(SubAccountKey::to_action)
+            .chain(
+                self.delete_keys
+                    .iter()
+                    .map(|public_key| PromiseAction::DeleteKey {
+                        public_key: *public_key,
+                    }),
+            )
+            .collect()
+    }
+}
+
+/// Access key added to a sub-account of a router.
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize, BorshSerialize)]
+pub enum SubAccountKey {
+    FullAccess(PublicKey),
+    FunctionCall {
+        public_key: PublicKey,
+        allowance: Yocto,
+        receiver_id: AccountId,
+        function_names: String,
+    },
+}
+
+impl SubAccountKey {
+    #[must_use]
+    pub fn to_action(&self) -> PromiseAction {
+        match self {
+            Self::FullAccess(public_key) => PromiseAction::AddFullAccessKey {
+                public_key: *public_key,
+                nonce: 0,
+            },
+            Self::FunctionCall {
+                public_key,
+                allowance,
+                receiver_id,
+                function_names,
+            } => PromiseAction::AddFunctionCallKey {
+                public_key: *public_key,
+                nonce: 0,
+                allowance: *allowance,
+                receiver_id: receiver_id.clone(),
+                function_names: function_names.clone(),
+            },
+        }
+    }
+}
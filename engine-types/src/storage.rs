@@ -34,6 +34,12 @@ pub enum KeyPrefix {
     CrossContractCall = 0xa,
     RelayerFunctionCallKey = 0xb,
     Hashchain = 0xc,
+    NativeErc20 = 0xd,
+    Erc20Code = 0xe,
+    Denylist = 0xf,
+    DeployAllowlist = 0x10,
+    FeeWaivedSenders = 0x11,
+    Whitelist = 0x12,
 }
 
 impl From<KeyPrefix> for u8 {
@@ -52,6 +58,12 @@ impl From<KeyPrefix> for u8 {
             KeyPrefix::CrossContractCall => 0xa,
             KeyPrefix::RelayerFunctionCallKey => 0xb,
             KeyPrefix::Hashchain => 0xc,
+            KeyPrefix::NativeErc20 => 0xd,
+            KeyPrefix::Erc20Code => 0xe,
+            KeyPrefix::Denylist => 0xf,
+            KeyPrefix::DeployAllowlist => 0x10,
+            KeyPrefix::FeeWaivedSenders => 0x11,
+            KeyPrefix::Whitelist => 0x12,
         }
     }
 }
@@ -83,10 +95,16 @@ impl From<EthConnectorStorageId> for u8 {
 /// We can't use const generic over Enum, but we can do it over integral type
 pub type KeyPrefixU8 = u8;
 
-// TODO: Derive From<u8> using macro to avoid missing new arguments in the future
-impl From<KeyPrefixU8> for KeyPrefix {
-    fn from(value: KeyPrefixU8) -> Self {
-        match value {
+/// Error returned when a byte is not the value of any `KeyPrefix`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownKeyPrefix(pub KeyPrefixU8);
+
+// TODO: Derive TryFrom<u8> using macro to avoid missing new arguments in the future
+impl TryFrom<KeyPrefixU8> for KeyPrefix {
+    type Error = UnknownKeyPrefix;
+
+    fn try_from(value: KeyPrefixU8) -> Result<Self, Self::Error> {
+        Ok(match value {
             0x0 => Self::Config,
             0x1 => Self::Nonce,
             0x2 => Self::Balance,
@@ -99,8 +117,15 @@ impl From<KeyPrefixU8> for KeyPrefix {
             0x9 => Self::Erc20Nep141Map,
             0xa => Self::CrossContractCall,
             0xb => Self::RelayerFunctionCallKey,
-            _ => unreachable!("Unknown key prefix"),
-        }
+            0xc => Self::Hashchain,
+            0xd => Self::NativeErc20,
+            0xe => Self::Erc20Code,
+            0xf => Self::Denylist,
+            0x10 => Self::DeployAllowlist,
+            0x11 => Self::FeeWaivedSenders,
+            0x12 => Self::Whitelist,
+            _ => return Err(UnknownKeyPrefix(value)),
+        })
     }
 }
 
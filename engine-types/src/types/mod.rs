@@ -25,6 +25,9 @@ pub type StorageUsage = u64;
 /// Selector to call `mint` function in ERC 20 contract.
 /// `keccak(b"mint(address,uint256)")[..4];`
 pub const ERC20_MINT_SELECTOR: &[u8] = &[64, 193, 15, 25];
+/// Selector to call `transfer` function in ERC-20 contract.
+/// `keccak(b"transfer(address,uint256)")[..4];`
+pub const ERC20_TRANSFER_SELECTOR: &[u8] = &[169, 5, 156, 187];
 /// Selector to call `setMetadata` function in ERC-20 contact.
 /// `keccak(b"setMetadata(string,string,uint8)")[..4];`
 pub const ERC20_SET_METADATA_SELECTOR: &[u8] = &[55, 210, 194, 244];
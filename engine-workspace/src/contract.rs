@@ -1,26 +1,34 @@
 use crate::account::Account;
 use crate::node::Node;
 use crate::operation::{
-    CallAddRelayerKey, CallCall, CallDeployCode, CallDeployErc20Token, CallDeployUpgrade,
-    CallDeposit, CallFactorySetWNearAddress, CallFactoryUpdate, CallFactoryUpdateAddressVersion,
+    CallAddDeployer, CallAddFeeWaivedSenders, CallAddRelayerKey, CallAddWhitelistedAccounts,
+    CallAddWhitelistedAddresses, CallAllowAddress, CallCall, CallCancelUpgrade, CallDenyAddress,
+    CallDeployCode, CallDeployErc20Token, CallDeployUpgrade, CallDeposit,
+    CallFactorySetWNearAddress, CallFactoryUpdate, CallFactoryUpdateAddressVersion,
     CallFtOnTransfer, CallFtTransfer, CallFtTransferCall, CallFundXccSubAccount, CallMintAccount,
-    CallNew, CallNewEthConnector, CallPausePrecompiles, CallRefundOnError, CallRegisterRelayer,
-    CallRemoveRelayerKey, CallResumePrecompiles, CallSetEthConnectorContractData,
-    CallSetKeyManager, CallSetPausedFlags, CallStageUpgrade, CallStateMigration,
-    CallStorageDeposit, CallStorageUnregister, CallStorageWithdraw, CallSubmit, CallWithdraw,
-    ViewAccountsCounter, ViewBalance, ViewBlockHash, ViewBridgeProver, ViewChainId, ViewCode,
-    ViewErc20FromNep141, ViewFactoryWnearAddress, ViewFtBalanceOf, ViewFtBalanceOfEth,
-    ViewFtMetadata, ViewFtTotalEthSupplyOnAurora, ViewFtTotalEthSupplyOnNear, ViewFtTotalSupply,
-    ViewIsUsedProof, ViewNep141FromErc20, ViewNonce, ViewOwner, ViewPausedFlags,
-    ViewPausedPrecompiles, ViewStorageAt, ViewStorageBalanceOf, ViewUpgradeIndex, ViewVersion,
-    ViewView,
+    CallNew, CallNewEthConnector, CallPauseMethods, CallPausePrecompiles, CallRefundOnError,
+    CallRegisterRelayer, CallRemoveDeployer, CallRemoveExpiredRelayerKeys,
+    CallRemoveFeeWaivedSenders, CallRemoveRelayerKey, CallRemoveWhitelistedAccounts,
+    CallRemoveWhitelistedAddresses, CallResumeMethods, CallResumePrecompiles,
+    CallSetDeployAllowlistEnabled, CallSetEthConnectorContractData, CallSetKeyManager,
+    CallSetPausedFlags, CallSetSiloParams, CallSetWhitelistStatus, CallStageUpgrade,
+    CallStateMigration, CallStorageDeposit, CallStorageUnregister, CallStorageWithdraw, CallSubmit,
+    CallWithdraw, ViewAccountWhitelist, ViewAccountsCounter, ViewAddressWhitelist, ViewBalance,
+    ViewBlockHash, ViewBridgeProver, ViewChainId, ViewCode, ViewDenylist, ViewDeployers,
+    ViewErc20FromNep141, ViewFactoryWnearAddress, ViewFeeWaivedSenders, ViewFtBalanceOf,
+    ViewFtBalanceOfEth, ViewFtMetadata, ViewFtTotalEthSupplyOnAurora, ViewFtTotalEthSupplyOnNear,
+    ViewFtTotalSupply, ViewIsAddressDenied, ViewIsDeployAllowlistEnabled, ViewIsDeployerAllowed,
+    ViewIsUsedProof, ViewNep141FromErc20, ViewNonce, ViewOwner, ViewPausedFlags, ViewPausedMethods,
+    ViewPausedPrecompiles, ViewRelayerKeys, ViewSiloParams, ViewStagedUpgrade, ViewStorageAt,
+    ViewStorageBalanceOf, ViewUpgradeIndex, ViewVersion, ViewView,
 };
 use crate::transaction::{CallTransaction, ViewTransaction};
 use aurora_engine_types::account_id::AccountId;
 use aurora_engine_types::parameters::connector::{FungibleTokenMetadata, Proof};
 use aurora_engine_types::parameters::engine::{
-    CallArgs, FunctionCallArgsV2, NewCallArgs, NewCallArgsV2, PausedMask, RelayerKeyArgs,
-    RelayerKeyManagerArgs,
+    AccountsArgs, AddressesArgs, CallArgs, DenylistArgs, DeployAllowlistModeArgs, DeployerArgs,
+    FunctionCallArgsV2, NewCallArgs, NewCallArgsV2, PauseMethodsArgs, PausedMask, RelayerKeyArgs,
+    RelayerKeyArgsV2, RelayerKeyManagerArgs, SiloParams, WhitelistKind, WhitelistStatusArgs,
 };
 use aurora_engine_types::parameters::xcc::FundXccArgs;
 use aurora_engine_types::types::{Address, RawU256, WeiU256};
@@ -243,6 +251,10 @@ impl EngineContract {
         CallStageUpgrade::call(&self.contract).args(bytes)
     }
 
+    pub fn cancel_upgrade(&self) -> CallCancelUpgrade {
+        CallCancelUpgrade::call(&self.contract)
+    }
+
     pub fn deploy_upgrade(&self) -> CallDeployUpgrade {
         CallDeployUpgrade::call(&self.contract)
     }
@@ -276,13 +288,92 @@ impl EngineContract {
         CallSetKeyManager::call(&self.contract).args_json(args)
     }
 
-    pub fn add_relayer_key(&self, key: RelayerKeyArgs) -> CallAddRelayerKey {
+    pub fn add_relayer_key(&self, key: RelayerKeyArgsV2) -> CallAddRelayerKey {
         CallAddRelayerKey::call(&self.contract).args_json(key)
     }
 
     pub fn remove_relayer_key(&self, key: RelayerKeyArgs) -> CallRemoveRelayerKey {
         CallRemoveRelayerKey::call(&self.contract).args_json(key)
     }
+
+    pub fn remove_expired_relayer_keys(&self) -> CallRemoveExpiredRelayerKeys {
+        CallRemoveExpiredRelayerKeys::call(&self.contract)
+    }
+
+    pub fn pause_methods(&self, args: PauseMethodsArgs) -> CallPauseMethods {
+        CallPauseMethods::call(&self.contract).args_json(args)
+    }
+
+    pub fn resume_methods(&self, args: PauseMethodsArgs) -> CallResumeMethods {
+        CallResumeMethods::call(&self.contract).args_json(args)
+    }
+
+    pub fn deny_address(&self, address: Address) -> CallDenyAddress {
+        CallDenyAddress::call(&self.contract).args_json(DenylistArgs { address })
+    }
+
+    pub fn allow_address(&self, address: Address) -> CallAllowAddress {
+        CallAllowAddress::call(&self.contract).args_json(DenylistArgs { address })
+    }
+
+    pub fn set_deploy_allowlist_enabled(&self, enabled: bool) -> CallSetDeployAllowlistEnabled {
+        CallSetDeployAllowlistEnabled::call(&self.contract)
+            .args_json(DeployAllowlistModeArgs { enabled })
+    }
+
+    pub fn add_deployer(&self, address: Address) -> CallAddDeployer {
+        CallAddDeployer::call(&self.contract).args_json(DeployerArgs { address })
+    }
+
+    pub fn remove_deployer(&self, address: Address) -> CallRemoveDeployer {
+        CallRemoveDeployer::call(&self.contract).args_json(DeployerArgs { address })
+    }
+
+    pub fn set_silo_params(&self, params: SiloParams) -> CallSetSiloParams {
+        CallSetSiloParams::call(&self.contract).args_json(params)
+    }
+
+    pub fn add_fee_waived_senders(&self, addresses: Vec<Address>) -> CallAddFeeWaivedSenders {
+        CallAddFeeWaivedSenders::call(&self.contract).args_json(AddressesArgs { addresses })
+    }
+
+    pub fn remove_fee_waived_senders(&self, addresses: Vec<Address>) -> CallRemoveFeeWaivedSenders {
+        CallRemoveFeeWaivedSenders::call(&self.contract).args_json(AddressesArgs { addresses })
+    }
+
+    pub fn set_whitelist_status(
+        &self,
+        kind: WhitelistKind,
+        enabled: bool,
+    ) -> CallSetWhitelistStatus {
+        CallSetWhitelistStatus::call(&self.contract)
+            .args_json(WhitelistStatusArgs { kind, enabled })
+    }
+
+    pub fn add_whitelisted_addresses(
+        &self,
+        addresses: Vec<Address>,
+    ) -> CallAddWhitelistedAddresses {
+        CallAddWhitelistedAddresses::call(&self.contract).args_json(AddressesArgs { addresses })
+    }
+
+    pub fn remove_whitelisted_addresses(
+        &self,
+        addresses: Vec<Address>,
+    ) -> CallRemoveWhitelistedAddresses {
+        CallRemoveWhitelistedAddresses::call(&self.contract).args_json(AddressesArgs { addresses })
+    }
+
+    pub fn add_whitelisted_accounts(&self, accounts: Vec<AccountId>) -> CallAddWhitelistedAccounts {
+        CallAddWhitelistedAccounts::call(&self.contract).args_json(AccountsArgs { accounts })
+    }
+
+    pub fn remove_whitelisted_accounts(
+        &self,
+        accounts: Vec<AccountId>,
+    ) -> CallRemoveWhitelistedAccounts {
+        CallRemoveWhitelistedAccounts::call(&self.contract).args_json(AccountsArgs { accounts })
+    }
 }
 
 /// View functions
@@ -323,10 +414,58 @@ impl EngineContract {
         ViewUpgradeIndex::view(&self.contract)
     }
 
+    pub fn get_staged_upgrade(&self) -> ViewStagedUpgrade {
+        ViewStagedUpgrade::view(&self.contract)
+    }
+
+    pub fn get_relayer_keys(&self) -> ViewRelayerKeys {
+        ViewRelayerKeys::view(&self.contract)
+    }
+
     pub fn get_paused_precompiles(&self) -> ViewPausedPrecompiles {
         ViewPausedPrecompiles::view(&self.contract)
     }
 
+    pub fn get_paused_methods(&self) -> ViewPausedMethods {
+        ViewPausedMethods::view(&self.contract)
+    }
+
+    pub fn get_denylist(&self) -> ViewDenylist {
+        ViewDenylist::view(&self.contract)
+    }
+
+    pub fn is_address_denied(&self, address: Address) -> ViewIsAddressDenied {
+        ViewIsAddressDenied::view(&self.contract).args_json(DenylistArgs { address })
+    }
+
+    pub fn is_deploy_allowlist_enabled(&self) -> ViewIsDeployAllowlistEnabled {
+        ViewIsDeployAllowlistEnabled::view(&self.contract)
+    }
+
+    pub fn get_deployers(&self) -> ViewDeployers {
+        ViewDeployers::view(&self.contract)
+    }
+
+    pub fn is_deployer_allowed(&self, address: Address) -> ViewIsDeployerAllowed {
+        ViewIsDeployerAllowed::view(&self.contract).args_json(DeployerArgs { address })
+    }
+
+    pub fn get_silo_params(&self) -> ViewSiloParams {
+        ViewSiloParams::view(&self.contract)
+    }
+
+    pub fn get_fee_waived_senders(&self) -> ViewFeeWaivedSenders {
+        ViewFeeWaivedSenders::view(&self.contract)
+    }
+
+    pub fn get_address_whitelist(&self) -> ViewAddressWhitelist {
+        ViewAddressWhitelist::view(&self.contract)
+    }
+
+    pub fn get_account_whitelist(&self) -> ViewAccountWhitelist {
+        ViewAccountWhitelist::view(&self.contract)
+    }
+
     pub fn get_block_hash(&self, block_height: u64) -> ViewBlockHash {
         ViewBlockHash::view(&self.contract).args_borsh(block_height)
     }
@@ -1,4 +1,5 @@
 use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::public_key::PublicKey;
 use std::str::FromStr;
 use std::time::Duration;
 use tokio::time::Instant;
@@ -40,6 +41,27 @@ impl Node {
             .map_err(Into::into)
     }
 
+    pub async fn get_code(&self, account_id: &AccountId) -> anyhow::Result<Vec<u8>> {
+        let account_id = workspaces::AccountId::from_str(account_id.as_ref())?;
+
+        self.worker.view_code(&account_id).await.map_err(Into::into)
+    }
+
+    pub async fn get_access_keys(&self, account_id: &AccountId) -> anyhow::Result<Vec<PublicKey>> {
+        let account_id = workspaces::AccountId::from_str(account_id.as_ref())?;
+
+        self.worker
+            .view_access_keys(&account_id)
+            .await?
+            .into_iter()
+            .map(|info| {
+                serde_json::to_value(info.public_key)
+                    .and_then(serde_json::from_value)
+                    .map_err(Into::into)
+            })
+            .collect()
+    }
+
     async fn create_root_account(
         worker: &Worker<Sandbox>,
         root_acc_name: &str,
@@ -1,6 +1,9 @@
 use aurora_engine_types::account_id::AccountId;
 use aurora_engine_types::parameters::connector::{FungibleTokenMetadata, WithdrawResult};
-use aurora_engine_types::parameters::engine::{StorageBalance, SubmitResult, TransactionStatus};
+use aurora_engine_types::parameters::engine::{
+    RelayerKeyArgsV2, SiloParams, StorageBalance, SubmitResult, TransactionStatus, Whitelist,
+};
+use aurora_engine_types::parameters::events::UpgradeInfo;
 use aurora_engine_types::types::Address;
 use aurora_engine_types::{H256, U256};
 use near_sdk::json_types::U128;
@@ -33,12 +36,38 @@ impl_call_return![
     (CallResumePrecompiles, Call::ResumePrecompiles),
     (CallPausePrecompiles, Call::PausePrecompiles),
     (CallStageUpgrade, Call::StageUpgrade),
+    (CallCancelUpgrade, Call::CancelUpgrade),
     (CallStateMigration, Call::StateMigration),
     (CallMintAccount, Call::MintAccount),
     (CallSetPausedFlags, Call::SetPausedFlags),
     (CallSetKeyManager, Call::SetKeyManager),
     (CallAddRelayerKey, Call::AddRelayerKey),
     (CallRemoveRelayerKey, Call::RemoveRelayerKey),
+    (CallRemoveExpiredRelayerKeys, Call::RemoveExpiredRelayerKeys),
+    (CallPauseMethods, Call::PauseMethods),
+    (CallResumeMethods, Call::ResumeMethods),
+    (CallDenyAddress, Call::DenyAddress),
+    (CallAllowAddress, Call::AllowAddress),
+    (
+        CallSetDeployAllowlistEnabled,
+        Call::SetDeployAllowlistEnabled
+    ),
+    (CallAddDeployer, Call::AddDeployer),
+    (CallRemoveDeployer, Call::RemoveDeployer),
+    (CallSetSiloParams, Call::SetSiloParams),
+    (CallAddFeeWaivedSenders, Call::AddFeeWaivedSenders),
+    (CallRemoveFeeWaivedSenders, Call::RemoveFeeWaivedSenders),
+    (CallSetWhitelistStatus, Call::SetWhitelistStatus),
+    (CallAddWhitelistedAddresses, Call::AddWhitelistedAddresses),
+    (
+        CallRemoveWhitelistedAddresses,
+        Call::RemoveWhitelistedAddresses
+    ),
+    (CallAddWhitelistedAccounts, Call::AddWhitelistedAccounts),
+    (
+        CallRemoveWhitelistedAccounts,
+        Call::RemoveWhitelistedAccounts
+    ),
 ];
 
 impl_call_return![
@@ -64,7 +93,19 @@ impl_view_return![
     (ViewBridgeProver => AccountId, View::BridgeProver, borsh),
     (ViewChainId => U256, View::ChainId, borsh_U256),
     (ViewUpgradeIndex => u64, View::UpgradeIndex, borsh),
+    (ViewStagedUpgrade => UpgradeInfo, View::StagedUpgrade, json),
+    (ViewRelayerKeys => Vec<RelayerKeyArgsV2>, View::RelayerKeys, json),
     (ViewPausedPrecompiles => u32, View::PausedPrecompiles, borsh),
+    (ViewPausedMethods => Vec<String>, View::PausedMethods, json),
+    (ViewDenylist => Vec<Address>, View::Denylist, json),
+    (ViewIsAddressDenied => bool, View::IsAddressDenied, json),
+    (ViewIsDeployAllowlistEnabled => bool, View::IsDeployAllowlistEnabled, json),
+    (ViewDeployers => Vec<Address>, View::Deployers, json),
+    (ViewIsDeployerAllowed => bool, View::IsDeployerAllowed, json),
+    (ViewSiloParams => SiloParams, View::SiloParams, json),
+    (ViewFeeWaivedSenders => Vec<Address>, View::FeeWaivedSenders, json),
+    (ViewAddressWhitelist => Whitelist<Address>, View::AddressWhitelist, json),
+    (ViewAccountWhitelist => Whitelist<AccountId>, View::AccountWhitelist, json),
     (ViewBlockHash => H256, View::BlockHash, borsh_H256),
     (ViewCode => Vec<u8>, View::Code, vec),
     (ViewBalance => U256, View::Balance, borsh_U256),
@@ -102,6 +143,7 @@ pub(crate) enum Call {
     StorageWithdraw,
     PausePrecompiles,
     StageUpgrade,
+    CancelUpgrade,
     DeployUpgrade,
     StateMigration,
     ResumePrecompiles,
@@ -116,6 +158,22 @@ pub(crate) enum Call {
     SetKeyManager,
     AddRelayerKey,
     RemoveRelayerKey,
+    RemoveExpiredRelayerKeys,
+    PauseMethods,
+    ResumeMethods,
+    DenyAddress,
+    AllowAddress,
+    SetDeployAllowlistEnabled,
+    AddDeployer,
+    RemoveDeployer,
+    SetSiloParams,
+    AddFeeWaivedSenders,
+    RemoveFeeWaivedSenders,
+    SetWhitelistStatus,
+    AddWhitelistedAddresses,
+    RemoveWhitelistedAddresses,
+    AddWhitelistedAccounts,
+    RemoveWhitelistedAccounts,
 }
 
 impl AsRef<str> for Call {
@@ -138,6 +196,7 @@ impl AsRef<str> for Call {
             Call::StorageWithdraw => "storage_withdraw",
             Call::PausePrecompiles => "pause_precompiles",
             Call::StageUpgrade => "stage_upgrade",
+            Call::CancelUpgrade => "cancel_upgrade",
             Call::DeployUpgrade => "deploy_upgrade",
             Call::StateMigration => "state_migration",
             Call::ResumePrecompiles => "resume_precompiles",
@@ -152,6 +211,22 @@ impl AsRef<str> for Call {
             Call::SetKeyManager => "set_key_manager",
             Call::AddRelayerKey => "add_relayer_key",
             Call::RemoveRelayerKey => "remove_relayer_key",
+            Call::RemoveExpiredRelayerKeys => "remove_expired_relayer_keys",
+            Call::PauseMethods => "pause_methods",
+            Call::ResumeMethods => "resume_methods",
+            Call::DenyAddress => "deny_address",
+            Call::AllowAddress => "allow_address",
+            Call::SetDeployAllowlistEnabled => "set_deploy_allowlist_enabled",
+            Call::AddDeployer => "add_deployer",
+            Call::RemoveDeployer => "remove_deployer",
+            Call::SetSiloParams => "set_silo_params",
+            Call::AddFeeWaivedSenders => "add_fee_waived_senders",
+            Call::RemoveFeeWaivedSenders => "remove_fee_waived_senders",
+            Call::SetWhitelistStatus => "set_whitelist_status",
+            Call::AddWhitelistedAddresses => "add_whitelisted_addresses",
+            Call::RemoveWhitelistedAddresses => "remove_whitelisted_addresses",
+            Call::AddWhitelistedAccounts => "add_whitelisted_accounts",
+            Call::RemoveWhitelistedAccounts => "remove_whitelisted_accounts",
         }
     }
 }
@@ -163,7 +238,19 @@ pub enum View {
     BridgeProver,
     ChainId,
     UpgradeIndex,
+    StagedUpgrade,
+    RelayerKeys,
     PausedPrecompiles,
+    PausedMethods,
+    Denylist,
+    IsAddressDenied,
+    IsDeployAllowlistEnabled,
+    Deployers,
+    IsDeployerAllowed,
+    SiloParams,
+    FeeWaivedSenders,
+    AddressWhitelist,
+    AccountWhitelist,
     BlockHash,
     Code,
     Balance,
@@ -193,7 +280,19 @@ impl AsRef<str> for View {
             View::BridgeProver => "get_bridge_prover",
             View::ChainId => "get_chain_id",
             View::UpgradeIndex => "get_upgrade_index",
+            View::StagedUpgrade => "get_staged_upgrade",
+            View::RelayerKeys => "get_relayer_keys",
             View::PausedPrecompiles => "get_paused_precompiles",
+            View::PausedMethods => "get_paused_methods",
+            View::Denylist => "get_denylist",
+            View::IsAddressDenied => "is_address_denied",
+            View::IsDeployAllowlistEnabled => "is_deploy_allowlist_enabled",
+            View::Deployers => "get_deployers",
+            View::IsDeployerAllowed => "is_deployer_allowed",
+            View::SiloParams => "get_silo_params",
+            View::FeeWaivedSenders => "get_fee_waived_senders",
+            View::AddressWhitelist => "get_address_whitelist",
+            View::AccountWhitelist => "get_account_whitelist",
             View::BlockHash => "get_block_hash",
             View::Code => "get_code",
             View::Balance => "get_balance",
@@ -1,4 +1,5 @@
 use crate::admin_controlled::{AdminControlled, PausedMask};
+use crate::denylist;
 use crate::deposit_event::{DepositedEvent, FtTransferMessageData, TokenMessageData};
 use crate::engine::Engine;
 use crate::fungible_token::{self, FungibleToken, FungibleTokenMetadata, FungibleTokenOps};
@@ -91,8 +92,6 @@ impl<I: IO + Copy> EthConnectorContract<I> {
             return Err(error::InitContractError::AlreadyInitialized);
         }
 
-        sdk::log!("[init contract]");
-
         let contract_data = set_contract_data(
             &mut io,
             SetContractDataCallArgs {
@@ -254,8 +253,6 @@ impl<I: IO + Copy> EthConnectorContract<I> {
         data: FinishDepositCallArgs,
         prepaid_gas: NearGas,
     ) -> Result<Option<PromiseWithCallbackArgs>, error::FinishDepositError> {
-        sdk::log!("Finish deposit with the amount: {}", data.amount);
-
         // Mint tokens to recipient minus fee
         if let Some(msg) = data.msg {
             // Mint - calculate new balances
@@ -301,8 +298,6 @@ impl<I: IO + Copy> EthConnectorContract<I> {
 
     /// Record hash of the used proof in the storage.
     fn record_proof(&mut self, key: &str) -> Result<(), error::ProofUsed> {
-        sdk::log!("Record proof: {}", key);
-
         if self.is_used_event(key) {
             return Err(error::ProofUsed);
         }
@@ -317,8 +312,6 @@ impl<I: IO + Copy> EthConnectorContract<I> {
         owner_id: &AccountId,
         amount: NEP141Wei,
     ) -> Result<(), fungible_token::error::DepositError> {
-        sdk::log!("Mint {} nETH tokens for: {}", amount, owner_id);
-
         if self.ft.get_account_eth_balance(owner_id).is_none() {
             self.ft.accounts_insert(owner_id, ZERO_NEP141_WEI);
         }
@@ -331,7 +324,6 @@ impl<I: IO + Copy> EthConnectorContract<I> {
         address: Address,
         amount: Wei,
     ) -> Result<(), fungible_token::error::DepositError> {
-        sdk::log!("Mint {} ETH tokens for: {}", amount, address.encode());
         self.ft.internal_deposit_eth_to_aurora(address, amount)
     }
 
@@ -584,10 +576,12 @@ impl<I: IO + Copy> EthConnectorContract<I> {
         engine: &Engine<I, E, M>,
         args: &NEP141FtOnTransferArgs,
     ) -> Result<(), error::FtTransferCallError> {
-        sdk::log!("Call ft_on_transfer");
         // Parse message with specific rules
         let message_data = FtTransferMessageData::parse_on_transfer_message(&args.msg)
             .map_err(error::FtTransferCallError::MessageParseFailed)?;
+        if denylist::is_denied(&self.io, &message_data.recipient) {
+            return Err(error::FtTransferCallError::DeniedRecipient);
+        }
         let amount = Wei::new_u128(args.amount.as_u128());
         // Special case when predecessor_account_id is current_account_id
         let fee = Wei::from(message_data.fee);
@@ -840,6 +834,7 @@ pub mod error {
         MessageParseFailed(ParseOnTransferMessageError),
         InsufficientAmountForFee,
         Transfer(fungible_token::error::TransferError),
+        DeniedRecipient,
     }
 
     impl From<fungible_token::error::TransferError> for FtTransferCallError {
@@ -867,6 +862,7 @@ pub mod error {
                 Self::InsufficientAmountForFee => super::ERR_NOT_ENOUGH_BALANCE_FOR_FEE.as_bytes(),
                 Self::Transfer(e) => e.as_ref(),
                 Self::BalanceOverflow(e) => e.as_ref(),
+                Self::DeniedRecipient => crate::errors::ERR_DENIED_RECIPIENT,
             }
         }
     }
@@ -9,17 +9,21 @@
 use crate::{
     connector::EthConnectorContract,
     contract_methods::{
-        predecessor_address, require_key_manager_only, require_owner_only, require_paused,
-        require_running, ContractError,
+        emit_event, predecessor_address, require_key_manager_only, require_method_not_paused,
+        require_owner_only, require_paused, require_role, require_running, ContractError,
     },
+    denylist, deploy_allowlist,
     engine::{self, Engine},
-    errors,
+    errors, fee_waiver,
     hashchain::with_hashchain,
+    migrations,
     pausables::{
-        Authorizer, EngineAuthorizer, EnginePrecompilesPauser, PausedPrecompilesChecker,
-        PausedPrecompilesManager, PrecompileFlags,
+        Authorizer, EngineAuthorizer, EngineMethodsPauser, EnginePrecompilesPauser,
+        PausedMethodsChecker, PausedMethodsManager, PausedPrecompilesChecker,
+        PausedPrecompilesManager, PrecompileFlags, PAUSABLE_METHODS, UNPAUSABLE_METHODS,
     },
     state::{self, EngineState},
+    whitelist,
 };
 use aurora_engine_hashchain::{bloom::Bloom, hashchain::Hashchain};
 use aurora_engine_modexp::AuroraModExp;
@@ -30,22 +34,48 @@ use aurora_engine_sdk::{
     promise::PromiseHandler,
 };
 use aurora_engine_types::{
-    borsh::BorshDeserialize,
+    account_id::AccountId,
+    borsh::{self, BorshDeserialize, BorshSerialize},
     parameters::{
         engine::{
-            NewCallArgs, PausePrecompilesCallArgs, RelayerKeyArgs, RelayerKeyManagerArgs,
-            SetOwnerArgs, SetUpgradeDelayBlocksArgs, StartHashchainArgs,
+            AccountsArgs, AddressesArgs, DenylistArgs, DeployAllowlistModeArgs, DeployUpgradeArgs,
+            DeployerArgs, NewCallArgs, PauseMethodsArgs, PausePrecompilesCallArgs, RelayerKeyArgs,
+            RelayerKeyArgsV2, RelayerKeyManagerArgs, RelayerKeysArgs, Role, RoleArgs, SetOwnerArgs,
+            SetUpgradeDelayBlocksArgs, SiloParams, StartHashchainArgs, StateMigrationArgs,
+            WhitelistStatusArgs,
+        },
+        events::{
+            Accounts, AddressInfo, Addresses, Enabled, EngineEvent, HashchainStart, KeyManager,
+            Methods, OwnerChange, PrecompilesMask, Relayer, RelayerKey, RelayerKeys, RoleChange,
+            UpgradeDelayBlocks, UpgradeInfo, WhitelistStatus,
         },
         promise::{PromiseAction, PromiseBatchAction},
     },
     storage::{self, KeyPrefix},
-    types::{Address, Yocto},
-    vec,
+    types::{Address, RawH256, Yocto},
+    vec, Vec,
 };
 use function_name::named;
 
 const CODE_KEY: &[u8; 4] = b"CODE";
 const CODE_STAGE_KEY: &[u8; 10] = b"CODE_STAGE";
+const CODE_HASH_KEY: &[u8; 9] = b"CODE_HASH";
+
+/// Hash and size of the staged code, recorded to avoid reading the code itself.
+#[derive(BorshSerialize, BorshDeserialize)]
+struct StagedCode {
+    code_hash: RawH256,
+    code_size: u64,
+}
+
+impl StagedCode {
+    fn new(code: &[u8]) -> Result<Self, ContractError> {
+        Ok(Self {
+            code_hash: aurora_engine_sdk::sha256(code).0,
+            code_size: u64::try_from(code.len()).map_err(|_| errors::ERR_INVALID_UPGRADE)?,
+        })
+    }
+}
 
 #[named]
 pub fn new<I: IO + Copy, E: Env>(mut io: I, env: &E) -> Result<(), ContractError> {
@@ -79,6 +109,8 @@ pub fn new<I: IO + Copy, E: Env>(mut io: I, env: &E) -> Result<(), ContractError
     }
 
     state::set_state(&mut io, &state)?;
+    // A new storage doesn't need any migrations.
+    migrations::set_schema_version(&mut io, migrations::LATEST_SCHEMA_VERSION);
     Ok(())
 }
 
@@ -108,8 +140,12 @@ pub fn set_owner<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractErr
             return Err(errors::ERR_SAME_OWNER.into());
         }
 
-        state.owner_id = args.new_owner;
+        let old_owner = core::mem::replace(&mut state.owner_id, args.new_owner.clone());
         state::set_state(&mut io, &state)?;
+        emit_event(&EngineEvent::SetOwner(OwnerChange {
+            old_owner,
+            new_owner: args.new_owner,
+        }));
 
         Ok(())
     })
@@ -137,10 +173,13 @@ pub fn set_upgrade_delay_blocks<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<
     with_hashchain(io, env, function_name!(), |mut io| {
         let mut state = state::get_state(&io)?;
         require_running(&state)?;
-        require_owner_only(&state, &env.predecessor_account_id())?;
+        require_role(&state, Role::Upgrader, &env.predecessor_account_id())?;
         let args: SetUpgradeDelayBlocksArgs = io.read_input_borsh()?;
         state.upgrade_delay_blocks = args.upgrade_delay_blocks;
         state::set_state(&mut io, &state)?;
+        emit_event(&EngineEvent::SetUpgradeDelayBlocks(UpgradeDelayBlocks {
+            upgrade_delay_blocks: args.upgrade_delay_blocks,
+        }));
         Ok(())
     })
 }
@@ -157,16 +196,130 @@ pub fn stage_upgrade<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Contrac
         let state = state::get_state(&io)?;
         require_running(&state)?;
         let delay_block_height = env.block_height() + state.upgrade_delay_blocks;
-        require_owner_only(&state, &env.predecessor_account_id())?;
-        io.read_input_and_store(&storage::bytes_to_key(KeyPrefix::Config, CODE_KEY));
+        require_role(&state, Role::Upgrader, &env.predecessor_account_id())?;
+        let code = io.read_input().to_vec();
+        let staged_code = StagedCode::new(&code)?;
+        io.write_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_KEY), &code);
+        io.write_storage(
+            &storage::bytes_to_key(KeyPrefix::Config, CODE_HASH_KEY),
+            &staged_code
+                .try_to_vec()
+                .map_err(|_| errors::ERR_SERIALIZE)?,
+        );
         io.write_storage(
             &storage::bytes_to_key(KeyPrefix::Config, CODE_STAGE_KEY),
             &delay_block_height.to_le_bytes(),
         );
+        emit_event(&EngineEvent::StageUpgrade(staged_upgrade_info(&io)?));
         Ok(())
     })
 }
 
+#[named]
+pub fn cancel_upgrade<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::Upgrader, &env.predecessor_account_id())?;
+        let info = staged_upgrade_info(&io)?;
+        io.remove_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_KEY));
+        io.remove_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_HASH_KEY));
+        io.remove_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_STAGE_KEY));
+        emit_event(&EngineEvent::CancelUpgrade(info));
+        Ok(())
+    })
+}
+
+/// Checks that the staged upgrade can be deployed and clears the staging information.
+/// The deployment of the code itself only makes sense in the context of the Near runtime,
+/// so it is done by the caller.
+pub fn prepare_deploy_upgrade<I: IO + Copy, E: Env>(
+    mut io: I,
+    env: &E,
+) -> Result<(), ContractError> {
+    let state = state::get_state(&io)?;
+    require_running(&state)?;
+    let info = staged_upgrade_info(&io)?;
+    if env.block_height() <= info.deploy_after {
+        return Err(errors::ERR_NOT_ALLOWED_TOO_EARLY.into());
+    }
+
+    let input = io.read_input().to_vec();
+    if !input.is_empty() {
+        let args: DeployUpgradeArgs =
+            serde_json::from_slice(&input).map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        if !args.code_hash.eq_ignore_ascii_case(&info.code_hash) {
+            return Err(errors::ERR_UPGRADE_CODE_HASH_MISMATCH.into());
+        }
+    }
+
+    io.remove_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_HASH_KEY));
+    io.remove_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_STAGE_KEY));
+    emit_event(&EngineEvent::DeployUpgrade(info));
+    Ok(())
+}
+
+#[named]
+pub fn state_migration<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        // The migration is started by the engine itself right after the upgrade. If it
+        // doesn't complete in one call, it is resumed by an upgrader.
+        if env.assert_private_call().is_err() {
+            let state = state::get_state(&io)?;
+            require_role(&state, Role::Upgrader, &env.predecessor_account_id())?;
+        }
+
+        let input = io.read_input().to_vec();
+        let batch_size = if input.is_empty() {
+            migrations::DEFAULT_BATCH_SIZE
+        } else {
+            StateMigrationArgs::try_from_slice(&input)
+                .map_err(|_| errors::ERR_BORSH_DESERIALIZE)?
+                .batch_size
+        };
+        let status = migrations::migrate(&mut io, batch_size)?;
+        let bytes = serde_json::to_vec(&status).map_err(|_| errors::ERR_SERIALIZE)?;
+        io.return_output(&bytes);
+        Ok(())
+    })
+}
+
+pub fn get_schema_version<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let version = migrations::get_schema_version(&io)?;
+    io.return_output(&version.to_le_bytes());
+    Ok(())
+}
+
+pub fn get_staged_upgrade<I: IO>(mut io: I) -> Result<(), ContractError> {
+    let info = staged_upgrade_info(&io)?;
+    let bytes = serde_json::to_vec(&info).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+/// Returns the hash, size and deployment height of the staged upgrade.
+pub fn staged_upgrade_info<I: IO>(io: &I) -> Result<UpgradeInfo, ContractError> {
+    let deploy_after = internal_get_upgrade_index(io)?;
+    let staged_code =
+        match io.read_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_HASH_KEY)) {
+            Some(bytes) => StagedCode::try_from_slice(&bytes.to_vec())
+                .map_err(|_| errors::ERR_BORSH_DESERIALIZE)?,
+            // The code was staged before its hash started to be recorded.
+            None => {
+                let code = io
+                    .read_storage(&storage::bytes_to_key(KeyPrefix::Config, CODE_KEY))
+                    .ok_or(errors::ERR_NO_UPGRADE)?;
+                StagedCode::new(&code.to_vec())?
+            }
+        };
+
+    Ok(UpgradeInfo {
+        code_hash: hex::encode(staged_code.code_hash),
+        code_size: staged_code.code_size,
+        deploy_after,
+    })
+}
+
 #[named]
 pub fn resume_precompiles<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
     with_hashchain(io, env, function_name!(), |io| {
@@ -174,12 +327,15 @@ pub fn resume_precompiles<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Co
         require_running(&state)?;
         let predecessor_account_id = env.predecessor_account_id();
 
-        require_owner_only(&state, &predecessor_account_id)?;
+        require_role(&state, Role::Unpauser, &predecessor_account_id)?;
 
         let args: PausePrecompilesCallArgs = io.read_input_borsh()?;
         let flags = PrecompileFlags::from_bits_truncate(args.paused_mask);
         let mut pauser = EnginePrecompilesPauser::from_io(io);
         pauser.resume_precompiles(flags);
+        emit_event(&EngineEvent::ResumePrecompiles(PrecompilesMask {
+            paused_mask: flags.bits(),
+        }));
         Ok(())
     })
 }
@@ -198,6 +354,9 @@ pub fn pause_precompiles<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Con
         let flags = PrecompileFlags::from_bits_truncate(args.paused_mask);
         let mut pauser = EnginePrecompilesPauser::from_io(io);
         pauser.pause_precompiles(flags);
+        emit_event(&EngineEvent::PausePrecompiles(PrecompilesMask {
+            paused_mask: flags.bits(),
+        }));
         Ok(())
     })
 }
@@ -209,14 +368,66 @@ pub fn paused_precompiles<I: IO + Copy>(mut io: I) -> Result<(), ContractError>
     Ok(())
 }
 
+#[named]
+pub fn pause_methods<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        let state = state::get_state(&io)?;
+        require_role(&state, Role::Pauser, &env.predecessor_account_id())?;
+
+        let args: PauseMethodsArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        for method in &args.methods {
+            let method = method.as_str();
+            if UNPAUSABLE_METHODS.contains(&method) {
+                return Err(errors::ERR_METHOD_NOT_PAUSABLE.into());
+            }
+            if !PAUSABLE_METHODS.contains(&method) {
+                return Err(errors::ERR_UNKNOWN_METHOD.into());
+            }
+        }
+
+        let mut pauser = EngineMethodsPauser::from_io(io);
+        pauser.pause_methods(&args.methods)?;
+        emit_event(&EngineEvent::PauseMethods(Methods {
+            methods: args.methods,
+        }));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn resume_methods<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        let state = state::get_state(&io)?;
+        require_role(&state, Role::Unpauser, &env.predecessor_account_id())?;
+
+        let args: PauseMethodsArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        let mut pauser = EngineMethodsPauser::from_io(io);
+        pauser.resume_methods(&args.methods)?;
+        emit_event(&EngineEvent::ResumeMethods(Methods {
+            methods: args.methods,
+        }));
+        Ok(())
+    })
+}
+
+pub fn get_paused_methods<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let pauser = EngineMethodsPauser::from_io(io);
+    let bytes = serde_json::to_vec(&pauser.paused_methods()?).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
 #[named]
 pub fn pause_contract<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
     with_hashchain(io, env, function_name!(), |mut io| {
         let mut state = state::get_state(&io)?;
-        require_owner_only(&state, &env.predecessor_account_id())?;
+        require_role(&state, Role::Pauser, &env.predecessor_account_id())?;
         require_running(&state)?;
         state.is_paused = true;
         state::set_state(&mut io, &state)?;
+        emit_event(&EngineEvent::PauseContract);
         Ok(())
     })
 }
@@ -225,10 +436,11 @@ pub fn pause_contract<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Contra
 pub fn resume_contract<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
     with_hashchain(io, env, function_name!(), |mut io| {
         let mut state = state::get_state(&io)?;
-        require_owner_only(&state, &env.predecessor_account_id())?;
+        require_role(&state, Role::Unpauser, &env.predecessor_account_id())?;
         require_paused(&state)?;
         state.is_paused = false;
         state::set_state(&mut io, &state)?;
+        emit_event(&EngineEvent::ResumeContract);
         Ok(())
     })
 }
@@ -250,13 +462,400 @@ pub fn set_key_manager<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Contr
             return Err(errors::ERR_SAME_KEY_MANAGER.into());
         }
 
-        state.key_manager = key_manager;
+        state.key_manager = key_manager.clone();
+        state::set_state(&mut io, &state)?;
+        emit_event(&EngineEvent::SetKeyManager(KeyManager { key_manager }));
+
+        Ok(())
+    })
+}
+
+#[named]
+pub fn grant_role<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let mut state = state::get_state(&io)?;
+
+        require_running(&state)?;
+        require_owner_only(&state, &env.predecessor_account_id())?;
+
+        let args: RoleArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+
+        if !state.grant_role(args.role, args.account_id.clone()) {
+            return Err(errors::ERR_ROLE_ALREADY_GRANTED.into());
+        }
+        state::set_state(&mut io, &state)?;
+        emit_event(&EngineEvent::GrantRole(RoleChange {
+            role: args.role,
+            account_id: args.account_id,
+        }));
+
+        Ok(())
+    })
+}
+
+#[named]
+pub fn revoke_role<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let mut state = state::get_state(&io)?;
+
+        require_running(&state)?;
+        require_owner_only(&state, &env.predecessor_account_id())?;
+
+        let args: RoleArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+
+        if !state.revoke_role(args.role, &args.account_id) {
+            return Err(errors::ERR_ROLE_NOT_GRANTED.into());
+        }
+        state::set_state(&mut io, &state)?;
+        emit_event(&EngineEvent::RevokeRole(RoleChange {
+            role: args.role,
+            account_id: args.account_id,
+        }));
+
+        Ok(())
+    })
+}
+
+pub fn get_roles<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let state = state::get_state(&io)?;
+    let bytes = serde_json::to_vec(&state.roles).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn deny_address<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::DenylistManager, &env.predecessor_account_id())?;
+
+        let args: DenylistArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        if !denylist::deny(&mut io, &args.address) {
+            return Err(errors::ERR_ALREADY_DENIED.into());
+        }
+        emit_event(&EngineEvent::DenyAddress(AddressInfo {
+            address: args.address,
+        }));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn allow_address<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::DenylistManager, &env.predecessor_account_id())?;
+
+        let args: DenylistArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        if !denylist::allow(&mut io, &args.address) {
+            return Err(errors::ERR_NOT_DENIED.into());
+        }
+        emit_event(&EngineEvent::AllowAddress(AddressInfo {
+            address: args.address,
+        }));
+        Ok(())
+    })
+}
+
+pub fn is_address_denied<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let args: DenylistArgs = serde_json::from_slice(&io.read_input().to_vec())
+        .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+    let bytes = serde_json::to_vec(&denylist::is_denied(&io, &args.address))
+        .map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+pub fn get_denylist<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let bytes =
+        serde_json::to_vec(&denylist::get_denylist(&io)).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn set_deploy_allowlist_enabled<I: IO + Copy, E: Env>(
+    io: I,
+    env: &E,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let mut state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::DeployerManager, &env.predecessor_account_id())?;
+
+        let args: DeployAllowlistModeArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        state.is_deploy_allowlist_enabled = args.enabled;
+        state::set_state(&mut io, &state)?;
+        emit_event(&EngineEvent::SetDeployAllowlistEnabled(Enabled {
+            enabled: args.enabled,
+        }));
+        Ok(())
+    })
+}
+
+pub fn is_deploy_allowlist_enabled<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let state = state::get_state(&io)?;
+    let bytes = serde_json::to_vec(&state.is_deploy_allowlist_enabled)
+        .map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn add_deployer<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::DeployerManager, &env.predecessor_account_id())?;
+
+        let args: DeployerArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        if !deploy_allowlist::add(&mut io, &args.address) {
+            return Err(errors::ERR_DEPLOYER_ALREADY_ALLOWED.into());
+        }
+        emit_event(&EngineEvent::AddDeployer(AddressInfo {
+            address: args.address,
+        }));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn remove_deployer<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::DeployerManager, &env.predecessor_account_id())?;
+
+        let args: DeployerArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        if !deploy_allowlist::remove(&mut io, &args.address) {
+            return Err(errors::ERR_DEPLOYER_NOT_FOUND.into());
+        }
+        emit_event(&EngineEvent::RemoveDeployer(AddressInfo {
+            address: args.address,
+        }));
+        Ok(())
+    })
+}
+
+pub fn is_deployer_allowed<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let args: DeployerArgs = serde_json::from_slice(&io.read_input().to_vec())
+        .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+    let bytes = serde_json::to_vec(&deploy_allowlist::is_allowed(&io, &args.address))
+        .map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+pub fn get_deployers<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let bytes = serde_json::to_vec(&deploy_allowlist::get_deployers(&io))
+        .map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn set_silo_params<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let mut state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::SiloAdmin, &env.predecessor_account_id())?;
+
+        let args: SiloParams = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        if args
+            .fixed_gas_price
+            .map_or(false, |price| price < args.min_gas_price)
+        {
+            return Err(errors::ERR_INVALID_SILO_PARAMS.into());
+        }
+        state.silo_params = args.clone();
         state::set_state(&mut io, &state)?;
+        emit_event(&EngineEvent::SetSiloParams(args));
+        Ok(())
+    })
+}
+
+pub fn get_silo_params<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let state = state::get_state(&io)?;
+    let bytes = serde_json::to_vec(&state.silo_params).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn add_fee_waived_senders<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::SiloAdmin, &env.predecessor_account_id())?;
+
+        let args: AddressesArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        for address in &args.addresses {
+            fee_waiver::add(&mut io, address);
+        }
+        emit_event(&EngineEvent::AddFeeWaivedSenders(Addresses {
+            addresses: args.addresses,
+        }));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn remove_fee_waived_senders<I: IO + Copy, E: Env>(
+    io: I,
+    env: &E,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::SiloAdmin, &env.predecessor_account_id())?;
+
+        let args: AddressesArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        for address in &args.addresses {
+            fee_waiver::remove(&mut io, address);
+        }
+        emit_event(&EngineEvent::RemoveFeeWaivedSenders(Addresses {
+            addresses: args.addresses,
+        }));
+        Ok(())
+    })
+}
+
+pub fn get_fee_waived_senders<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let bytes =
+        serde_json::to_vec(&fee_waiver::get_senders(&io)).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn set_whitelist_status<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::SiloAdmin, &env.predecessor_account_id())?;
+
+        let args: WhitelistStatusArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        whitelist::set_enabled(&mut io, args.kind, args.enabled);
+        emit_event(&EngineEvent::SetWhitelistStatus(WhitelistStatus {
+            kind: args.kind,
+            enabled: args.enabled,
+        }));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn add_whitelisted_addresses<I: IO + Copy, E: Env>(
+    io: I,
+    env: &E,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::SiloAdmin, &env.predecessor_account_id())?;
+
+        let args: AddressesArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        for entry in &args.addresses {
+            whitelist::add(&mut io, entry);
+        }
+        emit_event(&EngineEvent::AddWhitelistedAddresses(Addresses {
+            addresses: args.addresses,
+        }));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn remove_whitelisted_addresses<I: IO + Copy, E: Env>(
+    io: I,
+    env: &E,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::SiloAdmin, &env.predecessor_account_id())?;
+
+        let args: AddressesArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        for entry in &args.addresses {
+            whitelist::remove(&mut io, entry);
+        }
+        emit_event(&EngineEvent::RemoveWhitelistedAddresses(Addresses {
+            addresses: args.addresses,
+        }));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn add_whitelisted_accounts<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::SiloAdmin, &env.predecessor_account_id())?;
+
+        let args: AccountsArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        for entry in &args.accounts {
+            whitelist::add(&mut io, entry);
+        }
+        emit_event(&EngineEvent::AddWhitelistedAccounts(Accounts {
+            accounts: args.accounts,
+        }));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn remove_whitelisted_accounts<I: IO + Copy, E: Env>(
+    io: I,
+    env: &E,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::SiloAdmin, &env.predecessor_account_id())?;
 
+        let args: AccountsArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        for entry in &args.accounts {
+            whitelist::remove(&mut io, entry);
+        }
+        emit_event(&EngineEvent::RemoveWhitelistedAccounts(Accounts {
+            accounts: args.accounts,
+        }));
         Ok(())
     })
 }
 
+pub fn get_address_whitelist<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let bytes = serde_json::to_vec(&whitelist::get_whitelist::<_, Address>(&io))
+        .map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+pub fn get_account_whitelist<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let bytes = serde_json::to_vec(&whitelist::get_whitelist::<_, AccountId>(&io))
+        .map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
 #[named]
 pub fn add_relayer_key<I: IO + Copy, E: Env, H: PromiseHandler>(
     io: I,
@@ -269,9 +868,17 @@ pub fn add_relayer_key<I: IO + Copy, E: Env, H: PromiseHandler>(
         require_running(&state)?;
         require_key_manager_only(&state, &env.predecessor_account_id())?;
 
-        let public_key = serde_json::from_slice::<RelayerKeyArgs>(&io.read_input().to_vec())
-            .map(|args| args.public_key)
+        let args: RelayerKeyArgsV2 = serde_json::from_slice(&io.read_input().to_vec())
             .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        let metadata = args.metadata();
+
+        if !metadata.has_valid_methods() {
+            return Err(errors::ERR_INVALID_RELAYER_KEY_METHODS.into());
+        }
+        if metadata.is_expired(env.block_height()) {
+            return Err(errors::ERR_INVALID_RELAYER_KEY_EXPIRY.into());
+        }
+
         let allowance = Yocto::new(env.attached_deposit());
         aurora_engine_sdk::log!("attached key allowance: {allowance}");
 
@@ -280,15 +887,15 @@ pub fn add_relayer_key<I: IO + Copy, E: Env, H: PromiseHandler>(
             return Err(errors::ERR_NOT_ALLOWED.into());
         }
 
-        engine::add_function_call_key(&mut io, &public_key);
+        engine::add_function_call_key(&mut io, &args.public_key, &metadata);
 
         let current_account_id = env.current_account_id();
         let action = PromiseAction::AddFunctionCallKey {
-            public_key,
+            public_key: args.public_key,
             allowance,
             nonce: 0, // not actually used - depends on block height
             receiver_id: current_account_id.clone(),
-            function_names: "call,submit,submit_with_args".into(),
+            function_names: metadata.function_names(),
         };
         let promise = PromiseBatchAction {
             target_account_id: current_account_id,
@@ -297,6 +904,9 @@ pub fn add_relayer_key<I: IO + Copy, E: Env, H: PromiseHandler>(
 
         let promise_id = unsafe { handler.promise_create_batch(&promise) };
         handler.promise_return(promise_id);
+        emit_event(&EngineEvent::AddRelayerKey(RelayerKey {
+            public_key: args.public_key,
+        }));
 
         Ok(())
     })
@@ -329,11 +939,111 @@ pub fn remove_relayer_key<I: IO + Copy, E: Env, H: PromiseHandler>(
 
         let promise_id = unsafe { handler.promise_create_batch(&promise) };
         handler.promise_return(promise_id);
+        emit_event(&EngineEvent::RemoveRelayerKey(RelayerKey {
+            public_key: args.public_key,
+        }));
 
         Ok(())
     })
 }
 
+/// Deletes all expired relayer function call keys. Anyone can call it. Returns the JSON list
+/// of the deleted keys.
+#[named]
+pub fn remove_expired_relayer_keys<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+
+        let block_height = env.block_height();
+        let expired_keys: Vec<_> = engine::get_function_call_keys(&io)
+            .into_iter()
+            .filter(|public_key| {
+                engine::get_function_call_key_metadata(&io, public_key)
+                    .map_or(false, |metadata| metadata.is_expired(block_height))
+            })
+            .collect();
+
+        for public_key in &expired_keys {
+            engine::remove_function_call_key(&mut io, public_key)?;
+        }
+
+        if !expired_keys.is_empty() {
+            let actions = expired_keys
+                .iter()
+                .map(|public_key| PromiseAction::DeleteKey {
+                    public_key: *public_key,
+                })
+                .collect();
+            let promise = PromiseBatchAction {
+                target_account_id: env.current_account_id(),
+                actions,
+            };
+            // Safety: the batch only deletes keys of the engine account which were added by
+            // the engine itself and expired.
+            unsafe { handler.promise_create_batch(&promise) };
+            emit_event(&EngineEvent::RemoveExpiredRelayerKeys(RelayerKeys {
+                public_keys: expired_keys.clone(),
+            }));
+        }
+
+        let bytes = serde_json::to_vec(&expired_keys).map_err(|_| errors::ERR_SERIALIZE)?;
+        io.return_output(&bytes);
+
+        Ok(())
+    })
+}
+
+/// Adds the relayer function call keys which were added before the list of the keys was
+/// introduced to the list, so they are returned by `get_relayer_keys` and can be removed by
+/// `remove_expired_relayer_keys`. Returns the JSON list of the newly listed keys.
+#[named]
+pub fn list_legacy_relayer_keys<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+
+        require_running(&state)?;
+        require_key_manager_only(&state, &env.predecessor_account_id())?;
+
+        let args: RelayerKeysArgs = serde_json::from_slice(&io.read_input().to_vec())
+            .map_err(|_| errors::ERR_JSON_DESERIALIZE)?;
+        let mut listed_keys = Vec::new();
+        for public_key in args.public_keys {
+            if engine::list_function_call_key(&mut io, &public_key)? {
+                listed_keys.push(public_key);
+            }
+        }
+
+        let bytes = serde_json::to_vec(&listed_keys).map_err(|_| errors::ERR_SERIALIZE)?;
+        io.return_output(&bytes);
+
+        Ok(())
+    })
+}
+
+/// Returns the JSON list of the relayer function call keys with their metadata.
+pub fn get_relayer_keys<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let keys: Vec<_> = engine::get_function_call_keys(&io)
+        .into_iter()
+        .filter_map(|public_key| {
+            engine::get_function_call_key_metadata(&io, &public_key).map(|metadata| {
+                RelayerKeyArgsV2 {
+                    public_key,
+                    allowed_methods: metadata.allowed_methods,
+                    expires_at: metadata.expires_at,
+                }
+            })
+        })
+        .collect();
+    let bytes = serde_json::to_vec(&keys).map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
 #[named]
 pub fn register_relayer<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
     with_hashchain(io, env, function_name!(), |io| {
@@ -350,16 +1060,20 @@ pub fn register_relayer<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Cont
             io,
             env,
         );
-        engine.register_relayer(
-            predecessor_account_id.as_bytes(),
-            Address::from_array(relayer_address),
-        );
+        let address = Address::from_array(relayer_address);
+        engine.register_relayer(predecessor_account_id.as_bytes(), address);
+        emit_event(&EngineEvent::RegisterRelayer(Relayer {
+            account_id: predecessor_account_id,
+            address,
+        }));
         Ok(())
     })
 }
 
 #[named]
 pub fn start_hashchain<I: IO + Copy, E: Env>(mut io: I, env: &E) -> Result<(), ContractError> {
+    // The method is not run through `with_hashchain`, which checks it for the other methods.
+    require_method_not_paused(&io, function_name!())?;
     let mut state = state::get_state(&io)?;
     require_paused(&state)?;
     require_key_manager_only(&state, &env.predecessor_account_id())?;
@@ -395,6 +1109,9 @@ pub fn start_hashchain<I: IO + Copy, E: Env>(mut io: I, env: &E) -> Result<(), C
 
     state.is_paused = false;
     state::set_state(&mut io, &state)?;
+    emit_event(&EngineEvent::StartHashchain(HashchainStart {
+        block_height: args.block_height,
+    }));
 
     Ok(())
 }
@@ -1,10 +1,13 @@
 use crate::{
     connector::{self, EthConnectorContract},
-    contract_methods::{predecessor_address, require_owner_only, require_running, ContractError},
+    contract_methods::{
+        code_info, emit_event, predecessor_address, require_owner_only, require_role,
+        require_running, ContractError,
+    },
     engine::{self, Engine},
-    errors,
+    erc20_code, errors,
     hashchain::with_hashchain,
-    state,
+    native_erc20, state,
 };
 use aurora_engine_modexp::AuroraModExp;
 use aurora_engine_sdk::{
@@ -13,24 +16,31 @@ use aurora_engine_sdk::{
     promise::PromiseHandler,
 };
 use aurora_engine_types::parameters::{
-    ExitToNearPrecompileCallbackCallArgs, PromiseAction, PromiseBatchAction,
+    ExitToNearPrecompileCallbackCallArgs, PromiseAction, PromiseBatchAction, PromiseCreateArgs,
 };
 use aurora_engine_types::{
+    account_id::AccountId,
     borsh::{BorshDeserialize, BorshSerialize},
     parameters::{
         connector::{
-            InitCallArgs, NEP141FtOnTransferArgs, ResolveTransferCallArgs, SetContractDataCallArgs,
+            Erc20Metadata, FinishDepositCallArgs, FungibleTokenMetadata, InitCallArgs,
+            NEP141FtOnTransferArgs, ResolveTransferCallArgs, SetContractDataCallArgs,
             SetErc20MetadataArgs, StorageDepositCallArgs, StorageWithdrawCallArgs,
             TransferCallArgs, TransferCallCallArgs,
         },
         engine::{
-            errors::ParseTypeFromJsonError, DeployErc20TokenArgs, PauseEthConnectorCallArgs,
-            SubmitResult,
+            errors::ParseTypeFromJsonError, DeployErc20TokenArgs, DeployErc20TokenArgsV2,
+            DeployNep141TokenArgs, PauseEthConnectorCallArgs, RegisterNep141TokenArgs, Role,
+            SubmitResult, SyncErc20MetadataArgs,
+        },
+        events::{
+            EngineEvent, Erc20MetadataChange, Erc20Migration, EthConnectorData, FinishDepositInfo,
+            FtOnTransferInfo, PausedFlags, RefundInfo, TokenPair, WithdrawInfo,
         },
         PromiseWithCallbackArgs,
     },
-    types::{Address, PromiseResult, Yocto},
-    vec, Vec,
+    types::{Address, NEP141Wei, PromiseResult, Wei, Yocto},
+    vec, Vec, U256,
 };
 use function_name::named;
 
@@ -56,15 +66,25 @@ pub fn ft_on_transfer<I: IO + Copy, E: Env, H: PromiseHandler>(
         let args: NEP141FtOnTransferArgs = serde_json::from_slice(&io.read_input().to_vec())
             .map_err(Into::<ParseTypeFromJsonError>::into)?;
 
-        if predecessor_account_id == current_account_id {
+        let is_received = if predecessor_account_id == current_account_id {
             EthConnectorContract::init_instance(io)?.ft_on_transfer(&engine, &args)?;
+            true
         } else {
             engine.receive_erc20_tokens(
                 &predecessor_account_id,
                 &args,
                 &current_account_id,
                 handler,
-            );
+            )
+        };
+        // The tokens which are refunded to the sender were not transferred to the engine.
+        if is_received {
+            emit_event(&EngineEvent::FtOnTransfer(FtOnTransferInfo {
+                token_id: predecessor_account_id,
+                sender_id: args.sender_id,
+                amount: args.amount,
+                msg: args.msg,
+            }));
         }
         Ok(())
     })
@@ -79,9 +99,30 @@ pub fn deploy_erc20_token<I: IO + Copy, E: Env, H: PromiseHandler>(
     with_hashchain(io, env, function_name!(), |mut io| {
         require_running(&state::get_state(&io)?)?;
         // Id of the NEP141 token in Near
-        let args: DeployErc20TokenArgs = io.read_input_borsh()?;
-
-        let address = engine::deploy_erc20_token(args, io, env, handler)?;
+        let args = DeployErc20TokenArgsV2::deserialize(&io.read_input().to_vec())
+            .ok_or(errors::ERR_BORSH_DESERIALIZE)?;
+        let nep141 = args.nep141.clone();
+
+        let address = engine::deploy_erc20_token(
+            DeployErc20TokenArgs {
+                nep141: args.nep141,
+            },
+            io,
+            env,
+            handler,
+        )?;
+        emit_event(&EngineEvent::DeployErc20Token(TokenPair {
+            nep141: nep141.clone(),
+            erc20: address,
+        }));
+
+        if args.sync_metadata {
+            let promise_args = sync_erc20_metadata_promise(nep141, env.current_account_id())?;
+            // Safety: this call is safe because the callback only writes the metadata returned
+            // by the NEP-141 token into the ERC-20 token which has just been deployed for it.
+            // The address of the token is returned, not the result of the callback.
+            let _promise_id = unsafe { handler.promise_create_with_callback(&promise_args) };
+        }
 
         io.return_output(
             &address
@@ -93,6 +134,220 @@ pub fn deploy_erc20_token<I: IO + Copy, E: Env, H: PromiseHandler>(
     })
 }
 
+#[named]
+pub fn set_nep141_code<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::TokenAdmin, &env.predecessor_account_id())?;
+        let bytes = io.read_input().to_vec();
+        let info = code_info(&bytes);
+        native_erc20::update_nep141_code(&mut io, &native_erc20::Nep141Code::new(bytes));
+        emit_event(&EngineEvent::SetNep141Code(info));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn deploy_nep141_token<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<AccountId, ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::TokenAdmin, &env.predecessor_account_id())?;
+        let args: DeployNep141TokenArgs = io.read_input_borsh()?;
+        let current_account_id = env.current_account_id();
+        let engine: Engine<_, E, AuroraModExp> = Engine::new_with_state(
+            state,
+            predecessor_address(&env.predecessor_account_id()),
+            current_account_id,
+            io,
+            env,
+        );
+        // Tokens without (standard) metadata are still allowed to be bridged.
+        let metadata = engine
+            .get_erc20_metadata(args.erc20_address)
+            .unwrap_or_default();
+
+        let nep141 = native_erc20::deploy_nep141_token(&io, env, handler, &args, metadata)?;
+        emit_event(&EngineEvent::DeployNep141Token(TokenPair {
+            nep141: nep141.clone(),
+            erc20: args.erc20_address,
+        }));
+
+        // The account id is returned by the `register_nep141_token` callback.
+        Ok(nep141)
+    })
+}
+
+#[named]
+pub fn register_nep141_token<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &H,
+) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        require_running(&state::get_state(&io)?)?;
+        // The function is only set to be private, otherwise callback error will happen.
+        env.assert_private_call()?;
+        let check_deploy: Result<(), &[u8]> = match handler.promise_result_check() {
+            Some(true) => Ok(()),
+            Some(false) => Err(b"ERR_NEP141_DEPLOY_FAILED"),
+            None => Err(b"ERR_NEP141_REGISTER_NOT_CALLBACK"),
+        };
+        check_deploy?;
+        let args: RegisterNep141TokenArgs = io.read_input_borsh()?;
+        native_erc20::register_nep141_token(&mut io, &args)?;
+        io.return_output(args.nep141.as_bytes());
+        emit_event(&EngineEvent::RegisterNep141Token(TokenPair {
+            nep141: args.nep141,
+            erc20: args.erc20_address,
+        }));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn set_erc20_code<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |mut io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::TokenAdmin, &env.predecessor_account_id())?;
+        let bytes = io.read_input().to_vec();
+        let info = code_info(&bytes);
+        erc20_code::update_erc20_code(&mut io, &erc20_code::Erc20Code::new(bytes));
+        emit_event(&EngineEvent::SetErc20Code(info));
+        Ok(())
+    })
+}
+
+#[named]
+pub fn migrate_erc20_token<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        require_role(&state, Role::TokenAdmin, &env.predecessor_account_id())?;
+        let address = io.read_input_arr20().map(Address::from_array)?;
+        let version = erc20_code::migrate_erc20_token(io, env, address)?;
+        emit_event(&EngineEvent::MigrateErc20Token(Erc20Migration {
+            erc20: address,
+            version: version.0,
+        }));
+        Ok(())
+    })
+}
+
+pub fn get_erc20_code_version<I: IO + Copy>(mut io: I) -> Result<(), ContractError> {
+    let address = io.read_input_arr20().map(Address::from_array)?;
+    let version = erc20_code::get_code_version_of_token(&io, &address);
+    let bytes = version.try_to_vec().map_err(|_| errors::ERR_SERIALIZE)?;
+    io.return_output(&bytes);
+    Ok(())
+}
+
+#[named]
+pub fn sync_erc20_metadata<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<PromiseWithCallbackArgs, ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        require_running(&state::get_state(&io)?)?;
+        let args: SyncErc20MetadataArgs = io.read_input_borsh()?;
+        // Only registered tokens can be synchronized.
+        engine::get_erc20_from_nep141(&io, &args.nep141)?;
+
+        let promise_args = sync_erc20_metadata_promise(args.nep141, env.current_account_id())?;
+        // Safety: this call is safe because the callback only writes the metadata returned
+        // by the NEP-141 token into the ERC-20 token mapped to it.
+        let promise_id = unsafe { handler.promise_create_with_callback(&promise_args) };
+        handler.promise_return(promise_id);
+        Ok(promise_args)
+    })
+}
+
+/// Creates the promise reading the metadata of the NEP-141 token with the callback writing
+/// this metadata into the ERC-20 token mapped to it.
+fn sync_erc20_metadata_promise(
+    nep141: AccountId,
+    current_account_id: AccountId,
+) -> Result<PromiseWithCallbackArgs, ContractError> {
+    let args = SyncErc20MetadataArgs {
+        nep141: nep141.clone(),
+    };
+    let ft_metadata_call = PromiseCreateArgs {
+        target_account_id: nep141,
+        method: "ft_metadata".into(),
+        args: Vec::new(),
+        attached_balance: Yocto::new(0),
+        attached_gas: engine::GAS_FOR_FT_METADATA,
+    };
+    let callback = PromiseCreateArgs {
+        target_account_id: current_account_id,
+        method: "sync_erc20_metadata_callback".into(),
+        args: args.try_to_vec().map_err(|_| errors::ERR_SERIALIZE)?,
+        attached_balance: Yocto::new(0),
+        attached_gas: engine::GAS_FOR_SYNC_ERC20_METADATA_CALLBACK,
+    };
+
+    Ok(PromiseWithCallbackArgs {
+        base: ft_metadata_call,
+        callback,
+    })
+}
+
+#[named]
+pub fn sync_erc20_metadata_callback<I: IO + Copy, E: Env, H: PromiseHandler>(
+    io: I,
+    env: &E,
+    handler: &mut H,
+) -> Result<SubmitResult, ContractError> {
+    with_hashchain(io, env, function_name!(), |io| {
+        let state = state::get_state(&io)?;
+        require_running(&state)?;
+        env.assert_private_call()?;
+
+        // This function should only be called as the callback of
+        // exactly one promise.
+        if handler.promise_results_count() != 1 {
+            return Err(errors::ERR_PROMISE_COUNT.into());
+        }
+        let ft_metadata: FungibleTokenMetadata = match handler.promise_result(0) {
+            Some(PromiseResult::Successful(bytes)) => {
+                serde_json::from_slice(&bytes).map_err(|_| errors::ERR_PROMISE_ENCODING)?
+            }
+            _ => return Err(errors::ERR_PROMISE_FAILED.into()),
+        };
+
+        let args: SyncErc20MetadataArgs = io.read_input_borsh()?;
+        let erc20_address = engine::get_erc20_from_nep141(&io, &args.nep141)?;
+        let erc20_address =
+            Address::try_from_slice(&erc20_address).map_err(|_| errors::ERR_PARSE_ADDRESS)?;
+        let erc20_metadata = Erc20Metadata {
+            name: ft_metadata.name,
+            symbol: ft_metadata.symbol,
+            decimals: ft_metadata.decimals,
+        };
+        let event = metadata_change_event(erc20_address, &erc20_metadata);
+
+        let current_account_id = env.current_account_id();
+        let mut engine: Engine<_, E, AuroraModExp> = Engine::new_with_state(
+            state,
+            predecessor_address(&env.predecessor_account_id()),
+            current_account_id,
+            io,
+            env,
+        );
+        let result = engine.set_erc20_metadata(erc20_address, erc20_metadata, handler)?;
+        emit_event(&event);
+
+        Ok(result)
+    })
+}
+
 #[named]
 pub fn exit_to_near_precompile_callback<I: IO + Copy, E: Env, H: PromiseHandler>(
     io: I,
@@ -136,6 +391,11 @@ pub fn exit_to_near_precompile_callback<I: IO + Copy, E: Env, H: PromiseHandler>
             if !refund_result.status.is_ok() {
                 return Err(errors::ERR_REFUND_FAILURE.into());
             }
+            emit_event(&EngineEvent::RefundOnError(RefundInfo {
+                recipient_address: args.recipient_address,
+                erc20_address: args.erc20_address,
+                amount: Wei::new(U256::from_big_endian(&args.amount)),
+            }));
 
             Some(refund_result)
         } else {
@@ -159,8 +419,10 @@ pub fn new_eth_connector<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Con
 
         let args: InitCallArgs = io.read_input_borsh()?;
         let owner_id = env.current_account_id();
+        let event = EngineEvent::NewEthConnector(connector_data_event(&args));
 
         EthConnectorContract::create_contract(io, &owner_id, args)?;
+        emit_event(&event);
         Ok(())
     })
 }
@@ -180,7 +442,9 @@ pub fn set_eth_connector_contract_data<I: IO + Copy, E: Env>(
         }
 
         let args: SetContractDataCallArgs = io.read_input_borsh()?;
+        let event = EngineEvent::SetEthConnectorContractData(connector_data_event(&args));
         connector::set_contract_data(&mut io, args)?;
+        emit_event(&event);
         Ok(())
     })
 }
@@ -199,6 +463,11 @@ pub fn withdraw<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<Vec<u8>, Contrac
             &args,
         )?;
         let result_bytes = result.try_to_vec().map_err(|_| errors::ERR_SERIALIZE)?;
+        emit_event(&EngineEvent::Withdraw(WithdrawInfo {
+            sender_id: predecessor_account_id,
+            recipient_address: result.recipient_id,
+            amount: result.amount,
+        }));
 
         // We only return the output via IO in the case of standalone.
         // In the case of contract we intentionally avoid IO to call Wasm directly.
@@ -261,7 +530,14 @@ pub fn finish_deposit<I: IO + Copy, E: Env, H: PromiseHandler>(
             return Err(errors::ERR_VERIFY_PROOF.into());
         }
 
-        let data = io.read_input_borsh()?;
+        let data: FinishDepositCallArgs = io.read_input_borsh()?;
+        let event = EngineEvent::FinishDeposit(FinishDepositInfo {
+            new_owner_id: data.new_owner_id.clone(),
+            amount: data.amount,
+            fee: NEP141Wei::new(data.fee.as_u128()),
+            relayer_id: data.relayer_id.clone(),
+            proof_key: data.proof_key.clone(),
+        });
         let current_account_id = env.current_account_id();
         let predecessor_account_id = env.predecessor_account_id();
         let maybe_promise_args = EthConnectorContract::init_instance(io)?.finish_deposit(
@@ -270,6 +546,7 @@ pub fn finish_deposit<I: IO + Copy, E: Env, H: PromiseHandler>(
             data,
             env.prepaid_gas(),
         )?;
+        emit_event(&event);
 
         if let Some(promise_args) = maybe_promise_args.as_ref() {
             // Safety: this call is safe because it comes from the eth-connector, not users.
@@ -423,6 +700,9 @@ pub fn set_paused_flags<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Cont
         }
         let args: PauseEthConnectorCallArgs = io.read_input_borsh()?;
         EthConnectorContract::init_instance(io)?.set_paused_flags(&args);
+        emit_event(&EngineEvent::SetPausedFlags(PausedFlags {
+            paused_mask: args.paused_mask,
+        }));
         Ok(())
     })
 }
@@ -436,10 +716,9 @@ pub fn set_erc20_metadata<I: IO + Copy, E: Env, H: PromiseHandler>(
     with_hashchain(io, env, function_name!(), |io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
-        // TODO: Define special role for this transaction. Potentially via multisig?
         let is_private = env.assert_private_call();
         if is_private.is_err() {
-            require_owner_only(&state, &env.predecessor_account_id())?;
+            require_role(&state, Role::TokenAdmin, &env.predecessor_account_id())?;
         }
 
         let args: SetErc20MetadataArgs = serde_json::from_slice(&io.read_input().to_vec())
@@ -452,7 +731,9 @@ pub fn set_erc20_metadata<I: IO + Copy, E: Env, H: PromiseHandler>(
             io,
             env,
         );
+        let event = metadata_change_event(args.erc20_address, &args.erc20_metadata);
         let result = engine.set_erc20_metadata(args.erc20_address, args.erc20_metadata, handler)?;
+        emit_event(&event);
 
         Ok(result)
     })
@@ -474,3 +755,19 @@ pub fn get_erc20_metadata<I: IO + Copy, E: Env>(mut io: I, env: &E) -> Result<()
     io.return_output(&serde_json::to_vec(&metadata).map_err(|_| errors::ERR_SERIALIZE)?);
     Ok(())
 }
+
+fn metadata_change_event(erc20: Address, metadata: &Erc20Metadata) -> EngineEvent {
+    EngineEvent::SetErc20Metadata(Erc20MetadataChange {
+        erc20,
+        name: metadata.name.clone(),
+        symbol: metadata.symbol.clone(),
+        decimals: metadata.decimals,
+    })
+}
+
+fn connector_data_event(args: &InitCallArgs) -> EthConnectorData {
+    EthConnectorData {
+        prover_account: args.prover_account.clone(),
+        eth_custodian_address: args.eth_custodian_address.clone(),
+    }
+}
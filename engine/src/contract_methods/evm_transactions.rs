@@ -1,5 +1,8 @@
 use crate::{
-    contract_methods::{predecessor_address, require_running, ContractError},
+    contract_methods::{
+        predecessor_address, require_relayer_key_not_expired, require_running,
+        require_whitelisted_account, require_whitelisted_signer, ContractError,
+    },
     engine::{self, Engine},
     errors,
     hashchain::with_logs_hashchain,
@@ -26,6 +29,7 @@ pub fn deploy_code<I: IO + Copy, E: Env, H: PromiseHandler>(
     with_logs_hashchain(io, env, function_name!(), |mut io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
+        require_whitelisted_account(&io, env)?;
         let input = io.read_input().to_vec();
         let current_account_id = env.current_account_id();
         let mut engine: Engine<_, E, AuroraModExp> = Engine::new_with_state(
@@ -51,6 +55,8 @@ pub fn call<I: IO + Copy, E: Env, H: PromiseHandler>(
     with_logs_hashchain(io, env, function_name!(), |mut io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
+        require_relayer_key_not_expired(&io, env)?;
+        require_whitelisted_account(&io, env)?;
         let bytes = io.read_input().to_vec();
         let args = CallArgs::deserialize(&bytes).ok_or(errors::ERR_BORSH_DESERIALIZE)?;
         let current_account_id = env.current_account_id();
@@ -90,17 +96,18 @@ pub fn submit<I: IO + Copy, E: Env, H: PromiseHandler>(
     with_logs_hashchain(io, env, function_name!(), |mut io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
+        require_relayer_key_not_expired(&io, env)?;
+        require_whitelisted_account(&io, env)?;
         let tx_data = io.read_input().to_vec();
+        let transaction = engine::parse_transaction(env, &tx_data)?;
+        require_whitelisted_signer(&io, &transaction)?;
         let current_account_id = env.current_account_id();
         let relayer_address = predecessor_address(&env.predecessor_account_id());
-        let args = SubmitArgs {
-            tx_data,
-            ..Default::default()
-        };
         let result = engine::submit(
             io,
             env,
-            &args,
+            transaction,
+            None,
             state,
             current_account_id,
             relayer_address,
@@ -122,13 +129,18 @@ pub fn submit_with_args<I: IO + Copy, E: Env, H: PromiseHandler>(
     with_logs_hashchain(io, env, function_name!(), |mut io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
+        require_relayer_key_not_expired(&io, env)?;
+        require_whitelisted_account(&io, env)?;
         let args: SubmitArgs = io.read_input_borsh()?;
+        let transaction = engine::parse_transaction(env, &args.tx_data)?;
+        require_whitelisted_signer(&io, &transaction)?;
         let current_account_id = env.current_account_id();
         let relayer_address = predecessor_address(&env.predecessor_account_id());
         let result = engine::submit(
             io,
             env,
-            &args,
+            transaction,
+            args.max_gas_price.map(Into::into),
             state,
             current_account_id,
             relayer_address,
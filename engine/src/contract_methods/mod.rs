@@ -6,8 +6,20 @@
 //! The reason to isolate these implementations is so that they can be shared between both
 //! the smart contract and the standalone.
 
-use crate::{errors, state};
-use aurora_engine_types::{account_id::AccountId, fmt, types::Address, Box};
+use crate::pausables::{EngineMethodsPauser, PausedMethodsChecker};
+use crate::prelude::transactions::NormalizedEthTransaction;
+use crate::{engine, errors, state, whitelist};
+use aurora_engine_sdk::{env::Env, io::IO};
+use aurora_engine_types::{
+    account_id::AccountId,
+    fmt,
+    parameters::{
+        engine::Role,
+        events::{CodeInfo, EngineEvent},
+    },
+    types::Address,
+    Box,
+};
 
 pub mod admin;
 pub mod connector;
@@ -84,10 +96,24 @@ fn require_owner_only(
     Ok(())
 }
 
+fn require_role(
+    state: &state::EngineState,
+    role: Role,
+    predecessor_account_id: &AccountId,
+) -> Result<(), ContractError> {
+    if !state.has_role(role, predecessor_account_id) {
+        return Err(errors::ERR_NOT_ALLOWED.into());
+    }
+    Ok(())
+}
+
 fn require_key_manager_only(
     state: &state::EngineState,
     predecessor_account_id: &AccountId,
 ) -> Result<(), ContractError> {
+    if state.has_role(Role::RelayerKeyManager, predecessor_account_id) {
+        return Ok(());
+    }
     let key_manager = state
         .key_manager
         .as_ref()
@@ -98,6 +124,67 @@ fn require_key_manager_only(
     Ok(())
 }
 
+pub(crate) fn require_method_not_paused<I: IO + Copy>(
+    io: &I,
+    method: &str,
+) -> Result<(), ContractError> {
+    if EngineMethodsPauser::from_io(*io).is_method_paused(method)? {
+        return Err(errors::ERR_METHOD_PAUSED.into());
+    }
+    Ok(())
+}
+
+/// Only the whitelisted NEAR accounts can execute EVM transactions if the whitelist is enabled.
+/// The engine calling itself, e.g. during the XCC flow, is always allowed.
+fn require_whitelisted_account<I: IO, E: Env>(io: &I, env: &E) -> Result<(), ContractError> {
+    let predecessor_account_id = env.predecessor_account_id();
+    if predecessor_account_id != env.current_account_id()
+        && !whitelist::is_allowed(io, &predecessor_account_id)
+    {
+        return Err(errors::ERR_NOT_WHITELISTED_ACCOUNT.into());
+    }
+    Ok(())
+}
+
+/// Only the transactions signed by the whitelisted EVM addresses can be submitted if the
+/// whitelist is enabled. It is checked before any gas is charged.
+fn require_whitelisted_signer<I: IO>(
+    io: &I,
+    transaction: &NormalizedEthTransaction,
+) -> Result<(), ContractError> {
+    if !whitelist::is_allowed(io, &transaction.address) {
+        return Err(errors::ERR_NOT_WHITELISTED_ADDRESS.into());
+    }
+    Ok(())
+}
+
+/// Transactions signed with an expired relayer function call key are rejected.
+fn require_relayer_key_not_expired<I: IO, E: Env>(io: &I, env: &E) -> Result<(), ContractError> {
+    if env.signer_account_id() != env.current_account_id() {
+        return Ok(());
+    }
+    let is_expired = env
+        .signer_account_pk()
+        .and_then(|public_key| engine::get_function_call_key_metadata(io, &public_key))
+        .map_or(false, |metadata| metadata.is_expired(env.block_height()));
+    if is_expired {
+        return Err(errors::ERR_RELAYER_KEY_EXPIRED.into());
+    }
+    Ok(())
+}
+
+fn emit_event(event: &EngineEvent) {
+    aurora_engine_sdk::log(&event.to_log());
+}
+
+/// Returns the hex encoded sha256 hash and the size of the code for the events.
+fn code_info(code: &[u8]) -> CodeInfo {
+    CodeInfo {
+        code_hash: hex::encode(aurora_engine_sdk::sha256(code).0),
+        code_size: u64::try_from(code.len()).unwrap_or(u64::MAX),
+    }
+}
+
 fn predecessor_address(predecessor_account_id: &AccountId) -> Address {
     aurora_engine_sdk::types::near_account_to_evm_address(predecessor_account_id.as_bytes())
 }
@@ -1,5 +1,5 @@
 use crate::{
-    contract_methods::{require_owner_only, require_running, ContractError},
+    contract_methods::{code_info, emit_event, require_role, require_running, ContractError},
     errors,
     hashchain::with_hashchain,
     state, xcc,
@@ -9,7 +9,14 @@ use aurora_engine_sdk::{
     io::{StorageIntermediate, IO},
     promise::PromiseHandler,
 };
-use aurora_engine_types::{borsh::BorshSerialize, types::Address};
+use aurora_engine_types::{
+    borsh::BorshSerialize,
+    parameters::{
+        engine::Role,
+        events::{AddressInfo, EngineEvent, RouterVersion, XccSubAccount},
+    },
+    types::Address,
+};
 use function_name::named;
 
 #[named]
@@ -17,10 +24,12 @@ pub fn factory_update<I: IO + Copy, E: Env>(io: I, env: &E) -> Result<(), Contra
     with_hashchain(io, env, function_name!(), |mut io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
-        require_owner_only(&state, &env.predecessor_account_id())?;
+        require_role(&state, Role::XccAdmin, &env.predecessor_account_id())?;
         let bytes = io.read_input().to_vec();
+        let info = code_info(&bytes);
         let router_bytecode = xcc::RouterCode::new(bytes);
         xcc::update_router_code(&mut io, &router_bytecode);
+        emit_event(&EngineEvent::FactoryUpdate(info));
         Ok(())
     })
 }
@@ -43,6 +52,10 @@ pub fn factory_update_address_version<I: IO + Copy, E: Env, H: PromiseHandler>(
         check_deploy?;
         let args: xcc::AddressVersionUpdateArgs = io.read_input_borsh()?;
         xcc::set_code_version_of_address(&mut io, &args.address, args.version);
+        emit_event(&EngineEvent::FactoryUpdateAddressVersion(RouterVersion {
+            address: args.address,
+            version: args.version.0,
+        }));
         Ok(())
     })
 }
@@ -55,9 +68,12 @@ pub fn factory_set_wnear_address<I: IO + Copy, E: Env>(
     with_hashchain(io, env, function_name!(), |mut io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
-        require_owner_only(&state, &env.predecessor_account_id())?;
-        let address = io.read_input_arr20()?;
-        xcc::set_wnear_address(&mut io, &Address::from_array(address));
+        require_role(&state, Role::XccAdmin, &env.predecessor_account_id())?;
+        let address = Address::from_array(io.read_input_arr20()?);
+        xcc::set_wnear_address(&mut io, &address);
+        emit_event(&EngineEvent::FactorySetWnearAddress(AddressInfo {
+            address,
+        }));
         Ok(())
     })
 }
@@ -78,13 +94,18 @@ pub fn fund_xcc_sub_account<I: IO + Copy, E: Env, H: PromiseHandler>(
     with_hashchain(io, env, function_name!(), |io| {
         let state = state::get_state(&io)?;
         require_running(&state)?;
-        // This method can only be called by the owner because it allows specifying the
+        // This method can only be called by the xcc admin because it allows specifying the
         // account ID of the wNEAR account. This information must be accurate for the
         // sub-account to work properly, therefore this method can only be called by
         // a trusted user.
-        require_owner_only(&state, &env.predecessor_account_id())?;
+        require_role(&state, Role::XccAdmin, &env.predecessor_account_id())?;
         let args: xcc::FundXccArgs = io.read_input_borsh()?;
+        let event = EngineEvent::FundXccSubAccount(XccSubAccount {
+            target: args.target,
+            wnear_account_id: args.wnear_account_id.clone(),
+        });
         xcc::fund_xcc_sub_account(&io, handler, env, args)?;
+        emit_event(&event);
         Ok(())
     })
 }
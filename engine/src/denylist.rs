@@ -0,0 +1,80 @@
+//! Denylist of EVM addresses, used to comply with sanctions without pausing the engine.
+//!
+//! A denied address can't sign transactions submitted to the engine, can't be the sender of
+//! calls made from NEAR accounts and can't receive assets bridged from NEAR. The NEAR accounts
+//! whose derived EVM address is denied can't receive assets exiting Aurora.
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_types::borsh::BorshDeserialize;
+use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
+use aurora_engine_types::types::Address;
+use aurora_engine_types::Vec;
+
+pub use aurora_engine_precompiles::native::denylist::{denylist_key, is_denied};
+
+/// Key of the list of all denied addresses, which is kept for the views.
+const DENYLIST_KEY: &[u8] = b"LIST";
+
+/// Adds the address to the denylist. Returns false if it was already denied.
+pub fn deny<I: IO>(io: &mut I, address: &Address) -> bool {
+    if io.write_storage(&denylist_key(address), &[1]).is_some() {
+        return false;
+    }
+    let mut addresses = get_denylist(io);
+    addresses.push(*address);
+    set_denylist(io, &addresses);
+    true
+}
+
+/// Removes the address from the denylist. Returns false if it wasn't denied.
+pub fn allow<I: IO>(io: &mut I, address: &Address) -> bool {
+    if io.remove_storage(&denylist_key(address)).is_none() {
+        return false;
+    }
+    let mut addresses = get_denylist(io);
+    addresses.retain(|a| a != address);
+    set_denylist(io, &addresses);
+    true
+}
+
+/// Returns all denied addresses in the order they were added.
+pub fn get_denylist<I: IO>(io: &I) -> Vec<Address> {
+    io.read_storage(&bytes_to_key(KeyPrefix::Denylist, DENYLIST_KEY))
+        .and_then(|value| Vec::try_from_slice(&value.to_vec()).ok())
+        .unwrap_or_default()
+}
+
+fn set_denylist<I: IO>(io: &mut I, addresses: &[Address]) {
+    let key = bytes_to_key(KeyPrefix::Denylist, DENYLIST_KEY);
+    if addresses.is_empty() {
+        io.remove_storage(&key);
+    } else {
+        io.write_borsh(&key, &addresses);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_deny_and_allow_address() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let address = Address::from_array([1; 20]);
+        let other = Address::from_array([2; 20]);
+
+        assert!(!is_denied(&io, &address));
+        assert!(deny(&mut io, &address));
+        assert!(!deny(&mut io, &address));
+        assert!(deny(&mut io, &other));
+        assert!(is_denied(&io, &address));
+        assert_eq!(get_denylist(&io), vec![address, other]);
+
+        assert!(allow(&mut io, &address));
+        assert!(!allow(&mut io, &address));
+        assert!(!is_denied(&io, &address));
+        assert_eq!(get_denylist(&io), vec![other]);
+    }
+}
@@ -0,0 +1,280 @@
+//! Allowlist of EVM addresses which can deploy contracts when the deploy allowlist mode is
+//! enabled in the engine state.
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_types::borsh::BorshDeserialize;
+use aurora_engine_types::storage::{address_to_key, bytes_to_key, KeyPrefix};
+use aurora_engine_types::types::Address;
+use aurora_engine_types::{Cow, Vec, H160, H256, U256};
+use evm::backend::{Backend, Basic};
+use evm::executor::stack::{StackState, StackSubstateMetadata};
+use evm::{ExitError, Transfer};
+
+/// Key of the list of all approved deployers, which is kept for the views.
+const DEPLOYERS_KEY: &[u8] = b"LIST";
+
+/// Returns true if the address is an approved deployer.
+pub fn is_allowed<I: IO>(io: &I, address: &Address) -> bool {
+    io.storage_has_key(&address_to_key(KeyPrefix::DeployAllowlist, address))
+}
+
+/// Adds the address to the approved deployers. Returns false if it was already approved.
+pub fn add<I: IO>(io: &mut I, address: &Address) -> bool {
+    let key = address_to_key(KeyPrefix::DeployAllowlist, address);
+    if io.write_storage(&key, &[1]).is_some() {
+        return false;
+    }
+    let mut addresses = get_deployers(io);
+    addresses.push(*address);
+    set_deployers(io, &addresses);
+    true
+}
+
+/// Removes the address from the approved deployers. Returns false if it wasn't approved.
+pub fn remove<I: IO>(io: &mut I, address: &Address) -> bool {
+    let key = address_to_key(KeyPrefix::DeployAllowlist, address);
+    if io.remove_storage(&key).is_none() {
+        return false;
+    }
+    let mut addresses = get_deployers(io);
+    addresses.retain(|a| a != address);
+    set_deployers(io, &addresses);
+    true
+}
+
+/// Returns all approved deployers in the order they were added.
+pub fn get_deployers<I: IO>(io: &I) -> Vec<Address> {
+    io.read_storage(&bytes_to_key(KeyPrefix::DeployAllowlist, DEPLOYERS_KEY))
+        .and_then(|value| Vec::try_from_slice(&value.to_vec()).ok())
+        .unwrap_or_default()
+}
+
+fn set_deployers<I: IO>(io: &mut I, addresses: &[Address]) {
+    let key = bytes_to_key(KeyPrefix::DeployAllowlist, DEPLOYERS_KEY);
+    if addresses.is_empty() {
+        io.remove_storage(&key);
+    } else {
+        io.write_borsh(&key, &addresses);
+    }
+}
+
+/// State of the EVM executor which makes the contract creations fail with
+/// `ERR_DEPLOYER_NOT_ALLOWED` if the origin of the transaction isn't an approved deployer.
+/// It is used for the calls, so the `CREATE` and `CREATE2` opcodes of the nested calls fail
+/// (pushing zero to the stack) instead of the whole transaction, as for the other creation errors.
+///
+/// The state relies on the create path of the executor: in the substate of a creation (and only
+/// there) the storage of the new contract is reset, then the value (even zero) is transferred to
+/// it and its nonce is incremented. Both the transfer and the nonce increment are rejected for
+/// the contract whose storage was reset in the current substate, so `CREATE` and `CREATE2` fail
+/// whatever the value and whichever of the two the executor does first.
+pub struct DeployGuardState<S> {
+    inner: S,
+    is_restricted: bool,
+    created: Option<H160>,
+}
+
+impl<S> DeployGuardState<S> {
+    pub const fn new(inner: S, is_restricted: bool) -> Self {
+        Self {
+            inner,
+            is_restricted,
+            created: None,
+        }
+    }
+
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    fn check_creation(&self, address: H160) -> Result<(), ExitError> {
+        if self.is_restricted && self.created == Some(address) {
+            return Err(ExitError::Other(Cow::Borrowed("ERR_DEPLOYER_NOT_ALLOWED")));
+        }
+        Ok(())
+    }
+}
+
+impl<S: Backend> Backend for DeployGuardState<S> {
+    fn gas_price(&self) -> U256 {
+        self.inner.gas_price()
+    }
+
+    fn origin(&self) -> H160 {
+        self.inner.origin()
+    }
+
+    fn block_hash(&self, number: U256) -> H256 {
+        self.inner.block_hash(number)
+    }
+
+    fn block_number(&self) -> U256 {
+        self.inner.block_number()
+    }
+
+    fn block_coinbase(&self) -> H160 {
+        self.inner.block_coinbase()
+    }
+
+    fn block_timestamp(&self) -> U256 {
+        self.inner.block_timestamp()
+    }
+
+    fn block_difficulty(&self) -> U256 {
+        self.inner.block_difficulty()
+    }
+
+    fn block_randomness(&self) -> Option<H256> {
+        self.inner.block_randomness()
+    }
+
+    fn block_gas_limit(&self) -> U256 {
+        self.inner.block_gas_limit()
+    }
+
+    fn block_base_fee_per_gas(&self) -> U256 {
+        self.inner.block_base_fee_per_gas()
+    }
+
+    fn chain_id(&self) -> U256 {
+        self.inner.chain_id()
+    }
+
+    fn exists(&self, address: H160) -> bool {
+        self.inner.exists(address)
+    }
+
+    fn basic(&self, address: H160) -> Basic {
+        self.inner.basic(address)
+    }
+
+    fn code(&self, address: H160) -> Vec<u8> {
+        self.inner.code(address)
+    }
+
+    fn storage(&self, address: H160, index: H256) -> H256 {
+        self.inner.storage(address, index)
+    }
+
+    fn original_storage(&self, address: H160, index: H256) -> Option<H256> {
+        self.inner.original_storage(address, index)
+    }
+}
+
+impl<'config, S: StackState<'config>> StackState<'config> for DeployGuardState<S> {
+    fn metadata(&self) -> &StackSubstateMetadata<'config> {
+        self.inner.metadata()
+    }
+
+    fn metadata_mut(&mut self) -> &mut StackSubstateMetadata<'config> {
+        self.inner.metadata_mut()
+    }
+
+    fn enter(&mut self, gas_limit: u64, is_static: bool) {
+        self.inner.enter(gas_limit, is_static);
+    }
+
+    fn exit_commit(&mut self) -> Result<(), ExitError> {
+        self.created = None;
+        self.inner.exit_commit()
+    }
+
+    fn exit_revert(&mut self) -> Result<(), ExitError> {
+        self.created = None;
+        self.inner.exit_revert()
+    }
+
+    fn exit_discard(&mut self) -> Result<(), ExitError> {
+        self.created = None;
+        self.inner.exit_discard()
+    }
+
+    fn is_empty(&self, address: H160) -> bool {
+        self.inner.is_empty(address)
+    }
+
+    fn deleted(&self, address: H160) -> bool {
+        self.inner.deleted(address)
+    }
+
+    fn is_cold(&self, address: H160) -> bool {
+        self.inner.is_cold(address)
+    }
+
+    fn is_storage_cold(&self, address: H160, key: H256) -> bool {
+        self.inner.is_storage_cold(address, key)
+    }
+
+    fn inc_nonce(&mut self, address: H160) -> Result<(), ExitError> {
+        self.check_creation(address)?;
+        self.inner.inc_nonce(address)
+    }
+
+    fn set_storage(&mut self, address: H160, key: H256, value: H256) {
+        self.inner.set_storage(address, key, value);
+    }
+
+    fn reset_storage(&mut self, address: H160) {
+        self.created = Some(address);
+        self.inner.reset_storage(address);
+    }
+
+    fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) {
+        self.inner.log(address, topics, data);
+    }
+
+    fn set_deleted(&mut self, address: H160) {
+        self.inner.set_deleted(address);
+    }
+
+    fn set_code(&mut self, address: H160, code: Vec<u8>) {
+        self.inner.set_code(address, code);
+    }
+
+    fn transfer(&mut self, transfer: Transfer) -> Result<(), ExitError> {
+        self.check_creation(transfer.target)?;
+        self.inner.transfer(transfer)
+    }
+
+    fn reset_balance(&mut self, address: H160) {
+        self.inner.reset_balance(address);
+    }
+
+    fn touch(&mut self, address: H160) {
+        self.inner.touch(address);
+    }
+
+    fn code_size(&self, address: H160) -> U256 {
+        self.inner.code_size(address)
+    }
+
+    fn code_hash(&self, address: H160) -> H256 {
+        self.inner.code_hash(address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_add_and_remove_deployer() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let address = Address::from_array([1; 20]);
+        let other = Address::from_array([2; 20]);
+
+        assert!(!is_allowed(&io, &address));
+        assert!(add(&mut io, &address));
+        assert!(!add(&mut io, &address));
+        assert!(add(&mut io, &other));
+        assert!(is_allowed(&io, &address));
+        assert_eq!(get_deployers(&io), vec![address, other]);
+
+        assert!(remove(&mut io, &address));
+        assert!(!remove(&mut io, &address));
+        assert!(!is_allowed(&io, &address));
+        assert_eq!(get_deployers(&io), vec![other]);
+    }
+}
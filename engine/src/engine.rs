@@ -1,6 +1,4 @@
-use crate::parameters::{
-    CallArgs, NEP141FtOnTransferArgs, ResultLog, SubmitArgs, SubmitResult, ViewCallArgs,
-};
+use crate::parameters::{CallArgs, NEP141FtOnTransferArgs, ResultLog, SubmitResult, ViewCallArgs};
 use aurora_engine_types::public_key::PublicKey;
 use aurora_engine_types::PhantomData;
 use core::mem;
@@ -9,8 +7,11 @@ use evm::executor;
 use evm::{Config, CreateScheme, ExitError, ExitFatal, ExitReason};
 
 use crate::connector::EthConnectorContract;
+use crate::deploy_allowlist::DeployGuardState;
+use crate::erc20_code;
 use crate::map::BijectionMap;
-use crate::{errors, state};
+use crate::native_erc20::{self, is_native_erc20};
+use crate::{denylist, deploy_allowlist, errors, fee_waiver, state};
 use aurora_engine_sdk::caching::FullCache;
 use aurora_engine_sdk::env::Env;
 use aurora_engine_sdk::io::{StorageIntermediate, IO};
@@ -28,15 +29,16 @@ use crate::prelude::precompiles::Precompiles;
 use crate::prelude::transactions::{EthTransactionKind, NormalizedEthTransaction};
 use crate::prelude::{
     address_to_key, bytes_to_key, sdk, storage_to_key, u256_to_arr, vec, AccountId, Address,
-    BTreeMap, BorshDeserialize, KeyPrefix, PromiseArgs, PromiseCreateArgs, Vec, Wei, Yocto,
-    ERC20_DIGITS_SELECTOR, ERC20_MINT_SELECTOR, ERC20_NAME_SELECTOR, ERC20_SET_METADATA_SELECTOR,
-    ERC20_SYMBOL_SELECTOR, H160, H256, U256,
+    BTreeMap, BorshDeserialize, KeyPrefix, NearGas, PromiseArgs, PromiseCreateArgs, Vec, Wei,
+    Yocto, ERC20_DIGITS_SELECTOR, ERC20_MINT_SELECTOR, ERC20_NAME_SELECTOR,
+    ERC20_SET_METADATA_SELECTOR, ERC20_SYMBOL_SELECTOR, ERC20_TRANSFER_SELECTOR, H160, H256, U256,
 };
 use crate::state::EngineState;
 use aurora_engine_modexp::{AuroraModExp, ModExpAlgorithm};
 use aurora_engine_precompiles::PrecompileConstructorContext;
 use aurora_engine_types::parameters::connector::Erc20Metadata;
-use aurora_engine_types::parameters::engine::FunctionCallArgsV2;
+use aurora_engine_types::parameters::engine::{FunctionCallArgsV2, RelayerKeyMetadata, Role};
+use aurora_engine_types::types::make_address;
 use core::cell::RefCell;
 use core::iter::once;
 
@@ -52,6 +54,22 @@ const CHAIN_ID_SIZE: usize = 32;
 /// fix is actually deployed.
 pub const ZERO_ADDRESS_FIX_HEIGHT: u64 = 61_200_152;
 
+/// Gas for the `ft_metadata` view of a NEP-141 token.
+pub const GAS_FOR_FT_METADATA: NearGas = NearGas::new(5_000_000_000_000);
+/// Gas for the `sync_erc20_metadata_callback` which sets the metadata of the ERC-20 token.
+pub const GAS_FOR_SYNC_ERC20_METADATA_CALLBACK: NearGas = NearGas::new(30_000_000_000_000);
+
+/// Origin of the calls made to the recipients of ERC-20 deposits with calldata.
+///
+/// Address: `0x4107689d77c4cfe1a466b5ced716887fa929f065`
+/// This address is computed as: `&keccak("depositCall")[12..]`
+///
+/// The calls are not made on behalf of the NEP-141 sender, because its account id is only
+/// claimed by the NEP-141 contract, which anyone can register. Since any deposit can make this
+/// address call any contract, it must not be granted any privilege nor hold any asset.
+pub const DEPOSIT_CALL_ORIGIN: Address =
+    make_address(0x4107689d, 0x77c4cfe1a466b5ced716887fa929f065);
+
 #[must_use]
 pub fn current_address(current_account_id: &AccountId) -> Address {
     aurora_engine_sdk::types::near_account_to_evm_address(current_account_id.as_bytes())
@@ -65,7 +83,7 @@ macro_rules! unwrap_res_or_finish {
                 #[cfg(feature = "log")]
                 sdk::log(crate::prelude::format!("{:?}", _e).as_str());
                 $io.return_output($output);
-                return;
+                return false;
             }
         }
     };
@@ -75,7 +93,7 @@ macro_rules! assert_or_finish {
     ($e:expr, $output:expr, $io:expr) => {
         if !$e {
             $io.return_output($output);
-            return;
+            return false;
         }
     };
 }
@@ -117,6 +135,12 @@ pub enum EngineErrorKind {
     GasPayment(GasPaymentError),
     GasOverflow,
     NonExistedKey,
+    /// The sender of the transaction is in the denylist.
+    DeniedSender,
+    /// The recipient of the bridged assets is in the denylist.
+    DeniedRecipient,
+    /// The deploy allowlist is enabled and the origin is not an approved deployer.
+    DeployerNotAllowed,
 }
 
 impl EngineErrorKind {
@@ -151,6 +175,9 @@ impl EngineErrorKind {
             Self::GasPayment(e) => e.as_ref(),
             Self::GasOverflow => errors::ERR_GAS_OVERFLOW,
             Self::NonExistedKey => errors::ERR_FUNCTION_CALL_KEY_NOT_FOUND,
+            Self::DeniedSender => errors::ERR_DENIED_SENDER,
+            Self::DeniedRecipient => errors::ERR_DENIED_RECIPIENT,
+            Self::DeployerNotAllowed => errors::ERR_DEPLOYER_NOT_ALLOWED,
             Self::EvmFatal(_) | Self::EvmError(_) => unreachable!(), // unused misc
         }
     }
@@ -216,6 +243,8 @@ pub enum GasPaymentError {
     EthAmountOverflow,
     /// Not enough balance for account to cover the gas cost
     OutOfFund,
+    /// The gas price of the transaction is lower than the minimum or fixed gas price of the silo
+    GasPriceTooLow,
 }
 
 impl AsRef<[u8]> for GasPaymentError {
@@ -224,6 +253,7 @@ impl AsRef<[u8]> for GasPaymentError {
             Self::BalanceOverflow(overflow) => overflow.as_ref(),
             Self::EthAmountOverflow => errors::ERR_GAS_ETH_AMOUNT_OVERFLOW,
             Self::OutOfFund => errors::ERR_OUT_OF_FUND,
+            Self::GasPriceTooLow => errors::ERR_GAS_PRICE_TOO_LOW,
         }
     }
 }
@@ -367,6 +397,7 @@ impl AsRef<[u8]> for ReadMetadataError {
 pub struct StackExecutorParams<'a, I, E, H> {
     precompiles: Precompiles<'a, I, E, H>,
     gas_limit: u64,
+    is_deploy_restricted: bool,
 }
 
 impl<'env, I: IO + Copy, E: Env, H: ReadOnlyPromiseHandler> StackExecutorParams<'env, I, E, H> {
@@ -374,9 +405,16 @@ impl<'env, I: IO + Copy, E: Env, H: ReadOnlyPromiseHandler> StackExecutorParams<
         Self {
             precompiles,
             gas_limit,
+            is_deploy_restricted: false,
         }
     }
 
+    /// Makes the contract creations of the executor fail, see `DeployGuardState`.
+    const fn with_deploy_restricted(mut self, is_deploy_restricted: bool) -> Self {
+        self.is_deploy_restricted = is_deploy_restricted;
+        self
+    }
+
     #[allow(clippy::type_complexity)]
     fn make_executor<'a, M: ModExpAlgorithm>(
         &'a self,
@@ -384,11 +422,14 @@ impl<'env, I: IO + Copy, E: Env, H: ReadOnlyPromiseHandler> StackExecutorParams<
     ) -> executor::stack::StackExecutor<
         'static,
         'a,
-        executor::stack::MemoryStackState<Engine<'env, I, E, M>>,
+        DeployGuardState<executor::stack::MemoryStackState<Engine<'env, I, E, M>>>,
         Precompiles<'env, I, E, H>,
     > {
         let metadata = executor::stack::StackSubstateMetadata::new(self.gas_limit, CONFIG);
-        let state = executor::stack::MemoryStackState::new(metadata, engine);
+        let state = DeployGuardState::new(
+            executor::stack::MemoryStackState::new(metadata, engine),
+            self.is_deploy_restricted,
+        );
         executor::stack::StackExecutor::new_with_precompiles(state, CONFIG, &self.precompiles)
     }
 }
@@ -398,6 +439,8 @@ pub struct GasPaymentResult {
     pub prepaid_amount: Wei,
     pub effective_gas_price: U256,
     pub priority_fee_per_gas: U256,
+    /// Receives the reward instead of the relayer if set.
+    pub fee_recipient: Option<Address>,
 }
 
 pub struct Engine<'env, I: IO, E: Env, M = AuroraModExp> {
@@ -449,23 +492,50 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         }
     }
 
+    /// Returns true if the origin can deploy contracts, i.e. the deploy allowlist is disabled
+    /// or the origin is an approved deployer.
+    fn is_deployer_allowed(&self, origin: &Address) -> bool {
+        !self.state.is_deploy_allowlist_enabled || deploy_allowlist::is_allowed(&self.io, origin)
+    }
+
+    /// Returns true if the contracts can't be created by the transactions sent by the origins.
+    fn is_deploy_restricted<'a>(&self, mut origins: impl Iterator<Item = &'a Address>) -> bool {
+        !origins.all(|origin| self.is_deployer_allowed(origin))
+    }
+
     pub fn charge_gas(
         &mut self,
         sender: &Address,
         transaction: &NormalizedEthTransaction,
         max_gas_price: Option<U256>,
     ) -> Result<GasPaymentResult, GasPaymentError> {
-        if transaction.max_fee_per_gas.is_zero() {
+        let silo_params = &self.state.silo_params;
+        let fixed_gas_price = silo_params.fixed_gas_price.map(U256::from);
+        // The transactions must accept the fixed gas price even if they are not charged for gas.
+        if fixed_gas_price.map_or(false, |price| transaction.max_fee_per_gas < price) {
+            return Err(GasPaymentError::GasPriceTooLow);
+        }
+        if fee_waiver::is_fee_waived(&self.io, sender) {
+            return Ok(GasPaymentResult::default());
+        }
+        if transaction.max_fee_per_gas.is_zero() && silo_params.min_gas_price == 0 {
             return Ok(GasPaymentResult::default());
         }
 
-        let priority_fee_per_gas = transaction
-            .max_priority_fee_per_gas
-            .min(transaction.max_fee_per_gas - self.block_base_fee_per_gas());
-        let priority_fee_per_gas = max_gas_price.map_or(priority_fee_per_gas, |price| {
-            price.min(priority_fee_per_gas)
-        });
+        let priority_fee_per_gas = if let Some(fixed_gas_price) = fixed_gas_price {
+            fixed_gas_price.saturating_sub(self.block_base_fee_per_gas())
+        } else {
+            let priority_fee_per_gas = transaction
+                .max_priority_fee_per_gas
+                .min(transaction.max_fee_per_gas - self.block_base_fee_per_gas());
+            max_gas_price.map_or(priority_fee_per_gas, |price| {
+                price.min(priority_fee_per_gas)
+            })
+        };
         let effective_gas_price = priority_fee_per_gas + self.block_base_fee_per_gas();
+        if effective_gas_price < U256::from(silo_params.min_gas_price) {
+            return Err(GasPaymentError::GasPriceTooLow);
+        }
         let gas_limit = transaction.gas_limit;
         let prepaid_amount = gas_limit
             .checked_mul(effective_gas_price)
@@ -484,6 +554,7 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
             prepaid_amount,
             effective_gas_price,
             priority_fee_per_gas,
+            fee_recipient: self.state.silo_params.fee_recipient,
         })
     }
 
@@ -505,6 +576,32 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         gas_limit: u64,
         access_list: Vec<(H160, Vec<H256>)>, // See EIP-2930
         handler: &mut P,
+    ) -> EngineResult<SubmitResult> {
+        if !self.is_deployer_allowed(&origin) {
+            return Err(EngineErrorKind::DeployerNotAllowed.into());
+        }
+        self.create(origin, value, input, gas_limit, access_list, handler)
+    }
+
+    /// Deploys a contract of the engine itself (e.g. a bridged ERC-20 token), so the deploy
+    /// allowlist doesn't apply to it.
+    fn deploy_engine_contract<P: PromiseHandler>(
+        &mut self,
+        input: Vec<u8>,
+        handler: &mut P,
+    ) -> EngineResult<SubmitResult> {
+        let origin = Address::new(self.origin());
+        self.create(origin, Wei::zero(), input, u64::MAX, Vec::new(), handler)
+    }
+
+    fn create<P: PromiseHandler>(
+        &mut self,
+        origin: Address,
+        value: Wei,
+        input: Vec<u8>,
+        gas_limit: u64,
+        access_list: Vec<(H160, Vec<H256>)>,
+        handler: &mut P,
     ) -> EngineResult<SubmitResult> {
         let pause_flags = EnginePrecompilesPauser::from_io(self.io).paused();
         let precompiles = self.create_precompiles(pause_flags, handler);
@@ -525,7 +622,7 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         let used_gas = executor.used_gas();
         let status = exit_reason.into_result(result)?;
 
-        let (values, logs) = executor.into_state().deconstruct();
+        let (values, logs) = executor.into_state().into_inner().deconstruct();
         let logs = filter_promises_from_logs(&self.io, handler, logs, &self.current_account_id);
 
         self.apply(values, Vec::<Log>::new(), true);
@@ -583,10 +680,14 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         access_list: Vec<(H160, Vec<H256>)>, // See EIP-2930
         handler: &mut P,
     ) -> EngineResult<SubmitResult> {
+        if denylist::is_denied(&self.io, origin) {
+            return Err(EngineErrorKind::DeniedSender.into());
+        }
         let pause_flags = EnginePrecompilesPauser::from_io(self.io).paused();
         let precompiles = self.create_precompiles(pause_flags, handler);
 
-        let executor_params = StackExecutorParams::new(gas_limit, precompiles);
+        let executor_params = StackExecutorParams::new(gas_limit, precompiles)
+            .with_deploy_restricted(self.is_deploy_restricted(once(origin)));
         let mut executor = executor_params.make_executor(self);
         let (exit_reason, result) = executor.transact_call(
             origin.raw(),
@@ -600,7 +701,7 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         let used_gas = executor.used_gas();
         let status = exit_reason.into_result(result)?;
 
-        let (values, logs) = executor.into_state().deconstruct();
+        let (values, logs) = executor.into_state().into_inner().deconstruct();
         let logs = filter_promises_from_logs(&self.io, handler, logs, &self.current_account_id);
         // The logs could be encoded as base64 or hex string.
         self.apply(values, Vec::<Log>::new(), true);
@@ -608,6 +709,52 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         Ok(SubmitResult::new(status, used_gas, logs))
     }
 
+    /// Executes the given calls `(origin, contract, input)` one after another within the same
+    /// EVM state. The state changes are only applied if all the calls succeed, otherwise the
+    /// result of the first unsuccessful call is returned and nothing is applied.
+    pub fn call_sequence<P: PromiseHandler>(
+        &mut self,
+        calls: &[(Address, Address, Vec<u8>)],
+        handler: &mut P,
+    ) -> EngineResult<SubmitResult> {
+        if calls
+            .iter()
+            .any(|(origin, _, _)| denylist::is_denied(&self.io, origin))
+        {
+            return Err(EngineErrorKind::DeniedSender.into());
+        }
+        let pause_flags = EnginePrecompilesPauser::from_io(self.io).paused();
+        let precompiles = self.create_precompiles(pause_flags, handler);
+
+        let executor_params = StackExecutorParams::new(u64::MAX, precompiles)
+            .with_deploy_restricted(
+                self.is_deploy_restricted(calls.iter().map(|(origin, _, _)| origin)),
+            );
+        let mut executor = executor_params.make_executor(self);
+        let mut status = TransactionStatus::Succeed(Vec::new());
+        for (origin, contract, input) in calls {
+            let (exit_reason, result) = executor.transact_call(
+                origin.raw(),
+                contract.raw(),
+                U256::zero(),
+                input.clone(),
+                u64::MAX,
+                Vec::new(),
+            );
+            status = exit_reason.into_result(result)?;
+            if !status.is_ok() {
+                return Ok(SubmitResult::new(status, executor.used_gas(), Vec::new()));
+            }
+        }
+
+        let used_gas = executor.used_gas();
+        let (values, logs) = executor.into_state().into_inner().deconstruct();
+        let logs = filter_promises_from_logs(&self.io, handler, logs, &self.current_account_id);
+        self.apply(values, Vec::<Log>::new(), true);
+
+        Ok(SubmitResult::new(status, used_gas, logs))
+    }
+
     pub fn view_with_args(&self, args: ViewCallArgs) -> Result<TransactionStatus, EngineErrorKind> {
         let origin = &args.sender;
         let contract = &args.address;
@@ -647,6 +794,39 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
         status.into_result(result)
     }
 
+    /// Executes the contract creation code without applying any state changes.
+    /// On success the returned status contains the code of the contract which would have been
+    /// deployed instead of its address.
+    pub fn view_deploy_code(
+        &self,
+        origin: &Address,
+        input: Vec<u8>,
+    ) -> Result<TransactionStatus, EngineErrorKind> {
+        // View calls cannot interact with promises
+        let handler = aurora_engine_sdk::promise::Noop;
+        let pause_flags = EnginePrecompilesPauser::from_io(self.io).paused();
+        let precompiles = self.create_precompiles(pause_flags, &handler);
+
+        let executor_params = StackExecutorParams::new(u64::MAX, precompiles);
+        let mut executor = executor_params.make_executor(self);
+        let address = executor.create_address(CreateScheme::Legacy {
+            caller: origin.raw(),
+        });
+        let (exit_reason, return_value) = executor.transact_create(
+            origin.raw(),
+            U256::zero(),
+            input,
+            executor_params.gas_limit,
+            Vec::new(),
+        );
+        let result = if exit_reason.is_succeed() {
+            executor.state().code(address)
+        } else {
+            return_value
+        };
+        exit_reason.into_result(result)
+    }
+
     fn relayer_key(account_id: &[u8]) -> Vec<u8> {
         bytes_to_key(KeyPrefix::RelayerEvmAddressMap, account_id)
     }
@@ -715,31 +895,29 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
     ///
     /// IMPORTANT: This function should not panic, otherwise it won't
     /// be possible to return the tokens to the sender.
+    ///
+    /// Returns false if the tokens are returned to the sender.
     pub fn receive_erc20_tokens<P: PromiseHandler>(
         &mut self,
         token: &AccountId,
         args: &NEP141FtOnTransferArgs,
         current_account_id: &AccountId,
         handler: &mut P,
-    ) {
+    ) -> bool {
         let str_amount = crate::prelude::format!("\"{}\"", args.amount);
         let output_on_fail = str_amount.as_bytes();
 
-        // Parse message to determine recipient
-        let recipient = {
-            // Message format:
-            //      Recipient of the transaction - 40 characters (Address in hex)
-            let message = args.msg.as_bytes();
-            assert_or_finish!(message.len() >= 40, output_on_fail, self.io);
-
-            Address::new(H160(unwrap_res_or_finish!(
-                unwrap_res_or_finish!(hex::decode(&message[..40]), output_on_fail, self.io)
-                    .as_slice()
-                    .try_into(),
-                output_on_fail,
-                self.io
-            )))
-        };
+        // Parse message to determine recipient and the optional call to make to it
+        let (recipient, call_data) = unwrap_res_or_finish!(
+            parse_erc20_deposit_message(&args.msg),
+            output_on_fail,
+            self.io
+        );
+        unwrap_res_or_finish!(
+            check_recipient(&self.io, &recipient),
+            output_on_fail,
+            self.io
+        );
 
         let erc20_token = Address::from_array(unwrap_res_or_finish!(
             unwrap_res_or_finish!(
@@ -753,18 +931,43 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
             self.io
         ));
 
-        let erc20_admin_address = current_address(current_account_id);
-        unwrap_res_or_finish!(
-            self.call(
-                &erc20_admin_address,
+        // Tokens native to Aurora are unlocked from the exit to NEAR precompile address,
+        // all other tokens are minted by the engine.
+        let is_native = is_native_erc20(&self.io, &erc20_token);
+        let (origin, input) = if is_native {
+            (
+                exit_to_near::ADDRESS,
+                setup_unlock_erc20_tokens_input(args, &recipient),
+            )
+        } else {
+            (
+                current_address(current_account_id),
+                setup_receive_erc20_tokens_input(args, &recipient),
+            )
+        };
+        let result = match call_data {
+            None => self.call(
+                &origin,
                 &erc20_token,
                 Wei::zero(),
-                setup_receive_erc20_tokens_input(args, &recipient),
+                input,
                 u64::MAX,
                 Vec::new(), // TODO: are there values we should put here?
                 handler,
-            )
-            .and_then(|submit_result| {
+            ),
+            // The recipient is called from a neutral origin right after receiving the tokens.
+            // Both calls are applied atomically, so if the call to the recipient fails, the
+            // tokens are not received either and the whole amount is refunded.
+            Some(call_data) => self.call_sequence(
+                &[
+                    (origin, erc20_token, input),
+                    (DEPOSIT_CALL_ORIGIN, recipient, call_data),
+                ],
+                handler,
+            ),
+        };
+        unwrap_res_or_finish!(
+            result.and_then(|submit_result| {
                 match submit_result.status {
                     TransactionStatus::Succeed(_) => Ok(()),
                     TransactionStatus::Revert(bytes) => {
@@ -801,9 +1004,18 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
             self.io
         );
 
+        if is_native {
+            // The NEP-141 tokens received by the engine are no longer backed by locked tokens.
+            let promise = native_erc20::burn_promise(token.clone(), args.amount.as_u128());
+            // Safety: this call is safe because it only burns the tokens the engine has just
+            // received from the NEP-141 contract deployed by the engine for this token.
+            unsafe { handler.promise_create_call(&promise) };
+        }
+
         // TODO(marX)
         // Everything succeed so return "0"
         self.io.return_output(b"\"0\"");
+        true
     }
 
     /// Read metadata of ERC-20 contract.
@@ -932,10 +1144,14 @@ impl<'env, I: IO + Copy, E: Env, M: ModExpAlgorithm> Engine<'env, I, E, M> {
     }
 }
 
+/// Executes the transaction returned by `parse_transaction`. The max gas price is the one from
+/// the `SubmitArgs`, if any.
+#[allow(clippy::too_many_arguments)]
 pub fn submit<I: IO + Copy, E: Env, P: PromiseHandler>(
     io: I,
     env: &E,
-    args: &SubmitArgs,
+    transaction: NormalizedEthTransaction,
+    max_gas_price: Option<U256>,
     state: EngineState,
     current_account_id: AccountId,
     relayer_address: Address,
@@ -944,7 +1160,8 @@ pub fn submit<I: IO + Copy, E: Env, P: PromiseHandler>(
     submit_with_alt_modexp::<_, _, _, AuroraModExp>(
         io,
         env,
-        args,
+        transaction,
+        max_gas_price,
         state,
         current_account_id,
         relayer_address,
@@ -952,24 +1169,15 @@ pub fn submit<I: IO + Copy, E: Env, P: PromiseHandler>(
     )
 }
 
-pub fn submit_with_alt_modexp<
-    I: IO + Copy,
-    E: Env,
-    P: PromiseHandler,
-    M: ModExpAlgorithm + 'static,
->(
-    mut io: I,
+/// Parses the signed transaction and recovers its signer.
+#[cfg_attr(feature = "contract", allow(unused_variables))]
+pub fn parse_transaction<E: Env>(
     env: &E,
-    args: &SubmitArgs,
-    state: EngineState,
-    current_account_id: AccountId,
-    relayer_address: Address,
-    handler: &mut P,
-) -> EngineResult<SubmitResult> {
+    tx_data: &[u8],
+) -> Result<NormalizedEthTransaction, EngineErrorKind> {
     #[cfg(feature = "contract")]
     let transaction = NormalizedEthTransaction::try_from(
-        EthTransactionKind::try_from(args.tx_data.as_slice())
-            .map_err(EngineErrorKind::FailedTransactionParse)?,
+        EthTransactionKind::try_from(tx_data).map_err(EngineErrorKind::FailedTransactionParse)?,
     )
     .map_err(|_e| EngineErrorKind::InvalidSignature)?;
 
@@ -984,12 +1192,31 @@ pub fn submit_with_alt_modexp<
             );
         let block_height = env.block_height();
         let tx: EthTransactionKind = adapter
-            .try_parse_bytes(args.tx_data.as_slice(), block_height)
+            .try_parse_bytes(tx_data, block_height)
             .map_err(EngineErrorKind::FailedTransactionParse)?;
         tx.try_into()
             .map_err(|_e| EngineErrorKind::InvalidSignature)?
     };
 
+    Ok(transaction)
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn submit_with_alt_modexp<
+    I: IO + Copy,
+    E: Env,
+    P: PromiseHandler,
+    M: ModExpAlgorithm + 'static,
+>(
+    mut io: I,
+    env: &E,
+    transaction: NormalizedEthTransaction,
+    max_gas_price: Option<U256>,
+    state: EngineState,
+    current_account_id: AccountId,
+    relayer_address: Address,
+    handler: &mut P,
+) -> EngineResult<SubmitResult> {
     // Validate the chain ID, if provided inside the signature:
     if let Some(chain_id) = transaction.chain_id {
         if U256::from(chain_id) != U256::from(state.chain_id) {
@@ -1002,6 +1229,10 @@ pub fn submit_with_alt_modexp<
 
     sdk::log!("signer_address {:?}", sender);
 
+    if denylist::is_denied(&io, &sender) {
+        return Err(EngineErrorKind::DeniedSender.into());
+    }
+
     check_nonce(&io, &sender, &transaction.nonce)?;
 
     // Check intrinsic gas is covered by transaction gas limit
@@ -1022,13 +1253,12 @@ pub fn submit_with_alt_modexp<
 
     let mut engine: Engine<_, _, M> =
         Engine::new_with_state(state, sender, current_account_id, io, env);
-    let prepaid_amount =
-        match engine.charge_gas(&sender, &transaction, args.max_gas_price.map(Into::into)) {
-            Ok(gas_result) => gas_result,
-            Err(err) => {
-                return Err(EngineErrorKind::GasPayment(err).into());
-            }
-        };
+    let prepaid_amount = match engine.charge_gas(&sender, &transaction, max_gas_price) {
+        Ok(gas_result) => gas_result,
+        Err(err) => {
+            return Err(EngineErrorKind::GasPayment(err).into());
+        }
+    };
     let gas_limit: u64 = transaction
         .gas_limit
         .try_into()
@@ -1102,7 +1332,24 @@ pub fn refund_on_error<I: IO + Copy, E: Env, P: PromiseHandler>(
     handler: &mut P,
 ) -> EngineResult<SubmitResult> {
     let current_account_id = env.current_account_id();
-    if let Some(erc20_address) = args.erc20_address {
+    if let Some(erc20_address) = args.erc20_address.filter(|a| is_native_erc20(&io, a)) {
+        // Native ERC-20 exit; unlock tokens from precompile address
+        let exit_address = exit_to_near::ADDRESS;
+        let mut engine: Engine<_, _> =
+            Engine::new_with_state(state, exit_address, current_account_id, io, env);
+        let amount = U256::from_big_endian(&args.amount);
+        let input = setup_transfer_erc20_input(amount, args.recipient_address);
+
+        engine.call(
+            &exit_address,
+            &erc20_address,
+            Wei::zero(),
+            input,
+            u64::MAX,
+            Vec::new(),
+            handler,
+        )
+    } else if let Some(erc20_address) = args.erc20_address {
         // ERC-20 exit; re-mint burned tokens
         let erc20_admin_address = current_address(&current_account_id);
         let mut engine: Engine<_, _> =
@@ -1172,9 +1419,12 @@ pub fn compute_block_hash(chain_id: [u8; 32], block_height: u64, account_id: &[u
 
 #[must_use]
 pub fn get_authorizer<I: IO + Copy>(io: &I) -> EngineAuthorizer {
-    // TODO: a temporary use the owner account only until the engine adapts std with near-plugins
+    // The owner and the accounts with the pauser role are authorized to pause.
     state::get_state(io)
-        .map(|state| EngineAuthorizer::from_accounts(once(state.owner_id)))
+        .map(|mut state| {
+            let pausers = state.roles.remove(&Role::Pauser).unwrap_or_default();
+            EngineAuthorizer::from_accounts(once(state.owner_id).chain(pausers))
+        })
         .unwrap_or_default()
 }
 
@@ -1205,11 +1455,43 @@ pub fn refund_unused_gas<I: IO>(
         .ok_or(GasPaymentError::EthAmountOverflow)?;
 
     add_balance(io, sender, refund)?;
-    add_balance(io, relayer, reward_amount)?;
+    add_balance(
+        io,
+        gas_result.fee_recipient.as_ref().unwrap_or(relayer),
+        reward_amount,
+    )?;
 
     Ok(())
 }
 
+/// Parses the message of `ft_on_transfer` for bridged ERC-20 tokens.
+///
+/// Message format:
+///      Recipient of the transaction - 40 characters (Address in hex)
+///      Optionally followed by `:` and the hex encoded calldata of a call to the recipient,
+///      made from `DEPOSIT_CALL_ORIGIN` once the tokens were received.
+pub fn parse_erc20_deposit_message(
+    message: &str,
+) -> Result<(Address, Option<Vec<u8>>), &'static [u8]> {
+    let message = message.as_bytes();
+    if message.len() < 40 {
+        return Err(errors::ERR_INVALID_ON_TRANSFER_MESSAGE_FORMAT.as_slice());
+    }
+    let recipient = hex::decode(&message[..40])
+        .ok()
+        .and_then(|bytes| Address::try_from_slice(&bytes).ok())
+        .ok_or(errors::ERR_PARSE_ADDRESS.as_slice())?;
+    let call_data = match message.get(40) {
+        Some(b':') => Some(
+            hex::decode(&message[41..])
+                .map_err(|_| errors::ERR_INVALID_ON_TRANSFER_MESSAGE_HEX.as_slice())?,
+        ),
+        _ => None,
+    };
+
+    Ok((recipient, call_data))
+}
+
 #[must_use]
 pub fn setup_receive_erc20_tokens_input(
     args: &NEP141FtOnTransferArgs,
@@ -1224,13 +1506,41 @@ pub fn setup_receive_erc20_tokens_input(
     [selector, tail.as_slice()].concat()
 }
 
+#[must_use]
+pub fn setup_unlock_erc20_tokens_input(
+    args: &NEP141FtOnTransferArgs,
+    recipient: &Address,
+) -> Vec<u8> {
+    setup_transfer_erc20_input(U256::from(args.amount.as_u128()), *recipient)
+}
+
+#[must_use]
+pub fn setup_transfer_erc20_input(amount: U256, recipient: Address) -> Vec<u8> {
+    let selector = ERC20_TRANSFER_SELECTOR;
+    let tail = ethabi::encode(&[
+        ethabi::Token::Address(recipient.raw()),
+        ethabi::Token::Uint(amount),
+    ]);
+
+    [selector, tail.as_slice()].concat()
+}
+
 #[must_use]
 pub fn setup_deploy_erc20_input(current_account_id: &AccountId) -> Vec<u8> {
     #[cfg(feature = "error_refund")]
-    let erc20_contract = include_bytes!("../../etc/eth-contracts/res/EvmErc20V2.bin");
+    let erc20_contract = include_bytes!("../../etc/eth-contracts/res/EvmErc20V3Refund.bin");
     #[cfg(not(feature = "error_refund"))]
-    let erc20_contract = include_bytes!("../../etc/eth-contracts/res/EvmErc20.bin");
+    let erc20_contract = include_bytes!("../../etc/eth-contracts/res/EvmErc20V3.bin");
 
+    setup_deploy_erc20_input_with_code(erc20_contract, current_account_id)
+}
+
+/// Creation code of the given ERC-20 contract followed by its constructor arguments.
+#[must_use]
+pub fn setup_deploy_erc20_input_with_code(
+    erc20_contract: &[u8],
+    current_account_id: &AccountId,
+) -> Vec<u8> {
     let erc20_admin_address = current_address(current_account_id);
     let erc20_metadata = Erc20Metadata::default();
 
@@ -1252,7 +1562,12 @@ pub fn deploy_erc20_token<I: IO + Copy, E: Env, P: PromiseHandler>(
     handler: &mut P,
 ) -> Result<Address, DeployErc20Error> {
     let current_account_id = env.current_account_id();
-    let input = setup_deploy_erc20_input(&current_account_id);
+    // New tokens use the latest ERC-20 code from the registry if it was set.
+    let erc20_code = erc20_code::get_erc20_code(&io);
+    let input = erc20_code.as_ref().map_or_else(
+        || setup_deploy_erc20_input(&current_account_id),
+        |code| setup_deploy_erc20_input_with_code(&code.0, &current_account_id),
+    );
     let mut engine: Engine<_, _> = Engine::new(
         aurora_engine_sdk::types::near_account_to_evm_address(
             env.predecessor_account_id().as_bytes(),
@@ -1263,7 +1578,7 @@ pub fn deploy_erc20_token<I: IO + Copy, E: Env, P: PromiseHandler>(
     )
     .map_err(DeployErc20Error::State)?;
 
-    let address = match Engine::deploy_code_with_input(&mut engine, input, handler) {
+    let address = match engine.deploy_engine_contract(input, handler) {
         Ok(result) => match result.status {
             TransactionStatus::Succeed(ret) => {
                 Address::new(H160(ret.as_slice().try_into().unwrap()))
@@ -1273,10 +1588,14 @@ pub fn deploy_erc20_token<I: IO + Copy, E: Env, P: PromiseHandler>(
         Err(e) => return Err(DeployErc20Error::Engine(e)),
     };
 
-    sdk::log!("Deployed ERC-20 in Aurora at: {:#?}", address);
     engine
         .register_token(address, args.nep141)
         .map_err(DeployErc20Error::Register)?;
+    if erc20_code.is_some() {
+        let mut io = io;
+        let version = erc20_code::get_latest_code_version(&io);
+        erc20_code::set_code_version_of_token(&mut io, &address, version);
+    }
 
     Ok(address)
 }
@@ -1311,6 +1630,14 @@ pub fn remove_nonce<I: IO>(io: &mut I, address: &Address) {
     io.remove_storage(&address_to_key(KeyPrefix::Nonce, address));
 }
 
+/// Checks that the recipient of bridged assets is not in the denylist.
+pub fn check_recipient<I: IO>(io: &I, recipient: &Address) -> Result<(), EngineErrorKind> {
+    if denylist::is_denied(io, recipient) {
+        return Err(EngineErrorKind::DeniedRecipient);
+    }
+    Ok(())
+}
+
 /// Checks the nonce to ensure that the address matches the transaction
 /// nonce.
 #[inline]
@@ -1445,10 +1772,34 @@ pub fn get_generation<I: IO>(io: &I, address: &Address) -> u32 {
         })
 }
 
+/// Key of the list of the relayer function call keys added by the engine.
+const RELAYER_KEYS_LIST_KEY: &[u8] = b"LIST";
+
 /// Adds a public function call key for a relayer.
-pub fn add_function_call_key<I: IO>(io: &mut I, key: &PublicKey) {
+pub fn add_function_call_key<I: IO>(io: &mut I, key: &PublicKey, metadata: &RelayerKeyMetadata) {
     let prefixed_key = bytes_to_key(KeyPrefix::RelayerFunctionCallKey, key.key_data());
-    io.write_storage(&prefixed_key, &[1]);
+    io.write_borsh(&prefixed_key, metadata);
+    let mut keys = get_function_call_keys(io);
+    if !keys.contains(key) {
+        keys.push(*key);
+        set_function_call_keys(io, &keys);
+    }
+}
+
+/// Adds a relayer function call key, which was added before the list of the keys was
+/// introduced, to the list. Returns `false` if the key is already listed.
+pub fn list_function_call_key<I: IO>(io: &mut I, key: &PublicKey) -> Result<bool, EngineError> {
+    let prefixed_key = bytes_to_key(KeyPrefix::RelayerFunctionCallKey, key.key_data());
+    if !io.storage_has_key(&prefixed_key) {
+        return Err(EngineErrorKind::NonExistedKey.into());
+    }
+    let mut keys = get_function_call_keys(io);
+    if keys.contains(key) {
+        return Ok(false);
+    }
+    keys.push(*key);
+    set_function_call_keys(io, &keys);
+    Ok(true)
 }
 
 /// Removes a public function call key for a relayer.
@@ -1457,9 +1808,47 @@ pub fn remove_function_call_key<I: IO>(io: &mut I, key: &PublicKey) -> Result<()
     io.remove_storage(&prefixed_key)
         .ok_or_else(|| EngineError::from(EngineErrorKind::NonExistedKey))?;
 
+    let mut keys = get_function_call_keys(io);
+    keys.retain(|k| k != key);
+    set_function_call_keys(io, &keys);
+
     Ok(())
 }
 
+/// Returns the metadata of a relayer function call key if the key was added by the engine.
+pub fn get_function_call_key_metadata<I: IO>(
+    io: &I,
+    key: &PublicKey,
+) -> Option<RelayerKeyMetadata> {
+    let prefixed_key = bytes_to_key(KeyPrefix::RelayerFunctionCallKey, key.key_data());
+    let value = io.read_storage(&prefixed_key)?.to_vec();
+    // Keys added before the metadata was introduced are marked with a single byte.
+    if value == [1] {
+        return Some(RelayerKeyMetadata::default());
+    }
+    RelayerKeyMetadata::try_from_slice(&value).ok()
+}
+
+/// Returns the relayer function call keys added by the engine. The keys added before the list
+/// was introduced are only included once they are listed with `list_function_call_key`.
+pub fn get_function_call_keys<I: IO>(io: &I) -> Vec<PublicKey> {
+    io.read_storage(&bytes_to_key(
+        KeyPrefix::RelayerFunctionCallKey,
+        RELAYER_KEYS_LIST_KEY,
+    ))
+    .and_then(|value| Vec::try_from_slice(&value.to_vec()).ok())
+    .unwrap_or_default()
+}
+
+fn set_function_call_keys<I: IO>(io: &mut I, keys: &[PublicKey]) {
+    let key = bytes_to_key(KeyPrefix::RelayerFunctionCallKey, RELAYER_KEYS_LIST_KEY);
+    if keys.is_empty() {
+        io.remove_storage(&key);
+    } else {
+        io.write_borsh(&key, &keys);
+    }
+}
+
 /// Removes all storage for the given address.
 fn remove_all_storage<I: IO>(io: &mut I, address: &Address, generation: u32) {
     // FIXME: there is presently no way to prefix delete trie state.
@@ -1492,6 +1881,8 @@ where
     P: PromiseHandler,
     I: IO + Copy,
 {
+    // The sub-account the next promise of the xcc precompile acts on.
+    let mut sub_account_id = None;
     logs.into_iter()
         .filter_map(|log| {
             if log.address == exit_to_near::ADDRESS.raw()
@@ -1540,9 +1931,14 @@ where
                             handler,
                             &promise,
                             required_near,
+                            sub_account_id.take().as_ref(),
                             current_account_id,
                         );
                     }
+                } else if log.topics[0] == cross_contract_call::SUB_ACCOUNT_TOPIC {
+                    sub_account_id = AccountId::try_from(log.data.as_slice()).ok();
+                    // The id of the sub-account is consumable by the caller.
+                    return Some(evm_log_to_result_log(log));
                 }
                 // do not pass on these "internal logs" to caller
                 None
@@ -1890,7 +2286,7 @@ mod tests {
     use aurora_engine_sdk::promise::Noop;
     use aurora_engine_test_doubles::io::{Storage, StoragePointer};
     use aurora_engine_test_doubles::promise::PromiseTracker;
-    use aurora_engine_types::parameters::engine::RelayerKeyArgs;
+    use aurora_engine_types::parameters::engine::{RelayerKeyArgs, SiloParams};
     use aurora_engine_types::types::{make_address, Balance, NearGas, RawU256};
     use std::cell::RefCell;
 
@@ -2106,7 +2502,9 @@ mod tests {
         engine
             .register_token(erc20_token, nep141_token.clone())
             .unwrap();
-        engine.receive_erc20_tokens(&nep141_token, &args, &current_account_id, &mut handler);
+        let received =
+            engine.receive_erc20_tokens(&nep141_token, &args, &current_account_id, &mut handler);
+        assert!(received);
 
         let storage = storage.borrow();
         let actual_output = storage.output.as_slice();
@@ -2191,11 +2589,129 @@ mod tests {
             prepaid_amount: Wei::zero(),
             effective_gas_price: U256::zero(),
             priority_fee_per_gas: U256::zero(),
+            fee_recipient: None,
         };
 
         assert_eq!(expected_result, actual_result);
     }
 
+    fn silo_transaction(max_fee_per_gas: u64) -> NormalizedEthTransaction {
+        NormalizedEthTransaction {
+            address: Address::default(),
+            chain_id: None,
+            nonce: U256::default(),
+            gas_limit: U256::from(1000),
+            max_priority_fee_per_gas: U256::from(max_fee_per_gas),
+            max_fee_per_gas: U256::from(max_fee_per_gas),
+            to: None,
+            value: Wei::default(),
+            data: vec![],
+            access_list: vec![],
+        }
+    }
+
+    #[test]
+    fn test_gas_charge_with_silo_params() {
+        let origin = Address::zero();
+        let fee_recipient = make_address(1, 1);
+        let env = Fixed::default();
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        add_balance(&mut io, &origin, Wei::new_u64(22000)).unwrap();
+        let state = EngineState {
+            silo_params: SiloParams {
+                min_gas_price: 2,
+                fixed_gas_price: Some(3),
+                fee_recipient: Some(fee_recipient),
+            },
+            ..Default::default()
+        };
+        let mut engine: Engine<_, _> =
+            Engine::new_with_state(state, origin, AccountId::default(), io, &env);
+
+        let actual_error = engine
+            .charge_gas(&origin, &silo_transaction(2), None)
+            .unwrap_err();
+        assert_eq!(actual_error, GasPaymentError::GasPriceTooLow);
+
+        let actual_result = engine
+            .charge_gas(&origin, &silo_transaction(10), None)
+            .unwrap();
+        let expected_result = GasPaymentResult {
+            prepaid_amount: Wei::new_u64(3000),
+            effective_gas_price: U256::from(3),
+            priority_fee_per_gas: U256::from(3),
+            fee_recipient: Some(fee_recipient),
+        };
+        assert_eq!(expected_result, actual_result);
+        assert_eq!(get_balance(&io, &origin), Wei::new_u64(19000));
+    }
+
+    #[test]
+    fn test_gas_charge_for_fee_waived_sender_is_zero() {
+        let origin = Address::zero();
+        let env = Fixed::default();
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        add_balance(&mut io, &origin, Wei::new_u64(22000)).unwrap();
+        let state = EngineState {
+            silo_params: SiloParams {
+                min_gas_price: 2,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        fee_waiver::add(&mut io, &origin);
+        let mut engine: Engine<_, _> =
+            Engine::new_with_state(state, origin, AccountId::default(), io, &env);
+
+        let actual_result = engine
+            .charge_gas(&origin, &silo_transaction(0), None)
+            .unwrap();
+        assert_eq!(GasPaymentResult::default(), actual_result);
+        assert_eq!(get_balance(&io, &origin), Wei::new_u64(22000));
+    }
+
+    #[test]
+    fn test_gas_charge_with_fixed_gas_price_and_zero_min_gas_price() {
+        let origin = Address::zero();
+        let waived_sender = make_address(1, 1);
+        let env = Fixed::default();
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        add_balance(&mut io, &origin, Wei::new_u64(22000)).unwrap();
+        fee_waiver::add(&mut io, &waived_sender);
+        let state = EngineState {
+            silo_params: SiloParams {
+                min_gas_price: 0,
+                fixed_gas_price: Some(3),
+                fee_recipient: None,
+            },
+            ..Default::default()
+        };
+        let mut engine: Engine<_, _> =
+            Engine::new_with_state(state, origin, AccountId::default(), io, &env);
+
+        let actual_error = engine
+            .charge_gas(&origin, &silo_transaction(0), None)
+            .unwrap_err();
+        assert_eq!(actual_error, GasPaymentError::GasPriceTooLow);
+        let actual_error = engine
+            .charge_gas(&waived_sender, &silo_transaction(0), None)
+            .unwrap_err();
+        assert_eq!(actual_error, GasPaymentError::GasPriceTooLow);
+
+        let actual_result = engine
+            .charge_gas(&origin, &silo_transaction(3), None)
+            .unwrap();
+        assert_eq!(actual_result.effective_gas_price, U256::from(3));
+        assert_eq!(get_balance(&io, &origin), Wei::new_u64(19000));
+        let actual_result = engine
+            .charge_gas(&waived_sender, &silo_transaction(3), None)
+            .unwrap();
+        assert_eq!(GasPaymentResult::default(), actual_result);
+    }
+
     #[test]
     fn test_scheduling_promise_creates_it() {
         use aurora_engine_test_doubles::promise::PromiseArgs;
@@ -2344,6 +2860,7 @@ mod tests {
             prepaid_amount: Wei::default(),
             effective_gas_price: U256::zero(),
             priority_fee_per_gas: U256::zero(),
+            fee_recipient: None,
         };
 
         refund_unused_gas(&mut io, &origin, 1000, &gas_result, &relayer).unwrap();
@@ -2361,6 +2878,7 @@ mod tests {
             prepaid_amount: Wei::new_u64(8000),
             effective_gas_price: Wei::new_u64(1).raw(),
             priority_fee_per_gas: U256::zero(),
+            fee_recipient: None,
         };
         let gas_used = 4000;
 
@@ -2372,6 +2890,27 @@ mod tests {
         assert_eq!(expected_refund, actual_refund);
     }
 
+    #[test]
+    fn test_refund_gas_rewards_fee_recipient() {
+        let origin = Address::zero();
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let relayer = make_address(1, 1);
+        let fee_recipient = make_address(2, 2);
+        let gas_result = GasPaymentResult {
+            prepaid_amount: Wei::new_u64(8000),
+            effective_gas_price: Wei::new_u64(2).raw(),
+            priority_fee_per_gas: Wei::new_u64(2).raw(),
+            fee_recipient: Some(fee_recipient),
+        };
+
+        refund_unused_gas(&mut io, &origin, 1000, &gas_result, &relayer).unwrap();
+
+        assert_eq!(get_balance(&io, &origin), Wei::new_u64(6000));
+        assert_eq!(get_balance(&io, &fee_recipient), Wei::new_u64(2000));
+        assert_eq!(get_balance(&io, &relayer), Wei::zero());
+    }
+
     #[test]
     fn test_check_nonce_with_increment_succeeds() {
         let origin = Address::zero();
@@ -2442,11 +2981,84 @@ mod tests {
         let result = remove_function_call_key(&mut io, &public_key);
         assert!(result.is_err()); // should fail because the key doesn't exist yet.
 
-        add_function_call_key(&mut io, &public_key);
+        add_function_call_key(&mut io, &public_key, &RelayerKeyMetadata::default());
 
         let result = remove_function_call_key(&mut io, &public_key);
         assert!(result.is_ok());
         let result = remove_function_call_key(&mut io, &public_key);
         assert!(result.is_err()); // should fail because the key doesn't exist anymore.
     }
+
+    #[test]
+    fn test_function_call_key_metadata() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let public_key: PublicKey = "ed25519:DcA2MzgpJbrUATQLLceocVckhhAqrkingax4oJ9kZ847"
+            .parse()
+            .unwrap();
+        let legacy_key: PublicKey = "ed25519:3gyjNWQWMZNrzogroPWa53J2bCqvWpUgBZVRhJrAWJ7M"
+            .parse()
+            .unwrap();
+        let metadata = RelayerKeyMetadata {
+            allowed_methods: vec!["submit".into()],
+            expires_at: Some(100),
+        };
+
+        assert_eq!(get_function_call_key_metadata(&io, &public_key), None);
+        add_function_call_key(&mut io, &public_key, &metadata);
+        assert_eq!(
+            get_function_call_key_metadata(&io, &public_key),
+            Some(metadata)
+        );
+        assert_eq!(get_function_call_keys(&io), vec![public_key]);
+
+        // Keys added by the previous versions of the engine have no metadata and aren't listed.
+        assert!(list_function_call_key(&mut io, &legacy_key).is_err());
+        io.write_storage(
+            &bytes_to_key(KeyPrefix::RelayerFunctionCallKey, legacy_key.key_data()),
+            &[1],
+        );
+        assert_eq!(
+            get_function_call_key_metadata(&io, &legacy_key),
+            Some(RelayerKeyMetadata::default())
+        );
+        assert_eq!(get_function_call_keys(&io), vec![public_key]);
+
+        // They can be added to the list.
+        assert_eq!(list_function_call_key(&mut io, &legacy_key), Ok(true));
+        assert_eq!(list_function_call_key(&mut io, &legacy_key), Ok(false));
+        assert_eq!(list_function_call_key(&mut io, &public_key), Ok(false));
+        assert_eq!(get_function_call_keys(&io), vec![public_key, legacy_key]);
+
+        remove_function_call_key(&mut io, &legacy_key).unwrap();
+        remove_function_call_key(&mut io, &public_key).unwrap();
+        assert!(get_function_call_keys(&io).is_empty());
+    }
+
+    #[test]
+    fn test_parse_erc20_deposit_message() {
+        let recipient = Address::from_array([0x11; 20]);
+        let (address, call_data) = parse_erc20_deposit_message(&recipient.encode()).unwrap();
+        assert_eq!(address, recipient);
+        assert_eq!(call_data, None);
+
+        let message = format!("{}:{}", recipient.encode(), hex::encode([0xab, 0xcd]));
+        let (address, call_data) = parse_erc20_deposit_message(&message).unwrap();
+        assert_eq!(address, recipient);
+        assert_eq!(call_data, Some(vec![0xab, 0xcd]));
+
+        let message = format!("{}:", recipient.encode());
+        let (_, call_data) = parse_erc20_deposit_message(&message).unwrap();
+        assert_eq!(call_data, Some(Vec::new()));
+
+        let message = format!("{}:xyz", recipient.encode());
+        assert_eq!(
+            parse_erc20_deposit_message(&message),
+            Err(errors::ERR_INVALID_ON_TRANSFER_MESSAGE_HEX.as_slice())
+        );
+        assert_eq!(
+            parse_erc20_deposit_message("1111"),
+            Err(errors::ERR_INVALID_ON_TRANSFER_MESSAGE_FORMAT.as_slice())
+        );
+    }
 }
@@ -0,0 +1,147 @@
+//! Versioned registry of the ERC-20 code used for bridged NEP-141 tokens.
+//!
+//! Tokens deployed before any code was set in the registry use the built-in bytecode
+//! (`EvmErc20V3`, or `EvmErc20V3Refund` with the `error_refund` feature; older tokens may
+//! still use `EvmErc20` or `EvmErc20V2`) and are considered to be of version 0. Each update
+//! of the code increments the latest version by 1. Existing tokens can be migrated to the
+//! latest version by the owner, which replaces the code of the token while preserving its
+//! storage. Therefore, the code set in the registry must keep the storage layout of the
+//! previous versions and must not use immutable variables (its runtime code must not depend
+//! on the constructor arguments).
+use crate::engine::{self, Engine};
+use crate::parameters::TransactionStatus;
+use aurora_engine_sdk::env::Env;
+use aurora_engine_sdk::error::ReadU32Error;
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_types::storage::{self, KeyPrefix};
+use aurora_engine_types::types::Address;
+use aurora_engine_types::{Cow, Vec};
+
+pub use aurora_engine_types::parameters::xcc::CodeVersion;
+
+pub const ERR_NO_ERC20_CODE: &str = "ERR_MISSING_ERC20_BYTECODE";
+pub const ERR_CORRUPTED_STORAGE: &str = "ERR_CORRUPTED_ERC20_CODE_STORAGE";
+pub const CODE_KEY: &[u8] = b"erc20_code";
+pub const VERSION_KEY: &[u8] = b"version";
+
+/// Type wrapper for ERC-20 creation bytecode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Erc20Code<'a>(pub Cow<'a, [u8]>);
+
+impl<'a> Erc20Code<'a> {
+    #[must_use]
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(Cow::Owned(bytes))
+    }
+
+    #[must_use]
+    pub const fn borrowed(bytes: &'a [u8]) -> Self {
+        Self(Cow::Borrowed(bytes))
+    }
+}
+
+/// Read the latest ERC-20 creation bytecode (if it was set).
+pub fn get_erc20_code<I: IO>(io: &I) -> Option<Erc20Code> {
+    let key = storage::bytes_to_key(KeyPrefix::Erc20Code, CODE_KEY);
+    io.read_storage(&key)
+        .map(|bytes| Erc20Code::new(bytes.to_vec()))
+}
+
+/// Set new ERC-20 creation bytecode, and increment the version by 1.
+pub fn update_erc20_code<I: IO>(io: &mut I, code: &Erc20Code) {
+    let key = storage::bytes_to_key(KeyPrefix::Erc20Code, CODE_KEY);
+    io.write_storage(&key, &code.0);
+
+    let current_version = get_latest_code_version(io);
+    set_latest_code_version(io, current_version.increment());
+}
+
+/// Get the latest ERC-20 code version.
+pub fn get_latest_code_version<I: IO>(io: &I) -> CodeVersion {
+    let key = storage::bytes_to_key(KeyPrefix::Erc20Code, VERSION_KEY);
+    read_version(io, &key).unwrap_or_default()
+}
+
+/// Get the version of the code of the given bridged token.
+pub fn get_code_version_of_token<I: IO>(io: &I, address: &Address) -> CodeVersion {
+    let key = storage::bytes_to_key(KeyPrefix::Erc20Code, address.as_bytes());
+    read_version(io, &key).unwrap_or_default()
+}
+
+/// Set the version of the code of the given bridged token.
+pub fn set_code_version_of_token<I: IO>(io: &mut I, address: &Address, version: CodeVersion) {
+    let key = storage::bytes_to_key(KeyPrefix::Erc20Code, address.as_bytes());
+    let value_bytes = version.0.to_le_bytes();
+    io.write_storage(&key, &value_bytes);
+}
+
+/// Replaces the code of the bridged token with the latest code from the registry.
+pub fn migrate_erc20_token<I: IO + Copy, E: Env>(
+    io: I,
+    env: &E,
+    address: Address,
+) -> Result<CodeVersion, MigrateErc20Error> {
+    let is_bridged = engine::nep141_erc20_map(io)
+        .lookup_right(&engine::ERC20Address(address))
+        .is_some();
+    if !is_bridged || crate::native_erc20::is_native_erc20(&io, &address) {
+        return Err(MigrateErc20Error::NotBridgedToken);
+    }
+    let latest_version = get_latest_code_version(&io);
+    if get_code_version_of_token(&io, &address) >= latest_version {
+        return Err(MigrateErc20Error::UpToDate);
+    }
+    let code = get_erc20_code(&io).ok_or(MigrateErc20Error::MissingCode)?;
+
+    let current_account_id = env.current_account_id();
+    let input = engine::setup_deploy_erc20_input_with_code(&code.0, &current_account_id);
+    let erc20_admin_address = engine::current_address(&current_account_id);
+    let view_engine: Engine<_, _> = Engine::new(erc20_admin_address, current_account_id, io, env)
+        .map_err(|_| MigrateErc20Error::Deploy)?;
+    let runtime_code = match view_engine.view_deploy_code(&erc20_admin_address, input) {
+        Ok(TransactionStatus::Succeed(runtime_code)) if !runtime_code.is_empty() => runtime_code,
+        _ => return Err(MigrateErc20Error::Deploy),
+    };
+
+    let mut io = io;
+    engine::set_code(&mut io, &address, &runtime_code);
+    set_code_version_of_token(&mut io, &address, latest_version);
+
+    Ok(latest_version)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrateErc20Error {
+    NotBridgedToken,
+    UpToDate,
+    MissingCode,
+    Deploy,
+}
+
+impl AsRef<[u8]> for MigrateErc20Error {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::NotBridgedToken => b"ERR_NOT_BRIDGED_ERC20",
+            Self::UpToDate => b"ERR_ERC20_CODE_UP_TO_DATE",
+            Self::MissingCode => ERR_NO_ERC20_CODE.as_bytes(),
+            Self::Deploy => b"ERR_ERC20_CODE_DEPLOY_FAILED",
+        }
+    }
+}
+
+/// Sets the latest ERC-20 code version. This function is intentionally private because
+/// it should never be set manually. The version is managed automatically by `update_erc20_code`.
+fn set_latest_code_version<I: IO>(io: &mut I, version: CodeVersion) {
+    let key = storage::bytes_to_key(KeyPrefix::Erc20Code, VERSION_KEY);
+    let value_bytes = version.0.to_le_bytes();
+    io.write_storage(&key, &value_bytes);
+}
+
+/// Private utility method for reading code version from storage.
+fn read_version<I: IO>(io: &I, key: &[u8]) -> Option<CodeVersion> {
+    match io.read_u32(key) {
+        Ok(value) => Some(CodeVersion(value)),
+        Err(ReadU32Error::MissingValue) => None,
+        Err(ReadU32Error::InvalidU32) => panic!("{}", ERR_CORRUPTED_STORAGE),
+    }
+}
@@ -21,6 +21,7 @@ pub const ERR_PROMISE_FAILED: &[u8; 18] = b"ERR_PROMISE_FAILED";
 pub const ERR_VERIFY_PROOF: &[u8; 16] = b"ERR_VERIFY_PROOF";
 pub const ERR_INVALID_UPGRADE: &[u8; 19] = b"ERR_INVALID_UPGRADE";
 pub const ERR_NO_UPGRADE: &[u8; 14] = b"ERR_NO_UPGRADE";
+pub const ERR_UPGRADE_CODE_HASH_MISMATCH: &[u8] = b"ERR_UPGRADE_CODE_HASH_MISMATCH";
 pub const ERR_NOT_ALLOWED: &[u8; 15] = b"ERR_NOT_ALLOWED";
 pub const ERR_PAUSED: &[u8; 10] = b"ERR_PAUSED";
 pub const ERR_RUNNING: &[u8; 11] = b"ERR_RUNNING";
@@ -43,6 +44,7 @@ pub const ERR_CREATE_COLLISION: &[u8; 20] = b"ERR_CREATE_COLLISION";
 pub const ERR_CREATE_CONTRACT_LIMIT: &[u8; 25] = b"ERR_CREATE_CONTRACT_LIMIT";
 pub const ERR_INVALID_OPCODE: &[u8; 18] = b"ERR_INVALID_OPCODE";
 pub const ERR_OUT_OF_FUND: &[u8; 15] = b"ERR_OUT_OF_FUND";
+pub const ERR_GAS_PRICE_TOO_LOW: &[u8; 21] = b"ERR_GAS_PRICE_TOO_LOW";
 pub const ERR_CREATE_EMPTY: &[u8; 16] = b"ERR_CREATE_EMPTY";
 pub const ERR_MAX_NONCE: &[u8; 13] = b"ERR_MAX_NONCE";
 pub const ERR_NOT_SUPPORTED: &[u8; 17] = b"ERR_NOT_SUPPORTED";
@@ -93,6 +95,25 @@ pub const ERR_SAME_OWNER: &[u8; 14] = b"ERR_SAME_OWNER";
 pub const ERR_SAME_KEY_MANAGER: &[u8] = b"ERR_SAME_KEY_MANAGER";
 pub const ERR_FUNCTION_CALL_KEY_NOT_FOUND: &[u8] = b"ERR_FUNCTION_CALL_KEY_NOT_FOUND";
 pub const ERR_KEY_MANAGER_IS_NOT_SET: &[u8] = b"ERR_KEY_MANAGER_IS_NOT_SET";
+pub const ERR_RELAYER_KEY_EXPIRED: &[u8] = b"ERR_RELAYER_KEY_EXPIRED";
+pub const ERR_METHOD_PAUSED: &[u8] = b"ERR_METHOD_PAUSED";
+pub const ERR_METHOD_NOT_PAUSABLE: &[u8] = b"ERR_METHOD_NOT_PAUSABLE";
+pub const ERR_UNKNOWN_METHOD: &[u8] = b"ERR_UNKNOWN_METHOD";
+pub const ERR_PAUSED_METHODS_CORRUPTED: &[u8] = b"ERR_PAUSED_METHODS_CORRUPTED";
+pub const ERR_DENIED_SENDER: &[u8] = b"ERR_DENIED_SENDER";
+pub const ERR_DENIED_RECIPIENT: &[u8] = b"ERR_DENIED_RECIPIENT";
+pub const ERR_ALREADY_DENIED: &[u8] = b"ERR_ALREADY_DENIED";
+pub const ERR_NOT_DENIED: &[u8] = b"ERR_NOT_DENIED";
+pub const ERR_DEPLOYER_NOT_ALLOWED: &[u8] = b"ERR_DEPLOYER_NOT_ALLOWED";
+pub const ERR_DEPLOYER_ALREADY_ALLOWED: &[u8] = b"ERR_DEPLOYER_ALREADY_ALLOWED";
+pub const ERR_DEPLOYER_NOT_FOUND: &[u8] = b"ERR_DEPLOYER_NOT_FOUND";
+pub const ERR_INVALID_SILO_PARAMS: &[u8] = b"ERR_INVALID_SILO_PARAMS";
+pub const ERR_NOT_WHITELISTED_ACCOUNT: &[u8] = b"ERR_NOT_WHITELISTED_ACCOUNT";
+pub const ERR_NOT_WHITELISTED_ADDRESS: &[u8] = b"ERR_NOT_WHITELISTED_ADDRESS";
+pub const ERR_INVALID_RELAYER_KEY_METHODS: &[u8] = b"ERR_INVALID_RELAYER_KEY_METHODS";
+pub const ERR_INVALID_RELAYER_KEY_EXPIRY: &[u8] = b"ERR_INVALID_RELAYER_KEY_EXPIRY";
+pub const ERR_ROLE_ALREADY_GRANTED: &[u8] = b"ERR_ROLE_ALREADY_GRANTED";
+pub const ERR_ROLE_NOT_GRANTED: &[u8] = b"ERR_ROLE_NOT_GRANTED";
 pub const ERR_ACCOUNTS_COUNTER_OVERFLOW: &str = "ERR_ACCOUNTS_COUNTER_OVERFLOW";
 pub const ERR_DECODING_TOKEN: &[u8] = b"ERR_DECODING_TOKEN";
 pub const ERR_GETTING_TOKEN: &[u8] = b"ERR_GETTING_TOKEN";
@@ -0,0 +1,54 @@
+//! EVM addresses whose transactions are not charged for gas in a silo.
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_types::borsh::BorshDeserialize;
+use aurora_engine_types::storage::{address_to_key, bytes_to_key, KeyPrefix};
+use aurora_engine_types::types::Address;
+use aurora_engine_types::Vec;
+
+/// Key of the list of all fee waived senders, which is kept for the views.
+const SENDERS_KEY: &[u8] = b"LIST";
+
+/// Returns true if the transactions of the address are not charged for gas.
+pub fn is_fee_waived<I: IO>(io: &I, address: &Address) -> bool {
+    io.storage_has_key(&address_to_key(KeyPrefix::FeeWaivedSenders, address))
+}
+
+/// Adds the address to the fee waived senders. Returns false if it was already added.
+pub fn add<I: IO>(io: &mut I, address: &Address) -> bool {
+    let key = address_to_key(KeyPrefix::FeeWaivedSenders, address);
+    if io.write_storage(&key, &[1]).is_some() {
+        return false;
+    }
+    let mut addresses = get_senders(io);
+    addresses.push(*address);
+    set_senders(io, &addresses);
+    true
+}
+
+/// Removes the address from the fee waived senders. Returns false if it wasn't added.
+pub fn remove<I: IO>(io: &mut I, address: &Address) -> bool {
+    let key = address_to_key(KeyPrefix::FeeWaivedSenders, address);
+    if io.remove_storage(&key).is_none() {
+        return false;
+    }
+    let mut addresses = get_senders(io);
+    addresses.retain(|a| a != address);
+    set_senders(io, &addresses);
+    true
+}
+
+/// Returns all fee waived senders in the order they were added.
+pub fn get_senders<I: IO>(io: &I) -> Vec<Address> {
+    io.read_storage(&bytes_to_key(KeyPrefix::FeeWaivedSenders, SENDERS_KEY))
+        .and_then(|value| Vec::try_from_slice(&value.to_vec()).ok())
+        .unwrap_or_default()
+}
+
+fn set_senders<I: IO>(io: &mut I, addresses: &[Address]) {
+    let key = bytes_to_key(KeyPrefix::FeeWaivedSenders, SENDERS_KEY);
+    if addresses.is_empty() {
+        io.remove_storage(&key);
+    } else {
+        io.write_borsh(&key, &addresses);
+    }
+}
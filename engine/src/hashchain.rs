@@ -1,4 +1,4 @@
-use crate::contract_methods::ContractError;
+use crate::contract_methods::{require_method_not_paused, ContractError};
 use aurora_engine_hashchain::{
     bloom::{self, Bloom},
     error::BlockchainHashchainError,
@@ -17,6 +17,9 @@ use core::cell::RefCell;
 
 pub const HASHCHAIN_STATE: &[u8] = b"HC_STATE";
 
+/// Runs the engine method and records it in the hashchain. All the methods changing the
+/// engine state are run via this function (or `with_logs_hashchain`), so it also rejects the
+/// calls of the paused methods.
 pub fn with_hashchain<I, E, T, F>(
     mut io: I,
     env: &E,
@@ -28,6 +31,7 @@ where
     E: Env,
     F: for<'a> FnOnce(CachedIO<'a, I>) -> Result<T, ContractError>,
 {
+    require_method_not_paused(&io, function_name)?;
     let block_height = env.block_height();
     let maybe_hashchain = load_hashchain(&io, block_height)?;
 
@@ -61,6 +65,7 @@ where
     E: Env,
     F: for<'a> FnOnce(CachedIO<'a, I>) -> Result<SubmitResult, ContractError>,
 {
+    require_method_not_paused(&io, function_name)?;
     let block_height = env.block_height();
     let maybe_hashchain = load_hashchain(&io, block_height)?;
 
@@ -32,14 +32,21 @@ pub mod admin_controlled;
 #[cfg_attr(feature = "contract", allow(dead_code))]
 pub mod connector;
 pub mod contract_methods;
+pub mod denylist;
+pub mod deploy_allowlist;
 pub mod deposit_event;
 pub mod engine;
+pub mod erc20_code;
 pub mod errors;
+pub mod fee_waiver;
 pub mod fungible_token;
 pub mod hashchain;
+pub mod migrations;
+pub mod native_erc20;
 pub mod pausables;
 mod prelude;
 pub mod state;
+pub mod whitelist;
 pub mod xcc;
 
 #[cfg(target_arch = "wasm32")]
@@ -104,7 +111,6 @@ mod contract {
     use crate::prelude::NearGas;
 
     const CODE_KEY: &[u8; 4] = b"CODE";
-    const CODE_STAGE_KEY: &[u8; 10] = b"CODE_STAGE";
 
     // TODO: rust-2023-08-24  #[allow(clippy::empty_line_after_doc_comments)]
     ///
@@ -203,29 +209,57 @@ mod contract {
             .sdk_unwrap();
     }
 
-    /// Deploy staged upgrade.
+    /// Cancel staged upgrade.
+    #[no_mangle]
+    pub extern "C" fn cancel_upgrade() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::cancel_upgrade(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Get the hash, size and deployment height of the staged upgrade.
+    #[no_mangle]
+    pub extern "C" fn get_staged_upgrade() {
+        let io = Runtime;
+        contract_methods::admin::get_staged_upgrade(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Deploy staged upgrade. Optionally takes the expected hash of the staged code.
     #[no_mangle]
     pub extern "C" fn deploy_upgrade() {
-        // This function is intentionally not implemented in `contract_methods`
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::prepare_deploy_upgrade(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+        // The deployment itself is intentionally not implemented in `contract_methods`
         // because it only make sense in the context of the Near runtime.
-        let mut io = Runtime;
-        let state = state::get_state(&io).sdk_unwrap();
-        require_running(&state);
-        let index = internal_get_upgrade_index();
-        if io.block_height() <= index {
-            sdk::panic_utf8(errors::ERR_NOT_ALLOWED_TOO_EARLY);
-        }
         Runtime::self_deploy(&bytes_to_key(KeyPrefix::Config, CODE_KEY));
-        io.remove_storage(&bytes_to_key(KeyPrefix::Config, CODE_STAGE_KEY));
     }
 
     /// Called as part of the upgrade process (see `engine-sdk::self_deploy`). This function is meant
     /// to make any necessary changes to the state such that it aligns with the newly deployed
-    /// code.
+    /// code. The migration can be resumed by calling this method again if it didn't complete.
     #[no_mangle]
-    #[allow(clippy::missing_const_for_fn)]
     pub extern "C" fn state_migration() {
-        // TODO: currently we don't have migrations
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::state_migration(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Get the version of the storage schema.
+    #[no_mangle]
+    pub extern "C" fn get_schema_version() {
+        let io = Runtime;
+        contract_methods::admin::get_schema_version(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
     }
 
     /// Resumes previously [`paused`] precompiles.
@@ -260,6 +294,36 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// Pauses the engine methods with the given names, e.g. `submit` or `ft_on_transfer`.
+    /// The methods controlling the pausing itself can't be paused.
+    #[no_mangle]
+    pub extern "C" fn pause_methods() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::pause_methods(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Resumes the engine methods with the given names.
+    #[no_mangle]
+    pub extern "C" fn resume_methods() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::resume_methods(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns the JSON list of the paused engine methods.
+    #[no_mangle]
+    pub extern "C" fn get_paused_methods() {
+        let io = Runtime;
+        contract_methods::admin::get_paused_methods(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     /// Sets the flag to pause the contract.
     #[no_mangle]
     pub extern "C" fn pause_contract() {
@@ -423,6 +487,86 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// Updates the bytecode of the NEP-141 contracts deployed for ERC-20 tokens native to Aurora.
+    /// Only tokens deployed after the update use the new bytecode.
+    #[no_mangle]
+    pub extern "C" fn set_nep141_code() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::connector::set_nep141_code(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Deploy NEP141 token mapped to an ERC20 native to Aurora. Only callable by the owner or
+    /// a token admin. The id of the NEP141 account is returned once the token is registered.
+    #[no_mangle]
+    pub extern "C" fn deploy_nep141_token() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::connector::deploy_nep141_token(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Registers the NEP141 token deployed for an ERC20 native to Aurora. This is only
+    /// called as a callback of `deploy_nep141_token`.
+    #[no_mangle]
+    pub extern "C" fn register_nep141_token() {
+        let io = Runtime;
+        let env = Runtime;
+        let handler = Runtime;
+        contract_methods::connector::register_nep141_token(io, &env, &handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Updates the bytecode of the ERC-20 contracts deployed for bridged NEP-141 tokens.
+    /// The new bytecode is used by `deploy_erc20_token` and `migrate_erc20_token`.
+    #[no_mangle]
+    pub extern "C" fn set_erc20_code() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::connector::set_erc20_code(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Replaces the code of an existing bridged ERC-20 token with the latest bytecode
+    /// set by `set_erc20_code`.
+    #[no_mangle]
+    pub extern "C" fn migrate_erc20_token() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::connector::migrate_erc20_token(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Sets metadata of the ERC-20 contract mapped to the NEP-141 token to the metadata
+    /// returned by the `ft_metadata` method of the NEP-141 token.
+    #[no_mangle]
+    pub extern "C" fn sync_erc20_metadata() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::connector::sync_erc20_metadata(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Callback of `sync_erc20_metadata` which writes the metadata into the ERC-20 contract.
+    #[no_mangle]
+    pub extern "C" fn sync_erc20_metadata_callback() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::connector::sync_erc20_metadata_callback(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     /// Set metadata of ERC-20 contract.
     #[no_mangle]
     pub extern "C" fn set_erc20_metadata() {
@@ -456,6 +600,246 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// Grants a role to an account. Only the owner can grant roles.
+    #[no_mangle]
+    pub extern "C" fn grant_role() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::grant_role(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Revokes a role from an account. Only the owner can revoke roles.
+    #[no_mangle]
+    pub extern "C" fn revoke_role() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::revoke_role(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns the accounts which were granted a role, grouped by role.
+    #[no_mangle]
+    pub extern "C" fn get_roles() {
+        let io = Runtime;
+        contract_methods::admin::get_roles(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Adds an EVM address to the denylist.
+    #[no_mangle]
+    pub extern "C" fn deny_address() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::deny_address(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Removes an EVM address from the denylist.
+    #[no_mangle]
+    pub extern "C" fn allow_address() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::allow_address(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns whether the EVM address is in the denylist.
+    #[no_mangle]
+    pub extern "C" fn is_address_denied() {
+        let io = Runtime;
+        contract_methods::admin::is_address_denied(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns the JSON list of the denied EVM addresses.
+    #[no_mangle]
+    pub extern "C" fn get_denylist() {
+        let io = Runtime;
+        contract_methods::admin::get_denylist(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Enables or disables the deploy allowlist mode.
+    #[no_mangle]
+    pub extern "C" fn set_deploy_allowlist_enabled() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::set_deploy_allowlist_enabled(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns whether the deploy allowlist mode is enabled.
+    #[no_mangle]
+    pub extern "C" fn is_deploy_allowlist_enabled() {
+        let io = Runtime;
+        contract_methods::admin::is_deploy_allowlist_enabled(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Adds an EVM address to the approved deployers.
+    #[no_mangle]
+    pub extern "C" fn add_deployer() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::add_deployer(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Removes an EVM address from the approved deployers.
+    #[no_mangle]
+    pub extern "C" fn remove_deployer() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::remove_deployer(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns whether the EVM address is an approved deployer.
+    #[no_mangle]
+    pub extern "C" fn is_deployer_allowed() {
+        let io = Runtime;
+        contract_methods::admin::is_deployer_allowed(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns the JSON list of the approved deployers.
+    #[no_mangle]
+    pub extern "C" fn get_deployers() {
+        let io = Runtime;
+        contract_methods::admin::get_deployers(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Sets the economic settings of the silo.
+    #[no_mangle]
+    pub extern "C" fn set_silo_params() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::set_silo_params(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns the economic settings of the silo.
+    #[no_mangle]
+    pub extern "C" fn get_silo_params() {
+        let io = Runtime;
+        contract_methods::admin::get_silo_params(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Adds EVM addresses to the senders which are not charged for gas.
+    #[no_mangle]
+    pub extern "C" fn add_fee_waived_senders() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::add_fee_waived_senders(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Removes EVM addresses from the senders which are not charged for gas.
+    #[no_mangle]
+    pub extern "C" fn remove_fee_waived_senders() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::remove_fee_waived_senders(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns the JSON list of the senders which are not charged for gas.
+    #[no_mangle]
+    pub extern "C" fn get_fee_waived_senders() {
+        let io = Runtime;
+        contract_methods::admin::get_fee_waived_senders(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Enables or disables a whitelist of the silo.
+    #[no_mangle]
+    pub extern "C" fn set_whitelist_status() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::set_whitelist_status(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Adds EVM addresses to the whitelist of the transaction signers.
+    #[no_mangle]
+    pub extern "C" fn add_whitelisted_addresses() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::add_whitelisted_addresses(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Removes EVM addresses from the whitelist of the transaction signers.
+    #[no_mangle]
+    pub extern "C" fn remove_whitelisted_addresses() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::remove_whitelisted_addresses(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Adds NEAR accounts to the whitelist of the accounts executing EVM transactions.
+    #[no_mangle]
+    pub extern "C" fn add_whitelisted_accounts() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::add_whitelisted_accounts(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Removes NEAR accounts from the whitelist of the accounts executing EVM transactions.
+    #[no_mangle]
+    pub extern "C" fn remove_whitelisted_accounts() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::remove_whitelisted_accounts(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns the whitelist of the transaction signers as JSON.
+    #[no_mangle]
+    pub extern "C" fn get_address_whitelist() {
+        let io = Runtime;
+        contract_methods::admin::get_address_whitelist(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns the whitelist of the accounts executing EVM transactions as JSON.
+    #[no_mangle]
+    pub extern "C" fn get_account_whitelist() {
+        let io = Runtime;
+        contract_methods::admin::get_account_whitelist(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     /// Adds a relayer function call key.
     #[no_mangle]
     pub extern "C" fn add_relayer_key() {
@@ -478,6 +862,37 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// Removes the relayer function call keys which expired. Can be called by anyone.
+    #[no_mangle]
+    pub extern "C" fn remove_expired_relayer_keys() {
+        let io = Runtime;
+        let env = Runtime;
+        let mut handler = Runtime;
+        contract_methods::admin::remove_expired_relayer_keys(io, &env, &mut handler)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Adds the relayer function call keys created before the list of the keys was introduced
+    /// to the list. Can be called by the key manager only.
+    #[no_mangle]
+    pub extern "C" fn list_legacy_relayer_keys() {
+        let io = Runtime;
+        let env = Runtime;
+        contract_methods::admin::list_legacy_relayer_keys(io, &env)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
+    /// Returns the relayer function call keys with their allowed methods and expiry.
+    #[no_mangle]
+    pub extern "C" fn get_relayer_keys() {
+        let io = Runtime;
+        contract_methods::admin::get_relayer_keys(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     /// Initialize the hashchain.
     #[no_mangle]
     pub extern "C" fn start_hashchain() {
@@ -568,6 +983,15 @@ mod contract {
             .sdk_unwrap();
     }
 
+    /// Return the version of the code of the bridged ERC-20 contract in borsh format.
+    #[no_mangle]
+    pub extern "C" fn get_erc20_code_version() {
+        let io = Runtime;
+        contract_methods::connector::get_erc20_code_version(io)
+            .map_err(ContractError::msg)
+            .sdk_unwrap();
+    }
+
     ///
     /// BENCHMARKING METHODS
     ///
@@ -930,23 +1354,6 @@ mod contract {
     /// Utility methods.
     ///
 
-    fn internal_get_upgrade_index() -> u64 {
-        let io = Runtime;
-        match io.read_u64(&bytes_to_key(KeyPrefix::Config, CODE_STAGE_KEY)) {
-            Ok(index) => index,
-            Err(sdk::error::ReadU64Error::InvalidU64) => {
-                sdk::panic_utf8(errors::ERR_INVALID_UPGRADE)
-            }
-            Err(sdk::error::ReadU64Error::MissingValue) => sdk::panic_utf8(errors::ERR_NO_UPGRADE),
-        }
-    }
-
-    fn require_running(state: &state::EngineState) {
-        if state.is_paused {
-            sdk::panic_utf8(errors::ERR_PAUSED);
-        }
-    }
-
     mod exports {
         extern "C" {
             pub(crate) fn value_return(value_len: u64, value_ptr: u64);
@@ -0,0 +1,255 @@
+//! Versioned migrations of the engine storage.
+//!
+//! The engine records the version of its storage schema. Each migration step upgrades the
+//! schema by one version and the steps are run in order by `state_migration`, which is
+//! called automatically after an upgrade. A step which needs to process more data than fits
+//! in a single transaction (e.g. rewriting storage keys) processes at most `batch_size`
+//! items per call and saves a cursor. The migration is then resumed by the next call of
+//! `state_migration`. Since the batches do not depend on the gas, the standalone engine
+//! runs exactly the same migrations as the contract.
+use crate::state;
+use aurora_engine_sdk::error::ReadU32Error;
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
+use aurora_engine_types::{vec, Vec};
+
+pub const SCHEMA_VERSION_KEY: &[u8] = b"SCHEMA_VERSION";
+pub const MIGRATION_CURSOR_KEY: &[u8] = b"MIGRATION_CURSOR";
+/// The number of items processed by a call of a batched step if not specified otherwise.
+pub const DEFAULT_BATCH_SIZE: u32 = 100;
+/// The schema version of a fully migrated storage. It must be equal to the number of steps.
+pub const LATEST_SCHEMA_VERSION: u32 = 1;
+
+/// Outcome of a single call of a migration step.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The step is complete.
+    Done,
+    /// The step must be resumed from the given cursor.
+    InProgress { cursor: Vec<u8> },
+}
+
+/// A migration step upgrading the storage schema to the next version. The step is given the
+/// cursor it saved previously (if any) and the maximum number of items it should process.
+pub struct MigrationStep<I> {
+    pub name: &'static str,
+    pub run: fn(&mut I, Option<&[u8]>, u32) -> Result<StepOutcome, MigrationError>,
+}
+
+/// Status of the migration after a call of `state_migration`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct MigrationStatus {
+    pub schema_version: u32,
+    pub is_complete: bool,
+}
+
+/// All migration steps, ordered by the schema version they upgrade to.
+#[must_use]
+pub fn migration_steps<I: IO + Copy>() -> Vec<MigrationStep<I>> {
+    vec![MigrationStep {
+        name: "rewrite_engine_state",
+        run: migrate_engine_state,
+    }]
+}
+
+/// Runs the pending migration steps.
+pub fn migrate<I: IO + Copy>(
+    io: &mut I,
+    batch_size: u32,
+) -> Result<MigrationStatus, MigrationError> {
+    run_migration_steps(io, &migration_steps(), batch_size)
+}
+
+/// Read the storage schema version. The storage without a version is of version 0.
+pub fn get_schema_version<I: IO>(io: &I) -> Result<u32, MigrationError> {
+    match io.read_u32(&bytes_to_key(KeyPrefix::Config, SCHEMA_VERSION_KEY)) {
+        Ok(version) => Ok(version),
+        Err(ReadU32Error::MissingValue) => Ok(0),
+        Err(ReadU32Error::InvalidU32) => Err(MigrationError::CorruptedVersion),
+    }
+}
+
+/// Set the storage schema version. It is only meant to be used for a new storage (which
+/// does not need any migrations) and by the migrations themselves.
+pub fn set_schema_version<I: IO>(io: &mut I, version: u32) {
+    io.write_storage(
+        &bytes_to_key(KeyPrefix::Config, SCHEMA_VERSION_KEY),
+        &version.to_le_bytes(),
+    );
+}
+
+fn run_migration_steps<I: IO + Copy>(
+    io: &mut I,
+    steps: &[MigrationStep<I>],
+    batch_size: u32,
+) -> Result<MigrationStatus, MigrationError> {
+    let cursor_key = bytes_to_key(KeyPrefix::Config, MIGRATION_CURSOR_KEY);
+    let mut version = get_schema_version(io)?;
+
+    let completed_steps = usize::try_from(version).map_err(|_| MigrationError::CorruptedVersion)?;
+    for step in steps.iter().skip(completed_steps) {
+        let cursor = io.read_storage(&cursor_key).map(|value| value.to_vec());
+        match (step.run)(io, cursor.as_deref(), batch_size)? {
+            StepOutcome::Done => {
+                aurora_engine_sdk::log!("Migration step {} is complete", step.name);
+                io.remove_storage(&cursor_key);
+                version += 1;
+                set_schema_version(io, version);
+            }
+            StepOutcome::InProgress { cursor } => {
+                io.write_storage(&cursor_key, &cursor);
+                return Ok(MigrationStatus {
+                    schema_version: version,
+                    is_complete: false,
+                });
+            }
+        }
+    }
+
+    Ok(MigrationStatus {
+        schema_version: version,
+        is_complete: true,
+    })
+}
+
+/// Rewrites the engine state in the latest format, instead of relying on the lazy migration
+/// happening when it is read.
+fn migrate_engine_state<I: IO + Copy>(
+    io: &mut I,
+    _cursor: Option<&[u8]>,
+    _batch_size: u32,
+) -> Result<StepOutcome, MigrationError> {
+    let state = state::get_state(io).map_err(|_| MigrationError::EngineState)?;
+    state::set_state(io, &state).map_err(|_| MigrationError::EngineState)?;
+    Ok(StepOutcome::Done)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MigrationError {
+    CorruptedVersion,
+    EngineState,
+    InvalidCursor,
+}
+
+impl AsRef<[u8]> for MigrationError {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::CorruptedVersion => b"ERR_CORRUPTED_SCHEMA_VERSION",
+            Self::EngineState => b"ERR_MIGRATE_ENGINE_STATE",
+            Self::InvalidCursor => b"ERR_INVALID_MIGRATION_CURSOR",
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "std")]
+mod tests {
+    use super::*;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use std::cell::RefCell;
+
+    const ITEMS: u32 = 5;
+
+    fn old_key(i: u32) -> Vec<u8> {
+        [b"old".as_slice(), &i.to_le_bytes()].concat()
+    }
+
+    fn new_key(i: u32) -> Vec<u8> {
+        [b"new".as_slice(), &i.to_le_bytes()].concat()
+    }
+
+    /// Moves the values stored under `ITEMS` old keys to new keys.
+    fn rewrite_keys<I: IO>(
+        io: &mut I,
+        cursor: Option<&[u8]>,
+        batch_size: u32,
+    ) -> Result<StepOutcome, MigrationError> {
+        let start = cursor.map_or(Ok(0), |bytes| {
+            bytes
+                .try_into()
+                .map(u32::from_le_bytes)
+                .map_err(|_| MigrationError::InvalidCursor)
+        })?;
+        let end = ITEMS.min(start + batch_size);
+        for i in start..end {
+            if let Some(value) = io.remove_storage(&old_key(i)) {
+                io.write_storage(&new_key(i), &value.to_vec());
+            }
+        }
+        if end == ITEMS {
+            Ok(StepOutcome::Done)
+        } else {
+            Ok(StepOutcome::InProgress {
+                cursor: end.to_le_bytes().to_vec(),
+            })
+        }
+    }
+
+    fn mark_done<I: IO>(
+        io: &mut I,
+        _cursor: Option<&[u8]>,
+        _batch_size: u32,
+    ) -> Result<StepOutcome, MigrationError> {
+        io.write_storage(b"done", &[1]);
+        Ok(StepOutcome::Done)
+    }
+
+    #[test]
+    fn test_latest_schema_version_matches_steps() {
+        let steps = migration_steps::<StoragePointer>();
+        assert_eq!(u32::try_from(steps.len()).unwrap(), LATEST_SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_batched_migration_is_resumed() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        for i in 0..ITEMS {
+            io.write_storage(&old_key(i), &[1]);
+        }
+        let steps = [
+            MigrationStep {
+                name: "rewrite_keys",
+                run: rewrite_keys,
+            },
+            MigrationStep {
+                name: "mark_done",
+                run: mark_done,
+            },
+        ];
+
+        let status = run_migration_steps(&mut io, &steps, 2).unwrap();
+        assert_eq!(
+            status,
+            MigrationStatus {
+                schema_version: 0,
+                is_complete: false
+            }
+        );
+        assert!(io.read_storage(&new_key(1)).is_some());
+        assert!(io.read_storage(&new_key(2)).is_none());
+
+        run_migration_steps(&mut io, &steps, 2).unwrap();
+        let status = run_migration_steps(&mut io, &steps, 2).unwrap();
+        assert_eq!(
+            status,
+            MigrationStatus {
+                schema_version: 2,
+                is_complete: true
+            }
+        );
+        for i in 0..ITEMS {
+            assert!(io.read_storage(&old_key(i)).is_none());
+            assert!(io.read_storage(&new_key(i)).is_some());
+        }
+        assert!(io.read_storage(b"done").is_some());
+        assert!(io
+            .read_storage(&bytes_to_key(KeyPrefix::Config, MIGRATION_CURSOR_KEY))
+            .is_none());
+
+        // Completed steps are not run again.
+        io.remove_storage(b"done");
+        run_migration_steps(&mut io, &steps, 2).unwrap();
+        assert!(io.read_storage(b"done").is_none());
+    }
+}
@@ -0,0 +1,251 @@
+//! Bridging of ERC-20 tokens native to Aurora into NEP-141 tokens on NEAR.
+//!
+//! For each such token the engine deploys a NEP-141 contract on the sub-account
+//! `{erc20_address}-nep141.{engine}`. The NEP-141 contract must implement:
+//!   - `new(owner_id, metadata)` to initialize the token;
+//!   - `mint(account_id, amount)`, only callable by the owner (i.e. the engine);
+//!   - `burn(amount)`, only callable by the owner, burning tokens held by the owner.
+//!
+//! Tokens moving to NEAR are locked in the exit to NEAR precompile address and minted by the
+//! NEP-141 contract. Tokens moving back to Aurora are transferred to the engine with
+//! `ft_transfer_call`, unlocked on the EVM side and then burned by the NEP-141 contract.
+use crate::errors::ERR_SERIALIZE;
+use crate::parameters::{DeployNep141TokenArgs, RegisterNep141TokenArgs};
+use aurora_engine_sdk::env::Env;
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_sdk::promise::PromiseHandler;
+use aurora_engine_types::account_id::{AccountId, ParseAccountError};
+use aurora_engine_types::borsh::BorshSerialize;
+use aurora_engine_types::parameters::connector::{Erc20Metadata, FungibleTokenMetadata};
+use aurora_engine_types::parameters::{PromiseAction, PromiseBatchAction, PromiseCreateArgs};
+use aurora_engine_types::storage::{self, KeyPrefix};
+use aurora_engine_types::types::{Address, NearGas, Yocto, ZERO_YOCTO};
+use aurora_engine_types::{format, vec, Cow, String, Vec};
+
+pub use aurora_engine_precompiles::native::native_erc20::{is_native_erc20, native_erc20_key};
+
+pub const ERR_NO_NEP141_CODE: &str = "ERR_MISSING_NEP141_BYTECODE";
+pub const CODE_KEY: &[u8] = b"nep141_code";
+/// Amount of NEAR required to cover the storage staking of a new NEP-141 account.
+pub const STORAGE_AMOUNT: Yocto = Yocto::new(5_000_000_000_000_000_000_000_000);
+/// Gas costs estimated from simulation tests.
+pub const INITIALIZE_GAS: NearGas = NearGas::new(15_000_000_000_000);
+pub const REGISTER_GAS: NearGas = NearGas::new(10_000_000_000_000);
+pub const BURN_GAS: NearGas = NearGas::new(10_000_000_000_000);
+/// Suffix of the NEP-141 sub-account deployed for a native ERC-20 token.
+const ACCOUNT_SUFFIX: &str = "-nep141";
+
+/// Type wrapper for NEP-141 bytecode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Nep141Code<'a>(pub Cow<'a, [u8]>);
+
+impl<'a> Nep141Code<'a> {
+    #[must_use]
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(Cow::Owned(bytes))
+    }
+
+    #[must_use]
+    pub const fn borrowed(bytes: &'a [u8]) -> Self {
+        Self(Cow::Borrowed(bytes))
+    }
+}
+
+/// Initialization arguments of the NEP-141 contract.
+#[derive(serde::Serialize)]
+struct NewNep141Args<'a> {
+    owner_id: &'a AccountId,
+    metadata: FungibleTokenMetadata,
+}
+
+/// Account id of the NEP-141 contract bridging the given ERC-20 token.
+pub fn nep141_account_id(
+    erc20_address: &Address,
+    current_account_id: &AccountId,
+) -> Result<AccountId, ParseAccountError> {
+    AccountId::new(&format!(
+        "{}{}.{}",
+        erc20_address.encode(),
+        ACCOUNT_SUFFIX,
+        current_account_id.as_ref()
+    ))
+}
+
+/// Creates the NEP-141 account for a native ERC-20 token, deploys the NEP-141 code there and
+/// initializes it with the given metadata. The token pair is registered in the engine by
+/// the `register_nep141_token` callback once the deployment succeeded.
+pub fn deploy_nep141_token<I, P, E>(
+    io: &I,
+    env: &E,
+    handler: &mut P,
+    args: &DeployNep141TokenArgs,
+    metadata: Erc20Metadata,
+) -> Result<AccountId, DeployNep141Error>
+where
+    I: IO + Copy,
+    P: PromiseHandler,
+    E: Env,
+{
+    if crate::engine::get_code_size(io, &args.erc20_address) == 0 {
+        return Err(DeployNep141Error::NotAContract);
+    }
+    if is_native_erc20(io, &args.erc20_address)
+        || crate::engine::nep141_erc20_map(*io)
+            .lookup_right(&crate::engine::ERC20Address(args.erc20_address))
+            .is_some()
+    {
+        return Err(DeployNep141Error::TokenAlreadyRegistered);
+    }
+    let deposit = Yocto::new(env.attached_deposit());
+    if deposit < STORAGE_AMOUNT {
+        return Err(DeployNep141Error::InsufficientBalance);
+    }
+    let code = get_nep141_code(io).ok_or(DeployNep141Error::MissingCode)?;
+
+    let current_account_id = env.current_account_id();
+    let nep141 = nep141_account_id(&args.erc20_address, &current_account_id)?;
+    let init_args = serde_json::to_vec(&NewNep141Args {
+        owner_id: &current_account_id,
+        metadata: FungibleTokenMetadata {
+            spec: String::from("ft-1.0.0"),
+            name: metadata.name,
+            symbol: metadata.symbol,
+            icon: None,
+            reference: None,
+            reference_hash: None,
+            decimals: metadata.decimals,
+        },
+    })
+    .map_err(|_| DeployNep141Error::SerializationFailure)?;
+
+    let batch = PromiseBatchAction {
+        target_account_id: nep141.clone(),
+        actions: vec![
+            PromiseAction::CreateAccount,
+            PromiseAction::Transfer { amount: deposit },
+            PromiseAction::DeployContract {
+                code: code.0.into_owned(),
+            },
+            PromiseAction::FunctionCall {
+                name: "new".into(),
+                args: init_args,
+                attached_yocto: ZERO_YOCTO,
+                gas: INITIALIZE_GAS,
+            },
+        ],
+    };
+    // Safety: This batch creation is safe because it only acts on the NEP-141 sub-account
+    // (not the main engine account), and the deployed code comes from our storage
+    // (i.e. it is controlled by us, not the user).
+    let promise_id = unsafe { handler.promise_create_batch(&batch) };
+
+    let callback_args = RegisterNep141TokenArgs {
+        erc20_address: args.erc20_address,
+        nep141: nep141.clone(),
+    };
+    let callback = PromiseCreateArgs {
+        target_account_id: current_account_id,
+        method: "register_nep141_token".into(),
+        args: callback_args
+            .try_to_vec()
+            .map_err(|_| DeployNep141Error::SerializationFailure)?,
+        attached_balance: ZERO_YOCTO,
+        attached_gas: REGISTER_GAS,
+    };
+    // Safety: A call from the engine to the engine's `register_nep141_token` method is safe
+    // because that method only registers the token pair which has just been deployed above.
+    let promise_id = unsafe { handler.promise_attach_callback(promise_id, &callback) };
+    handler.promise_return(promise_id);
+
+    Ok(nep141)
+}
+
+/// Registers the NEP-141 token deployed for a native ERC-20 token.
+pub fn register_nep141_token<I: IO + Copy>(
+    io: &mut I,
+    args: &RegisterNep141TokenArgs,
+) -> Result<(), DeployNep141Error> {
+    let mut map = crate::engine::nep141_erc20_map(*io);
+    if map
+        .lookup_left(&crate::engine::NEP141Account(args.nep141.clone()))
+        .is_some()
+    {
+        return Err(DeployNep141Error::TokenAlreadyRegistered);
+    }
+    map.insert(
+        &crate::engine::NEP141Account(args.nep141.clone()),
+        &crate::engine::ERC20Address(args.erc20_address),
+    );
+    io.write_storage(&native_erc20_key(&args.erc20_address), &[1]);
+    Ok(())
+}
+
+/// Creates the promise burning the NEP-141 tokens returned to the engine.
+#[must_use]
+pub fn burn_promise(nep141: AccountId, amount: u128) -> PromiseCreateArgs {
+    PromiseCreateArgs {
+        target_account_id: nep141,
+        method: "burn".into(),
+        args: format!(r#"{{"amount": "{amount}"}}"#).into_bytes(),
+        attached_balance: Yocto::new(1),
+        attached_gas: BURN_GAS,
+    }
+}
+
+/// Read the current wasm bytecode for the NEP-141 contracts
+pub fn get_nep141_code<I: IO>(io: &I) -> Option<Nep141Code> {
+    let key = storage::bytes_to_key(KeyPrefix::NativeErc20, CODE_KEY);
+    io.read_storage(&key)
+        .map(|bytes| Nep141Code::new(bytes.to_vec()))
+}
+
+/// Set new NEP-141 bytecode. It is only used for tokens deployed afterwards.
+pub fn update_nep141_code<I: IO>(io: &mut I, code: &Nep141Code) {
+    let key = storage::bytes_to_key(KeyPrefix::NativeErc20, CODE_KEY);
+    io.write_storage(&key, &code.0);
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum DeployNep141Error {
+    NotAContract,
+    TokenAlreadyRegistered,
+    InsufficientBalance,
+    MissingCode,
+    InvalidAccount,
+    SerializationFailure,
+}
+
+impl From<ParseAccountError> for DeployNep141Error {
+    fn from(_: ParseAccountError) -> Self {
+        Self::InvalidAccount
+    }
+}
+
+impl AsRef<[u8]> for DeployNep141Error {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Self::NotAContract => b"ERR_NOT_A_CONTRACT",
+            Self::TokenAlreadyRegistered => b"ERR_NEP141_TOKEN_ALREADY_REGISTERED",
+            Self::InsufficientBalance => b"ERR_INSUFFICIENT_FUNDING_OF_NEW_NEP141_ACCOUNT",
+            Self::MissingCode => ERR_NO_NEP141_CODE.as_bytes(),
+            Self::InvalidAccount => b"ERR_INVALID_NEP141_ACCOUNT",
+            Self::SerializationFailure => ERR_SERIALIZE.as_bytes(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nep141_account_id() {
+        let erc20_address = Address::from_array([0x11; 20]);
+        let engine = AccountId::new("aurora").unwrap();
+        let nep141 = nep141_account_id(&erc20_address, &engine).unwrap();
+        assert_eq!(
+            nep141.as_ref(),
+            "1111111111111111111111111111111111111111-nep141.aurora"
+        );
+    }
+}
@@ -1,5 +1,9 @@
-use crate::prelude::{AccountId, Address, BTreeSet, Vec};
+use crate::prelude::{AccountId, Address, BTreeSet, String, Vec};
+use aurora_engine_precompiles::account_ids::{predecessor_account, CurrentAccount};
 use aurora_engine_precompiles::native::{exit_to_ethereum, exit_to_near};
+use aurora_engine_precompiles::random::RandomSeed;
+use aurora_engine_precompiles::xcc::cross_contract_call;
+use aurora_engine_precompiles::{prepaid_gas, promise_result};
 use aurora_engine_sdk::io::{StorageIntermediate, IO};
 use aurora_engine_types::borsh::{self, BorshDeserialize, BorshSerialize};
 use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
@@ -9,8 +13,98 @@ bitflags! {
     /// Wraps unsigned integer where each bit identifies a different precompile.
     #[derive(BorshSerialize, BorshDeserialize, Default)]
     pub struct PrecompileFlags: u32 {
-        const EXIT_TO_NEAR        = 0b01;
-        const EXIT_TO_ETHEREUM    = 0b10;
+        const EXIT_TO_NEAR           = 0b0000_0001;
+        const EXIT_TO_ETHEREUM       = 0b0000_0010;
+        const CROSS_CONTRACT_CALL    = 0b0000_0100;
+        const PREDECESSOR_ACCOUNT_ID = 0b0000_1000;
+        const CURRENT_ACCOUNT_ID     = 0b0001_0000;
+        const PREPAID_GAS            = 0b0010_0000;
+        const PROMISE_RESULT         = 0b0100_0000;
+        const RANDOM_SEED            = 0b1000_0000;
+    }
+}
+
+/// Methods which can't be paused, so that pausing can always be reverted and the callbacks
+/// finishing (or refunding) operations started earlier are never blocked. The refunds of
+/// failed exits are done by `exit_to_near_precompile_callback`.
+pub const UNPAUSABLE_METHODS: [&str; 15] = [
+    "pause_methods",
+    "resume_methods",
+    "pause_contract",
+    "resume_contract",
+    "pause_precompiles",
+    "resume_precompiles",
+    "grant_role",
+    "revoke_role",
+    "state_migration",
+    "finish_deposit",
+    "ft_resolve_transfer",
+    "exit_to_near_precompile_callback",
+    "register_nep141_token",
+    "sync_erc20_metadata_callback",
+    "factory_update_address_version",
+];
+
+/// Methods which can be paused. These are all the methods changing the engine state, except for
+/// the initialization ones and `UNPAUSABLE_METHODS`.
+pub const PAUSABLE_METHODS: [&str; 48] = [
+    "add_deployer",
+    "add_fee_waived_senders",
+    "add_relayer_key",
+    "add_whitelisted_accounts",
+    "add_whitelisted_addresses",
+    "allow_address",
+    "call",
+    "cancel_upgrade",
+    "deny_address",
+    "deploy_code",
+    "deploy_erc20_token",
+    "deploy_nep141_token",
+    "deposit",
+    "factory_set_wnear_address",
+    "factory_update",
+    "ft_on_transfer",
+    "ft_transfer",
+    "ft_transfer_call",
+    "fund_xcc_sub_account",
+    "list_legacy_relayer_keys",
+    "migrate_erc20_token",
+    "register_relayer",
+    "remove_deployer",
+    "remove_expired_relayer_keys",
+    "remove_fee_waived_senders",
+    "remove_relayer_key",
+    "remove_whitelisted_accounts",
+    "remove_whitelisted_addresses",
+    "set_deploy_allowlist_enabled",
+    "set_erc20_code",
+    "set_erc20_metadata",
+    "set_eth_connector_contract_data",
+    "set_key_manager",
+    "set_nep141_code",
+    "set_owner",
+    "set_paused_flags",
+    "set_silo_params",
+    "set_upgrade_delay_blocks",
+    "set_whitelist_status",
+    "stage_upgrade",
+    "start_hashchain",
+    "storage_deposit",
+    "storage_unregister",
+    "storage_withdraw",
+    "submit",
+    "submit_with_args",
+    "sync_erc20_metadata",
+    "withdraw",
+];
+
+/// The set of paused methods read from the storage can't be deserialized.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PausedMethodsCorrupted;
+
+impl AsRef<[u8]> for PausedMethodsCorrupted {
+    fn as_ref(&self) -> &[u8] {
+        crate::errors::ERR_PAUSED_METHODS_CORRUPTED
     }
 }
 
@@ -21,6 +115,18 @@ impl PrecompileFlags {
             Self::EXIT_TO_ETHEREUM
         } else if address == &exit_to_near::ADDRESS {
             Self::EXIT_TO_NEAR
+        } else if address == &cross_contract_call::ADDRESS {
+            Self::CROSS_CONTRACT_CALL
+        } else if address == &predecessor_account::ADDRESS {
+            Self::PREDECESSOR_ACCOUNT_ID
+        } else if address == &CurrentAccount::ADDRESS {
+            Self::CURRENT_ACCOUNT_ID
+        } else if address == &prepaid_gas::ADDRESS {
+            Self::PREPAID_GAS
+        } else if address == &promise_result::ADDRESS {
+            Self::PROMISE_RESULT
+        } else if address == &RandomSeed::ADDRESS {
+            Self::RANDOM_SEED
         } else {
             return None;
         })
@@ -81,6 +187,24 @@ pub trait PausedPrecompilesManager {
     fn pause_precompiles(&mut self, precompiles_to_pause: PrecompileFlags);
 }
 
+/// Can check if a method of the engine is currently paused or not.
+pub trait PausedMethodsChecker {
+    /// Checks if the method with the given name is paused.
+    fn is_method_paused(&self, method: &str) -> Result<bool, PausedMethodsCorrupted>;
+
+    /// Returns the names of all paused methods.
+    fn paused_methods(&self) -> Result<BTreeSet<String>, PausedMethodsCorrupted>;
+}
+
+/// Responsible for resuming and pausing of the engine methods.
+pub trait PausedMethodsManager {
+    /// Resumes all the given `methods`. Methods which aren't paused are ignored.
+    fn resume_methods(&mut self, methods: &[String]) -> Result<(), PausedMethodsCorrupted>;
+
+    /// Pauses all the given `methods`. Methods which are already paused are ignored.
+    fn pause_methods(&mut self, methods: &[String]) -> Result<(), PausedMethodsCorrupted>;
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone)]
 pub struct EngineAuthorizer {
     /// List of [AccountId]s with the permission to pause precompiles.
@@ -133,6 +257,41 @@ impl<I: IO> EnginePrecompilesPauser<I> {
     }
 }
 
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default, Clone)]
+pub struct EngineMethodsPauser<I: IO> {
+    /// Storage to read paused methods from and write into.
+    io: I,
+}
+
+impl<I: IO> EngineMethodsPauser<I> {
+    /// Key for storing the set of paused methods.
+    const PAUSED_METHODS_KEY: &'static [u8; 14] = b"PAUSED_METHODS";
+
+    /// Creates new [`EngineMethodsPauser`] instance that reads from and writes into storage accessed using `io`.
+    pub const fn from_io(io: I) -> Self {
+        Self { io }
+    }
+
+    fn read_methods_from_storage(&self) -> Result<BTreeSet<String>, PausedMethodsCorrupted> {
+        self.io.read_storage(&Self::storage_key()).map_or_else(
+            || Ok(BTreeSet::new()),
+            |bytes| BTreeSet::try_from_slice(&bytes.to_vec()).map_err(|_| PausedMethodsCorrupted),
+        )
+    }
+
+    fn write_methods_into_storage(&mut self, methods: &BTreeSet<String>) {
+        if methods.is_empty() {
+            self.io.remove_storage(&Self::storage_key());
+        } else {
+            self.io.write_borsh(&Self::storage_key(), methods);
+        }
+    }
+
+    fn storage_key() -> Vec<u8> {
+        bytes_to_key(KeyPrefix::Config, Self::PAUSED_METHODS_KEY)
+    }
+}
+
 impl Authorizer for EngineAuthorizer {
     fn is_authorized(&self, account: &AccountId) -> bool {
         self.acl.get(account).is_some()
@@ -163,6 +322,38 @@ impl<I: IO> PausedPrecompilesManager for EnginePrecompilesPauser<I> {
     }
 }
 
+impl<I: IO> PausedMethodsChecker for EngineMethodsPauser<I> {
+    fn is_method_paused(&self, method: &str) -> Result<bool, PausedMethodsCorrupted> {
+        // Avoid deserializing the set for every call if nothing is paused.
+        if !self.io.storage_has_key(&Self::storage_key()) {
+            return Ok(false);
+        }
+        Ok(self.read_methods_from_storage()?.contains(method))
+    }
+
+    fn paused_methods(&self) -> Result<BTreeSet<String>, PausedMethodsCorrupted> {
+        self.read_methods_from_storage()
+    }
+}
+
+impl<I: IO> PausedMethodsManager for EngineMethodsPauser<I> {
+    fn resume_methods(&mut self, methods: &[String]) -> Result<(), PausedMethodsCorrupted> {
+        let mut paused_methods = self.read_methods_from_storage()?;
+        for method in methods {
+            paused_methods.remove(method);
+        }
+        self.write_methods_into_storage(&paused_methods);
+        Ok(())
+    }
+
+    fn pause_methods(&mut self, methods: &[String]) -> Result<(), PausedMethodsCorrupted> {
+        let mut paused_methods = self.read_methods_from_storage()?;
+        paused_methods.extend(methods.iter().cloned());
+        self.write_methods_into_storage(&paused_methods);
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -173,6 +364,12 @@ mod tests {
 
     #[test_case(PrecompileFlags::EXIT_TO_ETHEREUM, exit_to_ethereum::ADDRESS)]
     #[test_case(PrecompileFlags::EXIT_TO_NEAR, exit_to_near::ADDRESS)]
+    #[test_case(PrecompileFlags::CROSS_CONTRACT_CALL, cross_contract_call::ADDRESS)]
+    #[test_case(PrecompileFlags::PREDECESSOR_ACCOUNT_ID, predecessor_account::ADDRESS)]
+    #[test_case(PrecompileFlags::CURRENT_ACCOUNT_ID, CurrentAccount::ADDRESS)]
+    #[test_case(PrecompileFlags::PREPAID_GAS, prepaid_gas::ADDRESS)]
+    #[test_case(PrecompileFlags::PROMISE_RESULT, promise_result::ADDRESS)]
+    #[test_case(PrecompileFlags::RANDOM_SEED, RandomSeed::ADDRESS)]
     fn test_paused_flag_marks_precompiles_address_as_paused(
         flags: PrecompileFlags,
         address: Address,
@@ -213,6 +410,58 @@ mod tests {
         assert!(!pauser.is_paused(flags));
     }
 
+    #[test]
+    fn test_pausing_and_resuming_methods() {
+        let storage = RefCell::new(Storage::default());
+        let io = StoragePointer(&storage);
+        let mut pauser = EngineMethodsPauser::from_io(io);
+        let methods = ["submit".to_string(), "ft_on_transfer".to_string()];
+
+        assert!(!pauser.is_method_paused("submit").unwrap());
+        pauser.pause_methods(&methods).unwrap();
+        assert!(pauser.is_method_paused("submit").unwrap());
+        assert!(pauser.is_method_paused("ft_on_transfer").unwrap());
+        assert!(!pauser.is_method_paused("call").unwrap());
+        assert_eq!(
+            pauser.paused_methods().unwrap(),
+            methods.iter().cloned().collect()
+        );
+
+        pauser.resume_methods(&methods[..1]).unwrap();
+        assert!(!pauser.is_method_paused("submit").unwrap());
+        assert!(pauser.is_method_paused("ft_on_transfer").unwrap());
+
+        pauser.resume_methods(&methods[1..]).unwrap();
+        assert!(pauser.paused_methods().unwrap().is_empty());
+        assert!(!io.storage_has_key(&EngineMethodsPauser::<StoragePointer>::storage_key()));
+    }
+
+    #[test]
+    fn test_corrupted_paused_methods_are_an_error() {
+        let key = EngineMethodsPauser::<StoragePointer>::storage_key();
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        io.write_storage(key.as_slice(), &[7u8]);
+        let mut pauser = EngineMethodsPauser::from_io(io);
+
+        assert_eq!(
+            pauser.is_method_paused("submit"),
+            Err(PausedMethodsCorrupted)
+        );
+        assert_eq!(pauser.paused_methods(), Err(PausedMethodsCorrupted));
+        assert_eq!(
+            pauser.pause_methods(&["submit".to_string()]),
+            Err(PausedMethodsCorrupted)
+        );
+    }
+
+    #[test]
+    fn test_unpausable_methods_are_not_pausable() {
+        for method in UNPAUSABLE_METHODS {
+            assert!(!PAUSABLE_METHODS.contains(&method), "{method}");
+        }
+    }
+
     #[test]
     fn test_granting_permission_to_account_authorizes_it() {
         let account = AccountId::default();
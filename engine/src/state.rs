@@ -4,8 +4,9 @@ use crate::parameters::{
 use aurora_engine_sdk::io::{StorageIntermediate, IO};
 use aurora_engine_types::account_id::AccountId;
 use aurora_engine_types::borsh::{self, BorshDeserialize, BorshSerialize};
+use aurora_engine_types::parameters::engine::{Role, SiloParams};
 use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
-use aurora_engine_types::{Cow, Vec};
+use aurora_engine_types::{BTreeMap, Cow, Vec};
 
 pub use error::EngineStateError;
 
@@ -27,9 +28,50 @@ pub struct EngineState {
     pub is_paused: bool,
     /// Relayer key manager.
     pub key_manager: Option<AccountId>,
+    /// Accounts which were granted a role, in addition to the owner.
+    pub roles: BTreeMap<Role, Vec<AccountId>>,
+    /// If true, only the approved deployers can create contracts.
+    pub is_deploy_allowlist_enabled: bool,
+    /// Economic settings of the silo: gas prices and fee recipient.
+    pub silo_params: SiloParams,
 }
 
 impl EngineState {
+    /// Returns true if the account has the role. The owner implicitly has all the roles.
+    #[must_use]
+    pub fn has_role(&self, role: Role, account_id: &AccountId) -> bool {
+        &self.owner_id == account_id
+            || self
+                .roles
+                .get(&role)
+                .map_or(false, |accounts| accounts.contains(account_id))
+    }
+
+    /// Grants the role to the account. Returns false if the account already had the role.
+    pub fn grant_role(&mut self, role: Role, account_id: AccountId) -> bool {
+        let accounts = self.roles.entry(role).or_default();
+        if accounts.contains(&account_id) {
+            return false;
+        }
+        accounts.push(account_id);
+        true
+    }
+
+    /// Revokes the role from the account. Returns false if the account did not have the role.
+    pub fn revoke_role(&mut self, role: Role, account_id: &AccountId) -> bool {
+        let Some(accounts) = self.roles.get_mut(&role) else {
+            return false;
+        };
+        let Some(index) = accounts.iter().position(|account| account == account_id) else {
+            return false;
+        };
+        accounts.remove(index);
+        if accounts.is_empty() {
+            self.roles.remove(&role);
+        }
+        true
+    }
+
     pub fn borsh_serialize(&self) -> Result<Vec<u8>, EngineStateError> {
         let borshable: BorshableEngineState = self.into();
         borshable
@@ -58,6 +100,7 @@ pub enum BorshableEngineState<'a> {
     V1(BorshableEngineStateV1<'a>),
     V2(BorshableEngineStateV2<'a>),
     V3(BorshableEngineStateV3<'a>),
+    V4(BorshableEngineStateV4<'a>),
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Default, Clone, PartialEq, Eq, Debug)]
@@ -84,14 +127,29 @@ pub struct BorshableEngineStateV3<'a> {
     pub key_manager: Option<Cow<'a, AccountId>>,
 }
 
+#[derive(BorshSerialize, BorshDeserialize, Default, Clone, PartialEq, Eq, Debug)]
+pub struct BorshableEngineStateV4<'a> {
+    pub chain_id: [u8; 32],
+    pub owner_id: Cow<'a, AccountId>,
+    pub upgrade_delay_blocks: u64,
+    pub is_paused: bool,
+    pub key_manager: Option<Cow<'a, AccountId>>,
+    pub roles: Cow<'a, BTreeMap<Role, Vec<AccountId>>>,
+    pub is_deploy_allowlist_enabled: bool,
+    pub silo_params: Cow<'a, SiloParams>,
+}
+
 impl<'a> From<&'a EngineState> for BorshableEngineState<'a> {
     fn from(state: &'a EngineState) -> Self {
-        Self::V3(BorshableEngineStateV3 {
+        Self::V4(BorshableEngineStateV4 {
             chain_id: state.chain_id,
             owner_id: Cow::Borrowed(&state.owner_id),
             upgrade_delay_blocks: state.upgrade_delay_blocks,
             is_paused: state.is_paused,
             key_manager: state.key_manager.as_ref().map(Cow::Borrowed),
+            roles: Cow::Borrowed(&state.roles),
+            is_deploy_allowlist_enabled: state.is_deploy_allowlist_enabled,
+            silo_params: Cow::Borrowed(&state.silo_params),
         })
     }
 }
@@ -102,6 +160,7 @@ impl<'a> From<BorshableEngineState<'a>> for EngineState {
             BorshableEngineState::V1(state) => state.into(),
             BorshableEngineState::V2(state) => state.into(),
             BorshableEngineState::V3(state) => state.into(),
+            BorshableEngineState::V4(state) => state.into(),
         }
     }
 }
@@ -114,6 +173,9 @@ impl<'a> From<BorshableEngineStateV1<'a>> for EngineState {
             upgrade_delay_blocks: state.upgrade_delay_blocks,
             is_paused: false,
             key_manager: None,
+            roles: BTreeMap::new(),
+            is_deploy_allowlist_enabled: false,
+            silo_params: SiloParams::default(),
         }
     }
 }
@@ -126,6 +188,9 @@ impl<'a> From<BorshableEngineStateV2<'a>> for EngineState {
             upgrade_delay_blocks: state.upgrade_delay_blocks,
             is_paused: false,
             key_manager: None,
+            roles: BTreeMap::new(),
+            is_deploy_allowlist_enabled: false,
+            silo_params: SiloParams::default(),
         }
     }
 }
@@ -138,6 +203,24 @@ impl<'a> From<BorshableEngineStateV3<'a>> for EngineState {
             upgrade_delay_blocks: state.upgrade_delay_blocks,
             is_paused: state.is_paused,
             key_manager: state.key_manager.map(Cow::into_owned),
+            roles: BTreeMap::new(),
+            is_deploy_allowlist_enabled: false,
+            silo_params: SiloParams::default(),
+        }
+    }
+}
+
+impl<'a> From<BorshableEngineStateV4<'a>> for EngineState {
+    fn from(state: BorshableEngineStateV4<'a>) -> Self {
+        Self {
+            chain_id: state.chain_id,
+            owner_id: state.owner_id.into_owned(),
+            upgrade_delay_blocks: state.upgrade_delay_blocks,
+            is_paused: state.is_paused,
+            key_manager: state.key_manager.map(Cow::into_owned),
+            roles: state.roles.into_owned(),
+            is_deploy_allowlist_enabled: state.is_deploy_allowlist_enabled,
+            silo_params: state.silo_params.into_owned(),
         }
     }
 }
@@ -150,6 +233,9 @@ impl From<LegacyNewCallArgs> for EngineState {
             upgrade_delay_blocks: args.upgrade_delay_blocks,
             is_paused: false,
             key_manager: None,
+            roles: BTreeMap::new(),
+            is_deploy_allowlist_enabled: false,
+            silo_params: SiloParams::default(),
         }
     }
 }
@@ -162,6 +248,9 @@ impl From<NewCallArgsV2> for EngineState {
             upgrade_delay_blocks: args.upgrade_delay_blocks,
             is_paused: false,
             key_manager: None,
+            roles: BTreeMap::new(),
+            is_deploy_allowlist_enabled: false,
+            silo_params: SiloParams::default(),
         }
     }
 }
@@ -174,6 +263,9 @@ impl From<NewCallArgsV3> for EngineState {
             upgrade_delay_blocks: args.upgrade_delay_blocks,
             is_paused: false,
             key_manager: Some(args.key_manager),
+            roles: BTreeMap::new(),
+            is_deploy_allowlist_enabled: false,
+            silo_params: SiloParams::default(),
         }
     }
 }
@@ -186,6 +278,9 @@ impl From<NewCallArgsV4> for EngineState {
             upgrade_delay_blocks: args.upgrade_delay_blocks,
             is_paused: false,
             key_manager: Some(args.key_manager),
+            roles: BTreeMap::new(),
+            is_deploy_allowlist_enabled: false,
+            silo_params: SiloParams::default(),
         }
     }
 }
@@ -279,4 +374,46 @@ mod tests {
 
         assert_eq!(expected_error, actual_error);
     }
+
+    #[test]
+    fn test_engine_state_roles() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let owner: AccountId = "owner.near".parse().unwrap();
+        let pauser: AccountId = "pauser.near".parse().unwrap();
+        let mut state = EngineState {
+            owner_id: owner.clone(),
+            ..Default::default()
+        };
+
+        assert!(state.has_role(Role::Pauser, &owner));
+        assert!(!state.has_role(Role::Pauser, &pauser));
+
+        assert!(state.grant_role(Role::Pauser, pauser.clone()));
+        assert!(!state.grant_role(Role::Pauser, pauser.clone()));
+        assert!(state.has_role(Role::Pauser, &pauser));
+        assert!(!state.has_role(Role::Unpauser, &pauser));
+
+        set_state(&mut io, &state).unwrap();
+        let mut state = get_state(&io).unwrap();
+        assert!(state.has_role(Role::Pauser, &pauser));
+
+        assert!(state.revoke_role(Role::Pauser, &pauser));
+        assert!(!state.revoke_role(Role::Pauser, &pauser));
+        assert!(!state.has_role(Role::Pauser, &pauser));
+        assert!(state.roles.is_empty());
+    }
+
+    #[test]
+    fn test_engine_state_deploy_allowlist_mode() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let state = EngineState {
+            is_deploy_allowlist_enabled: true,
+            ..Default::default()
+        };
+
+        set_state(&mut io, &state).unwrap();
+        assert_eq!(get_state(&io).unwrap(), state);
+    }
 }
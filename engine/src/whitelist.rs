@@ -0,0 +1,167 @@
+//! Whitelists of a permissioned silo: the EVM addresses which can sign the submitted
+//! transactions and the NEAR accounts which can execute EVM transactions.
+//!
+//! The status and the entries of each whitelist are stored under their own keys, so checking
+//! an entry doesn't read the whole list.
+use aurora_engine_sdk::io::{StorageIntermediate, IO};
+use aurora_engine_types::account_id::AccountId;
+use aurora_engine_types::borsh::{BorshDeserialize, BorshSerialize};
+use aurora_engine_types::parameters::engine::{Whitelist, WhitelistKind};
+use aurora_engine_types::storage::{bytes_to_key, KeyPrefix};
+use aurora_engine_types::types::Address;
+use aurora_engine_types::Vec;
+
+/// Key of the status of a whitelist, present only if the whitelist is enabled.
+const STATUS_KEY: &[u8] = b"STATUS";
+/// Key of the list of all entries of a whitelist, which is kept for the views.
+const LIST_KEY: &[u8] = b"LIST";
+
+/// Entry of a whitelist.
+pub trait WhitelistEntry: BorshSerialize + BorshDeserialize + PartialEq {
+    const KIND: WhitelistKind;
+
+    fn as_key_bytes(&self) -> &[u8];
+}
+
+impl WhitelistEntry for Address {
+    const KIND: WhitelistKind = WhitelistKind::Address;
+
+    fn as_key_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl WhitelistEntry for AccountId {
+    const KIND: WhitelistKind = WhitelistKind::Account;
+
+    fn as_key_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+/// The keys of the addresses and accounts can't collide with the status and list keys, because
+/// the addresses are 20 bytes long and the account ids are lowercase.
+fn whitelist_key(kind: WhitelistKind, body: &[u8]) -> Vec<u8> {
+    let kind = match kind {
+        WhitelistKind::Address => 0,
+        WhitelistKind::Account => 1,
+    };
+    bytes_to_key(KeyPrefix::Whitelist, &[&[kind], body].concat())
+}
+
+/// Returns true if the whitelist is enabled.
+pub fn is_enabled<I: IO>(io: &I, kind: WhitelistKind) -> bool {
+    io.storage_has_key(&whitelist_key(kind, STATUS_KEY))
+}
+
+/// Enables or disables the whitelist.
+pub fn set_enabled<I: IO>(io: &mut I, kind: WhitelistKind, enabled: bool) {
+    let key = whitelist_key(kind, STATUS_KEY);
+    if enabled {
+        io.write_storage(&key, &[1]);
+    } else {
+        io.remove_storage(&key);
+    }
+}
+
+/// Returns true if the whitelist is disabled or contains the entry.
+pub fn is_allowed<I: IO, T: WhitelistEntry>(io: &I, entry: &T) -> bool {
+    !is_enabled(io, T::KIND) || io.storage_has_key(&whitelist_key(T::KIND, entry.as_key_bytes()))
+}
+
+/// Adds the entry to the whitelist. Returns false if it was already added.
+pub fn add<I: IO, T: WhitelistEntry + Clone>(io: &mut I, entry: &T) -> bool {
+    let key = whitelist_key(T::KIND, entry.as_key_bytes());
+    if io.write_storage(&key, &[1]).is_some() {
+        return false;
+    }
+    let mut entries = get_entries::<_, T>(io);
+    entries.push(entry.clone());
+    set_entries(io, &entries);
+    true
+}
+
+/// Removes the entry from the whitelist. Returns false if it wasn't added.
+pub fn remove<I: IO, T: WhitelistEntry>(io: &mut I, entry: &T) -> bool {
+    let key = whitelist_key(T::KIND, entry.as_key_bytes());
+    if io.remove_storage(&key).is_none() {
+        return false;
+    }
+    let mut entries = get_entries::<_, T>(io);
+    entries.retain(|e| e != entry);
+    set_entries(io, &entries);
+    true
+}
+
+/// Returns the status of the whitelist and its entries in the order they were added.
+pub fn get_whitelist<I: IO, T: WhitelistEntry>(io: &I) -> Whitelist<T> {
+    Whitelist {
+        enabled: is_enabled(io, T::KIND),
+        entries: get_entries(io),
+    }
+}
+
+fn get_entries<I: IO, T: WhitelistEntry>(io: &I) -> Vec<T> {
+    io.read_storage(&whitelist_key(T::KIND, LIST_KEY))
+        .and_then(|value| Vec::try_from_slice(&value.to_vec()).ok())
+        .unwrap_or_default()
+}
+
+fn set_entries<I: IO, T: WhitelistEntry>(io: &mut I, entries: &[T]) {
+    let key = whitelist_key(T::KIND, LIST_KEY);
+    if entries.is_empty() {
+        io.remove_storage(&key);
+    } else {
+        io.write_borsh(&key, &entries);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aurora_engine_test_doubles::io::{Storage, StoragePointer};
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_whitelist() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let first = Address::from_array([1; 20]);
+        let second = Address::from_array([2; 20]);
+        assert!(is_allowed(&io, &first));
+
+        set_enabled(&mut io, WhitelistKind::Address, true);
+        assert!(!is_allowed(&io, &first));
+
+        assert!(add(&mut io, &first));
+        assert!(add(&mut io, &second));
+        assert!(!add(&mut io, &first));
+        assert_eq!(get_entries::<_, Address>(&io), vec![first, second]);
+        assert!(is_allowed(&io, &first));
+
+        assert!(remove(&mut io, &first));
+        assert!(!remove(&mut io, &first));
+        assert!(!is_allowed(&io, &first));
+        assert!(is_allowed(&io, &second));
+    }
+
+    #[test]
+    fn test_whitelists_are_independent() {
+        let storage = RefCell::new(Storage::default());
+        let mut io = StoragePointer(&storage);
+        let account: AccountId = "alice.near".parse().unwrap();
+        set_enabled(&mut io, WhitelistKind::Account, true);
+        add(&mut io, &account);
+
+        assert!(is_enabled(&io, WhitelistKind::Account));
+        assert!(!is_enabled(&io, WhitelistKind::Address));
+        assert_eq!(
+            get_whitelist::<_, AccountId>(&io),
+            Whitelist {
+                enabled: true,
+                entries: vec![account],
+            }
+        );
+        assert_eq!(get_whitelist::<_, Address>(&io), Whitelist::default());
+    }
+}
@@ -3,13 +3,16 @@ use crate::parameters::{CallArgs, FunctionCallArgsV2};
 use aurora_engine_precompiles::xcc::state::{self, ERR_MISSING_WNEAR_ADDRESS};
 use aurora_engine_sdk::env::Env;
 use aurora_engine_sdk::io::{StorageIntermediate, IO};
-use aurora_engine_sdk::promise::PromiseHandler;
+use aurora_engine_sdk::promise::{PromiseHandler, PromiseId};
 use aurora_engine_types::account_id::AccountId;
 use aurora_engine_types::borsh::BorshSerialize;
-use aurora_engine_types::parameters::{PromiseAction, PromiseBatchAction, PromiseCreateArgs};
+use aurora_engine_types::parameters::{
+    NearPromise, PromiseAction, PromiseArgs, PromiseBatchAction, PromiseCreateArgs,
+    SimpleNearPromise,
+};
 use aurora_engine_types::storage::{self, KeyPrefix};
 use aurora_engine_types::types::{Address, NearGas, Yocto, ZERO_YOCTO};
-use aurora_engine_types::{format, Cow, Vec, U256};
+use aurora_engine_types::{format, vec, Cow, Vec, U256};
 
 pub use aurora_engine_types::parameters::xcc::{AddressVersionUpdateArgs, FundXccArgs};
 
@@ -19,7 +22,7 @@ pub const ERR_ATTACHED_NEAR: &str = "ERR_ATTACHED_XCC_NEAR";
 pub const CODE_KEY: &[u8] = b"router_code";
 /// Gas costs estimated from simulation tests.
 pub const VERSION_UPDATE_GAS: NearGas = NearGas::new(5_000_000_000_000);
-pub const INITIALIZE_GAS: NearGas = NearGas::new(15_000_000_000_000);
+pub const INITIALIZE_GAS: NearGas = aurora_engine_precompiles::xcc::costs::ROUTER_INITIALIZE;
 pub const UNWRAP_AND_REFUND_GAS: NearGas = NearGas::new(25_000_000_000_000);
 pub const WITHDRAW_GAS: NearGas = NearGas::new(40_000_000_000_000);
 /// Solidity selector for the `withdrawToNear` function
@@ -156,6 +159,7 @@ pub fn handle_precompile_promise<I, P>(
     handler: &mut P,
     promise: &PromiseCreateArgs,
     required_near: Yocto,
+    sub_account_id: Option<&AccountId>,
     current_account_id: &AccountId,
 ) where
     P: PromiseHandler,
@@ -195,13 +199,9 @@ pub fn handle_precompile_promise<I, P>(
                 code: get_router_code(io).0.into_owned(),
             });
             // After the deployment we call the contract's initialize function
-            let wnear_address = get_wnear_address(io);
-            let wnear_account = crate::engine::nep141_erc20_map(*io)
-                .lookup_right(&crate::engine::ERC20Address(wnear_address))
-                .expect("wnear account not found");
             let init_args = format!(
                 r#"{{"wnear_account": "{}", "must_register": {}}}"#,
-                wnear_account.0.as_ref(),
+                get_wnear_account(io).as_ref(),
                 create_needed,
             );
             promise_actions.push(PromiseAction::FunctionCall {
@@ -239,6 +239,43 @@ pub fn handle_precompile_promise<I, P>(
         }
         AddressVersionStatus::UpToDate => None,
     };
+    // The routers deployed to the sub-accounts of the router contract are versioned the same way,
+    // under the address derived from the sub-account id.
+    let sub_account_status = sub_account_id.map(|account_id| {
+        // Confirm the sub-account is of the form `{name}.{address}.{aurora}`
+        let name = account_id
+            .as_ref()
+            .strip_suffix(target_account)
+            .and_then(|prefix| prefix.strip_suffix('.'));
+        assert!(
+            name.map_or(false, |name| !name.is_empty() && !name.contains('.')),
+            "{ERR_INVALID_ACCOUNT}"
+        );
+        let sub_account_version =
+            get_code_version_of_address(io, &state::sub_account_address(account_id));
+        (
+            account_id,
+            AddressVersionStatus::new(latest_code_version, sub_account_version),
+        )
+    });
+    // If the router of the sub-account is out of date then it is upgraded before being used.
+    let setup_id = match sub_account_status {
+        Some((
+            account_id,
+            AddressVersionStatus::DeployNeeded {
+                create_needed: false,
+            },
+        )) => Some(deploy_sub_account_router(
+            io,
+            handler,
+            &promise.target_account_id,
+            account_id,
+            latest_code_version,
+            current_account_id,
+            setup_id,
+        )),
+        _ => setup_id,
+    };
     // 2. If some NEAR is required for this call (from storage staking for a new account
     //    and/or attached NEAR to the call the user wants to make), then we need to have the
     //    engine withdraw that amount of wNEAR to the router account and then have the router
@@ -299,12 +336,107 @@ pub fn handle_precompile_promise<I, P>(
     // user directly. The XCC precompile will only construct promises that target the `execute`
     // and `schedule` methods of the user's router contract. Therefore, the user cannot have
     // the engine make arbitrary calls.
-    let _promise_id = unsafe {
+    let promise_id = unsafe {
         match withdraw_id {
             None => handler.promise_create_call(promise),
             Some(withdraw_id) => handler.promise_attach_callback(withdraw_id, promise),
         }
     };
+    // 4. If the call created a sub-account of the router contract then the router code is
+    //    deployed to it afterwards.
+    if let Some((
+        account_id,
+        AddressVersionStatus::DeployNeeded {
+            create_needed: true,
+        },
+    )) = sub_account_status
+    {
+        deploy_sub_account_router(
+            io,
+            handler,
+            &promise.target_account_id,
+            account_id,
+            latest_code_version,
+            current_account_id,
+            Some(promise_id),
+        );
+    }
+}
+
+/// Has the router contract deploy the latest router code to its sub-account (after the promise
+/// `after` if any), then records the version of the sub-account like the one of a router.
+fn deploy_sub_account_router<I, P>(
+    io: &I,
+    handler: &mut P,
+    router_account_id: &AccountId,
+    sub_account_id: &AccountId,
+    version: CodeVersion,
+    current_account_id: &AccountId,
+    after: Option<PromiseId>,
+) -> PromiseId
+where
+    P: PromiseHandler,
+    I: IO + Copy,
+{
+    // The router of the sub-account is initialized by the router contract, which makes it the
+    // only account allowed to call it.
+    let init_args = format!(
+        r#"{{"wnear_account": "{}", "must_register": false}}"#,
+        get_wnear_account(io).as_ref(),
+    );
+    let batch = PromiseArgs::Recursive(NearPromise::Simple(SimpleNearPromise::Batch(
+        PromiseBatchAction {
+            target_account_id: sub_account_id.clone(),
+            actions: vec![
+                PromiseAction::DeployContract {
+                    code: get_router_code(io).0.into_owned(),
+                },
+                PromiseAction::FunctionCall {
+                    name: "initialize".into(),
+                    args: init_args.into_bytes(),
+                    attached_yocto: ZERO_YOCTO,
+                    gas: INITIALIZE_GAS,
+                },
+            ],
+        },
+    )));
+    let deploy_call = PromiseCreateArgs {
+        target_account_id: router_account_id.clone(),
+        method: "execute".into(),
+        args: batch.try_to_vec().unwrap(),
+        attached_balance: ZERO_YOCTO,
+        attached_gas: aurora_engine_precompiles::xcc::costs::router_deploy_sub_account(),
+    };
+    // Safety: This call is safe because the router contract only acts on its own sub-account,
+    // and the only action performed is deploying the code from our storage.
+    let promise_id = unsafe {
+        match after {
+            None => handler.promise_create_call(&deploy_call),
+            Some(after) => handler.promise_attach_callback(after, &deploy_call),
+        }
+    };
+    let args = AddressVersionUpdateArgs {
+        address: state::sub_account_address(sub_account_id),
+        version,
+    };
+    let callback = PromiseCreateArgs {
+        target_account_id: current_account_id.clone(),
+        method: "factory_update_address_version".into(),
+        args: args.try_to_vec().unwrap(),
+        attached_balance: ZERO_YOCTO,
+        attached_gas: VERSION_UPDATE_GAS,
+    };
+    // Safety: same as the version update of the router in `handle_precompile_promise`.
+    unsafe { handler.promise_attach_callback(promise_id, &callback) }
+}
+
+/// Read the NEAR account of the bridged `wNEAR` token.
+fn get_wnear_account<I: IO + Copy>(io: &I) -> AccountId {
+    let wnear_address = get_wnear_address(io);
+    crate::engine::nep141_erc20_map(*io)
+        .lookup_right(&crate::engine::ERC20Address(wnear_address))
+        .expect("wnear account not found")
+        .0
 }
 
 /// Read the current wasm bytecode for the router contracts
@@ -0,0 +1,21 @@
+// SPDX-License-Identifier: CC0-1.0
+pragma solidity ^0.8.0;
+
+import "./EvmErc20V3Base.sol";
+
+
+/**
+ * @title EvmErc20V3
+ * @dev `EvmErc20V3Base` for engines built without the `error_refund` feature.
+ */
+contract EvmErc20V3 is EvmErc20V3Base {
+    // slither-disable-next-line shadowing-local
+    constructor (string memory metadata_name, string memory metadata_symbol, uint8 metadata_decimals, address admin)
+        EvmErc20V3Base(metadata_name, metadata_symbol, metadata_decimals, admin)
+    {
+    }
+
+    function _exitToNearInput(address, uint256 amount, bytes memory recipient) internal pure override returns (bytes memory) {
+        return abi.encodePacked("\x01", bytes32(amount), recipient);
+    }
+}
@@ -0,0 +1,153 @@
+// SPDX-License-Identifier: CC0-1.0
+pragma solidity ^0.8.0;
+
+import "@openzeppelin/contracts/token/ERC20/ERC20.sol";
+import "@openzeppelin/contracts/token/ERC20/extensions/draft-IERC20Permit.sol";
+import "@openzeppelin/contracts/interfaces/IERC1363Receiver.sol";
+import "@openzeppelin/contracts/utils/Address.sol";
+import "@openzeppelin/contracts/utils/cryptography/ECDSA.sol";
+import "./AdminControlled.sol";
+import "./IExit.sol";
+
+
+/**
+ * @title EvmErc20V3Base
+ * @dev Bridged ERC20 token supporting EIP-2612 `permit` and ERC-1363 `transferAndCall`.
+ * The storage layout extends the one of `EvmErc20` and `EvmErc20V2`, so existing tokens can
+ * be migrated by replacing their code. For the same reason the contract must not use immutable
+ * variables: the EIP-712 domain separator is computed on every call.
+ * The input of the exit to NEAR precompile depends on the `error_refund` feature of the engine,
+ * so it is provided by `EvmErc20V3` and `EvmErc20V3Refund`.
+ */
+abstract contract EvmErc20V3Base is ERC20, AdminControlled, IExit, IERC20Permit {
+    bytes32 private constant _TYPE_HASH =
+        keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)");
+    bytes32 private constant _PERMIT_TYPEHASH =
+        keccak256("Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)");
+    bytes32 private constant _HASHED_VERSION = keccak256("1");
+
+    string private _name;
+    string private _symbol;
+    uint8 private _decimals;
+    mapping(address => uint256) private _nonces;
+
+    // slither-disable-next-line shadowing-local
+    constructor (string memory metadata_name, string memory metadata_symbol, uint8 metadata_decimals, address admin)
+        ERC20(metadata_name, metadata_symbol)
+        AdminControlled(admin, 0)
+    {
+        _name = metadata_name;
+        _symbol = metadata_symbol;
+        _decimals = metadata_decimals;
+    }
+
+    function name() public view override returns (string memory) {
+        return _name;
+    }
+
+    function symbol() public view override returns (string memory) {
+        return _symbol;
+    }
+
+    function decimals() public view override returns (uint8) {
+        return _decimals;
+    }
+
+    // slither-disable-next-line events-maths
+    function setMetadata(string memory metadata_name, string memory metadata_symbol, uint8 metadata_decimals) external onlyAdmin {
+        _name = metadata_name;
+        _symbol = metadata_symbol;
+        _decimals = metadata_decimals;
+    }
+
+    function mint(address account, uint256 amount) public onlyAdmin {
+        _mint(account, amount);
+    }
+
+    // slither-disable-next-line timestamp
+    function permit(
+        address owner,
+        address spender,
+        uint256 value,
+        uint256 deadline,
+        uint8 v,
+        bytes32 r,
+        bytes32 s
+    ) external override {
+        require(block.timestamp <= deadline, "ERC20Permit: expired deadline");
+
+        bytes32 structHash = keccak256(abi.encode(_PERMIT_TYPEHASH, owner, spender, value, _useNonce(owner), deadline));
+        bytes32 hash = ECDSA.toTypedDataHash(DOMAIN_SEPARATOR(), structHash);
+
+        address signer = ECDSA.recover(hash, v, r, s);
+        require(signer == owner, "ERC20Permit: invalid signature");
+
+        _approve(owner, spender, value);
+    }
+
+    function nonces(address owner) external view override returns (uint256) {
+        return _nonces[owner];
+    }
+
+    // solhint-disable-next-line func-name-mixedcase
+    function DOMAIN_SEPARATOR() public view override returns (bytes32) {
+        return keccak256(abi.encode(_TYPE_HASH, keccak256(bytes(_name)), _HASHED_VERSION, block.chainid, address(this)));
+    }
+
+    function transferAndCall(address to, uint256 amount) external returns (bool) {
+        return transferAndCall(to, amount, "");
+    }
+
+    function transferAndCall(address to, uint256 amount, bytes memory data) public returns (bool) {
+        address sender = _msgSender();
+        _transfer(sender, to, amount);
+        require(_checkOnTransferReceived(sender, to, amount, data), "ERC1363: receiver returned wrong data");
+        return true;
+    }
+
+    function withdrawToNear(bytes memory recipient, uint256 amount) external override {
+        address sender = _msgSender();
+        _burn(sender, amount);
+
+        bytes memory input = _exitToNearInput(sender, amount, recipient);
+        uint input_size = input.length;
+        bool success;
+
+        assembly {
+            success := call(gas(), 0xe9217bc70b7ed1f598ddd3199e80b093fa71124f, 0, add(input, 32), input_size, 0, 32)
+        }
+        require(success, "EvmErc20: exit to NEAR failed");
+    }
+
+    function withdrawToEthereum(address recipient, uint256 amount) external override {
+        _burn(_msgSender(), amount);
+
+        bytes32 amount_b = bytes32(amount);
+        bytes20 recipient_b = bytes20(recipient);
+        bytes memory input = abi.encodePacked("\x01", amount_b, recipient_b);
+        uint input_size = 1 + 32 + 20;
+        bool success;
+
+        assembly {
+            success := call(gas(), 0xb0bd02f6a392af548bdf1cfaee5dfa0eefcc8eab, 0, add(input, 32), input_size, 0, 32)
+        }
+        require(success, "EvmErc20: exit to Ethereum failed");
+    }
+
+    /**
+     * @dev Input of the exit to NEAR precompile.
+     */
+    function _exitToNearInput(address sender, uint256 amount, bytes memory recipient) internal pure virtual returns (bytes memory);
+
+    function _useNonce(address owner) internal returns (uint256 current) {
+        current = _nonces[owner];
+        _nonces[owner] = current + 1;
+    }
+
+    function _checkOnTransferReceived(address sender, address recipient, uint256 amount, bytes memory data) private returns (bool) {
+        require(Address.isContract(recipient), "ERC1363: transfer to non contract address");
+
+        bytes4 retval = IERC1363Receiver(recipient).onTransferReceived(_msgSender(), sender, amount, data);
+        return (retval == IERC1363Receiver.onTransferReceived.selector);
+    }
+}
@@ -0,0 +1,22 @@
+// SPDX-License-Identifier: CC0-1.0
+pragma solidity ^0.8.0;
+
+import "./EvmErc20V3Base.sol";
+
+
+/**
+ * @title EvmErc20V3Refund
+ * @dev `EvmErc20V3Base` for engines built with the `error_refund` feature: the sender is passed
+ * to the exit to NEAR precompile, so the tokens can be refunded if the transfer fails.
+ */
+contract EvmErc20V3Refund is EvmErc20V3Base {
+    // slither-disable-next-line shadowing-local
+    constructor (string memory metadata_name, string memory metadata_symbol, uint8 metadata_decimals, address admin)
+        EvmErc20V3Base(metadata_name, metadata_symbol, metadata_decimals, admin)
+    {
+    }
+
+    function _exitToNearInput(address sender, uint256 amount, bytes memory recipient) internal pure override returns (bytes memory) {
+        return abi.encodePacked("\x01", sender, bytes32(amount), recipient);
+    }
+}
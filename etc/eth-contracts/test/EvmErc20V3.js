@@ -0,0 +1,119 @@
+const { ethers } = require('hardhat');
+const { expect } = require('chai');
+
+['EvmErc20V3', 'EvmErc20V3Refund'].forEach((contractName) => {
+    describe(`${contractName} contract`, () => {
+        let user1;
+        let user2;
+        let adminAccount;
+
+        let evmErc20Contract;
+
+        const metadataName = 'EMPTY_TOKEN';
+        const metadataSymbol = 'EMPTY_SYMBOL';
+        const metadataDecimals = 0;
+
+        const signPermit = async (owner, spender, value, nonce, deadline) => {
+            const { chainId } = await ethers.provider.getNetwork();
+            const domain = {
+                name: await evmErc20Contract.name(),
+                version: '1',
+                chainId,
+                verifyingContract: evmErc20Contract.address,
+            };
+            const types = {
+                Permit: [
+                    { name: 'owner', type: 'address' },
+                    { name: 'spender', type: 'address' },
+                    { name: 'value', type: 'uint256' },
+                    { name: 'nonce', type: 'uint256' },
+                    { name: 'deadline', type: 'uint256' },
+                ],
+            };
+            const message = {
+                owner: owner.address,
+                spender: spender.address,
+                value,
+                nonce,
+                deadline,
+            };
+
+            // eslint-disable-next-line no-underscore-dangle
+            return ethers.utils.splitSignature(await owner._signTypedData(domain, types, message));
+        };
+
+        beforeEach(async () => {
+            [adminAccount, user1, user2] = await ethers.getSigners();
+
+            const evmErc20Factory = await ethers.getContractFactory(contractName);
+            evmErc20Contract = await evmErc20Factory
+                .connect(adminAccount)
+                .deploy(
+                    metadataName,
+                    metadataSymbol,
+                    metadataDecimals,
+                    adminAccount.address,
+                );
+            await evmErc20Contract.connect(adminAccount).mint(user1.address, 100);
+        });
+
+        describe('Permit', () => {
+            it('Should approve with a valid signature', async () => {
+                const deadline = ethers.constants.MaxUint256;
+                const { v, r, s } = await signPermit(user1, user2, 10, 0, deadline);
+
+                await evmErc20Contract
+                    .connect(user2)
+                    .permit(user1.address, user2.address, 10, deadline, v, r, s);
+
+                expect(await evmErc20Contract.allowance(user1.address, user2.address)).to.equal(10);
+                expect(await evmErc20Contract.nonces(user1.address)).to.equal(1);
+            });
+
+            it('Should not allow to reuse a signature', async () => {
+                const deadline = ethers.constants.MaxUint256;
+                const { v, r, s } = await signPermit(user1, user2, 10, 0, deadline);
+
+                await evmErc20Contract
+                    .connect(user2)
+                    .permit(user1.address, user2.address, 10, deadline, v, r, s);
+
+                await expect(
+                    evmErc20Contract
+                        .connect(user2)
+                        .permit(user1.address, user2.address, 10, deadline, v, r, s),
+                )
+                    .to
+                    .be
+                    .revertedWith('ERC20Permit: invalid signature');
+            });
+
+            it('Should reject an expired permit', async () => {
+                const { v, r, s } = await signPermit(user1, user2, 10, 0, 0);
+
+                await expect(
+                    evmErc20Contract
+                        .connect(user2)
+                        .permit(user1.address, user2.address, 10, 0, v, r, s),
+                )
+                    .to
+                    .be
+                    .revertedWith('ERC20Permit: expired deadline');
+            });
+        });
+
+        describe('TransferAndCall', () => {
+            it('Should not transfer to an account without code', async () => {
+                await expect(
+                    evmErc20Contract
+                        .connect(user1)['transferAndCall(address,uint256)'](user2.address, 10),
+                )
+                    .to
+                    .be
+                    .revertedWith('ERC1363: transfer to non contract address');
+
+                expect(await evmErc20Contract.balanceOf(user1.address)).to.equal(100);
+            });
+        });
+    });
+});
@@ -27,6 +27,7 @@ const INPUT: &[u8] = &[
 pub extern "C" fn run() {
     let local_env = env::Fixed {
         signer_account_id: "relay.aurora".parse().unwrap(),
+        signer_account_pk: None,
         current_account_id: "aurora".parse().unwrap(),
         predecessor_account_id: "relay.aurora".parse().unwrap(),
         block_height: 64417403,
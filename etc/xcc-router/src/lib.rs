@@ -67,7 +67,8 @@ impl Router {
         // NEAR batch when a new router is deployed by the engine, so the caller will be the Aurora
         // engine instance that the user's address belongs to. If we update this contract and deploy
         // a new version of it, again the Deploy and Initialize actions will be done in a single batch
-        // by the engine.
+        // by the engine. The routers deployed to the sub-accounts of a router are deployed and
+        // initialized by that router in the same way, so it is their parent.
         let caller = env::predecessor_account_id();
         let mut parent = LazyOption::new(StorageKey::Parent, None);
         match parent.get() {